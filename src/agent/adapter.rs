@@ -32,6 +32,8 @@ pub struct SpawnConfig {
     /// Agent conversation ID to resume, when relaunching a session recovered
     /// from a previous Panoptes run. `None` starts a fresh conversation.
     pub resume: Option<String>,
+    /// Model to run, passed as `--model`. None = the agent's own default
+    pub model: Option<String>,
 }
 
 /// Result of spawning an agent
@@ -192,6 +194,7 @@ mod tests {
             claude_config_dir: None,
             codex_home: None,
             resume: None,
+            model: None,
         };
         assert_eq!(config.session_name, "test-session");
         assert_eq!(config.initial_prompt, Some("hello".to_string()));
@@ -209,6 +212,7 @@ mod tests {
             claude_config_dir: Some(PathBuf::from("/home/user/.claude-work")),
             codex_home: None,
             resume: None,
+            model: None,
         };
         assert_eq!(
             config.claude_config_dir,
@@ -348,6 +348,13 @@ impl AgentAdapter for ClaudeCodeAdapter {
 
     fn build_args(&self, spawn_config: &SpawnConfig) -> Vec<String> {
         let mut args = self.default_args();
+        // A model in the adapter's own arguments was asked for explicitly
+        if let Some(ref model) = spawn_config.model {
+            if !args.iter().any(|arg| arg == "--model") {
+                args.push("--model".to_string());
+                args.push(model.clone());
+            }
+        }
         args.extend(Self::conversation_args(spawn_config));
         if let Some(ref prompt) = spawn_config.initial_prompt {
             args.push("--print".to_string());
@@ -394,6 +401,7 @@ mod tests {
             claude_config_dir: None,
             codex_home: None,
             resume: None,
+            model: None,
         }
     }
 
@@ -432,6 +440,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_model_is_passed_unless_the_extra_args_name_one() {
+        let mut spawn_config = test_spawn_config(PathBuf::from("/tmp"));
+        spawn_config.model = Some("sonnet".to_string());
+
+        let args = ClaudeCodeAdapter::new().build_args(&spawn_config);
+        assert!(args.windows(2).any(|pair| pair == ["--model", "sonnet"]));
+
+        let pinned = ClaudeCodeAdapter::with_args(vec!["--model".to_string(), "opus".to_string()]);
+        let args = pinned.build_args(&spawn_config);
+        assert_eq!(args.iter().filter(|arg| *arg == "--model").count(), 1);
+        assert!(!args.contains(&"sonnet".to_string()));
+    }
+
     #[test]
     fn test_fresh_spawn_claims_the_panoptes_session_id() {
         let spawn_config = test_spawn_config(PathBuf::from("/tmp"));
@@ -516,6 +538,7 @@ mod tests {
             claude_config_dir: None,
             codex_home: None,
             resume: None,
+            model: None,
         };
 
         let env = adapter.generate_env(&config, &spawn_config);
@@ -548,6 +571,7 @@ mod tests {
             claude_config_dir: Some(claude_config_path.clone()),
             codex_home: None,
             resume: None,
+            model: None,
         };
 
         let env = adapter.generate_env(&config, &spawn_config);
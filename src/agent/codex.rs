@@ -118,6 +118,53 @@ pub fn rollout_path(codex_home: &Path, conversation_id: &str) -> Option<PathBuf>
         })
 }
 
+/// Make a conversation resumable from another `CODEX_HOME`
+///
+/// `codex resume` only looks in its own home's `sessions/` directory, so moving
+/// a conversation to another account means giving that account a copy of the
+/// rollout, at the same path relative to `sessions/`. A copy already there is
+/// replaced when the source is longer or newer, so moving a conversation back
+/// to an account it has been on before brings the turns made elsewhere with
+/// it. Returns where the conversation now lives under `to_home`.
+pub fn copy_rollout(from_home: &Path, to_home: &Path, conversation_id: &str) -> Result<PathBuf> {
+    let source = rollout_path(from_home, conversation_id).with_context(|| {
+        format!(
+            "no rollout for conversation {} under {}",
+            conversation_id,
+            from_home.display()
+        )
+    })?;
+    let relative = source
+        .strip_prefix(from_home.join("sessions"))
+        .context("rollout found outside the sessions directory")?;
+    let target = to_home.join("sessions").join(relative);
+    if !is_behind(&target, &source) {
+        return Ok(target);
+    }
+    if let Some(parent) = target.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("creating {}", parent.display()))?;
+    }
+    std::fs::copy(&source, &target)
+        .with_context(|| format!("copying {} to {}", source.display(), target.display()))?;
+    Ok(target)
+}
+
+/// Whether `copy` is missing or lacks turns that `source` has
+///
+/// Rollouts are only ever appended to, so a longer or more recently written
+/// source holds turns the copy does not.
+fn is_behind(copy: &Path, source: &Path) -> bool {
+    let (Ok(copy), Ok(source)) = (std::fs::metadata(copy), std::fs::metadata(source)) else {
+        return true;
+    };
+    let newer = match (source.modified(), copy.modified()) {
+        (Ok(source), Ok(copy)) => source > copy,
+        _ => true,
+    };
+    source.len() > copy.len() || newer
+}
+
 /// What installing the Panoptes notify hook into a Codex config requires
 ///
 /// The pure outcome of [`CodexAdapter::plan_notify`], separated from the
@@ -513,6 +560,14 @@ impl AgentAdapter for CodexAdapter {
 
         args.extend(self.default_args());
 
+        // A model in the adapter's own arguments was asked for explicitly
+        if let Some(ref model) = spawn_config.model {
+            if !args.iter().any(|arg| arg == "--model" || arg == "-m") {
+                args.push("--model".to_string());
+                args.push(model.clone());
+            }
+        }
+
         if !args.iter().any(|arg| arg == NO_ALT_SCREEN_FLAG) {
             args.push(NO_ALT_SCREEN_FLAG.to_string());
         }
@@ -590,6 +645,7 @@ mod tests {
             claude_config_dir: None,
             codex_home: None,
             resume: None,
+            model: None,
         };
 
         let env = adapter.generate_env(&config, &spawn_config);
@@ -629,6 +685,7 @@ mod tests {
             claude_config_dir: None,
             codex_home: Some(codex_home_path.clone()),
             resume: None,
+            model: None,
         };
 
         let env = adapter.generate_env(&config, &spawn_config);
@@ -661,6 +718,7 @@ mod tests {
             claude_config_dir: None,
             codex_home: None,
             resume: resume.map(|r| r.to_string()),
+            model: None,
         }
     }
 
@@ -723,6 +781,7 @@ mod tests {
             claude_config_dir: None,
             codex_home: None,
             resume: None,
+            model: None,
         };
 
         let args = adapter.build_args(&spawn_config);
@@ -743,6 +802,7 @@ mod tests {
             claude_config_dir: None,
             codex_home: None,
             resume: None,
+            model: None,
         };
 
         let args = adapter.build_args(&spawn_config);
@@ -763,6 +823,7 @@ mod tests {
             claude_config_dir: None,
             codex_home: None,
             resume: None,
+            model: None,
         };
 
         let args = adapter.build_args(&spawn_config);
@@ -1035,6 +1096,7 @@ notify = ["bash", "__LEGACY__", 42]
             claude_config_dir: None,
             codex_home: Some(codex_home.clone()),
             resume: None,
+            model: None,
         };
 
         let adapter = CodexAdapter::new();
@@ -1061,6 +1123,7 @@ notify = ["bash", "__LEGACY__", 42]
             claude_config_dir: None,
             codex_home: Some(PathBuf::from("/custom/codex")),
             resume: None,
+            model: None,
         };
 
         let resolved = CodexAdapter::resolve_codex_home(&spawn_config);
@@ -1079,6 +1142,7 @@ notify = ["bash", "__LEGACY__", 42]
             claude_config_dir: None,
             codex_home: None,
             resume: None,
+            model: None,
         };
 
         let resolved = CodexAdapter::resolve_codex_home(&spawn_config);
@@ -1205,6 +1269,56 @@ notify = ["bash", "__LEGACY__", 42]
         assert_eq!(rollout_path(home.path(), "no-such-id"), None);
     }
 
+    #[test]
+    fn test_copy_rollout_makes_a_conversation_resumable_from_another_home() {
+        let (work, spare) = (TempDir::new().unwrap(), TempDir::new().unwrap());
+        let cwd = TempDir::new().unwrap();
+        let original = write_rollout(work.path(), "moving-id", cwd.path(), an_hour_ago());
+
+        let copied = copy_rollout(work.path(), spare.path(), "moving-id").unwrap();
+        assert_eq!(
+            rollout_path(spare.path(), "moving-id"),
+            Some(copied.clone())
+        );
+        assert_eq!(
+            copied.strip_prefix(spare.path()).unwrap(),
+            original.strip_prefix(work.path()).unwrap()
+        );
+        // A second move finds the copy already in place
+        assert_eq!(
+            copy_rollout(work.path(), spare.path(), "moving-id").unwrap(),
+            copied
+        );
+        assert!(copy_rollout(work.path(), spare.path(), "no-such-id").is_err());
+    }
+
+    #[test]
+    fn test_moving_back_keeps_the_turns_made_on_the_other_account() {
+        use std::io::Write;
+
+        let (a, b) = (TempDir::new().unwrap(), TempDir::new().unwrap());
+        let cwd = TempDir::new().unwrap();
+        let original = write_rollout(a.path(), "moving-id", cwd.path(), an_hour_ago());
+
+        // A to B, then a turn on B
+        let on_b = copy_rollout(a.path(), b.path(), "moving-id").unwrap();
+        let turn = "{\"type\":\"response_item\",\"payload\":{\"turn\":\"made on B\"}}\n";
+        std::fs::OpenOptions::new()
+            .append(true)
+            .open(&on_b)
+            .unwrap()
+            .write_all(turn.as_bytes())
+            .unwrap();
+
+        // Back to A: A's copy catches up instead of being resumed stale
+        let on_a = copy_rollout(b.path(), a.path(), "moving-id").unwrap();
+        assert_eq!(on_a, original);
+        assert_eq!(
+            std::fs::read_to_string(&on_a).unwrap(),
+            std::fs::read_to_string(&on_b).unwrap()
+        );
+    }
+
     #[test]
     fn test_rollout_path_tolerates_a_missing_sessions_directory() {
         let home = TempDir::new().unwrap();
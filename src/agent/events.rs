@@ -120,6 +120,103 @@ pub struct UsageSnapshot {
     /// Plan name backing the rate limit (Codex only)
     #[serde(default)]
     pub plan: Option<String>,
+
+    /// Tokens billed since Panoptes started watching this conversation
+    ///
+    /// Built up by [`merge`](Self::merge) from each [`TokenReport`], so a
+    /// conversation resumed from an earlier run starts again from zero: what
+    /// it spent then was counted then.
+    #[serde(default)]
+    pub spent: TokenCounts,
+
+    /// The agent's latest token report, which the next one is measured against
+    #[serde(skip)]
+    pub report: Option<TokenReport>,
+
+    /// Whether `report` predates this run: a baseline to measure from, not
+    /// spending to count
+    #[serde(skip)]
+    pub baseline: bool,
+}
+
+/// Tokens by how they are billed
+///
+/// `input` is fresh input only: cache reads and cache writes are priced
+/// differently and are counted apart from it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenCounts {
+    #[serde(default)]
+    pub input: u64,
+    #[serde(default)]
+    pub output: u64,
+    #[serde(default)]
+    pub cache_write: u64,
+    #[serde(default)]
+    pub cache_read: u64,
+}
+
+impl TokenCounts {
+    pub fn total(&self) -> u64 {
+        self.input + self.output + self.cache_write + self.cache_read
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.total() == 0
+    }
+
+    /// What `self` has that `earlier` had not, per kind, never below zero
+    pub fn since(&self, earlier: &TokenCounts) -> TokenCounts {
+        TokenCounts {
+            input: self.input.saturating_sub(earlier.input),
+            output: self.output.saturating_sub(earlier.output),
+            cache_write: self.cache_write.saturating_sub(earlier.cache_write),
+            cache_read: self.cache_read.saturating_sub(earlier.cache_read),
+        }
+    }
+}
+
+impl std::ops::AddAssign for TokenCounts {
+    fn add_assign(&mut self, other: TokenCounts) {
+        self.input += other.input;
+        self.output += other.output;
+        self.cache_write += other.cache_write;
+        self.cache_read += other.cache_read;
+    }
+}
+
+/// How an agent reports what it has billed
+///
+/// The two agents count in opposite ways, and getting either wrong double
+/// counts: Claude writes each API call's tokens, often on several consecutive
+/// lines of one message, while Codex writes the conversation's running total.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TokenReport {
+    /// One API call, identified so a call split across lines counts once (Claude)
+    Call { id: String, tokens: TokenCounts },
+    /// Everything the conversation has billed so far (Codex)
+    Total(TokenCounts),
+}
+
+impl TokenReport {
+    /// The tokens this report adds to the one before it
+    fn spent_since(&self, previous: Option<&TokenReport>) -> TokenCounts {
+        match (self, previous) {
+            (TokenReport::Call { id, .. }, Some(TokenReport::Call { id: seen, .. }))
+                if id == seen =>
+            {
+                TokenCounts::default()
+            }
+            (TokenReport::Call { tokens, .. }, _) => *tokens,
+            // A total that went down belongs to a new conversation, which has
+            // spent everything it reports
+            (TokenReport::Total(total), Some(TokenReport::Total(seen)))
+                if total.total() >= seen.total() =>
+            {
+                total.since(seen)
+            }
+            (TokenReport::Total(total), _) => *total,
+        }
+    }
 }
 
 impl UsageSnapshot {
@@ -153,6 +250,12 @@ impl UsageSnapshot {
         if newer.plan.is_some() {
             self.plan = newer.plan;
         }
+        if let Some(report) = newer.report {
+            if !newer.baseline {
+                self.spent += report.spent_since(self.report.as_ref());
+            }
+            self.report = Some(report);
+        }
     }
 
     /// How full the context window is, as a percentage
@@ -251,6 +354,50 @@ mod tests {
         assert_eq!(usage.model.as_deref(), Some("gpt-5-codex"));
     }
 
+    #[test]
+    fn test_claude_calls_count_once_however_many_lines_they_span() {
+        let call = |id: &str, output| UsageSnapshot {
+            report: Some(TokenReport::Call {
+                id: id.to_string(),
+                tokens: TokenCounts {
+                    input: 10,
+                    output,
+                    ..Default::default()
+                },
+            }),
+            ..Default::default()
+        };
+        let mut usage = UsageSnapshot::default();
+        usage.merge(call("msg_1", 5));
+        usage.merge(call("msg_1", 5));
+        usage.merge(call("msg_2", 7));
+
+        assert_eq!(usage.spent.input, 20);
+        assert_eq!(usage.spent.output, 12);
+    }
+
+    #[test]
+    fn test_codex_totals_count_the_difference_and_a_baseline_counts_nothing() {
+        let total = |input, baseline| UsageSnapshot {
+            report: Some(TokenReport::Total(TokenCounts {
+                input,
+                ..Default::default()
+            })),
+            baseline,
+            ..Default::default()
+        };
+        let mut usage = UsageSnapshot::default();
+        // Spent in an earlier run, and seeded as the point to measure from
+        usage.merge(total(5_000, true));
+        usage.merge(total(5_400, false));
+        usage.merge(total(6_000, false));
+        assert_eq!(usage.spent.input, 1_000);
+
+        // A smaller total is a new conversation, which spent all of it
+        usage.merge(total(300, false));
+        assert_eq!(usage.spent.input, 1_300);
+    }
+
     #[test]
     fn test_context_percent() {
         let usage = UsageSnapshot {
@@ -111,6 +111,7 @@ mod tests {
             claude_config_dir: None,
             codex_home: None,
             resume: None,
+            model: None,
         }
     }
 
@@ -11,7 +11,7 @@
 
 use crate::config::config_dir;
 use crate::persistence::{self, LoadOutcome};
-use anyhow::Result;
+use anyhow::{Context, Result};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -50,7 +50,7 @@ struct StoreData<C> {
 }
 
 /// Store for persisting agent account profiles
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct ProfileStore<C: AgentProfile> {
     /// All configurations indexed by ID
     configs: HashMap<Uuid, C>,
@@ -237,6 +237,29 @@ impl<C: AgentProfile> ProfileStore<C> {
         }
     }
 
+    /// Re-read the store from its file for a live reload
+    ///
+    /// Unlike [`Self::load_with_status`], a file that does not parse is
+    /// reported and left in place rather than backed up: the running store is
+    /// still good, and the file is most likely a hand-edit in progress. `None`
+    /// when the file does not exist.
+    pub fn reload(&self) -> Result<Option<Self>> {
+        let path = &self.store_path;
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        };
+        let data: StoreData<C> = serde_json::from_str(&content)
+            .with_context(|| format!("{} is not valid", path.display()))?;
+        Ok(Some(Self::from_data(data, path)))
+    }
+
+    /// The file this store is saved to
+    pub fn path(&self) -> &Path {
+        &self.store_path
+    }
+
     /// Save store to disk
     pub fn save(&self) -> Result<()> {
         self.save_to(&self.store_path)
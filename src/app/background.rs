@@ -1,13 +1,16 @@
 //! Git work that runs off the event-loop thread
 //!
-//! Fetching remotes and creating or removing a worktree can take seconds. Run
-//! on the event loop they freeze the whole TUI; run here they leave it live,
-//! rendering an animated overlay, and - for fetches - cancellable with Esc.
+//! Fetching remotes, creating or removing a worktree, and pushing a commit can
+//! take seconds. Run on the event loop they freeze the whole TUI; run here they
+//! leave it live, rendering an animated overlay, and - for fetches and setup
+//! commands - cancellable with Esc.
 //!
 //! A job is a [`GitTask`] (what the worker thread does, knowing nothing about
 //! the app) plus a [`JobFollowUp`] (what the app does with the result once it
 //! arrives). [`App::tick_background_job`](crate::app::App::tick_background_job)
-//! polls for completion each pass of the event loop.
+//! polls for completion each pass of the event loop. A task can also report
+//! progress as it goes - a line of text the overlay shows in place of its
+//! message, e.g. git's "Receiving objects: 42%" while submodules clone.
 
 use std::collections::HashSet;
 use std::path::PathBuf;
@@ -17,6 +20,9 @@ use std::sync::Arc;
 
 use anyhow::{Context, Result};
 
+use crate::git::commit::PushTarget;
+use crate::git::seed::WorktreeSeed;
+use crate::git::worktree_setup::WorktreeSetup;
 use crate::git::{BranchRefInfo, FetchOutcome, GitOps};
 use crate::project::{Branch, ProjectId};
 
@@ -25,24 +31,58 @@ pub(crate) enum GitTask {
     /// Fetch every remote, then list all branch refs
     ///
     /// The fetch is best-effort: failures and cancellation both fall through
-    /// to the refs already on disk.
+    /// to the refs already on disk. Several repositories (a multi-repository
+    /// project) give one merged list.
     FetchAndListBranches {
-        repo_path: PathBuf,
+        repo_paths: Vec<PathBuf>,
         default_base_branch: Option<String>,
     },
-    /// Create a git worktree for a branch
+    /// Create a git worktree for a branch, set it up as the project asks,
+    /// apply `seed` to it (if any), then run the project's setup commands
     CreateWorktree {
         repo_path: PathBuf,
         branch_name: String,
         worktree_path: PathBuf,
         create_branch: bool,
         base_ref: Option<String>,
+        setup: WorktreeSetup,
+        seed: Option<WorktreeSeed>,
+        commands: Vec<String>,
+    },
+    /// Create a branch's worktree in every repository of a multi-repository
+    /// project, as `(repository, worktree path)` pairs
+    ///
+    /// The branch is created wherever it does not exist yet, from `base_ref`
+    /// in the repositories that have it and from `HEAD` in the rest. If any
+    /// repository fails, the worktrees already created are removed again.
+    ///
+    /// The project's setup commands run once, in the directory holding them.
+    CreateMultiRepoWorktree {
+        checkouts: Vec<(PathBuf, PathBuf)>,
+        parent: PathBuf,
+        branch_name: String,
+        base_ref: Option<String>,
+        setup: WorktreeSetup,
+        commands: Vec<String>,
     },
     /// Remove a branch's git worktree from disk
     RemoveWorktree {
         repo_path: PathBuf,
         branch_name: String,
     },
+    /// Remove a multi-repository branch's worktrees, as `(repository,
+    /// worktree path)` pairs, then the directory that held them
+    RemoveMultiRepoWorktree {
+        checkouts: Vec<(PathBuf, PathBuf)>,
+        parent: PathBuf,
+    },
+    /// Commit the chosen files of a checkout, then optionally push its branch
+    CommitAndPush {
+        checkout: PathBuf,
+        paths: Vec<String>,
+        message: String,
+        push: bool,
+    },
 }
 
 /// What the app does with a finished job
@@ -66,6 +106,8 @@ pub(crate) enum JobFollowUp {
     },
     /// Finish deleting the branch whose worktree was just removed
     FinishBranchDelete { branch: Box<Branch> },
+    /// Say how the commit (and push) of a branch went
+    ReportCommit { branch_name: String },
 }
 
 /// What a finished job produced
@@ -77,6 +119,26 @@ pub(crate) enum JobOutput {
     },
     /// An operation with nothing to return but success or failure
     Completed(Result<()>),
+    /// A created worktree, how updating its submodules went (if the project
+    /// asks for that), how applying its seed went (if it had one), and how
+    /// its setup commands went (if the project has allowed ones)
+    ///
+    /// All only happen once the worktree exists, so a failed creation always
+    /// comes with `submodules: None`, `seeded: None` and `commands: None`.
+    WorktreeCreated {
+        created: Result<()>,
+        submodules: Option<Result<()>>,
+        seeded: Option<Result<()>>,
+        commands: Option<Result<()>>,
+    },
+    /// The new commit's short ID, and where it was pushed (if a push was asked for)
+    ///
+    /// A push is only attempted once the commit exists, so a failed commit
+    /// always comes with `push: None`.
+    Committed {
+        commit: Result<String>,
+        push: Option<Result<PushTarget>>,
+    },
 }
 
 /// A finished job, as it comes back over the channel
@@ -91,29 +153,48 @@ pub(crate) struct BackgroundJob {
     rx: Receiver<JobResult>,
     /// Flipped when the user cancels, polled by the worker
     cancel: Arc<AtomicBool>,
+    /// Progress lines from the worker, newest last
+    progress: Receiver<String>,
 }
 
 impl BackgroundJob {
     /// Spawn `task` on a worker thread, to be finished by `follow_up`
     pub(crate) fn spawn(task: GitTask, follow_up: JobFollowUp) -> Self {
         let (tx, rx) = mpsc::channel();
+        let (progress_tx, progress) = mpsc::channel::<String>();
         let cancel = Arc::new(AtomicBool::new(false));
         let worker_cancel = Arc::clone(&cancel);
 
         std::thread::spawn(move || {
-            let output = run_task(task, &worker_cancel);
+            let report = |line: &str| {
+                let _ = progress_tx.send(line.to_string());
+            };
+            let output = run_task(task, &worker_cancel, &report);
             // A send error just means the app stopped caring (quit, or the
             // job was abandoned); there is nothing to do about it.
             let _ = tx.send(JobResult { output, follow_up });
         });
 
-        Self { rx, cancel }
+        Self {
+            rx,
+            cancel,
+            progress,
+        }
+    }
+
+    /// The latest progress line reported since the last call, if any
+    ///
+    /// Git can report hundreds of lines a second; only the newest is worth
+    /// drawing, so the rest are dropped here.
+    pub(crate) fn latest_progress(&self) -> Option<String> {
+        self.progress.try_iter().last()
     }
 
     /// Ask the running job to stop
     ///
-    /// Only fetches honour this; other tasks run to completion (interrupting a
-    /// half-created worktree would leave the repo in a worse state).
+    /// Only fetches and a new worktree's setup commands honour this; other
+    /// tasks run to completion (interrupting a half-created worktree would
+    /// leave the repo in a worse state).
     pub(crate) fn cancel(&self) {
         self.cancel.store(true, Ordering::Relaxed);
     }
@@ -144,15 +225,32 @@ pub(crate) enum JobPoll {
 }
 
 /// Run a task to completion on the calling (worker) thread
-fn run_task(task: GitTask, cancel: &AtomicBool) -> JobOutput {
+fn run_task(task: GitTask, cancel: &AtomicBool, progress: &dyn Fn(&str)) -> JobOutput {
     match task {
         GitTask::FetchAndListBranches {
-            repo_path,
+            repo_paths,
             default_base_branch,
         } => {
-            let (refs, fetch_error) =
-                fetch_and_list_branches(&repo_path, default_base_branch.as_deref(), cancel);
-            JobOutput::Branches { refs, fetch_error }
+            let mut per_repo = Vec::new();
+            let mut fetch_errors = Vec::new();
+            for repo_path in &repo_paths {
+                let (refs, fetch_error) =
+                    fetch_and_list_branches(repo_path, default_base_branch.as_deref(), cancel);
+                fetch_errors.extend(fetch_error);
+                match refs {
+                    Ok(refs) => per_repo.push(refs),
+                    Err(e) => {
+                        return JobOutput::Branches {
+                            refs: Err(e),
+                            fetch_error: None,
+                        }
+                    }
+                }
+            }
+            JobOutput::Branches {
+                refs: Ok(crate::git::multi_repo::merge_branch_refs(per_repo)),
+                fetch_error: (!fetch_errors.is_empty()).then(|| fetch_errors.join("; ")),
+            }
         }
         GitTask::CreateWorktree {
             repo_path,
@@ -160,18 +258,96 @@ fn run_task(task: GitTask, cancel: &AtomicBool) -> JobOutput {
             worktree_path,
             create_branch,
             base_ref,
-        } => JobOutput::Completed((|| {
-            let git = GitOps::open(&repo_path).context("Failed to open git repository")?;
-            crate::git::worktree::create_worktree(
-                git.repository(),
+            setup,
+            seed,
+            commands,
+        } => {
+            let worktree_name = worktree_path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let created = create_worktree(
+                &repo_path,
+                &worktree_name,
                 &branch_name,
                 &worktree_path,
                 create_branch,
                 base_ref.as_deref(),
+                setup.sparse_checkout,
+                progress,
             )
-            .map(|_| ())
-            .with_context(|| format!("Failed to create worktree for '{}'", branch_name))
-        })()),
+            .with_context(|| format!("Failed to create worktree for '{}'", branch_name));
+            let submodules = match &created {
+                Ok(()) if setup.submodules => Some(crate::git::worktree_setup::update_submodules(
+                    &worktree_path,
+                    progress,
+                )),
+                _ => None,
+            };
+            let seeded = match (&created, seed) {
+                (Ok(()), Some(seed)) => Some(crate::git::seed::apply_seed(&worktree_path, &seed)),
+                _ => None,
+            };
+            let commands = match &created {
+                Ok(()) if !commands.is_empty() => {
+                    Some(crate::git::worktree_setup::run_setup_commands(
+                        &worktree_path,
+                        &commands,
+                        cancel,
+                        progress,
+                    ))
+                }
+                _ => None,
+            };
+            JobOutput::WorktreeCreated {
+                created,
+                submodules,
+                seeded,
+                commands,
+            }
+        }
+        GitTask::CreateMultiRepoWorktree {
+            checkouts,
+            parent,
+            branch_name,
+            base_ref,
+            setup,
+            commands,
+        } => {
+            let created = create_multi_repo_worktree(
+                &checkouts,
+                &branch_name,
+                base_ref.as_deref(),
+                setup.sparse_checkout,
+                progress,
+            )
+            .with_context(|| format!("Failed to create worktrees for '{}'", branch_name));
+            let submodules = match &created {
+                Ok(()) if setup.submodules => {
+                    Some(checkouts.iter().try_for_each(|(_, worktree_path)| {
+                        crate::git::worktree_setup::update_submodules(worktree_path, progress)
+                    }))
+                }
+                _ => None,
+            };
+            let commands = match &created {
+                Ok(()) if !commands.is_empty() => {
+                    Some(crate::git::worktree_setup::run_setup_commands(
+                        &parent, &commands, cancel, progress,
+                    ))
+                }
+                _ => None,
+            };
+            JobOutput::WorktreeCreated {
+                created,
+                submodules,
+                seeded: None,
+                commands,
+            }
+        }
+        GitTask::RemoveMultiRepoWorktree { checkouts, parent } => {
+            JobOutput::Completed(remove_multi_repo_worktree(&checkouts, &parent))
+        }
         GitTask::RemoveWorktree {
             repo_path,
             branch_name,
@@ -180,9 +356,188 @@ fn run_task(task: GitTask, cancel: &AtomicBool) -> JobOutput {
             crate::git::worktree::remove_worktree(git.repository(), &branch_name, true)
                 .context("Failed to remove worktree")
         })()),
+        GitTask::CommitAndPush {
+            checkout,
+            paths,
+            message,
+            push,
+        } => commit_and_push(&checkout, &paths, &message, push),
     }
 }
 
+/// Create the worktree, sparse when asked and the main checkout is sparse
+///
+/// Asking for a sparse worktree of a repository whose main checkout is not
+/// sparse gets a full one: there are no patterns to carry over.
+#[allow(clippy::too_many_arguments)]
+fn create_worktree(
+    repo_path: &std::path::Path,
+    worktree_name: &str,
+    branch_name: &str,
+    worktree_path: &std::path::Path,
+    create_branch: bool,
+    base_ref: Option<&str>,
+    sparse_checkout: bool,
+    progress: &dyn Fn(&str),
+) -> Result<()> {
+    let git = GitOps::open(repo_path).context("Failed to open git repository")?;
+    let sparse = if sparse_checkout {
+        crate::git::worktree_setup::sparse_checkout_patterns(repo_path)?
+    } else {
+        None
+    };
+
+    match sparse {
+        Some(sparse) => crate::git::worktree_setup::create_sparse_worktree(
+            git.repository(),
+            branch_name,
+            worktree_path,
+            create_branch,
+            base_ref,
+            &sparse,
+            progress,
+        ),
+        None => {
+            if sparse_checkout {
+                tracing::info!(
+                    "Main checkout of {:?} is not sparse; creating a full worktree",
+                    repo_path
+                );
+            }
+            crate::git::worktree::create_worktree_named(
+                git.repository(),
+                worktree_name,
+                branch_name,
+                worktree_path,
+                create_branch,
+                base_ref,
+            )
+        }
+    }
+    .map(|_| ())
+}
+
+/// Create one worktree per repository, all or nothing
+///
+/// Every worktree is registered with git under the branch's directory name,
+/// since the directory itself is named after the repository.
+fn create_multi_repo_worktree(
+    checkouts: &[(PathBuf, PathBuf)],
+    branch_name: &str,
+    base_ref: Option<&str>,
+    sparse_checkout: bool,
+    progress: &dyn Fn(&str),
+) -> Result<()> {
+    let mut created: Vec<&(PathBuf, PathBuf)> = Vec::new();
+    for checkout in checkouts {
+        let (repo_path, worktree_path) = checkout;
+        let repo_name = worktree_path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        progress(&format!("Creating worktree in {}...", repo_name));
+
+        let result = (|| {
+            let worktree_name = worktree_path
+                .parent()
+                .and_then(|parent| parent.file_name())
+                .map(|name| name.to_string_lossy().into_owned())
+                .context("Invalid worktree path")?;
+            // A base that only some repositories have: the rest branch from HEAD
+            let base_ref = {
+                let git = GitOps::open(repo_path).context("Failed to open git repository")?;
+                base_ref.filter(|base| {
+                    crate::git::worktree::resolve_ref_to_commit(git.repository(), base).is_ok()
+                })
+            };
+            create_worktree(
+                repo_path,
+                &worktree_name,
+                branch_name,
+                worktree_path,
+                true,
+                base_ref,
+                sparse_checkout,
+                progress,
+            )
+        })();
+
+        if let Err(e) = result {
+            for (repo_path, worktree_path) in created {
+                let undone = GitOps::open(repo_path).and_then(|git| {
+                    crate::git::worktree::remove_worktree_at(git.repository(), worktree_path, true)
+                });
+                if let Err(undo_error) = undone {
+                    tracing::warn!(
+                        "Failed to roll back worktree {:?}: {}",
+                        worktree_path,
+                        undo_error
+                    );
+                }
+            }
+            return Err(e.context(format!("in {}", repo_name)));
+        }
+        created.push(checkout);
+    }
+    Ok(())
+}
+
+/// Remove every worktree of a multi-repository branch, then their directory
+///
+/// Keeps going past a repository that fails, so one broken worktree does not
+/// strand the others; the first failure is what gets reported.
+fn remove_multi_repo_worktree(
+    checkouts: &[(PathBuf, PathBuf)],
+    parent: &std::path::Path,
+) -> Result<()> {
+    let mut first_error = None;
+    for (repo_path, worktree_path) in checkouts {
+        let removed = GitOps::open(repo_path).and_then(|git| {
+            crate::git::worktree::remove_worktree_at(git.repository(), worktree_path, true)
+        });
+        if let Err(e) = removed {
+            tracing::warn!("Failed to remove worktree {:?}: {}", worktree_path, e);
+            first_error.get_or_insert(e);
+        }
+    }
+    if let Some(e) = first_error {
+        return Err(e);
+    }
+    // Only empty by now, unless something outside git was put there
+    if parent.exists() {
+        std::fs::remove_dir(parent)
+            .with_context(|| format!("Failed to remove directory {:?}", parent))?;
+    }
+    Ok(())
+}
+
+/// Commit `paths` in `checkout`, then push the branch if asked and committed
+fn commit_and_push(
+    checkout: &std::path::Path,
+    paths: &[String],
+    message: &str,
+    push: bool,
+) -> JobOutput {
+    let git = match GitOps::discover(checkout) {
+        Ok(git) => git,
+        Err(e) => {
+            return JobOutput::Committed {
+                commit: Err(e),
+                push: None,
+            }
+        }
+    };
+
+    let commit = git
+        .commit_paths(paths, message)
+        .map(|oid| oid.to_string().chars().take(7).collect::<String>());
+    let push = match (&commit, push) {
+        (Ok(_), true) => Some(git.push_current_branch()),
+        _ => None,
+    };
+    JobOutput::Committed { commit, push }
+}
+
 /// Fetch remotes (best-effort) and list the repository's branch refs
 ///
 /// A failed fetch only produces a message for the caller to show - the refs
@@ -250,7 +605,7 @@ mod tests {
     fn cancelling_a_missing_repo_still_finishes() {
         let job = BackgroundJob::spawn(
             GitTask::FetchAndListBranches {
-                repo_path: PathBuf::from("/nonexistent/panoptes-test-repo"),
+                repo_paths: vec![PathBuf::from("/nonexistent/panoptes-test-repo")],
                 default_base_branch: None,
             },
             JobFollowUp::OpenDefaultBaseSelector,
@@ -272,4 +627,49 @@ mod tests {
             _ => panic!("expected a Branches output"),
         }
     }
+
+    fn init_repo(path: &std::path::Path) {
+        let repo = git2::Repository::init(path).unwrap();
+        let sig = git2::Signature::now("Test User", "test@example.com").unwrap();
+        let tree_id = repo.index().unwrap().write_tree().unwrap();
+        let tree = repo.find_tree(tree_id).unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "Initial commit", &tree, &[])
+            .unwrap();
+    }
+
+    #[test]
+    fn multi_repo_worktrees_are_created_together_or_not_at_all() {
+        let src = tempfile::TempDir::new().unwrap();
+        let worktrees = tempfile::TempDir::new().unwrap();
+        for repo in ["backend", "frontend"] {
+            init_repo(&src.path().join(repo));
+        }
+        let parent = worktrees.path().join("feature");
+        let checkouts: Vec<(PathBuf, PathBuf)> = ["backend", "frontend"]
+            .iter()
+            .map(|repo| (src.path().join(repo), parent.join(repo)))
+            .collect();
+
+        create_multi_repo_worktree(&checkouts, "feature", Some("main"), false, &|_| {}).unwrap();
+        for (repo_path, worktree_path) in &checkouts {
+            assert!(worktree_path.join(".git").exists());
+            let repo = git2::Repository::open(repo_path).unwrap();
+            assert!(repo.find_branch("feature", git2::BranchType::Local).is_ok());
+        }
+        remove_multi_repo_worktree(&checkouts, &parent).unwrap();
+        assert!(!parent.exists());
+
+        // The second repository's directory is in the way: the first
+        // repository's worktree is rolled back rather than left alone
+        std::fs::create_dir_all(parent.join("frontend")).unwrap();
+        let err =
+            create_multi_repo_worktree(&checkouts, "other", None, false, &|_| {}).unwrap_err();
+        assert!(format!("{err:#}").contains("in frontend"), "{err:#}");
+        assert!(!parent.join("backend").exists());
+        let backend = git2::Repository::open(src.path().join("backend")).unwrap();
+        assert!(crate::git::worktree::list_worktrees(&backend)
+            .unwrap()
+            .iter()
+            .all(|wt| wt.is_main));
+    }
 }
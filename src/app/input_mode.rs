@@ -76,6 +76,15 @@ pub enum InputMode {
     ConfirmingCodexConfigDelete,
     /// Selecting Codex config for session creation or project default
     SelectingCodexConfig,
+    /// Commit Step 1: pick the files to commit, previewing each one's diff
+    CommitSelectFiles,
+    /// Commit Step 2: write the message and choose whether to push
+    CommitMessage,
+    /// The `Ctrl+P` command palette - typing to find a session, branch,
+    /// project, folder or action
+    CommandPalette,
+    /// The activity timeline: every session's recent states as bars
+    ViewingTimeline,
 }
 
 impl InputMode {
@@ -84,7 +93,7 @@ impl InputMode {
     /// Keep in sync with the enum; `test_all_lists_every_mode_once` fails if
     /// an entry is duplicated, and the dispatcher's routing-table test fails
     /// to compile if a new variant is missing from its match.
-    pub const ALL: [InputMode; 35] = [
+    pub const ALL: [InputMode; 39] = [
         InputMode::Normal,
         InputMode::Session,
         InputMode::CreatingSession,
@@ -120,6 +129,10 @@ impl InputMode {
         InputMode::AddingCodexConfigPath,
         InputMode::ConfirmingCodexConfigDelete,
         InputMode::SelectingCodexConfig,
+        InputMode::CommitSelectFiles,
+        InputMode::CommitMessage,
+        InputMode::CommandPalette,
+        InputMode::ViewingTimeline,
     ];
 }
 
@@ -7,14 +7,21 @@
 mod background;
 mod input_mode;
 mod nav;
+mod palette;
+mod reload;
 mod state;
 
 // Re-exports from submodules
 pub use input_mode::InputMode;
 pub use nav::{Focus, ProjectsNav, SettingsNav, Tab};
+pub use palette::{
+    fuzzy_score, palette_entries, rotation_entries, CommandPaletteState, PaletteAction,
+    PaletteEntry, PaletteTarget, MAX_PALETTE_QUERY_LEN,
+};
 pub use state::{
     cycle_next, cycle_prev, AppState, ClaudeSettingsCopyState, ClaudeSettingsMigrateState,
-    FolderMoveTarget, LoadingOverlay, SessionDraft, WorktreeWizardState,
+    CommitWizardState, DraftRequest, DraftStep, FolderMoveTarget, LoadingOverlay, SessionDraft,
+    TimelinePage, TimelineViewState, WorktreeWizardState, MAX_TILES, TIMELINE_WINDOWS,
 };
 
 // Re-exports from wizards (for backwards compatibility)
@@ -35,20 +42,24 @@ use crate::hooks::{
 };
 use crate::input::agent_configs::AgentKind;
 use crate::logging::LogFileInfo;
+use crate::notify::rules::NotifyTarget;
+use crate::notify::{self, Notification};
 use crate::project::{BranchId, ProjectId, ProjectStore};
-use crate::session::{mouse_event_to_bytes, SessionId, SessionManager, SessionType};
+use crate::session::{
+    mouse_event_to_bytes, AttentionReason, SessionId, SessionManager, SessionType,
+};
 use crate::transcript::{TranscriptKind, TranscriptWatcher, WatchTarget};
 use crate::tui::frame::{FrameConfig, FrameLayout};
 use crate::tui::panes::PaneLayout;
 use crate::tui::views::{
-    render_agent_config_delete_dialog, render_agent_config_name_input_dialog,
-    render_agent_config_path_input_dialog, render_agent_config_selector,
-    render_agent_type_selector, render_branch_delete_confirmation,
+    render_activity_timeline, render_agent_config_delete_dialog,
+    render_agent_config_name_input_dialog, render_agent_config_path_input_dialog,
+    render_agent_config_selector, render_agent_type_selector, render_branch_delete_confirmation,
     render_claude_settings_copy_dialog, render_claude_settings_migrate_dialog,
-    render_custom_shortcut_dialogs, render_default_base_selector, render_error_overlay,
-    render_folder_move_dialog, render_folder_remove_confirmation, render_help_overlay,
-    render_loading_indicator, render_panes, render_project_addition_dialog,
-    render_project_delete_confirmation, render_quit_confirm_dialog,
+    render_command_palette, render_commit_wizard, render_custom_shortcut_dialogs,
+    render_default_base_selector, render_error_overlay, render_folder_move_dialog,
+    render_folder_remove_confirmation, render_help_overlay, render_loading_indicator, render_panes,
+    render_project_addition_dialog, render_project_delete_confirmation, render_quit_confirm_dialog,
     render_session_delete_confirmation, render_session_view, render_startup_notice_overlay,
     render_worktree_wizard, PaneContext,
 };
@@ -69,6 +80,8 @@ pub const MAX_SESSION_NAME_LEN: usize = 256;
 pub const MAX_BRANCH_NAME_LEN: usize = 256;
 /// Maximum length for project names
 pub const MAX_PROJECT_NAME_LEN: usize = 256;
+/// Maximum length for commit messages
+pub const MAX_COMMIT_MESSAGE_LEN: usize = 4096;
 /// Maximum length for custom shortcut names (paste only; typing is unbounded)
 pub const MAX_SHORTCUT_NAME_LEN: usize = 256;
 /// Maximum length for custom shortcut commands (paste only; typing is unbounded)
@@ -114,6 +127,17 @@ pub struct App {
     last_transcript_sync: Option<Instant>,
     /// Git work running off the event loop (at most one at a time)
     background_job: Option<background::BackgroundJob>,
+    /// Changed-file overlap scan in flight, if any
+    overlap_scan: Option<std::sync::mpsc::Receiver<crate::project::FileOverlaps>>,
+    /// When the last overlap scan was started
+    last_overlap_scan: Option<Instant>,
+    /// Codex sessions already told their account is rate-limited, so the
+    /// header says it once per crossing rather than every tick
+    rotation_noticed: std::collections::HashSet<SessionId>,
+    /// How far each session left waiting has been re-notified
+    escalations: notify::escalation::Escalations,
+    /// `config.toml` and the account stores, polled for hand edits
+    watched_files: reload::WatchedFiles,
 }
 
 /// How often to reconcile transcript watching against the live session list
@@ -122,6 +146,12 @@ pub struct App {
 /// thread polls the files themselves far more often.
 const TRANSCRIPT_SYNC_INTERVAL: Duration = Duration::from_secs(2);
 
+/// How often worktrees are re-diffed to find files two branches both changed
+///
+/// Agents edit in bursts and the point is an early warning, not a live view,
+/// so this is slow: diffing every worktree of every project is not free.
+const OVERLAP_SCAN_INTERVAL: Duration = Duration::from_secs(30);
+
 /// The default `CLAUDE_CONFIG_DIR`, used when a session ran on the default account
 fn default_claude_config_dir() -> PathBuf {
     dirs::home_dir()
@@ -154,8 +184,15 @@ impl App {
             startup_warnings.push(warning);
         }
         // Pin the palette tier before the first render; `auto` reads
-        // COLORTERM/TERM, the other modes force a tier
-        crate::tui::theme::init(config.theme);
+        // COLORTERM/TERM, the other modes force a tier. A theme that fails to
+        // load falls back to dark rather than failing startup.
+        if let Some(warning) = crate::tui::theme::init(
+            config.theme,
+            &config.color_theme,
+            &crate::config::themes_dir(),
+        ) {
+            startup_warnings.push(warning);
+        }
         // A shortcut bound to a key that has since become reserved could never
         // fire - the built-in arm matches first - so it is dropped rather than
         // silently shadowed, and the user is told which ones went.
@@ -165,6 +202,18 @@ impl App {
                 tracing::warn!("Failed to persist shortcut migration: {}", e);
             }
         }
+        // A bad binding costs that binding, not the rest: each one is skipped
+        // and named in the notice, and the built-in key stays in its place
+        let (keymap, keymap_warnings) =
+            crate::keymap::Keymap::from_config(&config.keys, &config.custom_shortcuts);
+        startup_warnings.extend(keymap_warnings);
+        startup_warnings.extend(
+            config
+                .notify_on
+                .quiet_hours
+                .as_ref()
+                .and_then(|q| q.problem()),
+        );
         let mouse_debug_enabled = mouse_debug_enabled_from_env();
 
         // Load project store (or create empty if doesn't exist)
@@ -218,7 +267,10 @@ impl App {
         let terminal_width = tui.size().map(|size| size.width).unwrap_or(80);
         let panes = PaneLayout::new(terminal_width, Tab::default().index(), Instant::now());
 
-        let mut state = AppState::default();
+        let mut state = AppState {
+            keymap,
+            ..Default::default()
+        };
         // Surface any startup warnings (corrupt-file backups, etc.) as a
         // persistent, dismissable overlay rather than a 5-second toast that
         // scrolls away before it can be read
@@ -234,6 +286,13 @@ impl App {
             );
         }
 
+        // Started before the stores move into the app, from their paths
+        let watched_files = reload::WatchedFiles::new(
+            crate::config::config_file_path(),
+            claude_config_store.path().to_path_buf(),
+            codex_config_store.path().to_path_buf(),
+        );
+
         Ok(Self {
             config,
             state,
@@ -253,6 +312,11 @@ impl App {
             watched_transcripts: HashMap::new(),
             last_transcript_sync: None,
             background_job: None,
+            overlap_scan: None,
+            last_overlap_scan: None,
+            rotation_noticed: std::collections::HashSet::new(),
+            escalations: notify::escalation::Escalations::default(),
+            watched_files,
         })
     }
 
@@ -352,6 +416,7 @@ impl App {
             }
 
             let mut dirty = false;
+            dirty |= self.tick_config_reload();
             dirty |= self.tick_pane_transition();
             dirty |= self.tick_background_job();
             dirty |= self.tick_resize_debounce()?;
@@ -367,6 +432,9 @@ impl App {
             self.sync_transcript_watchers();
             dirty |= self.process_transcript_events();
             dirty |= self.tick_state_timeouts();
+            dirty |= self.tick_budgets();
+            dirty |= self.tick_escalations();
+            dirty |= self.tick_codex_rotation();
             // Whatever the events above flagged, the session filling the screen
             // is not one the user needs pointing at
             self.acknowledge_visible_session();
@@ -374,8 +442,11 @@ impl App {
             dirty |= self.tick_auto_close();
             dirty |= self.tick_idle_suspension();
             dirty |= self.tick_exited_cleanup();
+            dirty |= self.tick_session_tiles()?;
             dirty |= self.tick_dropped_events();
             dirty |= self.tick_server_health();
+            dirty |= self.tick_overlap_scan();
+            dirty |= self.tick_commit_draft();
             if dirty {
                 self.state.needs_render = true;
             }
@@ -473,6 +544,151 @@ impl App {
         Ok(handled)
     }
 
+    /// Apply hand edits to `config.toml` and the account stores (see
+    /// [`reload`])
+    fn tick_config_reload(&mut self) -> bool {
+        if !self.watched_files.due(Instant::now()) {
+            return false;
+        }
+        let mut changed = false;
+        if self.watched_files.config.changed() {
+            changed |= self.reload_config();
+        }
+        if self.watched_files.claude_configs.changed() {
+            changed |= self.reload_claude_configs();
+        }
+        if self.watched_files.codex_configs.changed() {
+            changed |= self.reload_codex_configs();
+        }
+        changed
+    }
+
+    /// Re-read `config.toml` and apply everything that can change live
+    ///
+    /// Settings fixed at startup are still taken into `self.config`, so the
+    /// next save from the Settings pane keeps the edit; the header says they
+    /// wait for a restart.
+    fn reload_config(&mut self) -> bool {
+        let mut edited = match Config::reload() {
+            Ok(Some(config)) => config,
+            Ok(None) => return false,
+            Err(e) => {
+                tracing::warn!("Not reloading config: {:#}", e);
+                self.state
+                    .header_notifications
+                    .push(format!("config.toml not reloaded: {:#}", e));
+                return true;
+            }
+        };
+        if reload::same_config(&self.config, &edited) {
+            return false;
+        }
+
+        let mut warnings = Vec::new();
+        if let Some(warning) = edited.drop_reserved_shortcuts() {
+            warnings.push(warning);
+        }
+        if edited.color_theme != self.config.color_theme {
+            if let Err(e) =
+                crate::tui::theme::switch(&edited.color_theme, &crate::config::themes_dir())
+            {
+                warnings.push(format!("{:#}. Keeping the current theme.", e));
+            }
+        }
+        if edited.log_agent_events != self.config.log_agent_events {
+            self.transcripts.set_debug_log_dir(
+                edited
+                    .log_agent_events
+                    .then(|| crate::config::logs_dir().join("agent-events")),
+            );
+        }
+        let (keymap, keymap_warnings) =
+            crate::keymap::Keymap::from_config(&edited.keys, &edited.custom_shortcuts);
+        self.state.keymap = keymap;
+        warnings.extend(keymap_warnings);
+        if edited.notify_on.quiet_hours != self.config.notify_on.quiet_hours {
+            warnings.extend(
+                edited
+                    .notify_on
+                    .quiet_hours
+                    .as_ref()
+                    .and_then(|q| q.problem()),
+            );
+        }
+
+        let restart = reload::needs_restart(&self.config, &edited);
+        self.config = edited;
+        self.sessions.apply_runtime_config(&self.config);
+        self.state.custom_shortcuts_selected = self
+            .state
+            .custom_shortcuts_selected
+            .min(self.config.custom_shortcuts.len().saturating_sub(1));
+
+        self.state.header_notifications.push(if restart.is_empty() {
+            "Reloaded config.toml".to_string()
+        } else {
+            format!(
+                "Reloaded config.toml - restart to apply {}",
+                restart.join(", ")
+            )
+        });
+        if !warnings.is_empty() {
+            let notice = self.state.startup_notice.take().into_iter();
+            self.state.startup_notice = Some(notice.chain(warnings).collect::<Vec<_>>().join("\n"));
+        }
+        true
+    }
+
+    /// Re-read the Claude account store after a hand edit
+    fn reload_claude_configs(&mut self) -> bool {
+        match self.claude_config_store.reload() {
+            Ok(Some(store)) if store != self.claude_config_store => {
+                self.claude_config_store = store;
+                self.state.claude_configs_selected_index = self
+                    .state
+                    .claude_configs_selected_index
+                    .min(self.claude_config_store.count().saturating_sub(1));
+                self.state
+                    .header_notifications
+                    .push("Reloaded Claude accounts");
+                true
+            }
+            Ok(_) => false,
+            Err(e) => {
+                tracing::warn!("Not reloading Claude accounts: {:#}", e);
+                self.state
+                    .header_notifications
+                    .push(format!("Claude accounts not reloaded: {:#}", e));
+                true
+            }
+        }
+    }
+
+    /// Re-read the Codex account store after a hand edit
+    fn reload_codex_configs(&mut self) -> bool {
+        match self.codex_config_store.reload() {
+            Ok(Some(store)) if store != self.codex_config_store => {
+                self.codex_config_store = store;
+                self.state.codex_configs_selected_index = self
+                    .state
+                    .codex_configs_selected_index
+                    .min(self.codex_config_store.count().saturating_sub(1));
+                self.state
+                    .header_notifications
+                    .push("Reloaded Codex accounts");
+                true
+            }
+            Ok(_) => false,
+            Err(e) => {
+                tracing::warn!("Not reloading Codex accounts: {:#}", e);
+                self.state
+                    .header_notifications
+                    .push(format!("Codex accounts not reloaded: {:#}", e));
+                true
+            }
+        }
+    }
+
     /// Process debounced resize: wait 50ms after last resize event before actually resizing.
     /// Resize ALL sessions to keep their PTYs in sync with terminal dimensions.
     fn tick_resize_debounce(&mut self) -> Result<bool> {
@@ -502,6 +718,9 @@ impl App {
         );
         let (rows, cols) = layout.pty_size();
         self.sessions.resize_all(cols, rows);
+        if !self.state.session_tiles.is_empty() {
+            self.resize_active_session_pty()?;
+        }
         Ok(true)
     }
 
@@ -544,6 +763,160 @@ impl App {
             .check_state_timeouts(self.config.state_timeout_secs)
     }
 
+    /// Flag, and if configured interrupt, sessions over a spending limit
+    fn tick_budgets(&mut self) -> bool {
+        let alerts = self
+            .sessions
+            .check_budgets(&self.project_store, chrono::Utc::now());
+        for alert in &alerts {
+            let action = if alert.interrupted {
+                "interrupted"
+            } else {
+                "still running"
+            };
+            self.state.header_notifications.push(format!(
+                "Session '{}' is over budget ({}) - {}",
+                alert.session_name, alert.limit, action
+            ));
+            if alert.rings {
+                self.notify_session_needs_attention(alert.session_id);
+            }
+        }
+        !alerts.is_empty()
+    }
+
+    /// Re-notify about sessions whose attention has gone unacknowledged for
+    /// an `[escalation]` step's time
+    fn tick_escalations(&mut self) -> bool {
+        let escalation = &self.config.escalation;
+        let waiting: Vec<(SessionId, AttentionReason)> = self
+            .sessions
+            .iter()
+            .filter_map(|(&id, session)| {
+                let reason = session.info.attention.as_ref()?;
+                escalation.covers(reason).then(|| (id, reason.clone()))
+            })
+            .collect();
+        let due = self.escalations.due(escalation, &waiting, Instant::now());
+        for due in &due {
+            self.escalate(due);
+        }
+        !due.is_empty()
+    }
+
+    /// Fire one escalation step for a session still waiting on the user
+    ///
+    /// The step goes through the same gates as the first notification: a
+    /// reason `[notify_on]` has off never escalates, the rules can silence
+    /// it, and the session on screen gets no sound or command.
+    fn escalate(&mut self, due: &notify::escalation::Due) {
+        let Some(step) = self.config.escalation.steps.get(due.step) else {
+            return;
+        };
+        let Some(reason) = self
+            .sessions
+            .get(due.session_id)
+            .and_then(|session| session.info.attention.clone())
+        else {
+            return;
+        };
+        if !self.config.notify_on.rings(&reason) || !self.notification_allowed(due.session_id) {
+            return;
+        }
+
+        let mut notification = self.notification_for(due.session_id, "Session", "needs attention");
+        notification.summary = format!(
+            "{} - waiting {}m",
+            notification.summary,
+            due.waited.as_secs() / 60
+        );
+        self.state.header_notifications.push(format!(
+            "Session '{}' is still waiting: {}",
+            notification.session, notification.summary
+        ));
+
+        if step.webhooks {
+            notify::webhook::send(&self.config.webhooks, &reason, &notification);
+        }
+        if self.state.active_session == Some(due.session_id) {
+            return;
+        }
+        let method = step.method.unwrap_or(self.config.notification_method);
+        notify::send(method, &notification);
+        if step.command {
+            notify::command::run(&self.config.notify_commands, &reason, &notification);
+        }
+    }
+
+    /// Offer, or with `[codex_rotation] auto` make, a move off a Codex
+    /// account that has used up its rate limit
+    fn tick_codex_rotation(&mut self) -> bool {
+        let offers = self.codex_rotation_offers();
+        self.rotation_noticed
+            .retain(|id| offers.iter().any(|offer| offer.session_id == *id));
+
+        let mut changed = false;
+        for offer in offers {
+            if self.rotation_noticed.contains(&offer.session_id) {
+                continue;
+            }
+            let Some((to, to_name)) = offer.to.clone() else {
+                self.rotation_noticed.insert(offer.session_id);
+                self.state.header_notifications.push(format!(
+                    "Codex account '{}' is at {:.0}% of its rate limit and no other account has room",
+                    offer.from_name, offer.used_percent
+                ));
+                changed = true;
+                continue;
+            };
+
+            if !self.config.codex_rotation.auto {
+                self.rotation_noticed.insert(offer.session_id);
+                self.state.header_notifications.push(format!(
+                    "Codex account '{}' is at {:.0}% - Ctrl+P \"Move {} to {}\" to switch",
+                    offer.from_name, offer.used_percent, offer.session_name, to_name
+                ));
+                changed = true;
+                continue;
+            }
+
+            // A turn in progress is left to finish; the next tick tries again
+            let busy = self
+                .sessions
+                .get(offer.session_id)
+                .is_some_and(|s| s.info.state.is_mid_turn());
+            if busy {
+                continue;
+            }
+            self.rotation_noticed.insert(offer.session_id);
+            if let Ok(true) = self.move_to_codex_account(offer.session_id, to) {
+                self.state.header_notifications.push(format!(
+                    "Moved '{}' from Codex account '{}' ({:.0}% used) to '{}'",
+                    offer.session_name, offer.from_name, offer.used_percent, to_name
+                ));
+            }
+            changed = true;
+        }
+        changed
+    }
+
+    /// Codex sessions whose account is over the rotation threshold
+    pub(crate) fn codex_rotation_offers(&self) -> Vec<crate::session::RotationOffer> {
+        let sessions: Vec<&crate::session::SessionInfo> = self
+            .sessions
+            .sessions_in_order()
+            .into_iter()
+            .map(|session| &session.info)
+            .collect();
+        crate::session::rate_limits::rotation_offers(
+            &sessions,
+            self.sessions.codex_limits(),
+            &self.codex_config_store.configs_sorted(),
+            self.config.codex_rotation.threshold_percent,
+            chrono::Utc::now(),
+        )
+    }
+
     /// Check shell session states via foreground detection
     fn tick_shell_state_notifications(&mut self) -> bool {
         let shell_notifications = self.sessions.check_shell_states(self.state.active_session);
@@ -554,13 +927,11 @@ impl App {
         // Send notifications for shell sessions that finished commands
         for session_id in shell_notifications {
             let is_active = self.state.active_session == Some(session_id);
-            if !is_active {
-                let session_name = self
-                    .sessions
-                    .get(session_id)
-                    .map(|s| s.info.name.as_str())
-                    .unwrap_or("Shell");
-                SessionManager::send_notification(self.config.notification_method, session_name);
+            if !is_active && self.notification_allowed(session_id) {
+                let notification = self.notification_for(session_id, "Shell", "command finished");
+                notify::send(self.config.notification_method, &notification);
+                self.run_notify_command(session_id);
+                self.send_webhooks(session_id);
             }
         }
         true
@@ -668,6 +1039,128 @@ impl App {
         }
     }
 
+    /// Ask for the commit message draft, and fill it in once the agent replies
+    ///
+    /// The prompt waits until the session is between turns: typed into a
+    /// turn under way it would interleave with it. The reply is the
+    /// `last_message` of the first turn to end after the prompt went in. A
+    /// message the user has typed into since is theirs: the draft then only
+    /// gets announced, not applied.
+    fn tick_commit_draft(&mut self) -> bool {
+        let Some(request) = self.state.commit_wizard.drafting.clone() else {
+            return false;
+        };
+        let Some(session) = self.sessions.get_mut(request.session_id) else {
+            self.state.commit_wizard.drafting = None;
+            return false;
+        };
+        let name = session.info.name.clone();
+
+        let reply = match request.step(&session.info) {
+            DraftStep::Wait => return false,
+            DraftStep::Send => {
+                let sent_at = chrono::Utc::now();
+                let before = session.info.last_message.clone();
+                let sent = session
+                    .write_paste(crate::wizards::commit::COMMIT_DRAFT_PROMPT)
+                    .and_then(|()| {
+                        session.send_key(KeyEvent::from(crossterm::event::KeyCode::Enter))
+                    });
+                match sent {
+                    Ok(()) => {
+                        self.state.commit_wizard.drafting = Some(DraftRequest {
+                            sent_at: Some(sent_at),
+                            before,
+                            ..request
+                        });
+                        self.state
+                            .header_notifications
+                            .push(format!("Asked {} to draft the message", name));
+                    }
+                    Err(e) => {
+                        self.state.commit_wizard.drafting = None;
+                        self.state.error_message = Some(format!("Could not ask {}: {}", name, e));
+                    }
+                }
+                return true;
+            }
+            DraftStep::Exited => {
+                self.state.commit_wizard.drafting = None;
+                self.state
+                    .header_notifications
+                    .push(format!("{} exited before drafting the message", name));
+                return true;
+            }
+            DraftStep::NoReply => {
+                self.state.commit_wizard.drafting = None;
+                self.state
+                    .header_notifications
+                    .push(format!("{}'s turn ended without a draft", name));
+                return true;
+            }
+            DraftStep::Reply(reply) => reply,
+        };
+
+        let draft = reply
+            .trim()
+            .trim_matches('`')
+            .trim_matches('"')
+            .trim()
+            .to_string();
+        let wizard = &mut self.state.commit_wizard;
+        wizard.drafting = None;
+        if wizard.message_edited {
+            self.state
+                .header_notifications
+                .push("Agent replied, but the message was edited meanwhile");
+        } else {
+            wizard.message = draft;
+            self.state
+                .header_notifications
+                .push("Commit message drafted by the agent");
+        }
+        true
+    }
+
+    /// Start a changed-file overlap scan when one is due, and apply the last
+    ///
+    /// The diffing happens on a worker thread; this only hands it the current
+    /// worktree list and picks up the answer, so it never costs a frame.
+    fn tick_overlap_scan(&mut self) -> bool {
+        if let Some(rx) = &self.overlap_scan {
+            return match rx.try_recv() {
+                Ok(overlaps) => {
+                    self.overlap_scan = None;
+                    self.state.file_overlaps = overlaps;
+                    true
+                }
+                Err(std::sync::mpsc::TryRecvError::Empty) => false,
+                Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                    tracing::warn!("Overlap scan ended without a result");
+                    self.overlap_scan = None;
+                    false
+                }
+            };
+        }
+
+        if let Some(last) = self.last_overlap_scan {
+            if last.elapsed() < OVERLAP_SCAN_INTERVAL {
+                return false;
+            }
+        }
+        self.last_overlap_scan = Some(Instant::now());
+
+        let scans = crate::project::overlap::scans_for(&self.project_store);
+        if scans.is_empty() {
+            // Nothing left to compare: drop any warning from an earlier scan
+            let had_overlaps = !self.state.file_overlaps.is_empty();
+            self.state.file_overlaps = crate::project::FileOverlaps::default();
+            return had_overlaps;
+        }
+        self.overlap_scan = Some(crate::project::overlap::spawn_scans(scans));
+        false
+    }
+
     /// Process pending hook events from the channel
     ///
     /// Processes all events sequentially to ensure no events are dropped.
@@ -733,6 +1226,7 @@ impl App {
                 crate::input::agent_configs::update_config_path_completions(self)
             }
             InputMode::MovingToFolder => crate::input::text_input::update_folder_completions(self),
+            InputMode::CommandPalette => self.state.command_palette.refilter(),
             InputMode::SelectingDefaultBase => {
                 self.state.filtered_branch_refs = filter_branch_refs(
                     &self.state.available_branch_refs,
@@ -805,6 +1299,11 @@ impl App {
                 state.shortcut_error = None;
                 (&mut state.new_shortcut_command, MAX_SHORTCUT_COMMAND_LEN)
             }
+            InputMode::CommitMessage => {
+                state.commit_wizard.message_edited = true;
+                (&mut state.commit_wizard.message, MAX_COMMIT_MESSAGE_LEN)
+            }
+            InputMode::CommandPalette => (&mut state.command_palette.query, MAX_PALETTE_QUERY_LEN),
             _ => return None,
         };
 
@@ -1037,14 +1536,25 @@ impl App {
         self.state.worktree_wizard = WorktreeWizardState::default();
         self.state.fetch_error = None;
 
-        let (project_name, repo_path) = {
+        let (project_name, repo_path, kind) = {
             let project = self
                 .project_store
                 .get_project(project_id)
                 .context("Project not found")?;
-            (project.name.clone(), project.repo_path.clone())
+            (
+                project.name.clone(),
+                project.repo_path.clone(),
+                project.kind.clone(),
+            )
         };
+        if !kind.has_worktrees() {
+            anyhow::bail!(
+                "'{}' is not a git repository: it has no worktrees",
+                project_name
+            );
+        }
         self.state.worktree_wizard.project_name = project_name;
+        self.state.worktree_wizard.multi_repo = !kind.is_git();
 
         // Get tracked branch names for this project
         let tracked_branches: std::collections::HashSet<String> = self
@@ -1054,6 +1564,18 @@ impl App {
             .map(|b| b.name.clone())
             .collect();
 
+        if !kind.is_git() {
+            // A multi-repository branch is several worktrees: there is no one
+            // untracked worktree to import, and no one stash list to start from
+            return self.spawn_branch_fetch(
+                project_id,
+                background::JobFollowUp::OpenWorktreeWizard {
+                    tracked_branches,
+                    git_worktree_branches: std::collections::HashSet::new(),
+                },
+            );
+        }
+
         // Get existing git worktrees to detect untracked worktrees. This is a
         // local, fast read, so it stays on this thread.
         let git = crate::git::GitOps::open(&repo_path).context("Failed to open git repository")?;
@@ -1067,6 +1589,13 @@ impl App {
             };
         drop(git);
 
+        // Stashes for step 2's "start from a stash"; also a local read
+        self.state.worktree_wizard.stashes = crate::git::seed::list_stashes(&repo_path)
+            .unwrap_or_else(|e| {
+                tracing::warn!("Failed to list stashes: {}", e);
+                Vec::new()
+            });
+
         self.spawn_branch_fetch(
             project_id,
             background::JobFollowUp::OpenWorktreeWizard {
@@ -1103,22 +1632,25 @@ impl App {
         project_id: ProjectId,
         follow_up: background::JobFollowUp,
     ) -> Result<()> {
-        let (repo_path, default_base_branch) = {
+        let (repo_paths, default_base_branch) = {
             let project = self
                 .project_store
                 .get_project(project_id)
                 .context("Project not found")?;
-            (
-                project.repo_path.clone(),
-                project.default_base_branch.clone(),
-            )
+            if project.repo_paths().is_empty() {
+                anyhow::bail!(
+                    "'{}' is not a git repository: it has no branches",
+                    project.name
+                );
+            }
+            (project.repo_paths(), project.default_base_branch.clone())
         };
 
         self.spawn_git_job(
             "Fetching branches from remotes...",
             true,
             background::GitTask::FetchAndListBranches {
-                repo_path,
+                repo_paths,
                 default_base_branch,
             },
             follow_up,
@@ -1128,7 +1660,8 @@ impl App {
 
     /// Create a worktree for a branch on a worker thread
     ///
-    /// The branch is registered and navigated to from
+    /// `seed`, when given, is applied to the new checkout as uncommitted
+    /// changes. The branch is registered and navigated to from
     /// [`Self::register_created_worktree`] once git is done.
     pub(crate) fn create_worktree(
         &mut self,
@@ -1136,13 +1669,20 @@ impl App {
         branch_name: &str,
         create_branch: bool,
         base_ref: Option<&str>,
+        seed: Option<crate::git::seed::WorktreeSeed>,
     ) -> Result<()> {
         // Get project info and clone what we need
-        let (repo_path, project_name) = {
+        let (repo_path, project_name, setup, kind, commands) = {
             let Some(project) = self.project_store.get_project(project_id) else {
                 anyhow::bail!("Project not found");
             };
-            (project.repo_path.clone(), project.name.clone())
+            (
+                project.repo_path.clone(),
+                project.name.clone(),
+                project.worktree_setup,
+                project.kind.clone(),
+                project.setup_commands().to_vec(),
+            )
         };
 
         let worktree_path = crate::git::worktree::worktree_path_for_branch(
@@ -1151,15 +1691,47 @@ impl App {
             branch_name,
         );
 
+        if let crate::project::ProjectKind::MultiRepo { repos } = kind {
+            // Sessions start in the shared directory, so that is the branch's
+            // working dir; each repository's worktree sits inside it
+            let checkouts = repos
+                .iter()
+                .map(|repo| (repo_path.join(repo), worktree_path.join(repo)))
+                .collect();
+            self.spawn_git_job(
+                &format!("Creating worktrees for '{}'...", branch_name),
+                !commands.is_empty(),
+                background::GitTask::CreateMultiRepoWorktree {
+                    checkouts,
+                    parent: worktree_path.clone(),
+                    branch_name: branch_name.to_string(),
+                    base_ref: base_ref.map(String::from),
+                    setup,
+                    commands,
+                },
+                background::JobFollowUp::RegisterWorktree {
+                    project_id,
+                    branch_name: branch_name.to_string(),
+                    worktree_path,
+                },
+            );
+            return Ok(());
+        }
+
+        // Setup commands (`npm ci`) can run for minutes, or hang; Esc stops
+        // them and keeps the worktree git has already made
         self.spawn_git_job(
             &format!("Creating worktree for '{}'...", branch_name),
-            false,
+            !commands.is_empty(),
             background::GitTask::CreateWorktree {
                 repo_path,
                 branch_name: branch_name.to_string(),
                 worktree_path: worktree_path.clone(),
                 create_branch,
                 base_ref: base_ref.map(String::from),
+                setup,
+                seed,
+                commands,
             },
             background::JobFollowUp::RegisterWorktree {
                 project_id,
@@ -1170,6 +1742,99 @@ impl App {
         Ok(())
     }
 
+    /// Start the commit wizard on a branch's checkout (Step 1)
+    ///
+    /// Reading the working-tree status is a local, fast read, so it stays on
+    /// this thread; only the commit and push go to a worker. The message starts
+    /// as the branch agent's last reply, which is usually a summary of the work.
+    pub(crate) fn start_commit_wizard(&mut self, branch_id: BranchId) -> Result<()> {
+        let (branch_name, checkout) = {
+            let branch = self
+                .project_store
+                .get_branch(branch_id)
+                .context("Branch not found")?;
+            (branch.name.clone(), branch.working_dir.clone())
+        };
+        if let Some(project) = self
+            .project_store
+            .get_branch(branch_id)
+            .and_then(|branch| self.project_store.get_project(branch.project_id))
+        {
+            match project.kind {
+                crate::project::ProjectKind::Git => {}
+                crate::project::ProjectKind::Directory => {
+                    anyhow::bail!("'{}' is not a git repository", project.name)
+                }
+                crate::project::ProjectKind::MultiRepo { .. } => anyhow::bail!(
+                    "'{}' spans several repositories: commit from a session in each one",
+                    project.name
+                ),
+            }
+        }
+
+        let git =
+            crate::git::GitOps::discover(&checkout).context("Failed to open git repository")?;
+        let changes = git.working_changes()?;
+        if changes.is_empty() {
+            self.state
+                .header_notifications
+                .push(format!("Nothing to commit on '{}'", branch_name));
+            return Ok(());
+        }
+        let (push_target, push_blocker) = match git.push_target() {
+            Ok(target) => (Some(target.to_string()), None),
+            Err(e) => (None, Some(format!("{:#}", e))),
+        };
+        drop(git);
+
+        let message = crate::wizards::commit::latest_agent_message(&self.sessions, branch_id)
+            .unwrap_or_default();
+        self.state.commit_wizard = CommitWizardState {
+            branch_id: Some(branch_id),
+            branch_name,
+            checkout,
+            selected: vec![true; changes.len()],
+            changes,
+            message,
+            push: push_target.is_some(),
+            push_target,
+            push_blocker,
+            ..Default::default()
+        };
+        crate::wizards::commit::refresh_commit_diff_preview(&mut self.state);
+        self.state.input_mode = InputMode::CommitSelectFiles;
+        Ok(())
+    }
+
+    /// Commit (and optionally push) on a worker thread
+    ///
+    /// The outcome is reported from [`Self::report_commit`] once git is done.
+    pub(crate) fn spawn_commit(
+        &mut self,
+        checkout: PathBuf,
+        branch_name: String,
+        paths: Vec<String>,
+        message: String,
+        push: bool,
+    ) {
+        let label = if push {
+            format!("Committing and pushing '{}'...", branch_name)
+        } else {
+            format!("Committing on '{}'...", branch_name)
+        };
+        self.spawn_git_job(
+            &label,
+            false,
+            background::GitTask::CommitAndPush {
+                checkout,
+                paths,
+                message,
+                push,
+            },
+            background::JobFollowUp::ReportCommit { branch_name },
+        );
+    }
+
     /// Import an existing git worktree that is not tracked by Panoptes
     ///
     /// This finds the worktree path from git's worktree list and creates a Branch
@@ -1289,15 +1954,23 @@ impl App {
     /// Resize the active session's PTY to match the output viewport
     ///
     /// Uses FrameLayout to calculate the correct PTY dimensions, ensuring
-    /// consistency with how the session view is rendered.
+    /// consistency with how the session view is rendered. When sessions are
+    /// tiled, every tiled session is sized to its own tile instead.
     pub(crate) fn resize_active_session_pty(&mut self) -> Result<()> {
+        let layout = self.session_view_layout()?;
+
+        if !self.state.session_tiles.is_empty() {
+            let tiles = layout.tiles(self.state.session_tiles.len());
+            for (session_id, tile) in self.state.session_tiles.iter().zip(tiles) {
+                let content = crate::tui::frame::tile_content(tile);
+                if let Some(session) = self.sessions.get_mut(*session_id) {
+                    session.resize(content.width, content.height)?;
+                }
+            }
+            return Ok(());
+        }
+
         if let Some(session_id) = self.state.active_session {
-            let size = self.tui.size()?;
-            let frame_config = FrameConfig::default();
-            let layout = FrameLayout::calculate(
-                ratatui::prelude::Rect::new(0, 0, size.width, size.height),
-                &frame_config,
-            );
             let (rows, cols) = layout.pty_size();
 
             if let Some(session) = self.sessions.get_mut(session_id) {
@@ -1307,6 +1980,69 @@ impl App {
         Ok(())
     }
 
+    /// The session view's layout for the current terminal size
+    fn session_view_layout(&self) -> Result<FrameLayout> {
+        let size = self.tui.size()?;
+        Ok(FrameLayout::calculate(
+            ratatui::prelude::Rect::new(0, 0, size.width, size.height),
+            &FrameConfig::default(),
+        ))
+    }
+
+    /// Tile `count` sessions in the session view, resizing their PTYs
+    ///
+    /// Sessions that leave the tiles go back to the full-screen size, so they
+    /// are not left drawing for a tile that is no longer there.
+    pub(crate) fn set_tile_count(&mut self, count: usize) -> Result<()> {
+        let before = self.state.session_tiles.clone();
+        let order = self.sessions.session_ids().to_vec();
+        self.state.set_tile_count(count, &order);
+        self.resize_untiled_sessions(&before)?;
+        self.resize_active_session_pty()
+    }
+
+    /// Give sessions that were in `before` but no longer have a tile their
+    /// full-screen size back
+    pub(crate) fn resize_untiled_sessions(&mut self, before: &[SessionId]) -> Result<()> {
+        let (rows, cols) = self.session_view_layout()?.pty_size();
+        for session_id in before {
+            if !self.state.session_tiles.contains(session_id) {
+                if let Some(session) = self.sessions.get_mut(*session_id) {
+                    session.resize(cols, rows)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Drop tiles whose session has gone (closed, cleaned up)
+    ///
+    /// The remaining tiles grow into the space, so their PTYs are resized.
+    fn tick_session_tiles(&mut self) -> Result<bool> {
+        let sessions = &self.sessions;
+        let before = self.state.session_tiles.len();
+        self.state
+            .session_tiles
+            .retain(|id| sessions.get(*id).is_some());
+        if self.state.session_tiles.len() == before {
+            return Ok(false);
+        }
+        if self.state.session_tiles.len() < 2 {
+            self.state.session_tiles.clear();
+        }
+        if let Some(&first) = self.state.session_tiles.first() {
+            if !self
+                .state
+                .active_session
+                .is_some_and(|id| self.state.session_tiles.contains(&id))
+            {
+                self.state.active_session = Some(first);
+            }
+        }
+        self.resize_active_session_pty()?;
+        Ok(true)
+    }
+
     /// Resolve conversation IDs for Codex sessions that do not have one yet
     ///
     /// Codex offers no flag to dictate its session ID, so unlike Claude Code it
@@ -1521,15 +2257,134 @@ impl App {
 
     /// Sound the configured notification for a session, unless the user is
     /// already looking at it
+    ///
+    /// Webhooks are the exception: they fire for the open session too, since
+    /// having it on screen says nothing about anyone being there to see it.
+    /// The `[notify_on]` rules silence every kind alike.
     fn notify_session_needs_attention(&self, session_id: SessionId) {
+        if !self.notification_allowed(session_id) {
+            return;
+        }
+        self.send_webhooks(session_id);
         let is_active_session = self.state.active_session == Some(session_id);
         if !is_active_session {
-            let session_name = self
-                .sessions
-                .get(session_id)
-                .map(|s| s.info.name.as_str())
-                .unwrap_or("Session");
-            SessionManager::send_notification(self.config.notification_method, session_name);
+            let notification = self.notification_for(session_id, "Session", "needs attention");
+            notify::send(self.config.notification_method, &notification);
+            self.run_notify_command(session_id);
+        }
+    }
+
+    /// Whether the `[notify_on]` rules - do-not-disturb, quiet hours, recency,
+    /// project and folder - let a notification about this session through
+    fn notification_allowed(&self, session_id: SessionId) -> bool {
+        let Some(info) = self.sessions.get(session_id).map(|s| &s.info) else {
+            return true;
+        };
+        let Some(reason) = info.attention.as_ref() else {
+            return true;
+        };
+        let project = self.project_store.get_project(info.project_id);
+        let target = NotifyTarget {
+            project: project.map(|p| p.name.as_str()),
+            folder: project.map(|p| p.folder.as_slice()).unwrap_or_default(),
+            repo_rule: project
+                .and_then(|p| p.repo_config.as_ref())
+                .and_then(|config| config.notify.as_ref()),
+            created_at: info.created_at,
+        };
+        let allowed = self
+            .config
+            .notify_on
+            .allows(reason, &target, chrono::Local::now());
+        if !allowed {
+            tracing::debug!(session = %info.name, reason = reason.key(), "Notification silenced by rules");
+        }
+        allowed
+    }
+
+    /// Turn do-not-disturb on or off, and save the choice
+    pub(crate) fn toggle_do_not_disturb(&mut self) {
+        self.config.notify_on.do_not_disturb = !self.config.notify_on.do_not_disturb;
+        self.sessions.apply_runtime_config(&self.config);
+        if let Err(e) = self.config.save() {
+            tracing::error!("Failed to save config: {}", e);
+            self.state.error_message = Some(format!("Failed to save config: {}", e));
+        }
+    }
+
+    /// Run the `[notify_commands]` entry for this session's attention reason
+    fn run_notify_command(&self, session_id: SessionId) {
+        let Some(reason) = self
+            .sessions
+            .get(session_id)
+            .and_then(|session| session.info.attention.clone())
+        else {
+            return;
+        };
+        if self.config.notify_commands.command_for(&reason).is_none() {
+            return;
+        }
+        let notification = self.notification_for(session_id, "Session", "needs attention");
+        notify::command::run(&self.config.notify_commands, &reason, &notification);
+    }
+
+    /// Fire the `[[webhooks]]` that want this session's attention reason
+    fn send_webhooks(&self, session_id: SessionId) {
+        if self.config.webhooks.is_empty() {
+            return;
+        }
+        let Some(reason) = self
+            .sessions
+            .get(session_id)
+            .and_then(|session| session.info.attention.clone())
+        else {
+            return;
+        };
+        if !self.config.notify_on.rings(&reason) {
+            return;
+        }
+        let notification = self.notification_for(session_id, "Session", "needs attention");
+        notify::webhook::send(&self.config.webhooks, &reason, &notification);
+    }
+
+    /// Describe a session for a notification: its name, why it wants the
+    /// user, and the project and branch it belongs to
+    ///
+    /// The reason is the session's attention summary when it has one, and
+    /// `default_summary` otherwise; `default_name` stands in for a session
+    /// that has already gone.
+    fn notification_for(
+        &self,
+        session_id: SessionId,
+        default_name: &str,
+        default_summary: &str,
+    ) -> Notification {
+        let Some(session) = self.sessions.get(session_id) else {
+            return Notification {
+                session: default_name.to_string(),
+                summary: default_summary.to_string(),
+                project: None,
+                branch: None,
+                last_message: None,
+            };
+        };
+        let info = &session.info;
+        Notification {
+            session: info.name.clone(),
+            summary: info
+                .attention
+                .as_ref()
+                .map(|reason| reason.summary())
+                .unwrap_or_else(|| default_summary.to_string()),
+            project: self
+                .project_store
+                .get_project(info.project_id)
+                .map(|p| p.name.clone()),
+            branch: self
+                .project_store
+                .get_branch(info.branch_id)
+                .map(|b| b.name.clone()),
+            last_message: info.last_message.clone(),
         }
     }
 
@@ -1636,6 +2491,65 @@ impl App {
         }
     }
 
+    /// Relaunch a Codex session's conversation under another account
+    ///
+    /// Returns false, having said why in the header, when the move could not
+    /// be made.
+    pub(crate) fn move_to_codex_account(
+        &mut self,
+        session_id: SessionId,
+        config_id: crate::codex_config::CodexConfigId,
+    ) -> Result<bool> {
+        let Some(session) = self.sessions.get(session_id) else {
+            return Ok(false);
+        };
+        let Some(target) = self.codex_config_store.get(config_id) else {
+            return Ok(false);
+        };
+        let from_home = session
+            .info
+            .codex_config_id
+            .and_then(|id| self.codex_config_store.get(id))
+            .and_then(|config| config.codex_home.clone())
+            .unwrap_or_else(default_codex_home);
+        let to_home = target.codex_home.clone().unwrap_or_else(default_codex_home);
+        let account = crate::session::AgentAccount {
+            id: target.id,
+            name: target.name.clone(),
+            dir: target.codex_home.clone(),
+        };
+
+        let size = self.tui.size()?;
+        let frame_config = FrameConfig::default();
+        let layout = FrameLayout::calculate(
+            ratatui::prelude::Rect::new(0, 0, size.width, size.height),
+            &frame_config,
+        );
+        let (rows, cols) = layout.pty_size();
+
+        match self.sessions.switch_codex_account(
+            session_id,
+            rows as usize,
+            cols as usize,
+            account,
+            &from_home,
+            &to_home,
+        ) {
+            Ok(()) => {
+                self.state.needs_render = true;
+                Ok(true)
+            }
+            Err(e) => {
+                tracing::warn!(session_id = %session_id, error = %e, "Failed to move Codex session");
+                self.state
+                    .header_notifications
+                    .push(format!("Could not switch Codex account: {}", e));
+                self.state.needs_render = true;
+                Ok(false)
+            }
+        }
+    }
+
     /// Resolve the account config directories a session should run under
     ///
     /// A config the user has since deleted is worth saying out loud: the
@@ -1677,9 +2591,20 @@ impl App {
         let mut total_stale = 0;
         let project_ids: Vec<_> = self.project_store.projects().map(|p| p.id).collect();
 
+        let mut reread = false;
         for project_id in project_ids {
             total_stale += self.project_store.refresh_branches(project_id);
+            reread |= self.reload_repo_config(project_id);
+        }
+        if reread {
+            if let Err(e) = self.project_store.save() {
+                tracing::error!("Failed to save project store: {}", e);
+                self.state.error_message = Some(format!("Failed to save projects: {}", e));
+            }
         }
+        // Re-diff the worktrees on the next tick rather than waiting out the
+        // interval: a refresh is the user asking for current git state
+        self.last_overlap_scan = None;
 
         if total_stale > 0 {
             self.state.header_notifications.push(format!(
@@ -1689,6 +2614,35 @@ impl App {
         }
     }
 
+    /// Re-read a project's `.panoptes.toml`, saying so if it is broken or
+    /// brings setup commands or shortcuts that need allowing
+    ///
+    /// Returns whether the project changed, so the caller can save it.
+    pub(crate) fn reload_repo_config(&mut self, project_id: ProjectId) -> bool {
+        match self.project_store.reload_repo_config(project_id) {
+            Ok(false) => false,
+            Ok(true) => {
+                if let Some(project) = self.project_store.get_project(project_id) {
+                    tracing::info!("Read .panoptes.toml for '{}'", project.name);
+                    if project.repo_commands_pending() {
+                        self.state.header_notifications.push(format!(
+                            "'{}' has new setup commands or shortcuts - allow them in its settings (,)",
+                            project.name
+                        ));
+                    }
+                }
+                true
+            }
+            Err(e) => {
+                tracing::warn!("Ignoring .panoptes.toml: {:#}", e);
+                self.state
+                    .header_notifications
+                    .push(format!("Ignored .panoptes.toml: {:#}", e));
+                false
+            }
+        }
+    }
+
     /// Show a loading indicator with the given message and force a render
     ///
     /// This is used before blocking operations to provide visual feedback
@@ -1751,20 +2705,33 @@ impl App {
             .projects_nav
             .project_id()
             .and_then(|project_id| self.project_store.get_project(project_id))
-            .map(|project| project.repo_path.clone());
-        let Some(repo_path) = repo_path else {
+            .map(|project| (project.repo_path.clone(), project.kind.clone()));
+        let Some((repo_path, kind)) = repo_path else {
             return false;
         };
 
+        let task = match kind {
+            crate::project::ProjectKind::MultiRepo { repos } => {
+                background::GitTask::RemoveMultiRepoWorktree {
+                    checkouts: repos
+                        .iter()
+                        .map(|repo| (repo_path.join(repo), branch.working_dir.join(repo)))
+                        .collect(),
+                    parent: branch.working_dir.clone(),
+                }
+            }
+            _ => background::GitTask::RemoveWorktree {
+                repo_path,
+                branch_name: branch.name.clone(),
+            },
+        };
+
         // The dialog is answered; don't leave it behind the overlay
         self.state.input_mode = InputMode::Normal;
         self.spawn_git_job(
             &format!("Removing worktree '{}'...", branch.name),
             false,
-            background::GitTask::RemoveWorktree {
-                repo_path,
-                branch_name: branch.name.clone(),
-            },
+            task,
             background::JobFollowUp::FinishBranchDelete {
                 branch: Box::new(branch.clone()),
             },
@@ -1819,6 +2786,17 @@ impl App {
             None => false,
         };
 
+        // "Cancelling..." stays up once shown: the job's own lines would
+        // otherwise make it look as if Esc did nothing
+        if let (Some(job), Some(loading)) = (&self.background_job, &mut self.state.loading) {
+            if let Some(line) = job.latest_progress() {
+                if !loading.cancelling {
+                    loading.message = line;
+                    dirty = true;
+                }
+            }
+        }
+
         let poll = match &self.background_job {
             Some(job) => job.poll(),
             None => return dirty,
@@ -1876,14 +2854,35 @@ impl App {
                 }
             }
             (
-                JobOutput::Completed(outcome),
+                JobOutput::WorktreeCreated {
+                    created,
+                    submodules,
+                    seeded,
+                    commands,
+                },
                 JobFollowUp::RegisterWorktree {
                     project_id,
                     branch_name,
                     worktree_path,
                 },
             ) => {
-                self.register_created_worktree(outcome, project_id, &branch_name, worktree_path);
+                self.register_created_worktree(created, project_id, &branch_name, worktree_path);
+                // The worktree is there and usable either way; the user only
+                // has to finish the setup or bring the changes over by hand
+                if let Some(Err(e)) = submodules
+                    .into_iter()
+                    .chain(seeded)
+                    .chain(commands)
+                    .find(Result::is_err)
+                {
+                    tracing::error!("Failed to finish worktree '{}': {:#}", branch_name, e);
+                    if self.state.error_message.is_none() {
+                        self.state.error_message = Some(format!(
+                            "Worktree '{}' was created, but: {:#}",
+                            branch_name, e
+                        ));
+                    }
+                }
             }
             (JobOutput::Completed(outcome), JobFollowUp::FinishBranchDelete { branch }) => {
                 if let Err(e) = outcome {
@@ -1900,6 +2899,9 @@ impl App {
                     &branch,
                 );
             }
+            (JobOutput::Committed { commit, push }, JobFollowUp::ReportCommit { branch_name }) => {
+                self.report_commit(&branch_name, commit, push);
+            }
             _ => {
                 // The spawn sites pair each task with its own follow-up, so a
                 // mismatch here can only be a programming error.
@@ -1908,6 +2910,45 @@ impl App {
         }
     }
 
+    /// Say how a commit (and its push) went
+    ///
+    /// A failed push does not undo the commit, so that case names the commit
+    /// that did land: the user only has to retry the push.
+    fn report_commit(
+        &mut self,
+        branch_name: &str,
+        commit: Result<String>,
+        push: Option<Result<crate::git::commit::PushTarget>>,
+    ) {
+        let short_id = match commit {
+            Ok(short_id) => short_id,
+            Err(e) => {
+                tracing::error!("Failed to commit on '{}': {:#}", branch_name, e);
+                self.state.error_message = Some(format!("Commit failed: {:#}", e));
+                return;
+            }
+        };
+        tracing::info!("Committed {} on '{}'", short_id, branch_name);
+
+        match push {
+            None => self
+                .state
+                .header_notifications
+                .push(format!("Committed {} on '{}'", short_id, branch_name)),
+            Some(Ok(target)) => self
+                .state
+                .header_notifications
+                .push(format!("Committed {} and pushed to {}", short_id, target)),
+            Some(Err(e)) => {
+                tracing::error!("Failed to push '{}': {:#}", branch_name, e);
+                self.state.error_message =
+                    Some(format!("Committed {}, but push failed: {:#}", short_id, e));
+            }
+        }
+        // The committed files no longer differ from the base
+        self.last_overlap_scan = None;
+    }
+
     /// Open the worktree wizard on freshly fetched branch refs
     ///
     /// Stamps on the tracking flags the git layer knows nothing about: which
@@ -2154,7 +3195,7 @@ impl App {
                 | InputMode::AddingCustomShortcutCommand
                 | InputMode::AddingCustomShortcutAutoClose
                 | InputMode::ConfirmingCustomShortcutDelete => {
-                    render_custom_shortcut_dialogs(frame, area, state, config);
+                    render_custom_shortcut_dialogs(frame, area, state, config, project_store);
                 }
                 // Centralized so the quit prompt is visible from any pane, not
                 // only where it is triggered
@@ -2177,6 +3218,9 @@ impl App {
                 | InputMode::WorktreeConfirm => {
                     render_worktree_wizard(frame, area, state, config);
                 }
+                InputMode::CommitSelectFiles | InputMode::CommitMessage => {
+                    render_commit_wizard(frame, area, state);
+                }
                 InputMode::SelectingDefaultBase => {
                     render_default_base_selector(frame, area, state);
                 }
@@ -2195,6 +3239,20 @@ impl App {
                 InputMode::ConfirmingFolderRemove => {
                     render_folder_remove_confirmation(frame, area, state, project_store);
                 }
+                InputMode::CommandPalette => {
+                    render_command_palette(frame, area, state);
+                }
+                InputMode::ViewingTimeline => {
+                    render_activity_timeline(
+                        frame,
+                        area,
+                        state,
+                        sessions,
+                        project_store,
+                        &config.pricing,
+                        chrono::Utc::now(),
+                    );
+                }
                 // The remaining modes are one-line inputs drawn inline in the
                 // pane that owns them, or need no overlay at all.
                 InputMode::Normal
@@ -144,17 +144,20 @@ pub enum SettingsNav {
     Shortcuts,
     /// Live notification toggles
     Notifications,
+    /// The colour theme, switchable live
+    Theme,
     /// Version, hook health, and where the files live
     About,
 }
 
 impl SettingsNav {
     /// The sections in list order (everything except [`SettingsNav::Sections`])
-    pub const SECTIONS: [SettingsNav; 5] = [
+    pub const SECTIONS: [SettingsNav; 6] = [
         SettingsNav::ClaudeConfigs,
         SettingsNav::CodexConfigs,
         SettingsNav::Shortcuts,
         SettingsNav::Notifications,
+        SettingsNav::Theme,
         SettingsNav::About,
     ];
 
@@ -166,6 +169,7 @@ impl SettingsNav {
             SettingsNav::CodexConfigs => "Codex configs",
             SettingsNav::Shortcuts => "Shortcuts",
             SettingsNav::Notifications => "Notifications",
+            SettingsNav::Theme => "Theme",
             SettingsNav::About => "About / paths",
         }
     }
@@ -178,6 +182,7 @@ impl SettingsNav {
             SettingsNav::CodexConfigs => "Codex accounts (CODEX_HOME)",
             SettingsNav::Shortcuts => "Custom keys that launch a shell command",
             SettingsNav::Notifications => "What interrupts you, and how",
+            SettingsNav::Theme => "Colours: dark, light, or your own in ~/.panoptes/themes",
             SettingsNav::About => "Version, hook server, and where the files live",
         }
     }
@@ -267,8 +272,9 @@ mod tests {
     #[test]
     fn test_settings_sections_list_is_ordered_and_addressable() {
         assert_eq!(SettingsNav::at(0), Some(SettingsNav::ClaudeConfigs));
-        assert_eq!(SettingsNav::at(4), Some(SettingsNav::About));
-        assert_eq!(SettingsNav::at(5), None);
+        assert_eq!(SettingsNav::at(4), Some(SettingsNav::Theme));
+        assert_eq!(SettingsNav::at(5), Some(SettingsNav::About));
+        assert_eq!(SettingsNav::at(6), None);
         assert!(!SettingsNav::SECTIONS.contains(&SettingsNav::Sections));
 
         for section in SettingsNav::SECTIONS {
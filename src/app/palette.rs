@@ -0,0 +1,411 @@
+//! The `Ctrl+P` command palette
+//!
+//! One prompt that reaches anything by name: sessions, branches, projects,
+//! folders, and a handful of actions that otherwise sit behind a pane and a
+//! drill-down or two. The entries are gathered once when the palette opens
+//! and filtered on every keystroke by a subsequence match, so "athfx" finds
+//! "auth-fix" the way editors' file finders do.
+
+use crate::codex_config::CodexConfigId;
+use crate::project::{all_folder_paths, folder_path_key, BranchId, ProjectId, ProjectStore};
+use crate::session::{RotationOffer, SessionId, SessionInfo};
+
+use super::SettingsNav;
+
+/// Longest query the palette accepts
+pub const MAX_PALETTE_QUERY_LEN: usize = 100;
+
+/// Something the palette runs that is not a place to go
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaletteAction {
+    /// Open the worktree wizard for a project
+    NewWorktree(ProjectId),
+    /// Re-check every project's worktrees, as `R` does
+    RefreshGit,
+    /// Open the add-project prompt
+    AddProject,
+    /// Open a section of the Settings pane
+    OpenSettings(SettingsNav),
+    /// Open the activity timeline, as `Ctrl+L` does
+    ShowTimeline,
+    /// Open the activity overlay on its stats page
+    ShowStats,
+    /// Open the activity overlay on its spending page
+    ShowSpend,
+    /// Turn do-not-disturb on or off
+    ToggleDoNotDisturb,
+    /// Relaunch a Codex session under another account, offered while its
+    /// own account is over the rate-limit threshold
+    MoveToCodexAccount(SessionId, CodexConfigId),
+}
+
+/// Where choosing an entry takes the user
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PaletteTarget {
+    /// Open a session full-screen
+    Session(SessionId),
+    /// Pane 1, at a branch's session list
+    Branch(ProjectId, BranchId),
+    /// Pane 1, at a project's branch list
+    Project(ProjectId),
+    /// Pane 1's overview, with the folder expanded and selected
+    Folder(Vec<String>),
+    /// Run an action
+    Action(PaletteAction),
+}
+
+/// One row of the palette
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaletteEntry {
+    /// What sort of thing this is ("session", "branch", ...), shown as a tag
+    pub kind: &'static str,
+    /// The name matched against first
+    pub label: String,
+    /// Where it lives, shown dimmed and matched only when the label is not
+    pub detail: String,
+    /// What choosing it does
+    pub target: PaletteTarget,
+}
+
+/// State of the open palette
+#[derive(Debug, Clone, Default)]
+pub struct CommandPaletteState {
+    /// What the user has typed
+    pub query: String,
+    /// Everything the palette can reach, gathered when it opened
+    pub entries: Vec<PaletteEntry>,
+    /// Indices into `entries` that match `query`, best first
+    pub matches: Vec<usize>,
+    /// Index into `matches` of the highlighted row
+    pub selected: usize,
+}
+
+impl CommandPaletteState {
+    /// A fresh palette over `entries`, with nothing typed yet
+    pub fn open(entries: Vec<PaletteEntry>) -> Self {
+        let mut palette = Self {
+            entries,
+            ..Default::default()
+        };
+        palette.refilter();
+        palette
+    }
+
+    /// Re-rank the entries against the query and select the best match
+    pub fn refilter(&mut self) {
+        let mut scored: Vec<(i32, usize)> = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter_map(|(index, entry)| entry_score(&self.query, entry).map(|s| (s, index)))
+            .collect();
+        // Stable, so ties keep the gathering order: sessions before branches
+        // before projects, which is what a bare query most often means
+        scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        self.matches = scored.into_iter().map(|(_, index)| index).collect();
+        self.selected = 0;
+    }
+
+    /// The highlighted entry, if anything matches
+    pub fn selected_entry(&self) -> Option<&PaletteEntry> {
+        self.matches
+            .get(self.selected)
+            .and_then(|&index| self.entries.get(index))
+    }
+
+    /// The matching entries, best first
+    pub fn matching(&self) -> Vec<&PaletteEntry> {
+        self.matches
+            .iter()
+            .filter_map(|&index| self.entries.get(index))
+            .collect()
+    }
+}
+
+/// Gather everything the palette can reach
+///
+/// `sessions` are the live sessions in their display order.
+pub fn palette_entries(store: &ProjectStore, sessions: &[&SessionInfo]) -> Vec<PaletteEntry> {
+    let project_name = |id: ProjectId| {
+        store
+            .get_project(id)
+            .map(|p| p.name.clone())
+            .unwrap_or_else(|| "?".to_string())
+    };
+    let mut entries = Vec::new();
+
+    for info in sessions {
+        let branch = store
+            .get_branch(info.branch_id)
+            .map(|b| b.name.as_str())
+            .unwrap_or("?");
+        entries.push(PaletteEntry {
+            kind: "session",
+            label: info.name.clone(),
+            detail: format!("{} / {}", project_name(info.project_id), branch),
+            target: PaletteTarget::Session(info.id),
+        });
+    }
+
+    let projects = store.projects_sorted();
+    for project in &projects {
+        for branch in store.branches_for_project_sorted(project.id) {
+            entries.push(PaletteEntry {
+                kind: "branch",
+                label: branch.name.clone(),
+                detail: project.name.clone(),
+                target: PaletteTarget::Branch(project.id, branch.id),
+            });
+        }
+    }
+
+    for project in &projects {
+        let detail = if project.folder.is_empty() {
+            project.repo_path.display().to_string()
+        } else {
+            folder_path_key(&project.folder)
+        };
+        entries.push(PaletteEntry {
+            kind: "project",
+            label: project.name.clone(),
+            detail,
+            target: PaletteTarget::Project(project.id),
+        });
+    }
+
+    for path in all_folder_paths(store) {
+        entries.push(PaletteEntry {
+            kind: "folder",
+            label: folder_path_key(&path),
+            detail: String::new(),
+            target: PaletteTarget::Folder(path),
+        });
+    }
+
+    for project in projects.iter().filter(|p| p.kind.has_worktrees()) {
+        entries.push(action(
+            format!("New worktree in {}", project.name),
+            PaletteAction::NewWorktree(project.id),
+        ));
+    }
+    entries.push(action(
+        "Refresh git state".to_string(),
+        PaletteAction::RefreshGit,
+    ));
+    entries.push(action("Add project".to_string(), PaletteAction::AddProject));
+    entries.push(action(
+        "Activity timeline".to_string(),
+        PaletteAction::ShowTimeline,
+    ));
+    entries.push(action(
+        "Session statistics".to_string(),
+        PaletteAction::ShowStats,
+    ));
+    entries.push(action(
+        "Spending report".to_string(),
+        PaletteAction::ShowSpend,
+    ));
+    entries.push(action(
+        "Toggle do not disturb".to_string(),
+        PaletteAction::ToggleDoNotDisturb,
+    ));
+    for section in SettingsNav::SECTIONS {
+        entries.push(action(
+            format!("Settings: {}", section.title()),
+            PaletteAction::OpenSettings(section),
+        ));
+    }
+
+    entries
+}
+
+/// A "move to another account" entry for each rate-limited Codex session
+/// that has somewhere to go
+pub fn rotation_entries(offers: &[RotationOffer]) -> Vec<PaletteEntry> {
+    offers
+        .iter()
+        .filter_map(|offer| {
+            let (to, to_name) = offer.to.as_ref()?;
+            Some(PaletteEntry {
+                detail: format!(
+                    "Codex account {} at {:.0}%",
+                    offer.from_name, offer.used_percent
+                ),
+                ..action(
+                    format!("Move {} to {}", offer.session_name, to_name),
+                    PaletteAction::MoveToCodexAccount(offer.session_id, *to),
+                )
+            })
+        })
+        .collect()
+}
+
+fn action(label: String, action: PaletteAction) -> PaletteEntry {
+    PaletteEntry {
+        kind: "action",
+        label,
+        detail: String::new(),
+        target: PaletteTarget::Action(action),
+    }
+}
+
+/// How well `query` matches an entry, or `None` if it does not
+///
+/// The label is what the user is naming; the detail ("project / branch") only
+/// counts when the label alone does not match, and then ranks lower, so
+/// typing a project's name lists the project before its sessions.
+fn entry_score(query: &str, entry: &PaletteEntry) -> Option<i32> {
+    if let Some(score) = fuzzy_score(query, &entry.label) {
+        return Some(score);
+    }
+    fuzzy_score(query, &format!("{} {}", entry.detail, entry.label)).map(|score| score - 20)
+}
+
+/// Score `text` as a case-insensitive subsequence match of `query`
+///
+/// Returns `None` when the query's characters do not all appear in order.
+/// Consecutive characters and characters starting a word ("fb" against
+/// "feature/bar") score more, and a match starting later scores less, so the
+/// tightest and earliest match ranks first. Whitespace in the query is
+/// ignored, and an empty query matches everything equally.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    let mut wanted = query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .peekable();
+    if wanted.peek().is_none() {
+        return Some(0);
+    }
+
+    let mut score = 0;
+    let mut first_match: Option<usize> = None;
+    let mut last_match: Option<usize> = None;
+    let mut previous: Option<char> = None;
+
+    for (index, c) in text.chars().enumerate() {
+        let Some(&next) = wanted.peek() else {
+            break;
+        };
+        let lower = c.to_lowercase().next().unwrap_or(c);
+        if lower == next {
+            wanted.next();
+            score += 1;
+            if last_match.is_some_and(|last| last + 1 == index) {
+                score += 5;
+            }
+            if previous.map_or(true, |p| !p.is_alphanumeric()) {
+                score += 8;
+            }
+            first_match.get_or_insert(index);
+            last_match = Some(index);
+        }
+        previous = Some(c);
+    }
+
+    if wanted.peek().is_some() {
+        return None;
+    }
+    Some(score - first_match.unwrap_or(0).min(10) as i32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project::{Branch, Project};
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_fuzzy_matches_subsequences_only() {
+        assert!(fuzzy_score("athfx", "auth-fix").is_some());
+        assert!(fuzzy_score("AUTH", "auth-fix").is_some());
+        assert!(fuzzy_score("fa", "auth-fix").is_none());
+        assert_eq!(fuzzy_score("", "anything"), Some(0));
+    }
+
+    #[test]
+    fn test_word_starts_and_runs_rank_higher() {
+        let tight = fuzzy_score("fb", "feature/bar").unwrap();
+        let loose = fuzzy_score("fb", "fabulous").unwrap();
+        assert!(tight > loose, "{tight} vs {loose}");
+
+        let run = fuzzy_score("uth", "mouth").unwrap();
+        let scattered = fuzzy_score("uth", "multipath").unwrap();
+        assert!(run > scattered, "{run} vs {scattered}");
+
+        let early = fuzzy_score("fix", "fix-auth").unwrap();
+        let late = fuzzy_score("fix", "auth-fix").unwrap();
+        assert!(early > late, "{early} vs {late}");
+    }
+
+    #[test]
+    fn test_palette_reaches_sessions_branches_projects_folders_and_actions() {
+        let mut store = ProjectStore::new();
+        let mut project = Project::new(
+            "panoptes".to_string(),
+            PathBuf::from("/src/panoptes"),
+            "main".to_string(),
+        );
+        project.folder = vec!["Work".to_string()];
+        let project_id = project.id;
+        store.add_project(project);
+        let branch = Branch::new(
+            project_id,
+            "feature/auth".to_string(),
+            PathBuf::from("/wt/auth"),
+            false,
+            true,
+        );
+        let branch_id = branch.id;
+        store.add_branch(branch);
+
+        let info = SessionInfo::new(
+            "auth-fix".to_string(),
+            PathBuf::from("/wt/auth"),
+            project_id,
+            branch_id,
+        );
+        let entries = palette_entries(&store, &[&info]);
+
+        let mut palette = CommandPaletteState::open(entries);
+        assert_eq!(palette.matches.len(), palette.entries.len());
+
+        palette.query = "athfx".to_string();
+        palette.refilter();
+        assert_eq!(
+            palette.selected_entry().map(|e| &e.target),
+            Some(&PaletteTarget::Session(info.id))
+        );
+        assert_eq!(
+            palette.selected_entry().map(|e| e.detail.as_str()),
+            Some("panoptes / feature/auth")
+        );
+
+        palette.query = "work".to_string();
+        palette.refilter();
+        assert_eq!(
+            palette.selected_entry().map(|e| &e.target),
+            Some(&PaletteTarget::Folder(vec!["Work".to_string()]))
+        );
+
+        palette.query = "new wt".to_string();
+        palette.refilter();
+        assert_eq!(
+            palette.selected_entry().map(|e| &e.target),
+            Some(&PaletteTarget::Action(PaletteAction::NewWorktree(
+                project_id
+            )))
+        );
+
+        // A project's name finds the project first, then what lives in it
+        palette.query = "panoptes".to_string();
+        palette.refilter();
+        let kinds: Vec<&str> = palette.matching().iter().map(|e| e.kind).collect();
+        assert_eq!(kinds[0], "project");
+        assert!(kinds.contains(&"session"));
+
+        palette.query = "zzz".to_string();
+        palette.refilter();
+        assert!(palette.selected_entry().is_none());
+    }
+}
@@ -6,8 +6,8 @@ use std::path::PathBuf;
 use std::time::{Duration, Instant};
 
 use crate::claude_config::ClaudeConfig;
-use crate::project::{BranchId, ProjectId};
-use crate::session::{SessionId, SessionManager};
+use crate::project::{BranchId, FileOverlaps, ProjectId, ShortcutScope};
+use crate::session::{SessionId, SessionInfo, SessionManager, SessionState};
 use crate::tui::HeaderNotificationManager;
 use crate::wizards::worktree::{BranchRef, WorktreeCreationType};
 
@@ -100,6 +100,92 @@ impl ConfigDraft {
     }
 }
 
+/// How far back the activity timeline looks, in hours, narrowest first
+pub const TIMELINE_WINDOWS: [i64; 5] = [1, 3, 6, 12, 24];
+
+/// Which page of the activity overlay is showing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TimelinePage {
+    /// Live sessions' recent states, as bars
+    #[default]
+    Activity,
+    /// Figures from the session history log
+    Stats,
+    /// Estimated spending, from the same log
+    Spend,
+}
+
+impl TimelinePage {
+    /// The page `Tab` moves to
+    pub fn next(self) -> Self {
+        match self {
+            Self::Activity => Self::Stats,
+            Self::Stats => Self::Spend,
+            Self::Spend => Self::Activity,
+        }
+    }
+
+    /// The page `Shift+Tab` moves to
+    pub fn previous(self) -> Self {
+        match self {
+            Self::Activity => Self::Spend,
+            Self::Stats => Self::Activity,
+            Self::Spend => Self::Stats,
+        }
+    }
+}
+
+/// The activity timeline overlay's zoom and scroll
+#[derive(Debug, Clone)]
+pub struct TimelineViewState {
+    /// Index into [`TIMELINE_WINDOWS`]
+    pub window_index: usize,
+    /// First session row shown
+    pub scroll: usize,
+    /// Which page is showing
+    pub page: TimelinePage,
+    /// The stats page's figures, read from the history log when it opens
+    pub stats: Option<crate::session::HistoryStats>,
+    /// The spending page's figures, read alongside
+    pub spend: Option<crate::session::SpendReport>,
+}
+
+impl TimelineViewState {
+    /// The window being shown, in hours
+    pub fn window_hours(&self) -> i64 {
+        TIMELINE_WINDOWS[self.window_index.min(TIMELINE_WINDOWS.len() - 1)]
+    }
+
+    /// Show more history; returns whether the window changed
+    pub fn zoom_out(&mut self) -> bool {
+        let changed = self.window_index + 1 < TIMELINE_WINDOWS.len();
+        if changed {
+            self.window_index += 1;
+        }
+        changed
+    }
+
+    /// Show less history; returns whether the window changed
+    pub fn zoom_in(&mut self) -> bool {
+        let changed = self.window_index > 0;
+        self.window_index = self.window_index.saturating_sub(1);
+        changed
+    }
+}
+
+impl Default for TimelineViewState {
+    /// Six hours: a working day's morning or afternoon at a glance
+    fn default() -> Self {
+        Self {
+            window_index: 2,
+            scroll: 0,
+            page: TimelinePage::Activity,
+            stats: None,
+            spend: None,
+        }
+    }
+}
+
 /// Draft state for a session being created
 ///
 /// Filled in by the view that starts session creation (name typed by the
@@ -180,8 +266,23 @@ pub struct WorktreeWizardState {
     pub creation_type: WorktreeCreationType,
     /// Project name for worktree path (cached during wizard)
     pub project_name: String,
+    /// Whether the project spans several repositories
+    ///
+    /// Such a branch can only start from a base branch: a commit, a stash or
+    /// a patch belongs to one repository, not to all of them.
+    pub multi_repo: bool,
     /// Validation error for branch name input (displayed in UI)
     pub branch_validation_error: Option<String>,
+    /// Stash entries of the main checkout, listed when the wizard opens
+    pub stashes: Vec<crate::git::seed::StashEntry>,
+    /// Selected index in `stashes` (step 2, starting from a stash)
+    pub stash_index: usize,
+    /// Commit a `FromRevision` branch starts at: full ID and summary line
+    pub start_revision: Option<(String, String)>,
+    /// Patch file a `FromPatch` worktree gets, once it is found to be a file
+    pub patch_path: Option<std::path::PathBuf>,
+    /// Why the typed commit, tag or patch path was rejected (step 2)
+    pub source_error: Option<String>,
 }
 
 impl WorktreeWizardState {
@@ -200,6 +301,11 @@ impl WorktreeWizardState {
         self.list_index = self.list_index.min(max_index);
     }
 
+    /// The stash highlighted in step 2, if there are any
+    pub fn selected_stash(&self) -> Option<&crate::git::seed::StashEntry> {
+        self.stashes.get(self.stash_index)
+    }
+
     /// Clamp base_list_index to valid range for the given filtered count
     pub fn clamp_base_list_index(&mut self, filtered_count: usize) {
         if filtered_count == 0 {
@@ -210,6 +316,124 @@ impl WorktreeWizardState {
     }
 }
 
+/// Commit wizard state
+///
+/// Filled in by `start_commit_wizard()` from the branch's checkout and
+/// cleared when the wizard is cancelled or hands the commit to a background
+/// job. `changes` and `selected` are parallel: one checkbox per file.
+#[derive(Debug, Clone, Default)]
+pub struct CommitWizardState {
+    /// Branch being committed on
+    pub branch_id: Option<BranchId>,
+    /// Its name, for the dialog title and the result message
+    pub branch_name: String,
+    /// Directory the commit is made in (the branch's working dir)
+    pub checkout: PathBuf,
+    /// Every changed file in the checkout, sorted by path
+    pub changes: Vec<crate::git::commit::WorkingChange>,
+    /// Whether each of `changes` goes into the commit
+    pub selected: Vec<bool>,
+    /// Highlighted row in the file list
+    pub list_index: usize,
+    /// Patch of the highlighted file, refreshed as the highlight moves
+    pub diff_preview: String,
+    /// Commit message being edited
+    pub message: String,
+    /// Whether the user has typed in the message since it was pre-filled
+    pub message_edited: bool,
+    /// Whether to push once the commit is made
+    pub push: bool,
+    /// Where a push would go, e.g. "origin/feature"; `None` when it cannot
+    pub push_target: Option<String>,
+    /// Why there is nowhere to push, when there is not
+    pub push_blocker: Option<String>,
+    /// Agent session asked to draft the message
+    pub drafting: Option<DraftRequest>,
+}
+
+/// An agent asked to draft the commit message
+///
+/// The prompt only goes in once the session is between turns, so it cannot
+/// interleave with a turn under way, and the draft is only taken from a turn
+/// that ended after it went in.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DraftRequest {
+    /// Session asked
+    pub session_id: SessionId,
+    /// When the prompt went in; `None` while it waits for the session to idle
+    pub sent_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// The session's `last_message` when the prompt went in
+    pub before: Option<String>,
+}
+
+impl DraftRequest {
+    /// A request not sent yet
+    pub fn new(session_id: SessionId) -> Self {
+        Self {
+            session_id,
+            sent_at: None,
+            before: None,
+        }
+    }
+
+    /// Where the request stands, given the asked session as it is now
+    pub fn step(&self, info: &SessionInfo) -> DraftStep {
+        if info.state == SessionState::Exited {
+            return DraftStep::Exited;
+        }
+        let Some(sent_at) = self.sent_at else {
+            return match info.state {
+                SessionState::Waiting => DraftStep::Send,
+                _ => DraftStep::Wait,
+            };
+        };
+        // Only a turn ending after the prompt went in answers it
+        if info.state != SessionState::Waiting || info.state_entered_at <= sent_at {
+            return DraftStep::Wait;
+        }
+        match &info.last_message {
+            Some(reply) if Some(reply) != self.before.as_ref() => DraftStep::Reply(reply.clone()),
+            _ => DraftStep::NoReply,
+        }
+    }
+}
+
+/// What a [`DraftRequest`] needs next
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DraftStep {
+    /// Nothing yet: a turn is under way
+    Wait,
+    /// The session is between turns: send the prompt now
+    Send,
+    /// The turn the prompt started ended with this reply
+    Reply(String),
+    /// The turn ended without a new reply (interrupted, say)
+    NoReply,
+    /// The session died first
+    Exited,
+}
+
+impl CommitWizardState {
+    /// The paths that will be committed
+    pub fn selected_paths(&self) -> Vec<String> {
+        self.changes
+            .iter()
+            .zip(&self.selected)
+            .filter(|(_, &selected)| selected)
+            .map(|(change, _)| change.path.clone())
+            .collect()
+    }
+
+    /// Tick every file, or untick every file if all are already ticked
+    pub fn toggle_all(&mut self) {
+        let all = self.selected.iter().all(|&s| s);
+        self.selected.iter_mut().for_each(|s| *s = !all);
+    }
+}
+
+/// Most sessions the session view tiles at once
+pub const MAX_TILES: usize = 4;
+
 /// Frames of the loading spinner, cycled while an operation is in flight
 pub const SPINNER_FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
 
@@ -295,8 +519,17 @@ pub struct AppState {
     pub project_settings_index: usize,
     /// Selected row in pane 3's notifications list
     pub notifications_index: usize,
+    /// Selected row in pane 3's theme list
+    pub theme_index: usize,
+    /// The theme names pane 3 lists, read from disk when the section opens
+    pub available_themes: Vec<String>,
     /// Session being viewed (in session view)
     pub active_session: Option<SessionId>,
+    /// Sessions tiled side by side in the session view, in tile order
+    ///
+    /// Empty when the view shows one session. Otherwise `active_session` is
+    /// always one of these - the focused tile, which gets the keystrokes.
+    pub session_tiles: Vec<SessionId>,
     /// Pane the session view was opened from, restored when it is left
     pub session_return_focus: Option<Focus>,
     /// Draft for the session being created (name plus project/branch context)
@@ -317,6 +550,8 @@ pub struct AppState {
     pub pending_session_subdir: Option<PathBuf>,
     /// Pending default branch (computed during path validation)
     pub pending_default_branch: String,
+    /// Pending project kind (computed during path validation)
+    pub pending_project_kind: crate::project::ProjectKind,
     /// Buffer for new branch name input (worktree creation)
     pub new_branch_name: String,
     /// Available branch refs (local and remote) for worktree creation
@@ -378,12 +613,27 @@ pub struct AppState {
     /// Worktree creation wizard state (grouped together)
     pub worktree_wizard: WorktreeWizardState,
 
+    /// Commit wizard state (grouped together)
+    pub commit_wizard: CommitWizardState,
+
+    /// The `Ctrl+P` command palette, while it is open
+    pub command_palette: super::CommandPaletteState,
+
+    /// The activity timeline's zoom and scroll, kept between openings
+    pub timeline: TimelineViewState,
+
+    /// The active key bindings, from the `[keys]` tables of `config.toml`
+    pub keymap: crate::keymap::Keymap,
+
     /// Loading overlay shown while an operation is in flight
     pub loading: Option<LoadingOverlay>,
 
     /// Header notification manager for transient header messages
     pub header_notifications: HeaderNotificationManager,
 
+    /// Files changed on more than one worktree branch, from the last scan
+    pub file_overlaps: FileOverlaps,
+
     // --- Agent config state (shared between Claude and Codex flows) ---
     /// Draft for the config being created (name + path steps, either agent)
     pub config_draft: ConfigDraft,
@@ -433,6 +683,9 @@ pub struct AppState {
     pub shortcut_error: Option<String>,
     /// Auto-close toggle for new shortcut being added
     pub new_shortcut_auto_close: bool,
+    /// Where the shortcut being added or deleted is kept: `None` for the
+    /// global list in `config.toml`, otherwise a project or folder of it
+    pub shortcut_scope: Option<ShortcutScope>,
 }
 
 impl AppState {
@@ -534,6 +787,9 @@ impl AppState {
             self.session_return_focus = Some(self.focus);
         }
         self.focus = Focus::Session;
+        // Jumping to a session that is not on screen swaps it into the
+        // focused tile, rather than leaving the tiles showing other sessions
+        self.show_in_focused_tile(session_id);
         self.active_session = Some(session_id);
         // Reset scroll offset when entering session view
         self.session_scroll_offset = 0;
@@ -557,8 +813,83 @@ impl AppState {
 
         self.focus = restored;
         self.active_session = None;
+        self.session_tiles.clear();
         self.input_mode = InputMode::Normal;
     }
+
+    /// Tile `count` sessions in the session view (1 means untiled)
+    ///
+    /// Sessions already tiled stay where they are, and the focused one always
+    /// stays on screen; free tiles are filled from `session_order`. With fewer
+    /// sessions than tiles, there are fewer tiles.
+    pub fn set_tile_count(&mut self, count: usize, session_order: &[SessionId]) {
+        let Some(active) = self.active_session else {
+            return;
+        };
+        let count = count.min(MAX_TILES);
+        if count <= 1 {
+            self.session_tiles.clear();
+            return;
+        }
+
+        let mut tiles: Vec<SessionId> = if self.session_tiles.is_empty() {
+            vec![active]
+        } else {
+            self.session_tiles
+                .iter()
+                .copied()
+                .filter(|id| *id == active || session_order.contains(id))
+                .collect()
+        };
+        if tiles.len() > count {
+            tiles.truncate(count);
+            if !tiles.contains(&active) {
+                tiles[count - 1] = active;
+            }
+        }
+        for id in session_order {
+            if tiles.len() >= count {
+                break;
+            }
+            if !tiles.contains(id) {
+                tiles.push(*id);
+            }
+        }
+
+        self.session_tiles = if tiles.len() > 1 { tiles } else { Vec::new() };
+    }
+
+    /// Move focus to the next tile, or the previous one, wrapping around
+    pub fn focus_tile(&mut self, forward: bool) {
+        let Some(index) = self
+            .active_session
+            .and_then(|active| self.session_tiles.iter().position(|id| *id == active))
+        else {
+            return;
+        };
+        let len = self.session_tiles.len();
+        let next = if forward {
+            cycle_next(index, len)
+        } else {
+            cycle_prev(index, len)
+        };
+        self.active_session = Some(self.session_tiles[next]);
+    }
+
+    /// Put `session_id` in the focused tile, unless it already has a tile
+    ///
+    /// Does not move focus itself; the caller makes the session active.
+    pub fn show_in_focused_tile(&mut self, session_id: SessionId) {
+        if self.session_tiles.contains(&session_id) {
+            return;
+        }
+        let focused = self
+            .active_session
+            .and_then(|active| self.session_tiles.iter().position(|id| *id == active));
+        if let Some(index) = focused {
+            self.session_tiles[index] = session_id;
+        }
+    }
 }
 
 #[cfg(test)]
@@ -716,6 +1047,48 @@ mod tests {
         assert_eq!(state.base_list_index, 0);
     }
 
+    #[test]
+    fn test_a_draft_is_asked_between_turns_and_taken_from_the_turn_it_started() {
+        let mut info = SessionInfo::new(
+            "api-fix".to_string(),
+            "/tmp".into(),
+            uuid::Uuid::new_v4(),
+            uuid::Uuid::new_v4(),
+        );
+        let start = chrono::Utc::now();
+        let at = |secs| start + chrono::Duration::seconds(secs);
+        let mut request = DraftRequest::new(info.id);
+
+        // Mid-turn, the prompt would interleave with the turn: hold it
+        info.set_state_at(SessionState::Thinking, at(0));
+        assert_eq!(request.step(&info), DraftStep::Wait);
+        info.set_last_message("Fixed the flaky test");
+        info.set_state_at(SessionState::Waiting, at(1));
+        assert_eq!(request.step(&info), DraftStep::Send);
+
+        request.sent_at = Some(at(2));
+        request.before = info.last_message.clone();
+        // The reply from before the prompt is not the draft
+        assert_eq!(request.step(&info), DraftStep::Wait);
+        info.set_state_at(SessionState::Thinking, at(3));
+        assert_eq!(request.step(&info), DraftStep::Wait);
+        info.set_last_message("Fix the flaky parser test");
+        info.set_state_at(SessionState::Waiting, at(4));
+        assert_eq!(
+            request.step(&info),
+            DraftStep::Reply("Fix the flaky parser test".to_string())
+        );
+
+        // An interrupted turn leaves the old reply in place
+        request.before = info.last_message.clone();
+        info.set_state_at(SessionState::Thinking, at(5));
+        info.set_state_at(SessionState::Waiting, at(6));
+        assert_eq!(request.step(&info), DraftStep::NoReply);
+
+        info.set_state_at(SessionState::Exited, at(7));
+        assert_eq!(request.step(&info), DraftStep::Exited);
+    }
+
     // AppState navigation tests
     #[test]
     fn test_app_state_default() {
@@ -922,4 +1295,55 @@ mod tests {
         state.pending_resize = false;
         assert!(!state.pending_resize);
     }
+
+    #[test]
+    fn test_tiling_keeps_the_focused_session_on_screen() {
+        let ids: Vec<SessionId> = (0..5).map(|_| SessionId::new_v4()).collect();
+        let mut state = AppState {
+            active_session: Some(ids[3]),
+            ..Default::default()
+        };
+
+        state.set_tile_count(3, &ids);
+        assert_eq!(state.session_tiles, vec![ids[3], ids[0], ids[1]]);
+
+        // Growing keeps the existing tiles where they are
+        state.set_tile_count(4, &ids);
+        assert_eq!(state.session_tiles, vec![ids[3], ids[0], ids[1], ids[2]]);
+
+        // Shrinking never drops the focused tile
+        state.active_session = Some(ids[2]);
+        state.set_tile_count(2, &ids);
+        assert_eq!(state.session_tiles, vec![ids[3], ids[2]]);
+
+        state.set_tile_count(1, &ids);
+        assert!(state.session_tiles.is_empty());
+
+        // Not enough sessions to tile
+        state.set_tile_count(4, &ids[2..3]);
+        assert!(state.session_tiles.is_empty());
+    }
+
+    #[test]
+    fn test_focus_moves_between_tiles_and_a_jump_replaces_the_focused_one() {
+        let ids: Vec<SessionId> = (0..4).map(|_| SessionId::new_v4()).collect();
+        let mut state = AppState {
+            active_session: Some(ids[0]),
+            ..Default::default()
+        };
+        state.set_tile_count(3, &ids);
+
+        state.focus_tile(true);
+        assert_eq!(state.active_session, Some(ids[1]));
+        state.focus_tile(false);
+        state.focus_tile(false);
+        assert_eq!(state.active_session, Some(ids[2]));
+
+        state.show_in_focused_tile(ids[3]);
+        assert_eq!(state.session_tiles, vec![ids[0], ids[1], ids[3]]);
+
+        // A session that already has a tile is left where it is
+        state.show_in_focused_tile(ids[0]);
+        assert_eq!(state.session_tiles, vec![ids[0], ids[1], ids[3]]);
+    }
 }
@@ -0,0 +1,525 @@
+//! Export and import of the whole Panoptes state
+//!
+//! `panoptes export` gathers the state files from `~/.panoptes` - the config,
+//! the projects with their folders, the sessions and both account stores -
+//! into one JSON bundle; `panoptes import` writes them back, on this machine
+//! or another. The bundle holds each file's text as it was, so a config.toml
+//! keeps its comments.
+//!
+//! Paths are what does not travel: a home directory or a checkout location
+//! that differs between a laptop and a VM. On import every string that is a
+//! path under a remapped prefix is rewritten - the exporting machine's home
+//! becomes this one's, and `--map OLD=NEW` moves anything else (the longest
+//! matching prefix wins). Only whole path components match, so mapping
+//! `/src/app` leaves `/src/application` alone.
+//!
+//! What the state points at - repositories, worktrees, agent config
+//! directories - is not in the bundle. Import lists the projects whose
+//! repository is not where it now says, and branches whose worktree is
+//! missing show as stale after the first refresh, as they would after a move.
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::config::{config_dir, Config};
+use crate::persistence;
+use crate::project::ProjectStore;
+
+/// Marks a file as a Panoptes bundle
+const BUNDLE_FORMAT: &str = "panoptes-backup";
+
+/// Bumped when a bundle stops being readable by older versions
+const BUNDLE_VERSION: u32 = 1;
+
+/// The state files a bundle carries, relative to the config directory
+pub const STATE_FILES: [&str; 5] = [
+    "config.toml",
+    "projects.json",
+    "sessions.json",
+    "claude_configs.json",
+    "codex_configs.json",
+];
+
+/// One exported copy of the state
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Bundle {
+    /// Always [`BUNDLE_FORMAT`]
+    pub format: String,
+    /// The bundle layout version
+    pub version: u32,
+    /// When it was exported
+    pub created_at: DateTime<Utc>,
+    /// Home directory of the exporting machine, remapped to this one's on import
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub home: Option<PathBuf>,
+    /// The config directory it was exported from
+    pub config_dir: PathBuf,
+    /// Each state file present at export, by name, as text
+    pub files: BTreeMap<String, String>,
+}
+
+/// A path prefix rewritten on import
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathMapping {
+    pub from: PathBuf,
+    pub to: PathBuf,
+}
+
+impl PathMapping {
+    /// Parse a command-line `OLD=NEW`, expanding `~` on either side
+    pub fn parse(arg: &str) -> Result<Self> {
+        let Some((from, to)) = arg.split_once('=') else {
+            bail!("Expected OLD=NEW, got '{}'", arg);
+        };
+        if from.is_empty() || to.is_empty() {
+            bail!("Expected OLD=NEW, got '{}'", arg);
+        }
+        let expand = |path: &str| PathBuf::from(shellexpand::tilde(path).into_owned());
+        Ok(Self {
+            from: expand(from),
+            to: expand(to),
+        })
+    }
+
+    /// `path` with this prefix replaced, if it is under it
+    fn apply(&self, path: &str) -> Option<String> {
+        let from = self.from.to_str()?.trim_end_matches('/');
+        let to = self.to.to_str()?.trim_end_matches('/');
+        let rest = path.strip_prefix(from)?;
+        (rest.is_empty() || rest.starts_with('/')).then(|| format!("{}{}", to, rest))
+    }
+}
+
+/// What an import did
+#[derive(Debug, Default)]
+pub struct ImportReport {
+    /// The files written
+    pub files: Vec<String>,
+    /// How many paths were rewritten
+    pub remapped: usize,
+    /// Where the state it replaced was exported to first, if there was any
+    pub previous: Option<PathBuf>,
+    /// Projects whose repository is not at its path after remapping
+    pub missing_repos: Vec<(String, PathBuf)>,
+}
+
+impl Bundle {
+    /// Gather the state files in `dir`
+    pub fn collect(dir: &Path) -> Result<Self> {
+        let mut files = BTreeMap::new();
+        for name in STATE_FILES {
+            let path = dir.join(name);
+            match std::fs::read_to_string(&path) {
+                Ok(text) => {
+                    files.insert(name.to_string(), text);
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => return Err(e).with_context(|| format!("Failed to read {:?}", path)),
+            }
+        }
+        Ok(Self {
+            format: BUNDLE_FORMAT.to_string(),
+            version: BUNDLE_VERSION,
+            created_at: Utc::now(),
+            home: dirs::home_dir(),
+            config_dir: dir.to_path_buf(),
+            files,
+        })
+    }
+
+    /// Read a bundle written by [`Bundle::save`]
+    pub fn load(path: &Path) -> Result<Self> {
+        let text =
+            std::fs::read_to_string(path).with_context(|| format!("Failed to read {:?}", path))?;
+        let bundle: Self = serde_json::from_str(&text)
+            .with_context(|| format!("{} is not a Panoptes export", path.display()))?;
+        if bundle.format != BUNDLE_FORMAT {
+            bail!("{} is not a Panoptes export", path.display());
+        }
+        if bundle.version > BUNDLE_VERSION {
+            bail!(
+                "{} was exported by a newer Panoptes (format {}); update to import it",
+                path.display(),
+                bundle.version
+            );
+        }
+        for name in bundle.files.keys() {
+            if !STATE_FILES.contains(&name.as_str()) {
+                bail!("{} holds an unexpected file '{}'", path.display(), name);
+            }
+        }
+        Ok(bundle)
+    }
+
+    /// Write the bundle to `path`
+    pub fn save(&self, path: &Path) -> Result<()> {
+        persistence::save_json_atomic(path, self, "export")
+    }
+
+    /// The mappings an import into `dir` applies: the ones given, then the
+    /// exporting machine's home and config directory to this one's
+    pub fn mappings(&self, dir: &Path, given: &[PathMapping]) -> Vec<PathMapping> {
+        let mut mappings = given.to_vec();
+        if self.config_dir != dir {
+            mappings.push(PathMapping {
+                from: self.config_dir.clone(),
+                to: dir.to_path_buf(),
+            });
+        }
+        if let (Some(from), Some(to)) = (&self.home, dirs::home_dir()) {
+            if *from != to {
+                mappings.push(PathMapping {
+                    from: from.clone(),
+                    to,
+                });
+            }
+        }
+        // Most specific first, so `~/code/app` beats `~`
+        mappings.sort_by_key(|m| std::cmp::Reverse(m.from.as_os_str().len()));
+        mappings
+    }
+
+    /// Rewrite the paths in every file, returning how many changed
+    pub fn remap(&mut self, mappings: &[PathMapping]) -> Result<usize> {
+        if mappings.is_empty() {
+            return Ok(0);
+        }
+        let remap = |text: &str| -> Option<String> {
+            mappings.iter().find_map(|mapping| mapping.apply(text))
+        };
+
+        let mut count = 0;
+        for (name, text) in self.files.iter_mut() {
+            if name.ends_with(".toml") {
+                let mut doc: toml_edit::DocumentMut = text
+                    .parse()
+                    .with_context(|| format!("{} in the export is not valid TOML", name))?;
+                count += remap_toml_table(doc.as_table_mut(), &remap);
+                *text = doc.to_string();
+            } else {
+                let mut value: serde_json::Value = serde_json::from_str(text)
+                    .with_context(|| format!("{} in the export is not valid JSON", name))?;
+                count += remap_json(&mut value, &remap);
+                *text = serde_json::to_string_pretty(&value)?;
+            }
+        }
+        Ok(count)
+    }
+}
+
+/// Export the state in `dir` to `out`, returning how many files it holds
+pub fn export(dir: &Path, out: &Path) -> Result<usize> {
+    let bundle = Bundle::collect(dir)?;
+    if bundle.files.is_empty() {
+        bail!("Nothing to export: no state files in {}", dir.display());
+    }
+    bundle.save(out)?;
+    Ok(bundle.files.len())
+}
+
+/// Where an export goes when no file is named: `panoptes-backup-<ts>.json`
+/// in `dir`
+pub fn default_export_path(dir: &Path) -> PathBuf {
+    dir.join(format!(
+        "panoptes-backup-{}.json",
+        Utc::now().format("%Y%m%d_%H%M%S")
+    ))
+}
+
+/// Import the bundle at `path` into `dir`, remapping paths on the way
+///
+/// The state already in `dir` is exported to `dir/backups/` first, so
+/// importing that file undoes the import. Files the bundle does not hold
+/// are left as they are.
+pub fn import(path: &Path, dir: &Path, given: &[PathMapping]) -> Result<ImportReport> {
+    let mut bundle = Bundle::load(path)?;
+    let mappings = bundle.mappings(dir, given);
+    let remapped = bundle.remap(&mappings)?;
+
+    let current = Bundle::collect(dir)?;
+    let previous = if current.files.is_empty() {
+        None
+    } else {
+        let backup = default_export_path(&dir.join("backups"));
+        current.save(&backup)?;
+        Some(backup)
+    };
+
+    let mut files = Vec::new();
+    for (name, text) in &bundle.files {
+        persistence::save_text_atomic(&dir.join(name), text, name)?;
+        files.push(name.clone());
+    }
+
+    let (store, _) = ProjectStore::load_from_with_status(&dir.join("projects.json"));
+    let mut missing_repos: Vec<(String, PathBuf)> = store
+        .projects()
+        .filter(|project| !project.repo_path.exists())
+        .map(|project| (project.name.clone(), project.repo_path.clone()))
+        .collect();
+    missing_repos.sort();
+
+    Ok(ImportReport {
+        files,
+        remapped,
+        previous,
+        missing_repos,
+    })
+}
+
+/// Run `panoptes export [FILE]` or `panoptes import FILE [--map OLD=NEW]...
+/// [--force]`, printing the outcome
+///
+/// Returns `None` when `args` (without the program name) is not one of them,
+/// so the dashboard starts as usual.
+pub fn run_cli(args: &[String]) -> Option<Result<()>> {
+    let (command, rest) = args.split_first()?;
+    match command.as_str() {
+        "export" => Some(cli_export(rest)),
+        "import" => Some(cli_import(rest)),
+        _ => None,
+    }
+}
+
+fn cli_export(args: &[String]) -> Result<()> {
+    let out = match args {
+        [] => default_export_path(&std::env::current_dir()?),
+        [file] => PathBuf::from(file),
+        _ => bail!("Usage: panoptes export [FILE]"),
+    };
+    let count = export(&config_dir(), &out)?;
+    println!("Exported {} files to {}", count, out.display());
+    Ok(())
+}
+
+fn cli_import(args: &[String]) -> Result<()> {
+    const USAGE: &str = "Usage: panoptes import FILE [--map OLD=NEW]... [--force]";
+    let mut file = None;
+    let mut mappings = Vec::new();
+    let mut force = false;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--map" => {
+                let mapping = args.next().context(USAGE)?;
+                mappings.push(PathMapping::parse(mapping)?);
+            }
+            "--force" => force = true,
+            _ if file.is_none() && !arg.starts_with("--") => file = Some(PathBuf::from(arg)),
+            _ => bail!(USAGE),
+        }
+    }
+    let file = file.context(USAGE)?;
+
+    // A running dashboard owns these files and would save over the import
+    let hook_port = Config::reload()
+        .ok()
+        .flatten()
+        .unwrap_or_default()
+        .hook_port;
+    if !force && std::net::TcpListener::bind(("127.0.0.1", hook_port)).is_err() {
+        bail!(
+            "Panoptes looks to be running (port {} is in use). Quit it first, \
+             or pass --force if the port belongs to something else.",
+            hook_port
+        );
+    }
+
+    let report = import(&file, &config_dir(), &mappings)?;
+    if let Some(previous) = &report.previous {
+        println!("Saved the previous state to {}", previous.display());
+    }
+    println!(
+        "Imported {} ({} paths remapped)",
+        report.files.join(", "),
+        report.remapped
+    );
+    if !report.missing_repos.is_empty() {
+        println!("Not found on this machine - move them there, or re-import with --map:");
+        for (name, path) in &report.missing_repos {
+            println!("  {}: {}", name, path.display());
+        }
+    }
+    Ok(())
+}
+
+fn remap_json(value: &mut serde_json::Value, remap: &impl Fn(&str) -> Option<String>) -> usize {
+    match value {
+        serde_json::Value::String(text) => match remap(text) {
+            Some(new) => {
+                *text = new;
+                1
+            }
+            None => 0,
+        },
+        serde_json::Value::Array(items) => items.iter_mut().map(|v| remap_json(v, remap)).sum(),
+        serde_json::Value::Object(map) => map.values_mut().map(|v| remap_json(v, remap)).sum(),
+        _ => 0,
+    }
+}
+
+fn remap_toml_table(
+    table: &mut dyn toml_edit::TableLike,
+    remap: &impl Fn(&str) -> Option<String>,
+) -> usize {
+    table
+        .iter_mut()
+        .map(|(_, item)| remap_toml_item(item, remap))
+        .sum()
+}
+
+fn remap_toml_item(item: &mut toml_edit::Item, remap: &impl Fn(&str) -> Option<String>) -> usize {
+    match item {
+        toml_edit::Item::Value(value) => remap_toml_value(value, remap),
+        toml_edit::Item::Table(table) => remap_toml_table(table, remap),
+        toml_edit::Item::ArrayOfTables(tables) => tables
+            .iter_mut()
+            .map(|table| remap_toml_table(table, remap))
+            .sum(),
+        toml_edit::Item::None => 0,
+    }
+}
+
+fn remap_toml_value(
+    value: &mut toml_edit::Value,
+    remap: &impl Fn(&str) -> Option<String>,
+) -> usize {
+    match value {
+        toml_edit::Value::String(text) => match remap(text.value()) {
+            Some(new) => {
+                // Keep the comment or spacing around the value
+                let decor = text.decor().clone();
+                *text = toml_edit::Formatted::new(new);
+                *text.decor_mut() = decor;
+                1
+            }
+            None => 0,
+        },
+        toml_edit::Value::Array(items) => items
+            .iter_mut()
+            .map(|value| remap_toml_value(value, remap))
+            .sum(),
+        toml_edit::Value::InlineTable(table) => remap_toml_table(table, remap),
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mapping(from: &str, to: &str) -> PathMapping {
+        PathMapping {
+            from: PathBuf::from(from),
+            to: PathBuf::from(to),
+        }
+    }
+
+    #[test]
+    fn test_mapping_matches_whole_components_only() {
+        let m = mapping("/home/ana/code", "/srv/code");
+        assert_eq!(
+            m.apply("/home/ana/code/app").as_deref(),
+            Some("/srv/code/app")
+        );
+        assert_eq!(m.apply("/home/ana/code").as_deref(), Some("/srv/code"));
+        assert_eq!(m.apply("/home/ana/codex"), None);
+        assert_eq!(m.apply("npm test"), None);
+
+        assert!(PathMapping::parse("/a=/b").is_ok());
+        assert!(PathMapping::parse("/a").is_err());
+        assert!(PathMapping::parse("=/b").is_err());
+    }
+
+    #[test]
+    fn test_remap_rewrites_json_and_toml_keeping_comments() {
+        let mut bundle = Bundle {
+            format: BUNDLE_FORMAT.to_string(),
+            version: BUNDLE_VERSION,
+            created_at: Utc::now(),
+            home: None,
+            config_dir: PathBuf::from("/home/ana/.panoptes"),
+            files: BTreeMap::from([
+                (
+                    "config.toml".to_string(),
+                    "# where worktrees go\nworktrees_dir = \"/home/ana/wt\" # mine\n".to_string(),
+                ),
+                (
+                    "projects.json".to_string(),
+                    r#"{"projects":[{"name":"app","repo_path":"/home/ana/code/app","folder":["Work"]}]}"#
+                        .to_string(),
+                ),
+            ]),
+        };
+
+        let count = bundle
+            .remap(&[
+                mapping("/home/ana/code", "/srv/code"),
+                mapping("/home/ana", "/home/dev"),
+            ])
+            .unwrap();
+
+        assert_eq!(count, 2);
+        let config = &bundle.files["config.toml"];
+        assert!(config.contains("# where worktrees go"), "{config}");
+        assert!(
+            config.contains("worktrees_dir = \"/home/dev/wt\" # mine"),
+            "{config}"
+        );
+        let projects = &bundle.files["projects.json"];
+        assert!(projects.contains("\"/srv/code/app\""), "{projects}");
+        assert!(projects.contains("\"Work\""), "{projects}");
+    }
+
+    #[test]
+    fn test_import_round_trip_backs_up_the_previous_state() {
+        let source = tempfile::tempdir().unwrap();
+        let target = tempfile::tempdir().unwrap();
+        let repo = tempfile::tempdir().unwrap();
+        let mut store = ProjectStore::with_path(source.path().join("projects.json"));
+        let mut project = crate::project::Project::new(
+            "app".to_string(),
+            PathBuf::from("/old/place/app"),
+            "main".to_string(),
+        );
+        project.folder = vec!["Work".to_string(), "Client".to_string()];
+        store.add_project(project);
+        store.save().unwrap();
+        std::fs::write(source.path().join("config.toml"), "hook_port = 9999\n").unwrap();
+        std::fs::write(target.path().join("config.toml"), "hook_port = 1\n").unwrap();
+
+        let bundle_path = source.path().join("export.json");
+        assert_eq!(export(source.path(), &bundle_path).unwrap(), 2);
+
+        let report = import(
+            &bundle_path,
+            target.path(),
+            &[mapping("/old/place", repo.path().to_str().unwrap())],
+        )
+        .unwrap();
+
+        assert_eq!(report.files, vec!["config.toml", "projects.json"]);
+        assert_eq!(report.missing_repos.len(), 1);
+        assert_eq!(report.missing_repos[0].1, repo.path().join("app"));
+        let previous = Bundle::load(report.previous.as_ref().unwrap()).unwrap();
+        assert_eq!(previous.files["config.toml"], "hook_port = 1\n");
+
+        let (imported, warning) =
+            ProjectStore::load_from_with_status(&target.path().join("projects.json"));
+        assert!(warning.is_none());
+        let project = imported.projects().next().unwrap();
+        assert_eq!(project.folder, vec!["Work", "Client"]);
+        assert_eq!(project.repo_path, repo.path().join("app"));
+    }
+
+    #[test]
+    fn test_load_rejects_other_json() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("other.json");
+        std::fs::write(&path, "{\"projects\": []}").unwrap();
+        assert!(Bundle::load(&path).is_err());
+    }
+}
@@ -1,9 +1,19 @@
 //! Configuration management for Panoptes
 
 use anyhow::{Context, Result};
+use chrono::{DateTime, Local, NaiveTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
+use crate::keymap::KeysConfig;
+use crate::notify::command::NotifyCommands;
+use crate::notify::escalation::EscalationConfig;
+use crate::notify::rules::{folder_matches, NotifyRule, NotifyTarget, QuietHours};
+use crate::notify::webhook::WebhookConfig;
+use crate::pricing::PricingConfig;
+use crate::session::{BudgetsConfig, RotationConfig};
+
 /// Custom shell session shortcut
 ///
 /// Defines a keyboard shortcut that spawns a shell session with a predefined command.
@@ -158,6 +168,11 @@ pub struct Config {
     #[serde(default)]
     pub theme: ThemeMode,
 
+    /// Which named theme colours the UI: `dark`, `light`, or the name of a
+    /// file in `~/.panoptes/themes/` (without `.toml`)
+    #[serde(default = "default_color_theme")]
+    pub color_theme: String,
+
     // Everything below serialises as a TOML table or array-of-tables. TOML has
     // no way to express a bare key after a table header, so any scalar field
     // added later must go ABOVE this line or it will be silently swallowed into
@@ -166,6 +181,39 @@ pub struct Config {
     #[serde(default)]
     pub notify_on: NotifyOn,
 
+    /// Commands to run per attention reason (see [`crate::notify::command`])
+    #[serde(default, skip_serializing_if = "NotifyCommands::is_default")]
+    pub notify_commands: NotifyCommands,
+
+    /// Re-notifying while attention goes unacknowledged (see
+    /// [`crate::notify::escalation`])
+    #[serde(default, skip_serializing_if = "EscalationConfig::is_default")]
+    pub escalation: EscalationConfig,
+
+    /// Rebound keys, per context (see [`crate::keymap`])
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub keys: KeysConfig,
+
+    /// Model prices for cost estimates, over the built-in table (see
+    /// [`crate::pricing`])
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub pricing: PricingConfig,
+
+    /// Spending limits per session, project and account (see
+    /// [`crate::session::budget`])
+    #[serde(default, skip_serializing_if = "BudgetsConfig::is_empty")]
+    pub budgets: BudgetsConfig,
+
+    /// When and whether to move Codex sessions off a rate-limited account
+    /// (see [`crate::session::rate_limits`])
+    #[serde(default, skip_serializing_if = "RotationConfig::is_default")]
+    pub codex_rotation: RotationConfig,
+
+    /// URLs to POST to when a session wants attention (see
+    /// [`crate::notify::webhook`])
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub webhooks: Vec<WebhookConfig>,
+
     /// Custom shell session shortcuts
     ///
     /// Each shortcut defines a key that spawns a shell session with a predefined command.
@@ -175,8 +223,8 @@ pub struct Config {
 
 /// How Panoptes gets the user's attention when a session needs it
 ///
-/// Serialises as the lowercase strings ("bell", "title", "none") this field
-/// has always used in `config.toml`.
+/// Serialises as the lowercase strings ("bell", "title", "desktop", "osc",
+/// "none") used in `config.toml`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum NotificationMethod {
@@ -185,6 +233,10 @@ pub enum NotificationMethod {
     Bell,
     /// Rewrite the terminal title with the session that wants attention
     Title,
+    /// Raise a desktop notification over the freedesktop D-Bus session bus
+    Desktop,
+    /// Ask the terminal to raise a native notification (OSC 9 / OSC 777)
+    Osc,
     /// Stay silent
     None,
 }
@@ -203,6 +255,8 @@ impl<'de> Deserialize<'de> for NotificationMethod {
         Ok(match value.as_str() {
             "bell" => Self::Bell,
             "title" => Self::Title,
+            "desktop" => Self::Desktop,
+            "osc" => Self::Osc,
             "none" => Self::None,
             other => {
                 tracing::warn!(
@@ -263,6 +317,9 @@ impl<'de> Deserialize<'de> for ThemeMode {
 /// the audible/terminal-title notification. The split is deliberate: a stalled
 /// tool is worth showing in the list but is rarely worth a sound, since nothing
 /// is blocked on you and the watchdog is guessing.
+///
+/// The fields after the five reasons are rules that silence by time and by
+/// session (see [`crate::notify::rules`]).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NotifyOn {
     /// A permission dialog or inline question is blocking a turn
@@ -280,6 +337,31 @@ pub struct NotifyOn {
     /// A session's process died unexpectedly
     #[serde(default = "default_true")]
     pub crashed: bool,
+
+    /// A session went over a `[budgets]` limit
+    #[serde(default = "default_true")]
+    pub over_budget: bool,
+
+    /// Silence every notification until turned off again
+    #[serde(default)]
+    pub do_not_disturb: bool,
+
+    /// Notify only for sessions created in the last this-many hours; 0 for
+    /// every session
+    #[serde(default)]
+    pub recent_hours: u64,
+
+    /// A daily window with no notifications
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quiet_hours: Option<QuietHours>,
+
+    /// Rules per project, by project name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub projects: BTreeMap<String, NotifyRule>,
+
+    /// Rules per folder, by `/`-separated folder path
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub folders: BTreeMap<String, NotifyRule>,
 }
 
 impl Default for NotifyOn {
@@ -289,6 +371,12 @@ impl Default for NotifyOn {
             turn_complete: true,
             stalled: false,
             crashed: true,
+            over_budget: true,
+            do_not_disturb: false,
+            recent_hours: 0,
+            quiet_hours: None,
+            projects: BTreeMap::new(),
+            folders: BTreeMap::new(),
         }
     }
 }
@@ -302,14 +390,59 @@ impl NotifyOn {
             AttentionReason::TurnComplete => self.turn_complete,
             AttentionReason::Stalled { .. } => self.stalled,
             AttentionReason::Crashed { .. } => self.crashed,
+            AttentionReason::OverBudget { .. } => self.over_budget,
         }
     }
+
+    /// Whether the rules let a notification about this session through
+    ///
+    /// Asked after [`Self::rings`] has already said the reason is worth one.
+    pub fn allows(
+        &self,
+        reason: &crate::session::AttentionReason,
+        target: &NotifyTarget,
+        now: DateTime<Local>,
+    ) -> bool {
+        if self.is_quiet(now.time()) {
+            return false;
+        }
+        if self.recent_hours > 0 {
+            let age = now.with_timezone(&Utc) - target.created_at;
+            if age > chrono::Duration::hours(self.recent_hours as i64) {
+                return false;
+            }
+        }
+        let project_rule = target
+            .project
+            .and_then(|name| self.projects.get(name))
+            .or(target.repo_rule);
+        if project_rule.is_some_and(|rule| rule.silences(reason)) {
+            return false;
+        }
+        !self
+            .folders
+            .iter()
+            .any(|(key, rule)| folder_matches(key, target.folder) && rule.silences(reason))
+    }
+
+    /// Whether do-not-disturb or the quiet hours are silencing everything
+    pub fn is_quiet(&self, now: NaiveTime) -> bool {
+        self.do_not_disturb
+            || self
+                .quiet_hours
+                .as_ref()
+                .is_some_and(|quiet| quiet.contains(now))
+    }
 }
 
 fn default_true() -> bool {
     true
 }
 
+fn default_color_theme() -> String {
+    "dark".to_string()
+}
+
 fn default_hook_port() -> u16 {
     9999
 }
@@ -352,7 +485,15 @@ impl Default for Config {
             log_agent_events: false,
             attention_on_idle: false,
             theme: ThemeMode::default(),
+            color_theme: default_color_theme(),
             notify_on: NotifyOn::default(),
+            notify_commands: NotifyCommands::default(),
+            escalation: EscalationConfig::default(),
+            keys: KeysConfig::new(),
+            pricing: PricingConfig::new(),
+            budgets: BudgetsConfig::default(),
+            codex_rotation: RotationConfig::default(),
+            webhooks: Vec::new(),
             custom_shortcuts: Vec::new(),
         }
     }
@@ -396,6 +537,29 @@ impl Config {
         }
     }
 
+    /// Re-read `config.toml` for a live reload
+    ///
+    /// Parses exactly as [`Self::load_with_status`] does, but a file that
+    /// does not parse is left where it is and reported as an error: at
+    /// startup a broken file is backed up so the app can start on defaults,
+    /// while a running app keeps the settings it has and waits for the next
+    /// save - the file is most likely mid-edit. `None` when the file does not
+    /// exist, which an editor's save-by-rename can make true for a moment.
+    pub fn reload() -> Result<Option<Self>> {
+        Self::reload_from(&config_file_path())
+    }
+
+    fn reload_from(path: &Path) -> Result<Option<Self>> {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        };
+        let config =
+            toml::from_str(&content).with_context(|| format!("{} is not valid", path.display()))?;
+        Ok(Some(config))
+    }
+
     /// Drop custom shortcuts bound to keys that have since become reserved
     ///
     /// `q` and `,` were legal shortcut keys before the three-pane layout gave
@@ -430,13 +594,38 @@ impl Config {
 
     /// Save configuration to file (atomically, via a sibling temp file)
     ///
-    /// The whole file is rewritten from the struct, so a hand-edited
-    /// `config.toml` loses its comments and key order the first time anything
-    /// writes it - which the Notifications section does on every keystroke.
+    /// Only the settings that differ from the file are written, so its
+    /// comments, key order and layout survive (see [`crate::config_edit`]).
     pub fn save(&self) -> Result<()> {
-        let path = config_file_path();
-        let content = toml::to_string_pretty(self).context("Failed to serialize config")?;
-        crate::persistence::save_text_atomic(&path, &content, "config")
+        self.save_to(&config_file_path())
+    }
+
+    fn save_to(&self, path: &Path) -> Result<()> {
+        let existing = match std::fs::read_to_string(path) {
+            Ok(text) => Some(text),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => return Err(e).with_context(|| format!("Failed to read {:?}", path)),
+        };
+        let content = self.file_content(existing.as_deref())?;
+        crate::persistence::save_text_atomic(path, &content, "config")
+    }
+
+    /// What `config.toml` should hold for this config, given what it holds now
+    ///
+    /// With no file the whole config is written out. A file that does not
+    /// parse is refused rather than replaced: it is most likely mid-edit in
+    /// another window, and writing over it would lose the edit along with
+    /// every comment. Saving works again once it parses.
+    fn file_content(&self, existing: Option<&str>) -> Result<String> {
+        let after = toml::to_string_pretty(self).context("Failed to serialize config")?;
+        let Some(existing) = existing else {
+            return Ok(after);
+        };
+        let on_disk = toml::from_str::<Config>(existing)
+            .context("config.toml has an error; fix it before changing settings here")?;
+        let before = toml::to_string_pretty(&on_disk).context("Failed to serialize config")?;
+        crate::config_edit::update(existing, &before, &after)
+            .context("Could not edit config.toml in place")
     }
 
     /// Get a custom shortcut by key
@@ -495,6 +684,11 @@ pub fn logs_dir() -> PathBuf {
     config_dir().join("logs")
 }
 
+/// Get the directory user themes are read from
+pub fn themes_dir() -> PathBuf {
+    config_dir().join("themes")
+}
+
 /// Ensure all required directories exist
 pub fn ensure_directories() -> Result<()> {
     let config = Config::default();
@@ -530,6 +724,31 @@ mod tests {
             "code . &".to_string(),
             false,
         ));
+        original.keys.insert(
+            "normal".to_string(),
+            BTreeMap::from([(
+                "down".to_string(),
+                crate::keymap::ChordList::Many(vec!["down".to_string(), "j".to_string()]),
+            )]),
+        );
+
+        original.notify_commands.approval = Some("paplay knock.oga".to_string());
+        original
+            .escalation
+            .steps
+            .push(crate::notify::escalation::EscalationStep {
+                after_mins: 15,
+                method: Some(NotificationMethod::Desktop),
+                command: false,
+                webhooks: true,
+            });
+        original.webhooks.push(WebhookConfig {
+            url: "https://ntfy.sh/panoptes".to_string(),
+            on: vec!["approval".to_string()],
+            template: Some("{session}: {summary}".to_string()),
+            content_type: "text/plain".to_string(),
+            headers: BTreeMap::from([("Priority".to_string(), "high".to_string())]),
+        });
 
         let text = toml::to_string_pretty(&original).expect("config must serialise");
         let parsed: Config = toml::from_str(&text).expect("config must round trip");
@@ -539,6 +758,10 @@ mod tests {
         assert!(parsed.notify_on.stalled);
         assert!(parsed.notify_on.approval);
         assert_eq!(parsed.custom_shortcuts.len(), 1);
+        assert_eq!(parsed.keys, original.keys);
+        assert_eq!(parsed.notify_commands, original.notify_commands);
+        assert_eq!(parsed.escalation, original.escalation);
+        assert_eq!(parsed.webhooks, original.webhooks);
         assert_eq!(parsed.scrollback_lines, original.scrollback_lines);
     }
 
@@ -565,6 +788,109 @@ notification_method = "title"
         assert!(!parsed.attention_on_idle);
     }
 
+    /// The rules silence by time, age, project and folder, and nothing else
+    #[test]
+    fn test_notify_rules_silence_only_what_they_name() {
+        use crate::session::AttentionReason;
+
+        let approval = AttentionReason::Approval { tool: None };
+        let done = AttentionReason::TurnComplete;
+        let now = Local::now();
+        let folder = vec!["Acme".to_string(), "Platform".to_string()];
+        let target = NotifyTarget {
+            project: Some("api"),
+            folder: &folder,
+            repo_rule: None,
+            created_at: now.with_timezone(&Utc) - chrono::Duration::hours(3),
+        };
+
+        let mut rules = NotifyOn::default();
+        assert!(rules.allows(&approval, &target, now));
+
+        rules.recent_hours = 2;
+        assert!(!rules.allows(&approval, &target, now), "session too old");
+        rules.recent_hours = 4;
+        assert!(rules.allows(&approval, &target, now));
+
+        rules.folders.insert(
+            "Acme".to_string(),
+            NotifyRule {
+                mute: false,
+                only: vec!["approval".to_string()],
+            },
+        );
+        assert!(rules.allows(&approval, &target, now));
+        assert!(
+            !rules.allows(&done, &target, now),
+            "folder allows approval only"
+        );
+
+        rules.projects.insert(
+            "api".to_string(),
+            NotifyRule {
+                mute: true,
+                only: Vec::new(),
+            },
+        );
+        assert!(!rules.allows(&approval, &target, now), "project is muted");
+        let other = NotifyTarget {
+            project: Some("web"),
+            folder: &[],
+            ..target
+        };
+        assert!(rules.allows(&done, &other, now));
+
+        // A repository's own rule applies until config.toml names the project
+        let quiet_repo = NotifyRule {
+            mute: true,
+            only: Vec::new(),
+        };
+        let repo_muted = NotifyTarget {
+            repo_rule: Some(&quiet_repo),
+            ..other
+        };
+        assert!(!rules.allows(&done, &repo_muted, now));
+        rules
+            .projects
+            .insert("web".to_string(), NotifyRule::default());
+        assert!(rules.allows(&done, &repo_muted, now));
+
+        rules.do_not_disturb = true;
+        assert!(!rules.allows(&done, &other, now));
+    }
+
+    #[test]
+    fn test_notify_rules_load_from_toml() {
+        let text = r#"
+[notify_on]
+approval = true
+do_not_disturb = true
+recent_hours = 6
+
+[notify_on.quiet_hours]
+start = "22:00"
+end = "07:00"
+
+[notify_on.projects.scratch]
+mute = true
+
+[notify_on.folders."Acme/Platform"]
+only = ["approval", "crashed"]
+"#;
+        let parsed: Config = toml::from_str(text).expect("rules must load");
+        let rules = &parsed.notify_on;
+        assert!(rules.do_not_disturb);
+        assert_eq!(rules.recent_hours, 6);
+        assert_eq!(rules.quiet_hours.as_ref().unwrap().start, "22:00");
+        assert!(rules.projects["scratch"].mute);
+        assert_eq!(rules.folders["Acme/Platform"].only.len(), 2);
+
+        let round_trip: Config = toml::from_str(&toml::to_string_pretty(&parsed).unwrap()).unwrap();
+        assert_eq!(round_trip.notify_on.projects, rules.projects);
+        assert_eq!(round_trip.notify_on.folders, rules.folders);
+        assert_eq!(round_trip.notify_on.quiet_hours, rules.quiet_hours);
+    }
+
     #[test]
     fn test_default_config() {
         let config = Config::default();
@@ -572,7 +898,7 @@ notification_method = "title"
         assert_eq!(config.notification_method, NotificationMethod::Bell);
     }
 
-    /// The three documented values parse to their variants; anything else -
+    /// The documented values parse to their variants; anything else -
     /// this field predates the enum, so arbitrary hand-typed strings exist in
     /// config files - falls back to the bell, exactly as the old string match
     /// treated it. A typo must not fail the whole config load.
@@ -581,6 +907,8 @@ notification_method = "title"
         for (raw, expected) in [
             ("bell", NotificationMethod::Bell),
             ("title", NotificationMethod::Title),
+            ("desktop", NotificationMethod::Desktop),
+            ("osc", NotificationMethod::Osc),
             ("none", NotificationMethod::None),
             ("gong", NotificationMethod::Bell),
         ] {
@@ -597,6 +925,8 @@ notification_method = "title"
         for method in [
             NotificationMethod::Bell,
             NotificationMethod::Title,
+            NotificationMethod::Desktop,
+            NotificationMethod::Osc,
             NotificationMethod::None,
         ] {
             let config = Config {
@@ -641,6 +971,129 @@ notification_method = "title"
         assert_eq!(config.hook_port, 9999);
     }
 
+    const COMMENTED: &str = r#"# Panoptes - kept in dotfiles
+hook_port = 9999   # must match the hook scripts
+notification_method = "bell"
+
+# Notifications
+[notify_on]
+approval = true
+stalled = false   # too noisy
+
+# Editor
+[[custom_shortcuts]]
+key = "v"
+name = "VSCode"
+command = "code ."
+
+# Lazygit, closes itself
+[[custom_shortcuts]]
+key = "g"
+command = "lazygit"
+auto_close = true
+"#;
+
+    #[test]
+    fn test_save_keeps_comments_and_changes_only_what_changed() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(&path, COMMENTED).unwrap();
+        let mut config: Config = toml::from_str(COMMENTED).unwrap();
+
+        config.notify_on.stalled = true;
+        config.notification_method = NotificationMethod::Title;
+        config.suspend_after_secs = 60;
+        config.custom_shortcuts[0].command = "code -n .".to_string();
+        config.custom_shortcuts.push(CustomShortcut::new(
+            't',
+            "Tests".to_string(),
+            "cargo test".to_string(),
+            false,
+        ));
+        config.save_to(&path).unwrap();
+
+        let saved = std::fs::read_to_string(&path).unwrap();
+        for kept in [
+            "# Panoptes - kept in dotfiles",
+            "hook_port = 9999   # must match the hook scripts",
+            "# Notifications",
+            "stalled = true   # too noisy",
+            "# Editor",
+            "# Lazygit, closes itself",
+        ] {
+            assert!(saved.contains(kept), "lost {kept:?}:\n{saved}");
+        }
+        assert!(saved.contains(r#"notification_method = "title""#));
+        assert!(saved.contains("suspend_after_secs = 60"));
+        // Defaults the user never wrote stay unwritten
+        assert!(!saved.contains("scrollback_lines"));
+        assert!(!saved.contains("turn_complete"));
+
+        let reloaded: Config = toml::from_str(&saved).unwrap();
+        assert_eq!(reloaded.custom_shortcuts, config.custom_shortcuts);
+        assert!(reloaded.notify_on.stalled);
+        assert_eq!(reloaded.suspend_after_secs, 60);
+    }
+
+    #[test]
+    fn test_save_deleting_a_shortcut_keeps_the_others_comments() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(&path, COMMENTED).unwrap();
+        let mut config: Config = toml::from_str(COMMENTED).unwrap();
+
+        config.custom_shortcuts.remove(0);
+        config.save_to(&path).unwrap();
+
+        let saved = std::fs::read_to_string(&path).unwrap();
+        assert!(!saved.contains("VSCode"));
+        assert!(saved.contains("# Lazygit, closes itself\n[[custom_shortcuts]]"));
+        let reloaded: Config = toml::from_str(&saved).unwrap();
+        assert_eq!(reloaded.custom_shortcuts, config.custom_shortcuts);
+    }
+
+    #[test]
+    fn test_save_without_a_file_writes_everything() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+
+        Config::default().save_to(&path).unwrap();
+
+        let saved = std::fs::read_to_string(&path).unwrap();
+        assert!(saved.contains("hook_port = 9999"));
+        assert!(saved.contains("scrollback_lines"));
+    }
+
+    #[test]
+    fn test_save_refuses_to_overwrite_a_file_that_does_not_parse() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        let broken = "# half-way through an edit\nsuspend_after_secs = \n";
+        std::fs::write(&path, broken).unwrap();
+
+        let err = Config::default().save_to(&path).unwrap_err();
+
+        assert!(format!("{:#}", err).contains("config.toml has an error"));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), broken);
+    }
+
+    /// A live reload must never rename away the file the user is editing
+    #[test]
+    fn test_reload_leaves_a_broken_file_in_place() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+
+        assert!(Config::reload_from(&path).unwrap().is_none());
+
+        std::fs::write(&path, "suspend_after_secs = 60\n").unwrap();
+        let config = Config::reload_from(&path).unwrap().unwrap();
+        assert_eq!(config.suspend_after_secs, 60);
+
+        std::fs::write(&path, "suspend_after_secs = \n").unwrap();
+        assert!(Config::reload_from(&path).is_err());
+        assert!(path.exists());
+    }
+
     #[test]
     fn test_config_serialization() {
         let config = Config::default();
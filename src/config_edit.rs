@@ -0,0 +1,279 @@
+//! Saving `config.toml` without losing what the user wrote in it
+//!
+//! Serialising [`crate::config::Config`] produces a complete, canonical file:
+//! every key, in struct order, no comments. Writing that over a hand-kept
+//! config destroys its comments and layout the first time the Settings pane
+//! changes anything. Instead the save is computed as an edit:
+//!
+//! 1. `before` is what the file on disk *means* - it is parsed into a `Config`
+//!    and serialised again, so it has the same shape as `after`
+//! 2. `after` is the running config, serialised the same way
+//! 3. only the keys that differ between the two are touched in the file
+//!
+//! A key the user never wrote stays unwritten until its value changes, a key
+//! serde does not know is left alone, and a changed value keeps the comment
+//! after it. Arrays of tables (`[[custom_shortcuts]]`, `[[webhooks]]`) are
+//! matched entry by entry, so deleting one shortcut does not disturb the
+//! comments above the others.
+
+use anyhow::{Context, Result};
+use toml_edit::{ArrayOfTables, DocumentMut, Item, Table, TableLike};
+
+/// Apply the difference between `before` and `after` to `existing`
+///
+/// All three are TOML documents: `existing` is the file as the user left it,
+/// `before` and `after` are serialiser output for what it meant and what it
+/// should now mean. Returns the edited file.
+pub fn update(existing: &str, before: &str, after: &str) -> Result<String> {
+    let mut doc: DocumentMut = existing.parse().context("config.toml is not valid TOML")?;
+    let before: DocumentMut = before.parse().context("Failed to re-read config")?;
+    let after: DocumentMut = after.parse().context("Failed to re-read config")?;
+    patch_table(
+        doc.as_table_mut(),
+        before.as_table(),
+        after.as_table(),
+        None,
+    );
+    Ok(doc.to_string())
+}
+
+/// Bring `doc` from `before` to `after`, touching only the keys that differ
+///
+/// `position` is where new tables inside this one are printed: the
+/// document orders tables by position, and a table taken from `after` would
+/// otherwise carry its position in *that* document.
+fn patch_table(
+    doc: &mut dyn TableLike,
+    before: &dyn TableLike,
+    after: &dyn TableLike,
+    position: Option<usize>,
+) {
+    for (key, _) in before.iter() {
+        if after.get(key).is_none() {
+            doc.remove(key);
+        }
+    }
+    for (key, new) in after.iter() {
+        let old = before.get(key);
+        if old.is_some_and(|old| same(old, new)) {
+            continue;
+        }
+        match doc.get_mut(key) {
+            Some(current) => patch_item(current, old, new, position),
+            None => {
+                let mut item = new.clone();
+                place(&mut item, position.unwrap_or(usize::MAX));
+                doc.insert(key, item);
+            }
+        }
+    }
+}
+
+fn patch_item(current: &mut Item, old: Option<&Item>, new: &Item, position: Option<usize>) {
+    // A key the user wrote that serde skips (a default, an empty table) has
+    // no `before`; it is patched as if from empty, keeping the rest of it
+    let empty = Item::Table(Table::new());
+    let old = old.unwrap_or(&empty);
+
+    if let (Some(old_table), Some(new_table)) = (old.as_table_like(), new.as_table_like()) {
+        // Only standard tables have a position; inline and dotted ones
+        // print with their parent
+        let at = match &*current {
+            Item::Table(table) => table.position().or(position),
+            _ => position,
+        };
+        if let Some(table) = current.as_table_like_mut() {
+            patch_table(table, old_table, new_table, at);
+            return;
+        }
+    }
+    match (current, old, new) {
+        (Item::ArrayOfTables(current), Item::ArrayOfTables(old), Item::ArrayOfTables(new))
+            if current.len() == old.len() =>
+        {
+            patch_array_of_tables(current, old, new, position);
+        }
+        (Item::ArrayOfTables(current), _, Item::ArrayOfTables(new)) => {
+            let at = current.iter().find_map(Table::position).or(position);
+            let mut item = Item::ArrayOfTables(new.clone());
+            place(&mut item, at.unwrap_or(usize::MAX));
+            *current = item.into_array_of_tables().unwrap_or_default();
+        }
+        (Item::Value(current), _, Item::Value(new)) => {
+            // The comment after a value lives in its decor
+            let decor = current.decor().clone();
+            *current = new.clone();
+            *current.decor_mut() = decor;
+        }
+        (current, _, new) => {
+            let mut item = new.clone();
+            place(&mut item, position.unwrap_or(usize::MAX));
+            *current = item;
+        }
+    }
+}
+
+/// Match entries up, keep the ones that did not change as they are, edit the
+/// ones that did in place, and add the new ones after them
+fn patch_array_of_tables(
+    doc: &mut ArrayOfTables,
+    before: &ArrayOfTables,
+    after: &ArrayOfTables,
+    position: Option<usize>,
+) {
+    let mut unused: Vec<Option<Table>> = doc.iter().cloned().map(Some).collect();
+    let mut chosen: Vec<Option<Table>> = vec![None; after.len()];
+
+    // Unchanged entries first, wherever they moved to
+    for (j, new) in after.iter().enumerate() {
+        let found = before
+            .iter()
+            .enumerate()
+            .position(|(i, old)| unused[i].is_some() && same_table(old, new));
+        if let Some(i) = found {
+            chosen[j] = unused[i].take();
+        }
+    }
+    // Then edits, to the entry that was at the same index
+    for (j, new) in after.iter().enumerate() {
+        if chosen[j].is_some() {
+            continue;
+        }
+        if let (Some(mut table), Some(old)) =
+            (unused.get_mut(j).and_then(Option::take), before.get(j))
+        {
+            let at = table.position().or(position);
+            patch_table(&mut table, old, new, at);
+            chosen[j] = Some(table);
+        }
+    }
+
+    // Tables print in position order, so the positions must not run
+    // backwards or the entries would come out shuffled
+    let mut last = doc.iter().find_map(Table::position).or(position);
+    doc.clear();
+    for (j, table) in chosen.into_iter().enumerate() {
+        let mut table = table.unwrap_or_else(|| {
+            let mut item = Item::Table(after.get(j).cloned().unwrap_or_default());
+            place(&mut item, last.unwrap_or(usize::MAX));
+            item.into_table().unwrap_or_default()
+        });
+        match (table.position(), last) {
+            (Some(p), Some(l)) if p < l => table.set_position(l),
+            (None, Some(l)) => table.set_position(l),
+            _ => {}
+        }
+        last = table.position();
+        doc.push(table);
+    }
+}
+
+/// Give an item taken from another document, and every table inside it, a
+/// position in this one
+fn place(item: &mut Item, position: usize) {
+    match item {
+        Item::Table(table) => place_table(table, position),
+        Item::ArrayOfTables(array) => {
+            for table in array.iter_mut() {
+                place_table(table, position);
+            }
+        }
+        Item::Value(_) | Item::None => {}
+    }
+}
+
+fn place_table(table: &mut Table, position: usize) {
+    table.set_position(position);
+    for (_, item) in table.iter_mut() {
+        place(item, position);
+    }
+}
+
+/// Whether two items from serialiser output mean the same
+fn same(a: &Item, b: &Item) -> bool {
+    match (a, b) {
+        (Item::Value(a), Item::Value(b)) => a.to_string() == b.to_string(),
+        (Item::ArrayOfTables(a), Item::ArrayOfTables(b)) => {
+            a.len() == b.len() && a.iter().zip(b.iter()).all(|(a, b)| same_table(a, b))
+        }
+        (a, b) => match (a.as_table_like(), b.as_table_like()) {
+            (Some(a), Some(b)) => same_table_like(a, b),
+            _ => false,
+        },
+    }
+}
+
+fn same_table(a: &Table, b: &Table) -> bool {
+    same_table_like(a, b)
+}
+
+fn same_table_like(a: &dyn TableLike, b: &dyn TableLike) -> bool {
+    a.len() == b.len()
+        && a.iter()
+            .all(|(key, item)| b.get(key).is_some_and(|other| same(item, other)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unknown_keys_and_unwritten_defaults_are_left_alone() {
+        let existing = "# mine\nold_key = 1\na = 1\n";
+        let before = "a = 1\nb = 2\n";
+        let after = "a = 5\nb = 2\n";
+
+        let updated = update(existing, before, after).unwrap();
+
+        assert_eq!(updated, "# mine\nold_key = 1\na = 5\n");
+    }
+
+    #[test]
+    fn test_removed_keys_and_tables_go() {
+        let existing = "a = 1\n\n# commands\n[cmds]\nx = \"knock\"\n\n[keep]\ny = 1\n";
+        let before = "a = 1\n\n[cmds]\nx = \"knock\"\n\n[keep]\ny = 1\n";
+        let after = "\n[keep]\ny = 1\n";
+
+        let updated = update(existing, before, after).unwrap();
+
+        assert!(!updated.contains("cmds"));
+        assert!(!updated.contains("a = 1"));
+        assert!(updated.contains("[keep]\ny = 1"));
+    }
+
+    #[test]
+    fn test_new_tables_land_after_their_parent_or_at_the_end() {
+        let existing = "[notify_on]\napproval = true\n\n[keys.normal]\nquit = \"q\"\n";
+        let before = "[notify_on]\napproval = true\n\n[keys.normal]\nquit = \"q\"\n";
+        let after = "[notify_on]\napproval = true\n\n[notify_on.projects.scratch]\nmute = true\n\n\
+                     [keys.normal]\nquit = \"q\"\n\n[escalation]\non = [\"approval\"]\n";
+
+        let updated = update(existing, before, after).unwrap();
+
+        let scratch = updated.find("[notify_on.projects.scratch]").unwrap();
+        let keys = updated.find("[keys.normal]").unwrap();
+        let escalation = updated.find("[escalation]").unwrap();
+        assert!(scratch < keys && keys < escalation, "{updated}");
+        let parsed: toml::Value = toml::from_str(&updated).unwrap();
+        assert_eq!(
+            parsed["notify_on"]["projects"]["scratch"]["mute"].as_bool(),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn test_inline_and_dotted_tables_are_edited_in_place() {
+        let existing =
+            "notify_on = { approval = true, stalled = false }\nkeys.normal.quit = \"q\"\n";
+        let before =
+            "[notify_on]\napproval = true\nstalled = false\n\n[keys.normal]\nquit = \"q\"\n";
+        let after = "[notify_on]\napproval = true\nstalled = true\n\n[keys.normal]\nquit = \"x\"\n";
+
+        let updated = update(existing, before, after).unwrap();
+
+        assert_eq!(
+            updated,
+            "notify_on = { approval = true, stalled = true }\nkeys.normal.quit = \"x\"\n"
+        );
+    }
+}
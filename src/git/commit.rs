@@ -0,0 +1,542 @@
+//! Committing and pushing a checkout's work
+//!
+//! The read side (what changed, and the diff of one file) and the write side
+//! (commit a chosen set of files, push the branch) of committing from the
+//! dashboard. Reading and committing go through git2; pushing shells out to
+//! the git CLI for the same reason fetching does - it handles SSH agents and
+//! credential helpers natively.
+
+use anyhow::{Context, Result};
+use git2::{DiffFormat, DiffOptions, Oid, Status, StatusOptions};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+use super::GitOps;
+use crate::process::{self, Finished, Limits};
+
+/// How long a push may run before it is killed
+///
+/// Generous for a large push over a slow link; what it guards against is a
+/// remote that never answers, which would otherwise hold the job forever.
+const PUSH_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// What happened to a changed file, as git's short status letter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    /// New file, staged or not
+    Added,
+    /// Existing file with different content
+    Modified,
+    /// File removed from the working tree or the index
+    Deleted,
+    /// File type changed (e.g. a file became a symlink)
+    TypeChange,
+    /// Unresolved merge conflict
+    Conflicted,
+}
+
+impl ChangeKind {
+    /// Single-letter code, as `git status --short` shows it
+    pub fn code(self) -> char {
+        match self {
+            ChangeKind::Added => 'A',
+            ChangeKind::Modified => 'M',
+            ChangeKind::Deleted => 'D',
+            ChangeKind::TypeChange => 'T',
+            ChangeKind::Conflicted => 'U',
+        }
+    }
+}
+
+/// One changed file in a checkout
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkingChange {
+    /// Repository-relative path, with `/` separators
+    pub path: String,
+    /// What happened to it
+    pub kind: ChangeKind,
+    /// Whether the index holds changes to it
+    pub staged: bool,
+    /// Whether the working tree holds changes the index does not
+    pub unstaged: bool,
+}
+
+/// Where a push went
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PushTarget {
+    /// Remote name, e.g. "origin"
+    pub remote: String,
+    /// Branch pushed, both locally and on the remote
+    pub branch: String,
+}
+
+impl std::fmt::Display for PushTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.remote, self.branch)
+    }
+}
+
+impl GitOps {
+    /// Every file with staged, unstaged or untracked changes, sorted by path
+    pub fn working_changes(&self) -> Result<Vec<WorkingChange>> {
+        let mut opts = StatusOptions::new();
+        opts.include_untracked(true)
+            .recurse_untracked_dirs(true)
+            .include_ignored(false);
+        let statuses = self
+            .repo
+            .statuses(Some(&mut opts))
+            .context("Failed to read repository status")?;
+
+        let mut changes: Vec<WorkingChange> = statuses
+            .iter()
+            .filter_map(|entry| {
+                let path = entry.path()?.replace('\\', "/");
+                let status = entry.status();
+                Some(WorkingChange {
+                    path,
+                    kind: change_kind(status)?,
+                    staged: status.intersects(
+                        Status::INDEX_NEW
+                            | Status::INDEX_MODIFIED
+                            | Status::INDEX_DELETED
+                            | Status::INDEX_RENAMED
+                            | Status::INDEX_TYPECHANGE,
+                    ),
+                    unstaged: status.intersects(
+                        Status::WT_NEW
+                            | Status::WT_MODIFIED
+                            | Status::WT_DELETED
+                            | Status::WT_RENAMED
+                            | Status::WT_TYPECHANGE
+                            | Status::CONFLICTED,
+                    ),
+                })
+            })
+            .collect();
+        changes.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(changes)
+    }
+
+    /// The patch for one file: its staged and unstaged changes against `HEAD`
+    ///
+    /// An untracked file shows as entirely added, which is what committing it
+    /// would record.
+    pub fn diff_for_path(&self, path: &str) -> Result<String> {
+        let head_tree = self.head_tree()?;
+        let mut opts = DiffOptions::new();
+        opts.pathspec(path)
+            .disable_pathspec_match(true)
+            .include_untracked(true)
+            .show_untracked_content(true)
+            .recurse_untracked_dirs(true);
+        let diff = self
+            .repo
+            .diff_tree_to_workdir_with_index(head_tree.as_ref(), Some(&mut opts))
+            .with_context(|| format!("Failed to diff '{}'", path))?;
+
+        let mut patch = String::new();
+        diff.print(DiffFormat::Patch, |_, _, line| {
+            // Content lines carry their +/- origin separately from the text
+            if matches!(line.origin(), '+' | '-' | ' ') {
+                patch.push(line.origin());
+            }
+            patch.push_str(&String::from_utf8_lossy(line.content()));
+            true
+        })
+        .context("Failed to format diff")?;
+        Ok(patch)
+    }
+
+    /// Commit exactly `paths` onto `HEAD`, leaving the rest of the index alone
+    ///
+    /// A chosen path with staged changes is committed as staged, so hunks the
+    /// user left unstaged stay out of the commit and in the working tree. A
+    /// chosen path with nothing staged is committed as it is in the working
+    /// tree (or its deletion is). The commit's tree is `HEAD`'s with just
+    /// those paths replaced, so whatever else the user staged stays staged
+    /// for a later commit. Returns the new commit's ID.
+    pub fn commit_paths(&self, paths: &[String], message: &str) -> Result<Oid> {
+        let message = message.trim();
+        if message.is_empty() {
+            anyhow::bail!("Commit message is empty");
+        }
+        if paths.is_empty() {
+            anyhow::bail!("No files selected to commit");
+        }
+
+        let workdir = self
+            .workdir()
+            .context("Repository has no working directory")?
+            .to_path_buf();
+        let head_commit = self
+            .repo
+            .head()
+            .ok()
+            .and_then(|head| head.peel_to_commit().ok());
+        let staged: Vec<String> = self
+            .working_changes()?
+            .into_iter()
+            .filter(|change| change.staged)
+            .map(|change| change.path)
+            .collect();
+
+        // Paths with nothing staged are staged from the working tree first
+        let mut index = self.repo.index().context("Failed to open the index")?;
+        let unstaged_picks: Vec<&String> = paths.iter().filter(|p| !staged.contains(p)).collect();
+        for path in &unstaged_picks {
+            let relative = Path::new(path.as_str());
+            if workdir.join(relative).symlink_metadata().is_ok() {
+                index
+                    .add_path(relative)
+                    .with_context(|| format!("Failed to stage '{}'", path))?;
+            } else if index.get_path(relative, 0).is_some() {
+                index
+                    .remove_path(relative)
+                    .with_context(|| format!("Failed to stage removal of '{}'", path))?;
+            }
+        }
+        if !unstaged_picks.is_empty() {
+            index.write().context("Failed to write the index")?;
+        }
+
+        // HEAD's tree with the chosen paths taken from the index
+        let mut picked = git2::Index::new().context("Failed to create an index")?;
+        if let Some(commit) = &head_commit {
+            let tree = commit.tree().context("Failed to read the HEAD tree")?;
+            picked
+                .read_tree(&tree)
+                .context("Failed to read the HEAD tree")?;
+        }
+        for path in paths {
+            let relative = Path::new(path);
+            match index.get_path(relative, 0) {
+                Some(entry) => picked
+                    .add(&entry)
+                    .with_context(|| format!("Failed to add '{}' to the commit", path))?,
+                None => {
+                    if picked.get_path(relative, 0).is_some() {
+                        picked.remove_path(relative).with_context(|| {
+                            format!("Failed to remove '{}' from the commit", path)
+                        })?;
+                    }
+                }
+            }
+        }
+        let tree_id = picked
+            .write_tree_to(&self.repo)
+            .context("Failed to write tree")?;
+        if head_commit.as_ref().map(|c| c.tree_id()) == Some(tree_id) {
+            anyhow::bail!("Nothing to commit: the selected files match HEAD");
+        }
+        let tree = self
+            .repo
+            .find_tree(tree_id)
+            .context("Failed to read tree")?;
+        let signature = self
+            .repo
+            .signature()
+            .context("No commit identity: set user.name and user.email in your git config")?;
+        let parents: Vec<&git2::Commit> = head_commit.iter().collect();
+
+        self.repo
+            .commit(
+                Some("HEAD"),
+                &signature,
+                &signature,
+                message,
+                &tree,
+                &parents,
+            )
+            .context("Failed to create commit")
+    }
+
+    /// Where [`push_current_branch`](Self::push_current_branch) would push
+    ///
+    /// The branch's configured upstream remote if it has one, else `origin`,
+    /// else the repository's only remote.
+    pub fn push_target(&self) -> Result<PushTarget> {
+        let branch = self
+            .current_branch()?
+            .context("HEAD is detached; check out a branch to push")?;
+
+        let configured = self
+            .repo
+            .config()
+            .ok()
+            .and_then(|config| config.get_string(&format!("branch.{}.remote", branch)).ok());
+        let remotes = self.repo.remotes().context("Failed to list remotes")?;
+        let names: Vec<&str> = remotes.iter().flatten().collect();
+
+        let remote = match configured {
+            Some(remote) => remote,
+            None if names.contains(&"origin") => "origin".to_string(),
+            None if names.len() == 1 => names[0].to_string(),
+            None if names.is_empty() => anyhow::bail!("No remote configured to push to"),
+            None => anyhow::bail!(
+                "Several remotes and no upstream for '{}'; set one with git push -u",
+                branch
+            ),
+        };
+        Ok(PushTarget { remote, branch })
+    }
+
+    /// Push the checked-out branch to its remote, setting it as upstream
+    ///
+    /// A slow operation; run it off the event loop. Terminal prompts are
+    /// disabled, so missing credentials fail instead of hanging on a prompt
+    /// nobody can see, and a push still running after two minutes is
+    /// killed, with any ssh or credential helper it started.
+    pub fn push_current_branch(&self) -> Result<PushTarget> {
+        let target = self.push_target()?;
+        let workdir = self
+            .workdir()
+            .context("Repository has no working directory")?;
+
+        tracing::debug!(
+            "Pushing {} to {} via git CLI in {:?}",
+            target.branch,
+            target.remote,
+            workdir
+        );
+
+        let mut push = Command::new("git");
+        push.args(["push", "--set-upstream", &target.remote, &target.branch])
+            .current_dir(workdir)
+            .env("GIT_TERMINAL_PROMPT", "0")
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped());
+        let limits = Limits {
+            timeout: Some(PUSH_TIMEOUT),
+            cancel: None,
+        };
+        match process::run(&mut push, limits)
+            .context("Failed to execute git push. Is git installed and in PATH?")?
+        {
+            Finished::Exited { status, .. } if status.success() => Ok(target),
+            Finished::Exited { stderr, .. } => anyhow::bail!("git push failed: {}", stderr.trim()),
+            Finished::TimedOut | Finished::Cancelled => anyhow::bail!(
+                "git push to {} gave no answer in {}s",
+                target.remote,
+                PUSH_TIMEOUT.as_secs()
+            ),
+        }
+    }
+
+    /// The tree `HEAD` points at, or `None` before the first commit
+    fn head_tree(&self) -> Result<Option<git2::Tree<'_>>> {
+        match self.repo.head() {
+            Ok(head) => Ok(Some(
+                head.peel_to_tree()
+                    .context("Failed to read the HEAD tree")?,
+            )),
+            Err(e) if e.code() == git2::ErrorCode::UnbornBranch => Ok(None),
+            Err(e) => Err(e).context("Failed to get HEAD"),
+        }
+    }
+}
+
+/// The kind of change a status entry describes, if it describes one
+fn change_kind(status: Status) -> Option<ChangeKind> {
+    if status.is_conflicted() {
+        Some(ChangeKind::Conflicted)
+    } else if status.intersects(Status::INDEX_NEW | Status::WT_NEW) {
+        Some(ChangeKind::Added)
+    } else if status.intersects(Status::INDEX_DELETED | Status::WT_DELETED) {
+        Some(ChangeKind::Deleted)
+    } else if status.intersects(Status::INDEX_TYPECHANGE | Status::WT_TYPECHANGE) {
+        Some(ChangeKind::TypeChange)
+    } else if status.intersects(
+        Status::INDEX_MODIFIED | Status::WT_MODIFIED | Status::INDEX_RENAMED | Status::WT_RENAMED,
+    ) {
+        Some(ChangeKind::Modified)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::Repository;
+    use tempfile::TempDir;
+
+    /// A repo with `a.rs` and `b.rs` committed on the initial commit
+    fn create_test_repo() -> (TempDir, Repository) {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+        {
+            let mut config = repo.config().unwrap();
+            config.set_str("user.name", "Test User").unwrap();
+            config.set_str("user.email", "test@example.com").unwrap();
+        }
+        std::fs::write(temp_dir.path().join("a.rs"), "fn a() {}\n").unwrap();
+        std::fs::write(temp_dir.path().join("b.rs"), "fn b() {}\n").unwrap();
+        let git = GitOps::open(temp_dir.path()).unwrap();
+        git.commit_paths(&["a.rs".into(), "b.rs".into()], "Initial commit")
+            .unwrap();
+        drop(git);
+        (temp_dir, repo)
+    }
+
+    fn committed_paths(repo: &Repository) -> Vec<String> {
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        let parent_tree = head.parent(0).ok().map(|p| p.tree().unwrap());
+        let diff = repo
+            .diff_tree_to_tree(parent_tree.as_ref(), Some(&head.tree().unwrap()), None)
+            .unwrap();
+        diff.deltas()
+            .map(|d| d.new_file().path().unwrap().to_string_lossy().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_working_changes_reports_staged_unstaged_and_untracked() {
+        let (temp_dir, repo) = create_test_repo();
+        std::fs::write(temp_dir.path().join("a.rs"), "fn a() { 1 }\n").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("a.rs")).unwrap();
+        index.write().unwrap();
+        std::fs::remove_file(temp_dir.path().join("b.rs")).unwrap();
+        std::fs::write(temp_dir.path().join("new.rs"), "").unwrap();
+
+        let changes = GitOps::open(temp_dir.path())
+            .unwrap()
+            .working_changes()
+            .unwrap();
+
+        let summary: Vec<(&str, char, bool, bool)> = changes
+            .iter()
+            .map(|c| (c.path.as_str(), c.kind.code(), c.staged, c.unstaged))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("a.rs", 'M', true, false),
+                ("b.rs", 'D', false, true),
+                ("new.rs", 'A', false, true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_for_path_shows_only_that_file() {
+        let (temp_dir, _repo) = create_test_repo();
+        std::fs::write(temp_dir.path().join("a.rs"), "fn a() { 1 }\n").unwrap();
+        std::fs::write(temp_dir.path().join("b.rs"), "fn b() { 2 }\n").unwrap();
+        std::fs::write(temp_dir.path().join("new.rs"), "fn new() {}\n").unwrap();
+        let git = GitOps::open(temp_dir.path()).unwrap();
+
+        let diff = git.diff_for_path("a.rs").unwrap();
+        assert!(diff.contains("-fn a() {}"), "{diff}");
+        assert!(diff.contains("+fn a() { 1 }"), "{diff}");
+        assert!(!diff.contains("fn b()"), "{diff}");
+
+        let untracked = git.diff_for_path("new.rs").unwrap();
+        assert!(untracked.contains("+fn new() {}"), "{untracked}");
+    }
+
+    #[test]
+    fn test_commit_paths_commits_only_the_chosen_files() {
+        let (temp_dir, repo) = create_test_repo();
+        std::fs::write(temp_dir.path().join("a.rs"), "fn a() { 1 }\n").unwrap();
+        std::fs::write(temp_dir.path().join("b.rs"), "fn b() { 2 }\n").unwrap();
+        // b.rs was staged by hand, but is left out of the pick
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("b.rs")).unwrap();
+        index.write().unwrap();
+        std::fs::remove_file(temp_dir.path().join("a.rs")).unwrap();
+        std::fs::write(temp_dir.path().join("new.rs"), "").unwrap();
+
+        let git = GitOps::open(temp_dir.path()).unwrap();
+        git.commit_paths(&["a.rs".into(), "new.rs".into()], "  Pick two  ")
+            .unwrap();
+
+        assert_eq!(committed_paths(&repo), vec!["a.rs", "new.rs"]);
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(head.message(), Some("Pick two"));
+        // b.rs is still staged, ready for the next commit
+        let remaining = git.working_changes().unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].path, "b.rs");
+        assert!(remaining[0].staged && !remaining[0].unstaged);
+    }
+
+    #[test]
+    fn test_commit_paths_commits_a_partly_staged_file_as_staged() {
+        let (temp_dir, repo) = create_test_repo();
+        std::fs::write(temp_dir.path().join("a.rs"), "fn a() { 1 }\n").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("a.rs")).unwrap();
+        index.write().unwrap();
+        // A further edit the user chose not to stage
+        std::fs::write(temp_dir.path().join("a.rs"), "fn a() { 2 }\n").unwrap();
+
+        let git = GitOps::open(temp_dir.path()).unwrap();
+        git.commit_paths(&["a.rs".into()], "Staged part").unwrap();
+
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        let entry = head.tree().unwrap().get_path(Path::new("a.rs")).unwrap();
+        let blob = repo.find_blob(entry.id()).unwrap();
+        assert_eq!(blob.content(), b"fn a() { 1 }\n");
+        // The unstaged edit is still there, still unstaged
+        let remaining = git.working_changes().unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert!(!remaining[0].staged && remaining[0].unstaged);
+        assert_eq!(
+            std::fs::read_to_string(temp_dir.path().join("a.rs")).unwrap(),
+            "fn a() { 2 }\n"
+        );
+    }
+
+    #[test]
+    fn test_commit_paths_refuses_an_empty_message_or_no_change() {
+        let (temp_dir, _repo) = create_test_repo();
+        let git = GitOps::open(temp_dir.path()).unwrap();
+        std::fs::write(temp_dir.path().join("a.rs"), "fn a() { 1 }\n").unwrap();
+
+        assert!(git.commit_paths(&["a.rs".into()], "   ").is_err());
+        assert!(git.commit_paths(&[], "Message").is_err());
+        // b.rs is unchanged, so committing just it records nothing
+        assert!(git.commit_paths(&["b.rs".into()], "Message").is_err());
+    }
+
+    #[test]
+    fn test_push_sends_the_branch_to_a_local_bare_remote() {
+        let (temp_dir, repo) = create_test_repo();
+        let remote_dir = TempDir::new().unwrap();
+        let bare = Repository::init_bare(remote_dir.path()).unwrap();
+        repo.remote("origin", remote_dir.path().to_str().unwrap())
+            .unwrap();
+
+        std::fs::write(temp_dir.path().join("a.rs"), "fn a() { 1 }\n").unwrap();
+        let git = GitOps::open(temp_dir.path()).unwrap();
+        let commit = git.commit_paths(&["a.rs".into()], "Edit a").unwrap();
+
+        let target = git.push_current_branch().unwrap();
+
+        assert_eq!(target.remote, "origin");
+        let pushed = bare
+            .find_reference(&format!("refs/heads/{}", target.branch))
+            .unwrap()
+            .target();
+        assert_eq!(pushed, Some(commit));
+        // And the branch now tracks it, so the next push needs no guessing
+        assert_eq!(git.push_target().unwrap(), target);
+        let upstream = repo
+            .config()
+            .unwrap()
+            .get_string(&format!("branch.{}.remote", target.branch))
+            .unwrap();
+        assert_eq!(upstream, "origin");
+    }
+
+    #[test]
+    fn test_push_target_needs_a_remote() {
+        let (temp_dir, _repo) = create_test_repo();
+        let git = GitOps::open(temp_dir.path()).unwrap();
+        assert!(git.push_target().is_err());
+    }
+}
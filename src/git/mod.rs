@@ -2,7 +2,12 @@
 //!
 //! Provides a safe wrapper around git2 for common repository operations.
 
+pub mod commit;
+pub mod multi_repo;
+pub mod overlap;
+pub mod seed;
 pub mod worktree;
+pub mod worktree_setup;
 
 use anyhow::{Context, Result};
 use git2::{BranchType, Repository};
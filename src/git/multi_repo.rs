@@ -0,0 +1,97 @@
+//! Projects spanning several repositories
+//!
+//! A multi-repository project is a parent directory whose subdirectories are
+//! git repositories - a frontend and a backend, say - that agents edit
+//! together. Branching such a project branches every repository the same
+//! way, with the worktrees side by side under one directory:
+//!
+//! ```text
+//! ~/.panoptes/worktrees/product/feature-auth/
+//!     backend/    <- worktree of ~/src/product/backend on feature/auth
+//!     frontend/   <- worktree of ~/src/product/frontend on feature/auth
+//! ```
+//!
+//! so a session started in the shared directory sees both, laid out the way
+//! they are in the main checkout.
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+use super::BranchRefInfo;
+
+/// The git repositories directly inside `dir`, relative to it and sorted
+///
+/// Only immediate subdirectories count: a repository nested deeper is part
+/// of whatever contains it, not a sibling. Hidden directories are skipped.
+pub fn child_repositories(dir: &Path) -> Result<Vec<PathBuf>> {
+    let entries =
+        std::fs::read_dir(dir).with_context(|| format!("Failed to read directory {:?}", dir))?;
+
+    let mut repos: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_ok_and(|t| t.is_dir()))
+        .filter(|entry| !entry.file_name().to_string_lossy().starts_with('.'))
+        // `.git` is a directory in a clone and a file in a worktree
+        .filter(|entry| entry.path().join(".git").exists())
+        .map(|entry| PathBuf::from(entry.file_name()))
+        .collect();
+    repos.sort();
+    Ok(repos)
+}
+
+/// Merge the branch refs of several repositories into one list
+///
+/// A branch that exists in more than one repository is listed once, in the
+/// position it first appears; it is the default base if it is that anywhere.
+pub fn merge_branch_refs(per_repo: Vec<Vec<BranchRefInfo>>) -> Vec<BranchRefInfo> {
+    let mut merged: Vec<BranchRefInfo> = Vec::new();
+    for refs in per_repo {
+        for branch_ref in refs {
+            match merged.iter_mut().find(|m| m.name == branch_ref.name) {
+                Some(existing) => existing.is_default_base |= branch_ref.is_default_base,
+                None => merged.push(branch_ref),
+            }
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::BranchRefInfoType;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_only_direct_child_repositories_are_found() {
+        let dir = TempDir::new().unwrap();
+        for repo in ["frontend", "backend", "docs/site"] {
+            git2::Repository::init(dir.path().join(repo)).unwrap();
+        }
+        std::fs::create_dir_all(dir.path().join("scripts")).unwrap();
+        git2::Repository::init(dir.path().join(".cache")).unwrap();
+
+        assert_eq!(
+            child_repositories(dir.path()).unwrap(),
+            vec![PathBuf::from("backend"), PathBuf::from("frontend")]
+        );
+    }
+
+    #[test]
+    fn test_shared_branches_are_listed_once() {
+        let local = |name: &str, is_default_base| BranchRefInfo {
+            ref_type: BranchRefInfoType::Local,
+            name: name.to_string(),
+            is_default_base,
+        };
+
+        let merged = merge_branch_refs(vec![
+            vec![local("main", false), local("feature", false)],
+            vec![local("main", true), local("api-v2", false)],
+        ]);
+
+        let names: Vec<&str> = merged.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["main", "feature", "api-v2"]);
+        assert!(merged[0].is_default_base);
+    }
+}
@@ -0,0 +1,170 @@
+//! Changed-file overlap between branches
+//!
+//! Parallel agents on separate worktrees never see each other's edits until
+//! merge time. Comparing what each branch has changed since it left the base
+//! branch shows early when two of them are working on the same file.
+
+use anyhow::{Context, Result};
+use git2::{DiffOptions, Repository};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+/// Files a checkout has changed relative to where it forked from `base_ref`
+///
+/// The comparison is against the merge base of `HEAD` and `base_ref`, not the
+/// base's current tip, so commits landing on the base after the fork do not
+/// read as this branch's changes. Committed, staged, unstaged and untracked
+/// changes all count: an agent's work in progress is exactly what is worth
+/// knowing about. Paths are repository-relative, with `/` separators.
+pub fn changed_files(checkout: &Path, base_ref: &str) -> Result<BTreeSet<String>> {
+    let repo = Repository::discover(checkout)
+        .with_context(|| format!("Failed to open git repository at {:?}", checkout))?;
+    let head = repo
+        .head()
+        .context("Failed to get HEAD")?
+        .peel_to_commit()
+        .context("Failed to get HEAD commit")?;
+    let base = super::worktree::resolve_ref_to_commit(&repo, base_ref)?;
+    let fork_point = repo
+        .merge_base(head.id(), base.id())
+        .with_context(|| format!("No common history with '{}'", base_ref))?;
+    let fork_tree = repo
+        .find_commit(fork_point)
+        .and_then(|commit| commit.tree())
+        .context("Failed to read the merge-base tree")?;
+
+    let mut opts = DiffOptions::new();
+    opts.include_untracked(true).recurse_untracked_dirs(true);
+    let diff = repo
+        .diff_tree_to_workdir_with_index(Some(&fork_tree), Some(&mut opts))
+        .context("Failed to diff against the merge base")?;
+
+    let mut files = BTreeSet::new();
+    for delta in diff.deltas() {
+        // A rename touches both ends, and either can collide with another branch
+        for file in [delta.old_file(), delta.new_file()] {
+            if let Some(path) = file.path() {
+                files.insert(path.to_string_lossy().replace('\\', "/"));
+            }
+        }
+    }
+    Ok(files)
+}
+
+/// Every file changed by more than one of `changes`, with who changed it
+///
+/// Keys keep the order they were given in, so the caller decides how the
+/// owners of a shared file are listed.
+pub fn find_overlaps<K: Clone>(changes: &[(K, BTreeSet<String>)]) -> BTreeMap<String, Vec<K>> {
+    let mut owners: BTreeMap<String, Vec<K>> = BTreeMap::new();
+    for (key, files) in changes {
+        for file in files {
+            owners.entry(file.clone()).or_default().push(key.clone());
+        }
+    }
+    owners.retain(|_, keys| keys.len() > 1);
+    owners
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// A repo with `shared.rs` and `other.rs` committed on the initial commit
+    fn create_test_repo() -> (TempDir, Repository) {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+        {
+            let mut config = repo.config().unwrap();
+            config.set_str("user.name", "Test User").unwrap();
+            config.set_str("user.email", "test@example.com").unwrap();
+        }
+        std::fs::write(temp_dir.path().join("shared.rs"), "fn a() {}\n").unwrap();
+        std::fs::write(temp_dir.path().join("other.rs"), "fn b() {}\n").unwrap();
+        commit_all(&repo, "Initial commit");
+        (temp_dir, repo)
+    }
+
+    fn commit_all(repo: &Repository, message: &str) {
+        let mut index = repo.index().unwrap();
+        index
+            .add_all(["*"].iter(), git2::IndexAddOption::DEFAULT, None)
+            .unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = repo.signature().unwrap();
+        let parents: Vec<git2::Commit> = repo
+            .head()
+            .ok()
+            .and_then(|h| h.peel_to_commit().ok())
+            .into_iter()
+            .collect();
+        let parent_refs: Vec<&git2::Commit> = parents.iter().collect();
+        repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parent_refs)
+            .unwrap();
+    }
+
+    fn base_name(repo: &Repository) -> String {
+        repo.head().unwrap().shorthand().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_changed_files_counts_committed_and_uncommitted_work() {
+        let (_temp_dir, repo) = create_test_repo();
+        let base = base_name(&repo);
+        let wt_dir = TempDir::new().unwrap();
+        let worktree = wt_dir.path().join("wt");
+        crate::git::worktree::create_worktree(&repo, "feature", &worktree, true, None).unwrap();
+        let wt_repo = Repository::open(&worktree).unwrap();
+
+        // One committed change, one unstaged edit, one untracked file
+        std::fs::write(worktree.join("shared.rs"), "fn a() { 1 }\n").unwrap();
+        commit_all(&wt_repo, "Edit shared");
+        std::fs::write(worktree.join("other.rs"), "fn b() { 2 }\n").unwrap();
+        std::fs::create_dir(worktree.join("new")).unwrap();
+        std::fs::write(worktree.join("new/file.rs"), "").unwrap();
+
+        let files = changed_files(&worktree, &base).unwrap();
+        let files: Vec<&str> = files.iter().map(String::as_str).collect();
+        assert_eq!(files, vec!["new/file.rs", "other.rs", "shared.rs"]);
+    }
+
+    #[test]
+    fn test_changed_files_ignores_base_commits_after_the_fork() {
+        let (temp_dir, repo) = create_test_repo();
+        let base = base_name(&repo);
+        let wt_dir = TempDir::new().unwrap();
+        let worktree = wt_dir.path().join("wt");
+        crate::git::worktree::create_worktree(&repo, "feature", &worktree, true, None).unwrap();
+
+        // The base moves on after the branch was cut
+        std::fs::write(temp_dir.path().join("other.rs"), "fn moved() {}\n").unwrap();
+        commit_all(&repo, "Base moves on");
+
+        assert!(changed_files(&worktree, &base).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_changed_files_unresolvable_base_is_an_error() {
+        let (temp_dir, _repo) = create_test_repo();
+        assert!(changed_files(temp_dir.path(), "no-such-branch").is_err());
+    }
+
+    #[test]
+    fn test_find_overlaps_keeps_only_shared_files() {
+        let set = |files: &[&str]| files.iter().map(|f| f.to_string()).collect();
+        let changes = vec![
+            ("a", set(&["src/manager.rs", "README.md"])),
+            ("b", set(&["src/manager.rs", "src/lib.rs"])),
+            ("c", set(&["src/lib.rs", "src/manager.rs"])),
+        ];
+
+        let overlaps = find_overlaps(&changes);
+
+        assert_eq!(overlaps.len(), 2);
+        assert_eq!(overlaps["src/manager.rs"], vec!["a", "b", "c"]);
+        assert_eq!(overlaps["src/lib.rs"], vec!["b", "c"]);
+        assert!(!overlaps.contains_key("README.md"));
+    }
+}
@@ -0,0 +1,231 @@
+//! Work carried into a freshly created worktree
+//!
+//! A worktree does not have to start clean: it can start from a stash entry
+//! taken in the main checkout, or from a `.patch` file. Stashes are listed
+//! through git2; applying either one shells out to the git CLI, whose
+//! `stash apply` and `apply` handle renames, binary patches and partial
+//! failures the way users already know from the command line.
+
+use anyhow::{Context, Result};
+use git2::Repository;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// One entry of `git stash list`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StashEntry {
+    /// Position in the stash list (`stash@{index}`)
+    pub index: usize,
+    /// The stash's message, e.g. "WIP on main: 1a2b3c4 Add parser"
+    pub message: String,
+    /// ID of the stash commit itself
+    ///
+    /// Stable where the index is not: a stash pushed or dropped while the
+    /// wizard is open shifts every index, but not this.
+    pub oid: String,
+    /// ID of the commit the stash was taken on top of
+    ///
+    /// Starting the worktree there means the stash applies without conflicts.
+    pub base: String,
+}
+
+/// What to apply to a worktree once it is checked out
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WorktreeSeed {
+    /// Apply a stash entry, by the ID of its stash commit
+    Stash { oid: String },
+    /// Apply a patch file
+    Patch { path: PathBuf },
+}
+
+impl WorktreeSeed {
+    /// What is being applied, for messages ("the stash", "the patch")
+    pub fn noun(&self) -> &'static str {
+        match self {
+            WorktreeSeed::Stash { .. } => "the stash",
+            WorktreeSeed::Patch { .. } => "the patch",
+        }
+    }
+}
+
+/// List the repository's stash entries, newest first
+///
+/// Stashes are shared by every worktree of a repository, so the ones taken in
+/// the main checkout are the ones listed.
+pub fn list_stashes(repo_path: &Path) -> Result<Vec<StashEntry>> {
+    // `stash_foreach` needs a mutable repository, hence a handle of its own
+    let mut repo = Repository::open(repo_path)
+        .with_context(|| format!("Failed to open git repository at {:?}", repo_path))?;
+
+    let mut raw = Vec::new();
+    repo.stash_foreach(|index, message, oid| {
+        raw.push((index, message.to_string(), *oid));
+        true
+    })
+    .context("Failed to list stashes")?;
+
+    raw.into_iter()
+        .map(|(index, message, oid)| {
+            let base = repo
+                .find_commit(oid)
+                .and_then(|stash| stash.parent_id(0))
+                .with_context(|| format!("Failed to read stash@{{{}}}", index))?;
+            Ok(StashEntry {
+                index,
+                message,
+                oid: oid.to_string(),
+                base: base.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Apply `seed` to the checkout at `worktree_path`
+///
+/// Nothing is committed: the stash or patch lands as uncommitted changes, the
+/// same as running the command by hand. A stash is applied, never popped - it
+/// stays in the list until the user drops it.
+pub fn apply_seed(worktree_path: &Path, seed: &WorktreeSeed) -> Result<()> {
+    let args: Vec<&std::ffi::OsStr> = match seed {
+        WorktreeSeed::Stash { oid } => vec!["stash".as_ref(), "apply".as_ref(), oid.as_ref()],
+        WorktreeSeed::Patch { path } => vec!["apply".as_ref(), path.as_os_str()],
+    };
+    tracing::debug!(
+        "Applying {} via git CLI in {:?}",
+        seed.noun(),
+        worktree_path
+    );
+
+    let mut child = Command::new("git")
+        .args(&args)
+        .current_dir(worktree_path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("Failed to execute git. Is git installed and in PATH?")?;
+
+    let mut stderr = String::new();
+    if let Some(mut pipe) = child.stderr.take() {
+        let _ = pipe.read_to_string(&mut stderr);
+    }
+    let status = child.wait().context("Failed to wait for git")?;
+    if !status.success() {
+        anyhow::bail!("Failed to apply {}: {}", seed.noun(), stderr.trim());
+    }
+    Ok(())
+}
+
+/// Resolve what the user typed as a starting point: a SHA, a tag, any revspec
+///
+/// Returns the full commit ID and the commit's summary line, which the
+/// confirmation step shows so a mistyped SHA is caught before anything is
+/// created.
+pub fn resolve_start_point(repo: &Repository, revision: &str) -> Result<(String, String)> {
+    let revision = revision.trim();
+    if revision.is_empty() {
+        anyhow::bail!("Type a commit SHA or a tag");
+    }
+    let commit = repo
+        .revparse_single(revision)
+        .and_then(|object| object.peel_to_commit())
+        .map_err(|_| anyhow::anyhow!("'{}' is not a commit or tag in this repository", revision))?;
+    Ok((
+        commit.id().to_string(),
+        commit.summary().unwrap_or_default().to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::GitOps;
+    use tempfile::TempDir;
+
+    fn create_test_repo() -> (TempDir, Repository) {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+        {
+            let mut config = repo.config().unwrap();
+            config.set_str("user.name", "Test User").unwrap();
+            config.set_str("user.email", "test@example.com").unwrap();
+        }
+        std::fs::write(temp_dir.path().join("a.rs"), "fn a() {}\n").unwrap();
+        let git = GitOps::open(temp_dir.path()).unwrap();
+        git.commit_paths(&["a.rs".into()], "Initial commit")
+            .unwrap();
+        drop(git);
+        (temp_dir, repo)
+    }
+
+    #[test]
+    fn test_stash_is_listed_and_applies_to_another_checkout() {
+        let (temp_dir, mut repo) = create_test_repo();
+        std::fs::write(temp_dir.path().join("a.rs"), "fn a() { 1 }\n").unwrap();
+        let sig = repo.signature().unwrap();
+        repo.stash_save(&sig, "half-finished", None).unwrap();
+        let head = repo.head().unwrap().peel_to_commit().unwrap().id();
+
+        let stashes = list_stashes(temp_dir.path()).unwrap();
+        assert_eq!(stashes.len(), 1);
+        assert_eq!(stashes[0].index, 0);
+        assert!(stashes[0].message.contains("half-finished"));
+        assert_eq!(stashes[0].base, head.to_string());
+
+        // Applying does not consume the stash, in this checkout or any other
+        apply_seed(
+            temp_dir.path(),
+            &WorktreeSeed::Stash {
+                oid: stashes[0].oid.clone(),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            std::fs::read_to_string(temp_dir.path().join("a.rs")).unwrap(),
+            "fn a() { 1 }\n"
+        );
+        assert_eq!(list_stashes(temp_dir.path()).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_patch_applies_and_a_bad_one_says_why() {
+        let (temp_dir, _repo) = create_test_repo();
+        let patch_dir = TempDir::new().unwrap();
+        let patch = patch_dir.path().join("change.patch");
+        std::fs::write(
+            &patch,
+            "--- a/a.rs\n+++ b/a.rs\n@@ -1 +1 @@\n-fn a() {}\n+fn a() { 2 }\n",
+        )
+        .unwrap();
+
+        apply_seed(temp_dir.path(), &WorktreeSeed::Patch { path: patch }).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(temp_dir.path().join("a.rs")).unwrap(),
+            "fn a() { 2 }\n"
+        );
+
+        let missing = patch_dir.path().join("missing.patch");
+        let err = apply_seed(temp_dir.path(), &WorktreeSeed::Patch { path: missing })
+            .unwrap_err()
+            .to_string();
+        assert!(err.starts_with("Failed to apply the patch"), "{err}");
+    }
+
+    #[test]
+    fn test_start_point_resolves_shas_and_tags() {
+        let (_temp_dir, repo) = create_test_repo();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.tag_lightweight("v1.0", head.as_object(), false)
+            .unwrap();
+
+        let short = &head.id().to_string()[..7];
+        let (id, summary) = resolve_start_point(&repo, short).unwrap();
+        assert_eq!(id, head.id().to_string());
+        assert_eq!(summary, "Initial commit");
+        assert_eq!(resolve_start_point(&repo, "v1.0").unwrap().0, id);
+
+        assert!(resolve_start_point(&repo, "nope").is_err());
+        assert!(resolve_start_point(&repo, "  ").is_err());
+    }
+}
@@ -36,6 +36,35 @@ pub fn create_worktree(
     worktree_path: &Path,
     create_branch: bool,
     base_ref: Option<&str>,
+) -> Result<PathBuf> {
+    // Get the worktree name from the path
+    let worktree_name = worktree_path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| anyhow::anyhow!("Invalid worktree path"))?;
+
+    create_worktree_named(
+        repo,
+        worktree_name,
+        branch_name,
+        worktree_path,
+        create_branch,
+        base_ref,
+    )
+}
+
+/// Create a new worktree, registered with git under `worktree_name`
+///
+/// [`create_worktree`] names the worktree after its directory. That is not
+/// unique when the directory is named after something else, like the
+/// repository in a multi-repository branch (`<branch>/frontend`).
+pub fn create_worktree_named(
+    repo: &Repository,
+    worktree_name: &str,
+    branch_name: &str,
+    worktree_path: &Path,
+    create_branch: bool,
+    base_ref: Option<&str>,
 ) -> Result<PathBuf> {
     // Check if worktree path already exists
     if worktree_path.exists() {
@@ -51,33 +80,7 @@ pub fn create_worktree(
             .with_context(|| format!("Failed to create parent directory: {:?}", parent))?;
     }
 
-    // Get the worktree name from the path
-    let worktree_name = worktree_path
-        .file_name()
-        .and_then(|n| n.to_str())
-        .ok_or_else(|| anyhow::anyhow!("Invalid worktree path"))?;
-
-    // Check if branch exists, create if needed
-    let branch_exists = repo.find_branch(branch_name, BranchType::Local).is_ok();
-
-    if !branch_exists {
-        if create_branch {
-            // Determine which commit to branch from
-            let commit = if let Some(base) = base_ref {
-                // Try to resolve the base reference
-                resolve_ref_to_commit(repo, base)?
-            } else {
-                // Default to HEAD
-                let head = repo.head().context("Failed to get HEAD")?;
-                head.peel_to_commit().context("Failed to get HEAD commit")?
-            };
-
-            repo.branch(branch_name, &commit, false)
-                .with_context(|| format!("Failed to create branch '{}'", branch_name))?;
-        } else {
-            anyhow::bail!("Branch '{}' does not exist", branch_name);
-        }
-    }
+    ensure_branch(repo, branch_name, create_branch, base_ref)?;
 
     // Find the branch reference
     let branch = repo
@@ -96,11 +99,42 @@ pub fn create_worktree(
     Ok(worktree_path.to_path_buf())
 }
 
+/// Make sure the local branch `branch_name` exists, creating it if allowed
+///
+/// A new branch starts at `base_ref` when given, otherwise at `HEAD`.
+pub(crate) fn ensure_branch(
+    repo: &Repository,
+    branch_name: &str,
+    create_branch: bool,
+    base_ref: Option<&str>,
+) -> Result<()> {
+    if repo.find_branch(branch_name, BranchType::Local).is_ok() {
+        return Ok(());
+    }
+    if !create_branch {
+        anyhow::bail!("Branch '{}' does not exist", branch_name);
+    }
+
+    // Determine which commit to branch from
+    let commit = if let Some(base) = base_ref {
+        // Try to resolve the base reference
+        resolve_ref_to_commit(repo, base)?
+    } else {
+        // Default to HEAD
+        let head = repo.head().context("Failed to get HEAD")?;
+        head.peel_to_commit().context("Failed to get HEAD commit")?
+    };
+
+    repo.branch(branch_name, &commit, false)
+        .with_context(|| format!("Failed to create branch '{}'", branch_name))?;
+    Ok(())
+}
+
 /// Resolve a reference (branch name, remote branch, or commit) to a commit
 ///
 /// Tries multiple resolution strategies and provides detailed error messages
 /// if none succeed.
-fn resolve_ref_to_commit<'repo>(
+pub(crate) fn resolve_ref_to_commit<'repo>(
     repo: &'repo Repository,
     ref_name: &str,
 ) -> Result<git2::Commit<'repo>> {
@@ -195,6 +229,22 @@ pub fn remove_worktree(repo: &Repository, worktree_name: &str, force: bool) -> R
     Ok(())
 }
 
+/// Remove the worktree checked out at `worktree_path`, whatever it is named
+///
+/// For worktrees git named itself (`git worktree add` suffixes a name that
+/// is taken), where the name cannot be worked out from the branch.
+pub fn remove_worktree_at(repo: &Repository, worktree_path: &Path, force: bool) -> Result<()> {
+    let wanted = worktree_path
+        .canonicalize()
+        .unwrap_or_else(|_| worktree_path.to_path_buf());
+    let worktree = list_worktrees(repo)?
+        .into_iter()
+        .filter(|wt| !wt.is_main)
+        .find(|wt| wt.path.canonicalize().unwrap_or_else(|_| wt.path.clone()) == wanted)
+        .with_context(|| format!("No worktree at {:?}", worktree_path))?;
+    remove_worktree(repo, &worktree.name, force)
+}
+
 /// Helper function to get branch name from a worktree path
 fn get_branch_from_worktree(path: &Path) -> Option<String> {
     let repo = Repository::open(path).ok()?;
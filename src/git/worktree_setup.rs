@@ -0,0 +1,425 @@
+//! Setting up a new worktree beyond the plain checkout
+//!
+//! Two per-project options for repositories where a plain worktree is not
+//! usable: initialising submodules (otherwise their directories are empty),
+//! and carrying the main checkout's sparse-checkout patterns over (otherwise
+//! the whole tree is checked out, however large).
+//!
+//! Both shell out to the git CLI: git2 has no sparse-checkout support, and
+//! the CLI's submodule handling covers nested submodules and credential
+//! helpers. Git's progress output is passed on line by line, so the loading
+//! overlay can say what a long clone is doing.
+//!
+//! A repository's `.panoptes.toml` can add setup commands of its own
+//! (`npm ci`, copying an `.env`), run here once the checkout is done.
+
+use anyhow::{Context, Result};
+use git2::Repository;
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::process::{self, Finished, Limits};
+
+/// How a project's new worktrees are set up
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct WorktreeSetup {
+    /// Run `git submodule update --init --recursive` in each new worktree
+    #[serde(default)]
+    pub submodules: bool,
+    /// Check out only what the main checkout's sparse-checkout patterns select
+    #[serde(default)]
+    pub sparse_checkout: bool,
+}
+
+/// The sparse-checkout configuration of a checkout
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparsePatterns {
+    /// Whether the patterns are cone-mode directories rather than gitignore-style
+    pub cone: bool,
+    /// The patterns, as `git sparse-checkout list` prints them
+    pub patterns: Vec<String>,
+}
+
+/// Read the sparse-checkout patterns of the checkout at `checkout`
+///
+/// Returns `None` when the checkout is not sparse.
+pub fn sparse_checkout_patterns(checkout: &Path) -> Result<Option<SparsePatterns>> {
+    let enabled = git_output(checkout, &["config", "--bool", "core.sparseCheckout"])?;
+    if enabled.as_deref() != Some("true") {
+        return Ok(None);
+    }
+    let cone = git_output(checkout, &["config", "--bool", "core.sparseCheckoutCone"])?;
+    let patterns = git_output(checkout, &["sparse-checkout", "list"])?.unwrap_or_default();
+
+    Ok(Some(SparsePatterns {
+        cone: cone.as_deref() == Some("true"),
+        patterns: patterns.lines().map(str::to_string).collect(),
+    }))
+}
+
+/// Create a worktree that only checks out what `sparse` selects
+///
+/// The worktree is added without a checkout, given the patterns, and only
+/// then checked out - checking out first and narrowing after would write the
+/// whole tree to disk, which is exactly what this exists to avoid.
+pub fn create_sparse_worktree(
+    repo: &Repository,
+    branch_name: &str,
+    worktree_path: &Path,
+    create_branch: bool,
+    base_ref: Option<&str>,
+    sparse: &SparsePatterns,
+    progress: &dyn Fn(&str),
+) -> Result<PathBuf> {
+    if worktree_path.exists() {
+        anyhow::bail!(
+            "Worktree path already exists: {}. Choose a different branch name or remove the existing directory.",
+            worktree_path.display()
+        );
+    }
+    if let Some(parent) = worktree_path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create parent directory: {:?}", parent))?;
+    }
+    super::worktree::ensure_branch(repo, branch_name, create_branch, base_ref)?;
+
+    let repo_dir = repo
+        .workdir()
+        .context("Repository has no working directory")?;
+    let worktree_arg = worktree_path.as_os_str().to_string_lossy();
+    run_git(
+        repo_dir,
+        &[
+            "worktree",
+            "add",
+            "--no-checkout",
+            &worktree_arg,
+            branch_name,
+        ],
+        None,
+        progress,
+    )
+    .with_context(|| format!("Failed to create worktree at {:?}", worktree_path))?;
+
+    progress("Applying sparse-checkout patterns...");
+    let mode = if sparse.cone { "--cone" } else { "--no-cone" };
+    let mut stdin = sparse.patterns.join("\n");
+    stdin.push('\n');
+    run_git(
+        worktree_path,
+        &["sparse-checkout", "set", mode, "--stdin"],
+        Some(&stdin),
+        progress,
+    )
+    .context("Failed to apply sparse-checkout patterns")?;
+
+    progress("Checking out...");
+    run_git(worktree_path, &["checkout", "--progress"], None, progress)
+        .context("Failed to check out the sparse worktree")?;
+
+    Ok(worktree_path.to_path_buf())
+}
+
+/// Initialise and update every submodule of the checkout at `worktree_path`
+pub fn update_submodules(worktree_path: &Path, progress: &dyn Fn(&str)) -> Result<()> {
+    update_submodules_with(worktree_path, &[], progress)
+}
+
+/// [`update_submodules`] with `git -c` settings in front, which git passes
+/// on to the clones it runs for each submodule
+fn update_submodules_with(
+    worktree_path: &Path,
+    config: &[&str],
+    progress: &dyn Fn(&str),
+) -> Result<()> {
+    progress("Updating submodules...");
+    let mut args = Vec::new();
+    for setting in config {
+        args.extend(["-c", setting]);
+    }
+    args.extend(["submodule", "update", "--init", "--recursive", "--progress"]);
+    run_git(worktree_path, &args, None, progress).context("Failed to update submodules")
+}
+
+/// Run a project's setup commands in a new checkout, in order, through `sh -c`
+///
+/// The first command to fail stops the rest: later ones usually build on it
+/// (`npm ci` before `npm run build`). Setting `cancel` kills the running
+/// command - with whatever it started, as the shell leads its own process
+/// group - and skips the rest; the checkout is left as they had it.
+pub fn run_setup_commands(
+    worktree_path: &Path,
+    commands: &[String],
+    cancel: &AtomicBool,
+    progress: &dyn Fn(&str),
+) -> Result<()> {
+    for command in commands {
+        if cancel.load(Ordering::Relaxed) {
+            anyhow::bail!("setup cancelled before '{}'", command);
+        }
+        progress(&format!("Running {}...", command));
+        tracing::debug!("Running setup command {:?} in {:?}", command, worktree_path);
+        let mut shell = process::shell(command);
+        shell.current_dir(worktree_path).stderr(Stdio::piped());
+        let limits = Limits {
+            timeout: None,
+            cancel: Some(cancel),
+        };
+        match process::run(&mut shell, limits)
+            .with_context(|| format!("Failed to run '{}'", command))?
+        {
+            Finished::Exited { status, .. } if status.success() => {}
+            Finished::Exited { status, stderr } => {
+                let last = stderr.lines().rev().find(|line| !line.trim().is_empty());
+                match last {
+                    Some(line) => anyhow::bail!("'{}' failed: {}", command, line.trim()),
+                    None => anyhow::bail!("'{}' failed with {}", command, status),
+                }
+            }
+            Finished::TimedOut | Finished::Cancelled => {
+                tracing::info!("Cancelled setup command {:?}", command);
+                anyhow::bail!("setup cancelled during '{}'", command);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Run a git command, passing each line of its stderr to `progress`
+///
+/// Git redraws progress in place with `\r`, so both `\r` and `\n` end a line.
+/// The error carries the last few lines, which is where git says what failed.
+fn run_git(dir: &Path, args: &[&str], stdin: Option<&str>, progress: &dyn Fn(&str)) -> Result<()> {
+    tracing::debug!("Running git {:?} in {:?}", args, dir);
+    let mut child = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .stdin(if stdin.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("Failed to execute git. Is git installed and in PATH?")?;
+
+    if let (Some(input), Some(mut pipe)) = (stdin, child.stdin.take()) {
+        use std::io::Write;
+        pipe.write_all(input.as_bytes())
+            .context("Failed to write to git")?;
+    }
+
+    let mut tail: Vec<String> = Vec::new();
+    if let Some(mut pipe) = child.stderr.take() {
+        let mut buf = [0u8; 4096];
+        let mut line = Vec::new();
+        loop {
+            let n = match pipe.read(&mut buf) {
+                Ok(0) | Err(_) => break,
+                Ok(n) => n,
+            };
+            for &byte in &buf[..n] {
+                if byte == b'\r' || byte == b'\n' {
+                    let text = String::from_utf8_lossy(&line).trim().to_string();
+                    line.clear();
+                    if !text.is_empty() {
+                        progress(&text);
+                        tail.push(text);
+                        if tail.len() > 3 {
+                            tail.remove(0);
+                        }
+                    }
+                } else {
+                    line.push(byte);
+                }
+            }
+        }
+        let text = String::from_utf8_lossy(&line).trim().to_string();
+        if !text.is_empty() {
+            tail.push(text);
+        }
+    }
+
+    let status = child.wait().context("Failed to wait for git")?;
+    if !status.success() {
+        anyhow::bail!("git {} failed: {}", args[0], tail.join(" / "));
+    }
+    Ok(())
+}
+
+/// Run a git query and return its trimmed stdout, or `None` if it failed
+///
+/// Used for questions whose "no" is a non-zero exit, like an unset config key.
+fn git_output(dir: &Path, args: &[&str]) -> Result<Option<String>> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .stdin(Stdio::null())
+        .output()
+        .context("Failed to execute git. Is git installed and in PATH?")?;
+    if !output.status.success() {
+        return Ok(None);
+    }
+    Ok(Some(
+        String::from_utf8_lossy(&output.stdout).trim().to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {args:?} failed");
+    }
+
+    /// A repo with `keep/a`, `skip/b` and `top.txt` committed
+    fn create_test_repo() -> (TempDir, Repository) {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        git(dir, &["init", "-q"]);
+        git(dir, &["config", "user.name", "Test User"]);
+        git(dir, &["config", "user.email", "test@example.com"]);
+        std::fs::create_dir_all(dir.join("keep")).unwrap();
+        std::fs::create_dir_all(dir.join("skip")).unwrap();
+        std::fs::write(dir.join("keep/a"), "a").unwrap();
+        std::fs::write(dir.join("skip/b"), "b").unwrap();
+        std::fs::write(dir.join("top.txt"), "top").unwrap();
+        git(dir, &["add", "-A"]);
+        git(dir, &["commit", "-qm", "Initial commit"]);
+        let repo = Repository::open(dir).unwrap();
+        (temp_dir, repo)
+    }
+
+    #[test]
+    fn test_a_plain_checkout_has_no_sparse_patterns() {
+        let (temp_dir, _repo) = create_test_repo();
+        assert_eq!(sparse_checkout_patterns(temp_dir.path()).unwrap(), None);
+    }
+
+    #[test]
+    fn test_sparse_worktree_checks_out_only_the_main_checkouts_patterns() {
+        let (temp_dir, repo) = create_test_repo();
+        git(
+            temp_dir.path(),
+            &["sparse-checkout", "set", "--cone", "keep"],
+        );
+
+        let sparse = sparse_checkout_patterns(temp_dir.path())
+            .unwrap()
+            .expect("main checkout is sparse");
+        assert!(sparse.cone);
+        assert_eq!(sparse.patterns, vec!["keep".to_string()]);
+
+        let worktrees = TempDir::new().unwrap();
+        let path = worktrees.path().join("feature");
+        let reported = RefCell::new(Vec::new());
+        create_sparse_worktree(&repo, "feature", &path, true, None, &sparse, &|line| {
+            reported.borrow_mut().push(line.to_string())
+        })
+        .unwrap();
+
+        assert!(path.join("keep/a").exists());
+        assert!(
+            path.join("top.txt").exists(),
+            "cone mode keeps top-level files"
+        );
+        assert!(!path.join("skip/b").exists());
+        assert!(reported
+            .borrow()
+            .iter()
+            .any(|line| line == "Applying sparse-checkout patterns..."));
+
+        // Nothing shows as deleted: the index matches what was checked out
+        let status = git_output(&path, &["status", "--porcelain"]).unwrap();
+        assert_eq!(status.as_deref(), Some(""));
+    }
+
+    #[test]
+    fn test_setup_commands_run_in_order_and_stop_at_the_first_failure() {
+        let dir = TempDir::new().unwrap();
+        let commands = vec![
+            "echo one > log".to_string(),
+            "echo nope >&2; false".to_string(),
+            "echo three >> log".to_string(),
+        ];
+
+        let err = run_setup_commands(dir.path(), &commands, &AtomicBool::new(false), &|_| {})
+            .unwrap_err();
+
+        assert!(err.to_string().contains("nope"), "{err}");
+        let log = std::fs::read_to_string(dir.path().join("log")).unwrap();
+        assert_eq!(log, "one\n");
+    }
+
+    #[test]
+    fn test_cancelling_kills_the_running_setup_command_and_skips_the_rest() {
+        let dir = TempDir::new().unwrap();
+        let commands = vec![
+            "(sleep 1; touch late) & sleep 5".to_string(),
+            "touch next".to_string(),
+        ];
+        let cancel = std::sync::Arc::new(AtomicBool::new(false));
+        let flag = std::sync::Arc::clone(&cancel);
+        std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(200));
+            flag.store(true, Ordering::Relaxed);
+        });
+        let started = std::time::Instant::now();
+
+        let err = run_setup_commands(dir.path(), &commands, &cancel, &|_| {}).unwrap_err();
+        std::thread::sleep(Duration::from_millis(1500));
+
+        assert!(err.to_string().contains("cancelled"), "{err}");
+        assert!(started.elapsed() < Duration::from_secs(4));
+        assert!(!dir.path().join("late").exists());
+        assert!(!dir.path().join("next").exists());
+    }
+
+    #[test]
+    fn test_submodules_are_initialised_in_the_new_checkout() {
+        let (sub_dir, _sub) = create_test_repo();
+        let (temp_dir, _repo) = create_test_repo();
+        git(
+            temp_dir.path(),
+            &[
+                "-c",
+                "protocol.file.allow=always",
+                "submodule",
+                "add",
+                "-q",
+                sub_dir.path().to_str().unwrap(),
+                "vendor/sub",
+            ],
+        );
+        git(temp_dir.path(), &["commit", "-qm", "Add submodule"]);
+
+        let clone_parent = TempDir::new().unwrap();
+        let clone = clone_parent.path().join("clone");
+        git(
+            clone_parent.path(),
+            &["clone", "-q", temp_dir.path().to_str().unwrap(), "clone"],
+        );
+        assert!(!clone.join("vendor/sub/keep/a").exists());
+
+        // Local file:// submodules are refused by default since git 2.38.1,
+        // and the submodule clone does not read the superproject's config
+        update_submodules_with(&clone, &["protocol.file.allow=always"], &|_| {}).unwrap();
+        assert!(clone.join("vendor/sub/keep/a").exists());
+    }
+}
@@ -1158,4 +1158,34 @@ mod tests {
         .unwrap();
         assert_eq!(f.codex.get_default_id(), Some(config_id));
     }
+
+    #[test]
+    fn test_set_as_default_keeps_s_when_new_shell_is_rebound() {
+        use crate::app::{Focus, SettingsNav, Tab};
+        use crate::keymap::{Keymap, Remapped};
+
+        let mut f = fixture();
+        f.codex.add(CodexConfig::new("Work".to_string(), None));
+        // Sorts first, so it is the selected row
+        let config = CodexConfig::new("Personal".to_string(), None);
+        let config_id = config.id;
+        f.codex.add(config);
+        assert_ne!(f.codex.get_default_id(), Some(config_id));
+
+        let (keymap, warnings) = Keymap::from_config(
+            &toml::from_str("[normal]\nnew_shell = \"S\"\n").unwrap(),
+            &[],
+        );
+        assert!(warnings.is_empty(), "{warnings:?}");
+        f.state.keymap = keymap;
+        f.state.focus = Focus::Panes(Tab::Settings);
+        f.state.settings_nav = SettingsNav::CodexConfigs;
+
+        let here = crate::input::dispatcher::actions_here(&f.state);
+        let Remapped::Key(key) = f.state.keymap.remap(&here, press(KeyCode::Char('s'))) else {
+            panic!("`s` was unbound in the config list");
+        };
+        configs_section_key(&mut f.state, &mut f.codex, AgentKind::Codex, key).unwrap();
+        assert_eq!(f.codex.get_default_id(), Some(config_id));
+    }
 }
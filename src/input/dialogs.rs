@@ -5,12 +5,13 @@
 use anyhow::Result;
 use crossterm::event::{KeyCode, KeyEvent, KeyEventKind};
 
-use crate::app::{App, AppState, Focus, InputMode, ProjectsNav};
+use crate::app::{cycle_next, App, AppState, Focus, InputMode, ProjectsNav};
 use crate::claude_config::ClaudeConfigStore;
 use crate::claude_json::ClaudeJsonStore;
 use crate::config::{is_reserved_key, CustomShortcut};
 use crate::project::{Branch, ProjectStore};
 use crate::session::SessionManager;
+use crate::tui::views::pane_projects::PROJECT_SETTINGS_ROWS;
 
 /// Handle key when confirming session deletion
 pub fn handle_confirming_delete_key(app: &mut App, key: KeyEvent) -> Result<()> {
@@ -669,15 +670,17 @@ pub fn handle_adding_custom_shortcut_key_key(app: &mut App, key: KeyEvent) -> Re
 
     match key.code {
         KeyCode::Esc => {
-            // Cancel; the Shortcuts section is behind the dialog already
+            // Cancel; the Shortcuts section (or the project's settings) is
+            // behind the dialog already
             app.state.shortcut_error = None;
             app.state.input_mode = InputMode::Normal;
         }
+        KeyCode::Tab => cycle_shortcut_scope(app),
         KeyCode::Char(c) => {
             // Validate the key
             if is_reserved_key(c) {
                 app.state.shortcut_error = Some(format!("Key '{}' is reserved", c));
-            } else if app.config.custom_shortcuts.iter().any(|s| s.key == c) {
+            } else if shortcuts_in_scope(app).iter().any(|s| s.key == c) {
                 app.state.shortcut_error = Some(format!("Key '{}' is already in use", c));
             } else {
                 // Valid key, proceed to name input
@@ -790,15 +793,26 @@ fn save_new_shortcut(app: &mut App) {
             app.state.new_shortcut_auto_close,
         );
 
-        if let Err(e) = app.config.add_shortcut(shortcut) {
-            app.state.shortcut_error = Some(e.to_string());
-            return;
-        }
-
-        // Save config to disk
-        if let Err(e) = app.config.save() {
-            tracing::error!("Failed to save config: {}", e);
-            app.state.error_message = Some(format!("Failed to save config: {}", e));
+        let saved = match app.state.shortcut_scope.clone() {
+            None => app
+                .config
+                .add_shortcut(shortcut)
+                .map(|()| app.config.save()),
+            Some(scope) => app
+                .project_store
+                .add_scoped_shortcut(&scope, shortcut)
+                .map(|()| app.project_store.save()),
+        };
+        match saved {
+            Err(e) => {
+                app.state.shortcut_error = Some(e.to_string());
+                return;
+            }
+            Ok(Err(e)) => {
+                tracing::error!("Failed to save shortcut: {}", e);
+                app.state.error_message = Some(format!("Failed to save shortcut: {}", e));
+            }
+            Ok(Ok(())) => {}
         }
 
         // Clear state and go back to management dialog
@@ -811,6 +825,32 @@ fn save_new_shortcut(app: &mut App) {
     }
 }
 
+/// The shortcuts already kept where the one being added will go
+fn shortcuts_in_scope(app: &App) -> &[CustomShortcut] {
+    match &app.state.shortcut_scope {
+        None => &app.config.custom_shortcuts,
+        Some(scope) => app.project_store.scoped_shortcuts(scope),
+    }
+}
+
+/// Move a shortcut being added from a project's settings to the next scope:
+/// the project, then each folder it sits under. A global one stays global.
+fn cycle_shortcut_scope(app: &mut App) {
+    let ProjectsNav::ProjectSettings(project_id) = app.state.projects_nav else {
+        return;
+    };
+    let Some(current) = &app.state.shortcut_scope else {
+        return;
+    };
+    let scopes = app.project_store.shortcut_scopes(project_id);
+    let index = scopes
+        .iter()
+        .position(|scope| scope == current)
+        .unwrap_or(0);
+    app.state.shortcut_scope = Some(scopes[cycle_next(index, scopes.len())].clone());
+    app.state.shortcut_error = None;
+}
+
 /// Handle key when confirming custom shortcut deletion
 pub fn handle_confirming_custom_shortcut_delete_key(app: &mut App, key: KeyEvent) -> Result<()> {
     if key.kind != KeyEventKind::Press {
@@ -821,7 +861,27 @@ pub fn handle_confirming_custom_shortcut_delete_key(app: &mut App, key: KeyEvent
         KeyCode::Char('y') | KeyCode::Char('Y') => {
             // Confirm deletion
             if let Some(index) = app.state.pending_delete_shortcut_index.take() {
-                if index < app.config.custom_shortcuts.len() {
+                if let Some(scope) = app.state.shortcut_scope.clone() {
+                    if app
+                        .project_store
+                        .remove_scoped_shortcut(&scope, index)
+                        .is_some()
+                    {
+                        if let Err(e) = app.project_store.save() {
+                            tracing::error!("Failed to save project: {}", e);
+                            app.state.error_message =
+                                Some(format!("Failed to save project: {}", e));
+                        }
+                    }
+
+                    // Keep the project settings selection on a row
+                    if let ProjectsNav::ProjectSettings(project_id) = app.state.projects_nav {
+                        let count = PROJECT_SETTINGS_ROWS.len()
+                            + app.project_store.editable_shortcuts(project_id).len();
+                        app.state.project_settings_index =
+                            app.state.project_settings_index.min(count - 1);
+                    }
+                } else if index < app.config.custom_shortcuts.len() {
                     app.config.custom_shortcuts.remove(index);
 
                     // Save config to disk
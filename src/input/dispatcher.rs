@@ -6,6 +6,7 @@ use anyhow::Result;
 use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
 
 use crate::app::{App, AppState, Focus, InputMode, ProjectsNav, SettingsNav, Tab};
+use crate::keymap::{Action, KeyContext, Remapped};
 
 /// Handle a key event by routing to the appropriate mode handler
 pub fn handle_key_event(app: &mut App, key: KeyEvent) -> Result<()> {
@@ -14,12 +15,17 @@ pub fn handle_key_event(app: &mut App, key: KeyEvent) -> Result<()> {
 
     // Handle help overlay first - it captures all keys when visible
     if app.state.show_help_overlay {
-        match key.code {
-            KeyCode::Char('?') | KeyCode::Esc if key.kind == KeyEventKind::Press => {
-                app.state.show_help_overlay = false;
-            }
-            _ => {} // Ignore other keys while overlay is visible
+        // `?` and `Esc` always close it, whatever they have been rebound to,
+        // and so do the keys for help and back
+        let closes = matches!(key.code, KeyCode::Char('?') | KeyCode::Esc)
+            || matches!(
+                app.state.keymap.action_for(KeyContext::Normal, &key),
+                Some(Action::Help | Action::Back)
+            );
+        if closes && key.kind == KeyEventKind::Press {
+            app.state.show_help_overlay = false;
         }
+        // Ignore other keys while overlay is visible
         return Ok(());
     }
 
@@ -35,6 +41,11 @@ pub fn handle_key_event(app: &mut App, key: KeyEvent) -> Result<()> {
         }
     }
 
+    // Rebound keys become the keys the handlers below were written against
+    let Some(key) = remap_key(app, key)? else {
+        return Ok(());
+    };
+
     // Global keys exist in normal mode only, which is what lets every other
     // mode own `Tab` completely
     if globals_apply(app.state.input_mode) && handle_global_normal_key(app, key)? {
@@ -93,6 +104,10 @@ pub fn handle_key_event(app: &mut App, key: KeyEvent) -> Result<()> {
             }
             None => Ok(()),
         },
+        InputMode::CommitSelectFiles => {
+            crate::wizards::commit::handle_commit_select_files_key(app, key)
+        }
+        InputMode::CommitMessage => crate::wizards::commit::handle_commit_message_key(app, key),
         InputMode::AddingClaudeConfigName => {
             agent_configs::handle_adding_config_name_key(app, key, AgentKind::Claude)
         }
@@ -149,10 +164,82 @@ pub fn handle_key_event(app: &mut App, key: KeyEvent) -> Result<()> {
         InputMode::ConfirmingFolderRemove => {
             super::dialogs::handle_confirming_folder_remove_key(app, key)
         }
+        InputMode::CommandPalette => super::palette::handle_command_palette_key(app, key),
+        InputMode::ViewingTimeline => super::timeline::handle_timeline_key(app, key),
+    }
+}
+
+/// Translate a key through the user's bindings (see [`crate::keymap`])
+///
+/// Returns `None` when the key is a built-in one whose action has been moved
+/// to another key. In normal mode such a key does nothing any more; in session
+/// mode it belongs to the agent, which is how `Esc` reaches the agent once
+/// `detach` is bound elsewhere.
+fn remap_key(app: &mut App, key: KeyEvent) -> Result<Option<KeyEvent>> {
+    if !matches!(app.state.input_mode, InputMode::Normal | InputMode::Session) {
+        return Ok(Some(key));
+    }
+    match app.state.keymap.remap(&actions_here(&app.state), key) {
+        Remapped::Key(key) => Ok(Some(key)),
+        Remapped::Unbound if app.state.input_mode == InputMode::Session => {
+            super::session_mode::forward_unbound_key(app, key)?;
+            Ok(None)
+        }
+        Remapped::Unbound => Ok(None),
     }
 }
 
-/// Whether the global keys (`Tab`, `←`/`→`, `q`, `?`, `Space`) apply in this mode
+/// The actions the handler for the current screen implements
+///
+/// Only these are remapped there: a letter that is an action in one pane is
+/// often something else in another (`s` is a new shell in the branch list and
+/// "set as default" in the config lists), and rebinding the action must not
+/// take the key from the pane that means something else by it.
+pub(crate) fn actions_here(state: &AppState) -> Vec<Action> {
+    use Action::*;
+
+    if state.input_mode == InputMode::Session {
+        return vec![Detach, SendEsc];
+    }
+    let mut actions = vec![
+        NextPane,
+        PrevPane,
+        Quit,
+        Help,
+        CommandPalette,
+        Timeline,
+        Up,
+        Down,
+        Select,
+        Back,
+    ];
+    // The Notifications section's toggle is `Space` itself, not the jump
+    let on_notifications = state.focus == Focus::Panes(Tab::Settings)
+        && state.settings_nav == SettingsNav::Notifications;
+    if !on_notifications {
+        actions.push(JumpToAttention);
+    }
+    let pane: &[Action] = match state.focus {
+        Focus::Panes(Tab::Projects) => match state.projects_nav {
+            ProjectsNav::Overview => &[New, Delete, Move, Rename, Refresh],
+            ProjectsNav::Project(_) => &[New, Delete, Refresh, Commit, ProjectSettings],
+            ProjectsNav::Branch(_, _) => &[New, NewShell, Delete],
+            ProjectsNav::ProjectSettings(_) => &[Delete],
+        },
+        Focus::Panes(Tab::Sessions) => &[Delete],
+        Focus::Panes(Tab::Settings) => match state.settings_nav {
+            SettingsNav::Shortcuts | SettingsNav::ClaudeConfigs | SettingsNav::CodexConfigs => {
+                &[New, Delete]
+            }
+            _ => &[],
+        },
+        Focus::Session => &[Tile],
+    };
+    actions.extend_from_slice(pane);
+    actions
+}
+
+/// Whether the global keys (`Tab`, `←`/`→`, `q`, `?`, `Space`, `Ctrl+P`, `Ctrl+L`) apply in this mode
 ///
 /// `Tab` is the load-bearing one: it switches panes *only* in normal mode,
 /// which means every other input mode owns it completely - path autocomplete
@@ -181,6 +268,10 @@ enum GlobalIntent {
     ConfirmQuit,
     /// `Space`: jump to the next session needing attention
     JumpToAttention,
+    /// `Ctrl+P`: open the command palette
+    OpenPalette,
+    /// `Ctrl+L`: open the activity timeline
+    OpenTimeline,
     /// Not a global key: the mode handler owns it
     NotGlobal,
 }
@@ -203,6 +294,12 @@ fn global_intent(key: &KeyEvent) -> GlobalIntent {
         // claims the horizontal arrows any more, so there is no guard here
         KeyCode::Right => GlobalIntent::CyclePane { forward: true },
         KeyCode::Left => GlobalIntent::CyclePane { forward: false },
+        KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            GlobalIntent::OpenPalette
+        }
+        KeyCode::Char('l') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            GlobalIntent::OpenTimeline
+        }
         KeyCode::Char('?') => GlobalIntent::ShowHelp,
         KeyCode::Char('q') => GlobalIntent::ConfirmQuit,
         KeyCode::Char(' ') => GlobalIntent::JumpToAttention,
@@ -215,12 +312,22 @@ fn global_intent(key: &KeyEvent) -> GlobalIntent {
 /// Returns whether the key was consumed.
 fn handle_global_normal_key(app: &mut App, key: KeyEvent) -> Result<bool> {
     match global_intent(&key) {
+        // With sessions tiled, ←/→ belong to the session view: they move
+        // focus between tiles
+        GlobalIntent::CyclePane { .. }
+            if app.state.focus == Focus::Session
+                && !app.state.session_tiles.is_empty()
+                && matches!(key.code, KeyCode::Left | KeyCode::Right) =>
+        {
+            Ok(false)
+        }
         GlobalIntent::CyclePane { forward } => {
             // Cycling only makes sense while the panes are what is on screen.
             // In the full-terminal session view `cycle_pane` returns false and
-            // the key is swallowed - deliberate: that view scrolls with
-            // ↑↓/PgUp/PgDn/Home/End and has nothing for ←/→ to do. In session
-            // *mode* this never runs, so the arrows still reach the agent.
+            // the key is swallowed - deliberate: an untiled session view
+            // scrolls with ↑↓/PgUp/PgDn/Home/End and has nothing for ←/→ to
+            // do. In session *mode* this never runs, so the arrows still
+            // reach the agent.
             if app.state.cycle_pane(forward) {
                 app.sync_pane_focus();
             }
@@ -230,6 +337,14 @@ fn handle_global_normal_key(app: &mut App, key: KeyEvent) -> Result<bool> {
             app.state.show_help_overlay = true;
             Ok(true)
         }
+        GlobalIntent::OpenPalette => {
+            super::palette::open_command_palette(app);
+            Ok(true)
+        }
+        GlobalIntent::OpenTimeline => {
+            super::timeline::open_timeline(app);
+            Ok(true)
+        }
         // Quit from every pane and from session-view normal mode. In session
         // *mode* this never runs, so `q` keeps reaching the agent.
         GlobalIntent::ConfirmQuit => {
@@ -238,7 +353,9 @@ fn handle_global_normal_key(app: &mut App, key: KeyEvent) -> Result<bool> {
         }
         // Jump to the next session needing attention - except in the
         // Notifications section, where Space is the toggle
-        GlobalIntent::JumpToAttention if !owns_space(&app.state) => {
+        GlobalIntent::JumpToAttention
+            if actions_here(&app.state).contains(&Action::JumpToAttention) =>
+        {
             app.jump_to_next_attention()?;
             Ok(true)
         }
@@ -246,11 +363,6 @@ fn handle_global_normal_key(app: &mut App, key: KeyEvent) -> Result<bool> {
     }
 }
 
-/// Whether the focused pane has a stronger claim on `Space` than the jump
-fn owns_space(state: &AppState) -> bool {
-    state.focus == Focus::Panes(Tab::Settings) && state.settings_nav == SettingsNav::Notifications
-}
-
 /// Validate that the current [`InputMode`] can exist where the user is.
 ///
 /// If an invalid combination is detected, reset to Normal mode to prevent
@@ -269,6 +381,11 @@ fn validate_mode_focus_consistency(state: &mut AppState) {
             on(Tab::Projects) && matches!(state.projects_nav, ProjectsNav::Project(_))
         }
 
+        // Committing starts from a branch row of pane 1's project level
+        InputMode::CommitSelectFiles | InputMode::CommitMessage => {
+            on(Tab::Projects) && matches!(state.projects_nav, ProjectsNav::Project(_))
+        }
+
         // The default-base selector is reached only from per-project settings
         InputMode::SelectingDefaultBase => {
             on(Tab::Projects) && matches!(state.projects_nav, ProjectsNav::ProjectSettings(_))
@@ -320,14 +437,18 @@ fn validate_mode_focus_consistency(state: &mut AppState) {
             on(Tab::Projects) && matches!(state.projects_nav, ProjectsNav::Branch(_, _))
         }
 
-        // The shortcut and config editors live in pane 3
+        // The shortcut and config editors live in pane 3, except for the
+        // shortcuts of a project, which its settings edit
         InputMode::AddingCustomShortcutKey
         | InputMode::AddingCustomShortcutName
         | InputMode::AddingCustomShortcutCommand
         | InputMode::AddingCustomShortcutAutoClose
-        | InputMode::ConfirmingCustomShortcutDelete => {
-            on(Tab::Settings) && state.settings_nav == SettingsNav::Shortcuts
-        }
+        | InputMode::ConfirmingCustomShortcutDelete => match state.shortcut_scope {
+            None => on(Tab::Settings) && state.settings_nav == SettingsNav::Shortcuts,
+            Some(_) => {
+                on(Tab::Projects) && matches!(state.projects_nav, ProjectsNav::ProjectSettings(_))
+            }
+        },
         InputMode::AddingClaudeConfigName
         | InputMode::AddingClaudeConfigPath
         | InputMode::ConfirmingClaudeConfigDelete => {
@@ -343,10 +464,13 @@ fn validate_mode_focus_consistency(state: &mut AppState) {
         // settings (also pane 1), so pinning them further would be wrong
         InputMode::SelectingClaudeConfig | InputMode::SelectingCodexConfig => on(Tab::Projects),
 
-        // Quit, the settings-copy offer, and normal mode are valid everywhere
-        InputMode::Normal | InputMode::ConfirmingQuit | InputMode::ConfirmingClaudeSettingsCopy => {
-            true
-        }
+        // Quit, the settings-copy offer, the palette, the timeline, and normal
+        // mode are valid everywhere
+        InputMode::Normal
+        | InputMode::ConfirmingQuit
+        | InputMode::ConfirmingClaudeSettingsCopy
+        | InputMode::CommandPalette
+        | InputMode::ViewingTimeline => true,
     };
 
     if !is_valid {
@@ -408,6 +532,7 @@ mod tests {
             SettingsNav::CodexConfigs,
             SettingsNav::Shortcuts,
             SettingsNav::Notifications,
+            SettingsNav::Theme,
             SettingsNav::About,
         ] {
             let mut state = state_at(Focus::Panes(Tab::Settings), InputMode::Normal);
@@ -815,20 +940,45 @@ mod tests {
 
     #[test]
     fn test_space_belongs_to_the_notifications_section_and_nowhere_else() {
+        let jumps = |state: &AppState| actions_here(state).contains(&Action::JumpToAttention);
         let mut state = AppState {
             focus: Focus::Panes(Tab::Settings),
             settings_nav: SettingsNav::Notifications,
             ..Default::default()
         };
-        assert!(owns_space(&state));
+        assert!(!jumps(&state));
 
         state.settings_nav = SettingsNav::About;
-        assert!(!owns_space(&state));
+        assert!(jumps(&state));
 
         state.focus = Focus::Panes(Tab::Projects);
         state.settings_nav = SettingsNav::Notifications;
-        assert!(!owns_space(&state), "another pane has focus");
+        assert!(jumps(&state), "another pane has focus");
+
+        assert!(jumps(&AppState::default()));
+    }
 
-        assert!(!owns_space(&AppState::default()));
+    #[test]
+    fn test_pane_actions_are_remapped_only_in_their_pane() {
+        let (keymap, warnings) = crate::keymap::Keymap::from_config(
+            &toml::from_str("[normal]\nrename = \"e\"\n").unwrap(),
+            &[],
+        );
+        assert!(warnings.is_empty(), "{warnings:?}");
+        let e = key(KeyCode::Char('e'), KeyModifiers::NONE, KeyEventKind::Press);
+        let r = key(KeyCode::Char('r'), KeyModifiers::NONE, KeyEventKind::Press);
+
+        // Renaming is a projects-overview action
+        let overview = AppState::default();
+        assert_eq!(keymap.remap(&actions_here(&overview), e), Remapped::Key(r));
+        assert_eq!(keymap.remap(&actions_here(&overview), r), Remapped::Unbound);
+
+        // About's `e` is the export, and stays it
+        let about = AppState {
+            focus: Focus::Panes(Tab::Settings),
+            settings_nav: SettingsNav::About,
+            ..Default::default()
+        };
+        assert_eq!(keymap.remap(&actions_here(&about), e), Remapped::Key(e));
     }
 }
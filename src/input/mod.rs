@@ -6,9 +6,11 @@ pub mod agent_configs;
 pub mod dialogs;
 pub mod dispatcher;
 pub mod normal;
+pub mod palette;
 pub mod session_mode;
 pub mod session_scroll;
 pub mod text_input;
+pub mod timeline;
 
 // Re-export commonly used items
 pub use dispatcher::handle_key_event;
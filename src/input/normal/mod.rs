@@ -55,6 +55,7 @@ pub(crate) fn launch_shortcut_session(
             initial_prompt: Some(shortcut.command.clone()),
             account: None,
             auto_close: shortcut.auto_close,
+            model: None,
         },
         rows,
         cols,
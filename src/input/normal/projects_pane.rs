@@ -11,8 +11,9 @@ use crate::app::{
     cycle_next, cycle_prev, App, FolderMoveTarget, InputMode, ProjectsNav, SessionDraft,
 };
 use crate::claude_json::ClaudeJsonStore;
+use crate::git::worktree_setup::WorktreeSetup;
 use crate::input::agent_configs::{open_config_selector, AgentKind};
-use crate::project::{self, BranchId, ProjectId, RowRef};
+use crate::project::{self, BranchId, ProjectId, RowRef, ShortcutScope};
 use crate::tui::views::pane_projects::PROJECT_SETTINGS_ROWS;
 
 /// Handle a normal-mode key while pane 1 has focus
@@ -171,6 +172,17 @@ fn handle_project_key(app: &mut App, key: KeyEvent, project_id: ProjectId) -> Re
                 app.state.error_message = Some(format!("{:#}", e));
             }
         }
+        KeyCode::Char('c') => {
+            let branches = app.project_store.branches_for_project_sorted(project_id);
+            let Some(branch) = branches.get(app.state.selected_branch_index) else {
+                return Ok(());
+            };
+            let branch_id = branch.id;
+            if let Err(e) = app.start_commit_wizard(branch_id) {
+                tracing::error!("Failed to start commit: {:#}", e);
+                app.state.error_message = Some(format!("{:#}", e));
+            }
+        }
         KeyCode::Char('d') => {
             let branches = app.project_store.branches_for_project_sorted(project_id);
             let Some(branch) = branches.get(app.state.selected_branch_index) else {
@@ -203,6 +215,12 @@ fn handle_project_key(app: &mut App, key: KeyEvent, project_id: ProjectId) -> Re
         }
         KeyCode::Char('R') => {
             let stale_count = app.project_store.refresh_branches(project_id);
+            if app.reload_repo_config(project_id) {
+                if let Err(e) = app.project_store.save() {
+                    tracing::error!("Failed to save project store: {}", e);
+                    app.state.error_message = Some(format!("Failed to save project: {}", e));
+                }
+            }
             if stale_count > 0 {
                 app.state.header_notifications.push(format!(
                     "Refreshed: {} worktree{} missing",
@@ -343,7 +361,16 @@ fn handle_branch_key(
             if let Some(branch) = app.project_store.get_branch(branch_id) {
                 app.state.session_draft =
                     SessionDraft::for_branch(project_id, branch_id, branch.working_dir.clone());
-                app.state.agent_type_selector_index = 0;
+                // Start on the agent the repository recommends, if it says
+                let recommended = app
+                    .project_store
+                    .get_project(project_id)
+                    .and_then(|p| p.repo_config.as_ref())
+                    .and_then(|config| config.agent.recommended);
+                app.state.agent_type_selector_index = match recommended {
+                    Some(project::RecommendedAgent::Codex) => 1,
+                    Some(project::RecommendedAgent::Claude) | None => 0,
+                };
                 app.state.input_mode = InputMode::SelectingAgentType;
             }
         }
@@ -361,7 +388,11 @@ fn handle_branch_key(
             }
         }
         KeyCode::Char(c) => {
-            if let Some(shortcut) = app.config.get_shortcut(c).cloned() {
+            let shortcut = app
+                .project_store
+                .shortcut_for(project_id, &app.config.custom_shortcuts, c)
+                .cloned();
+            if let Some(shortcut) = shortcut {
                 if let Some(branch) = app.project_store.get_branch(branch_id) {
                     let working_dir = branch.working_dir.clone();
                     if let Some(new_session_id) = super::launch_shortcut_session(
@@ -387,7 +418,20 @@ fn handle_branch_key(
 // ========================================================================
 
 fn handle_project_settings_key(app: &mut App, key: KeyEvent, project_id: ProjectId) -> Result<()> {
-    let row_count = PROJECT_SETTINGS_ROWS.len();
+    // The fixed rows, then one per shortcut of the project and its folders
+    let shortcuts = app.project_store.editable_shortcuts(project_id);
+    let row_count = PROJECT_SETTINGS_ROWS.len() + shortcuts.len();
+    let selected_shortcut = app
+        .state
+        .project_settings_index
+        .checked_sub(PROJECT_SETTINGS_ROWS.len())
+        .and_then(|i| shortcuts.get(i))
+        .map(|(scope, index, _)| (scope.clone(), *index));
+    // The base branch and worktree setup rows only mean something with git
+    let has_git = app
+        .project_store
+        .get_project(project_id)
+        .is_some_and(|project| project.kind.has_worktrees());
 
     match key.code {
         KeyCode::Esc => {
@@ -401,24 +445,114 @@ fn handle_project_settings_key(app: &mut App, key: KeyEvent, project_id: Project
             app.state.project_settings_index =
                 cycle_prev(app.state.project_settings_index, row_count);
         }
+        KeyCode::Enter if (2..=4).contains(&app.state.project_settings_index) && !has_git => {
+            app.state
+                .header_notifications
+                .push("Not a git repository: no branches or worktrees to set up");
+        }
         KeyCode::Enter => match app.state.project_settings_index {
             0 => open_project_default_config(app, project_id, AgentKind::Claude),
             1 => open_project_default_config(app, project_id, AgentKind::Codex),
             2 => app.start_default_base_selection(project_id),
-            3 => {
+            3 => toggle_worktree_setup(app, project_id, |setup| &mut setup.submodules),
+            4 => toggle_worktree_setup(app, project_id, |setup| &mut setup.sparse_checkout),
+            5 => toggle_repo_commands(app, project_id),
+            6 => {
                 if let Some(project) = app.project_store.get_project(project_id) {
                     app.state.new_project_name = project.name.clone();
                     app.state.renaming_project = Some(project_id);
                     app.state.input_mode = InputMode::RenamingProject;
                 }
             }
+            7 => {
+                app.state.new_shortcut_key = None;
+                app.state.new_shortcut_name.clear();
+                app.state.new_shortcut_command.clear();
+                app.state.new_shortcut_auto_close = false;
+                app.state.shortcut_error = None;
+                app.state.shortcut_scope = Some(ShortcutScope::Project(project_id));
+                app.state.input_mode = InputMode::AddingCustomShortcutKey;
+            }
+            _ if selected_shortcut.is_some() => {
+                app.state
+                    .header_notifications
+                    .push("d deletes the shortcut; add a new one to change it");
+            }
             _ => {}
         },
+        KeyCode::Char('d') => {
+            if let Some((scope, index)) = selected_shortcut {
+                app.state.pending_delete_shortcut_index = Some(index);
+                app.state.shortcut_scope = Some(scope);
+                app.state.input_mode = InputMode::ConfirmingCustomShortcutDelete;
+            }
+        }
         _ => {}
     }
     Ok(())
 }
 
+/// Flip one of a project's worktree setup options and persist it
+///
+/// Only worktrees created from now on are affected; existing ones keep the
+/// checkout they were created with.
+fn toggle_worktree_setup(
+    app: &mut App,
+    project_id: ProjectId,
+    option: fn(&mut WorktreeSetup) -> &mut bool,
+) {
+    let Some(project) = app.project_store.get_project_mut(project_id) else {
+        return;
+    };
+    let flag = option(&mut project.worktree_setup);
+    *flag = !*flag;
+    if let Err(e) = app.project_store.save() {
+        tracing::warn!("Failed to persist worktree setup: {}", e);
+        app.state.error_message = Some(format!("Failed to save project: {}", e));
+    }
+}
+
+/// Allow the project's `.panoptes.toml` setup commands and shortcuts, or
+/// withdraw them
+///
+/// What is allowed is the exact file: when it changes either, the new
+/// version needs allowing again, and until then its shortcuts do not fire.
+fn toggle_repo_commands(app: &mut App, project_id: ProjectId) {
+    let Some(project) = app.project_store.get_project_mut(project_id) else {
+        return;
+    };
+    if project.repo_setup().is_empty() && project.repo_shortcuts().is_empty() {
+        app.state
+            .header_notifications
+            .push("No setup commands or shortcuts in .panoptes.toml");
+        return;
+    }
+    if project.repo_commands_pending() {
+        project.allow_repo_commands();
+        let shortcuts: Vec<String> = project
+            .allowed_shortcuts
+            .iter()
+            .map(|shortcut| format!("{} runs {}", shortcut.key, shortcut.command))
+            .collect();
+        app.state.header_notifications.push(format!(
+            "Allowed: {}",
+            project
+                .allowed_setup
+                .iter()
+                .map(|command| format!("new worktrees run {}", command))
+                .chain(shortcuts)
+                .collect::<Vec<_>>()
+                .join("; ")
+        ));
+    } else {
+        project.disallow_repo_commands();
+    }
+    if let Err(e) = app.project_store.save() {
+        tracing::warn!("Failed to persist .panoptes.toml permissions: {}", e);
+        app.state.error_message = Some(format!("Failed to save project: {}", e));
+    }
+}
+
 /// Open the config selector to pick a project's default Claude/Codex config
 ///
 /// Shows a hint instead when no configs of that kind exist yet.
@@ -3,9 +3,9 @@
 //! Handles keyboard input in session view when NOT in session mode.
 
 use anyhow::Result;
-use crossterm::event::{KeyCode, KeyEvent, KeyEventKind};
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
 
-use crate::app::{App, InputMode};
+use crate::app::{App, InputMode, MAX_TILES};
 use crate::input::session_scroll;
 
 /// Handle key in session view (normal mode)
@@ -28,6 +28,21 @@ pub fn handle_session_view_normal_key(app: &mut App, key: KeyEvent) -> Result<()
             // Re-enable mouse capture for scroll wheel
             app.tui.enable_mouse_capture();
         }
+        KeyCode::Char('t') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            // Cycle 1 -> 2 -> 3 -> 4 tiles and back, as far as there are sessions
+            let current = app.state.session_tiles.len().max(1);
+            let available = app.sessions.len().min(MAX_TILES);
+            let next = if current >= available { 1 } else { current + 1 };
+            app.set_tile_count(next)?;
+        }
+        KeyCode::Left | KeyCode::Right if !app.state.session_tiles.is_empty() => {
+            // Move the keyboard to the next tile over
+            app.state.focus_tile(key.code == KeyCode::Right);
+            if let Some(session_id) = app.state.active_session {
+                app.sessions.acknowledge_attention(session_id);
+                app.clear_title_notification();
+            }
+        }
         KeyCode::Up => {
             // Scroll up a few lines (toward older content)
             if let Some(session_id) = app.state.active_session {
@@ -71,39 +86,43 @@ pub fn handle_session_view_normal_key(app: &mut App, key: KeyEvent) -> Result<()
                 // Use checked access for safety
                 if let Some(session) = app.sessions.get_by_index(target_index) {
                     let session_id = session.info.id;
+                    let tiles = app.state.session_tiles.clone();
+                    app.state.show_in_focused_tile(session_id);
                     app.state.active_session = Some(session_id);
                     // Reset scroll offset when switching sessions
                     session_scroll::reset_for_session_switch(app, session_id);
                     app.sessions.acknowledge_attention(session_id);
                     app.clear_title_notification();
+                    app.resize_untiled_sessions(&tiles)?;
                     app.resize_active_session_pty()?;
                 }
             }
         }
         KeyCode::Char(c) => {
-            // Check for custom shortcut trigger
-            if let Some(shortcut) = app.config.get_shortcut(c).cloned() {
-                // Get the current session's project/branch context
-                if let Some(session_id) = app.state.active_session {
-                    if let Some(session) = app.sessions.get(session_id) {
-                        let project_id = session.info.project_id;
-                        let branch_id = session.info.branch_id;
-                        let working_dir = session.info.working_dir.clone();
-
-                        if let Some(new_session_id) = super::launch_shortcut_session(
-                            app,
-                            &shortcut,
-                            project_id,
-                            branch_id,
-                            working_dir,
-                        ) {
-                            // Swap the screen over to the new session, keeping
-                            // the pane the original was opened from
-                            app.state.active_session = Some(new_session_id);
-                            session_scroll::reset_for_session_switch(app, new_session_id);
-                            app.state.input_mode = InputMode::Session;
-                        }
-                    }
+            // Check for a custom shortcut in the current session's project
+            let Some(session) = app.state.active_session.and_then(|id| app.sessions.get(id)) else {
+                return Ok(());
+            };
+            let project_id = session.info.project_id;
+            let branch_id = session.info.branch_id;
+            let working_dir = session.info.working_dir.clone();
+            let shortcut = app
+                .project_store
+                .shortcut_for(project_id, &app.config.custom_shortcuts, c)
+                .cloned();
+            if let Some(shortcut) = shortcut {
+                if let Some(new_session_id) = super::launch_shortcut_session(
+                    app,
+                    &shortcut,
+                    project_id,
+                    branch_id,
+                    working_dir,
+                ) {
+                    // Swap the screen over to the new session, keeping
+                    // the pane the original was opened from
+                    app.state.active_session = Some(new_session_id);
+                    session_scroll::reset_for_session_switch(app, new_session_id);
+                    app.state.input_mode = InputMode::Session;
                 }
             }
         }
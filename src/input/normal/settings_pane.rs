@@ -1,16 +1,19 @@
 //! Pane 3 input: settings sections and their drill-downs
 //!
 //! The Notification toggles write straight through [`Config::save`] on every
-//! keystroke. That is safe precisely because these six fields are the ones the
+//! keystroke. That is safe precisely because these seven fields are the ones the
 //! runtime re-reads on every event: nothing caches them, so a toggle takes
-//! effect on the next event with no reload path to build.
+//! effect on the next event with no reload path to build. The theme is saved
+//! the same way, and installed globally so the next frame draws with it.
 
 use anyhow::Result;
 use crossterm::event::{KeyCode, KeyEvent, KeyEventKind};
 
 use crate::app::{cycle_next, cycle_prev, App, InputMode, SettingsNav};
+use crate::backup;
 use crate::config::NotificationMethod;
 use crate::input::agent_configs::AgentKind;
+use crate::tui::theme;
 use crate::tui::views::pane_settings::NOTIFICATION_ROWS;
 
 /// Handle a normal-mode key while pane 3 has focus
@@ -29,6 +32,7 @@ pub fn handle_key(app: &mut App, key: KeyEvent) -> Result<()> {
         }
         SettingsNav::Shortcuts => handle_shortcuts_key(app, key),
         SettingsNav::Notifications => handle_notifications_key(app, key),
+        SettingsNav::Theme => handle_theme_key(app, key),
         SettingsNav::About => handle_about_key(app, key),
     }
 }
@@ -48,16 +52,7 @@ fn handle_sections_key(app: &mut App, key: KeyEvent) -> Result<()> {
         }
         KeyCode::Enter => {
             if let Some(section) = SettingsNav::at(app.state.settings_section_index) {
-                app.state.settings_nav = section;
-                // Each section starts at the top, so a stale index from a
-                // previous visit cannot point past a list that has shrunk
-                match section {
-                    SettingsNav::ClaudeConfigs => app.state.claude_configs_selected_index = 0,
-                    SettingsNav::CodexConfigs => app.state.codex_configs_selected_index = 0,
-                    SettingsNav::Shortcuts => app.state.custom_shortcuts_selected = 0,
-                    SettingsNav::Notifications => app.state.notifications_index = 0,
-                    _ => {}
-                }
+                open_section(app, section);
             }
         }
         _ => {}
@@ -65,6 +60,31 @@ fn handle_sections_key(app: &mut App, key: KeyEvent) -> Result<()> {
     Ok(())
 }
 
+/// Open one of pane 3's sections
+pub fn open_section(app: &mut App, section: SettingsNav) {
+    app.state.settings_nav = section;
+    // Each section starts at the top, so a stale index from a previous visit
+    // cannot point past a list that has shrunk
+    match section {
+        SettingsNav::ClaudeConfigs => app.state.claude_configs_selected_index = 0,
+        SettingsNav::CodexConfigs => app.state.codex_configs_selected_index = 0,
+        SettingsNav::Shortcuts => app.state.custom_shortcuts_selected = 0,
+        SettingsNav::Notifications => app.state.notifications_index = 0,
+        // Read the directory afresh, so a theme file written while Panoptes
+        // runs is offered without a restart
+        SettingsNav::Theme => {
+            app.state.available_themes = theme::theme_names(&crate::config::themes_dir());
+            app.state.theme_index = app
+                .state
+                .available_themes
+                .iter()
+                .position(|name| *name == app.config.color_theme)
+                .unwrap_or(0);
+        }
+        SettingsNav::Sections | SettingsNav::About => {}
+    }
+}
+
 fn handle_shortcuts_key(app: &mut App, key: KeyEvent) -> Result<()> {
     let count = app.config.custom_shortcuts.len();
     match key.code {
@@ -85,11 +105,13 @@ fn handle_shortcuts_key(app: &mut App, key: KeyEvent) -> Result<()> {
             app.state.new_shortcut_command.clear();
             app.state.new_shortcut_auto_close = false;
             app.state.shortcut_error = None;
+            app.state.shortcut_scope = None;
             app.state.input_mode = InputMode::AddingCustomShortcutKey;
         }
         KeyCode::Char('d') => {
             if count > 0 {
                 app.state.pending_delete_shortcut_index = Some(app.state.custom_shortcuts_selected);
+                app.state.shortcut_scope = None;
                 app.state.input_mode = InputMode::ConfirmingCustomShortcutDelete;
             }
         }
@@ -153,9 +175,17 @@ fn toggle_row(app: &mut App) -> bool {
             true
         }
         5 => {
+            app.config.notify_on.over_budget = !app.config.notify_on.over_budget;
+            true
+        }
+        6 => {
             app.config.attention_on_idle = !app.config.attention_on_idle;
             true
         }
+        7 => {
+            app.config.notify_on.do_not_disturb = !app.config.notify_on.do_not_disturb;
+            true
+        }
         _ => false,
     }
 }
@@ -173,17 +203,75 @@ fn persist(app: &mut App) {
     }
 }
 
+fn handle_theme_key(app: &mut App, key: KeyEvent) -> Result<()> {
+    let count = app.state.available_themes.len();
+    match key.code {
+        KeyCode::Esc => {
+            app.escape_back();
+        }
+        KeyCode::Down => {
+            app.state.theme_index = cycle_next(app.state.theme_index, count);
+        }
+        KeyCode::Up => {
+            app.state.theme_index = cycle_prev(app.state.theme_index, count);
+        }
+        KeyCode::Enter => {
+            let Some(name) = app
+                .state
+                .available_themes
+                .get(app.state.theme_index)
+                .cloned()
+            else {
+                return Ok(());
+            };
+            // The file is read again on every Enter, so editing a theme and
+            // re-applying it is how a theme is tuned
+            match theme::switch(&name, &crate::config::themes_dir()) {
+                Ok(()) => {
+                    app.config.color_theme = name;
+                    persist(app);
+                }
+                Err(e) => app.state.error_message = Some(format!("{:#}", e)),
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
 fn handle_about_key(app: &mut App, key: KeyEvent) -> Result<()> {
-    if key.code == KeyCode::Esc {
-        app.escape_back();
+    match key.code {
+        KeyCode::Esc => app.escape_back(),
+        KeyCode::Char('e') => export_state(app),
+        _ => {}
     }
     Ok(())
 }
 
+/// Export the whole state to a bundle in the home directory, for
+/// `panoptes import` on another machine
+fn export_state(app: &mut App) {
+    let dir = dirs::home_dir().unwrap_or_else(crate::config::config_dir);
+    let out = backup::default_export_path(&dir);
+    match backup::export(&crate::config::config_dir(), &out) {
+        Ok(count) => app.state.header_notifications.push(format!(
+            "Exported {} files to {} - load it with `panoptes import`",
+            count,
+            out.display()
+        )),
+        Err(e) => {
+            tracing::error!("Failed to export state: {:#}", e);
+            app.state.error_message = Some(format!("Failed to export: {:#}", e));
+        }
+    }
+}
+
 /// The notification methods in the order `Space`/`Enter` advance through
-const METHODS: [NotificationMethod; 3] = [
+const METHODS: [NotificationMethod; 5] = [
     NotificationMethod::Bell,
     NotificationMethod::Title,
+    NotificationMethod::Desktop,
+    NotificationMethod::Osc,
     NotificationMethod::None,
 ];
 
@@ -204,6 +292,14 @@ mod tests {
         );
         assert_eq!(
             next_method(NotificationMethod::Title),
+            NotificationMethod::Desktop
+        );
+        assert_eq!(
+            next_method(NotificationMethod::Desktop),
+            NotificationMethod::Osc
+        );
+        assert_eq!(
+            next_method(NotificationMethod::Osc),
             NotificationMethod::None
         );
         assert_eq!(
@@ -0,0 +1,193 @@
+//! Command palette input
+//!
+//! Typing filters, `↑`/`↓` move the highlight, `Enter` goes wherever the
+//! highlighted entry points, and `Esc` closes the palette without moving.
+
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+
+use crate::app::{
+    cycle_next, cycle_prev, palette_entries, rotation_entries, App, CommandPaletteState, Focus,
+    InputMode, PaletteAction, PaletteTarget, ProjectsNav, Tab, MAX_PALETTE_QUERY_LEN,
+};
+use crate::project;
+
+/// Open the palette over whatever is on screen
+pub fn open_command_palette(app: &mut App) {
+    let sessions: Vec<_> = app
+        .sessions
+        .sessions_in_order()
+        .into_iter()
+        .map(|session| &session.info)
+        .collect();
+    let mut entries = palette_entries(&app.project_store, &sessions);
+    // Offered first, so an empty query shows them before anything else
+    let offers = rotation_entries(&app.codex_rotation_offers());
+    entries.splice(0..0, offers);
+    app.state.command_palette = CommandPaletteState::open(entries);
+    app.state.input_mode = InputMode::CommandPalette;
+}
+
+/// Handle a key while the palette is open
+pub fn handle_command_palette_key(app: &mut App, key: KeyEvent) -> Result<()> {
+    if key.kind != KeyEventKind::Press {
+        return Ok(());
+    }
+    let palette = &mut app.state.command_palette;
+
+    match key.code {
+        KeyCode::Esc => close(app),
+        KeyCode::Up => palette.selected = cycle_prev(palette.selected, palette.matches.len()),
+        KeyCode::Down => palette.selected = cycle_next(palette.selected, palette.matches.len()),
+        // Ctrl+N / Ctrl+P move too, for hands that opened it with Ctrl+P
+        KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            palette.selected = cycle_prev(palette.selected, palette.matches.len())
+        }
+        KeyCode::Char('n') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            palette.selected = cycle_next(palette.selected, palette.matches.len())
+        }
+        KeyCode::Enter => {
+            let target = palette.selected_entry().map(|entry| entry.target.clone());
+            close(app);
+            if let Some(target) = target {
+                go_to(app, target)?;
+            }
+        }
+        KeyCode::Backspace => {
+            palette.query.pop();
+            palette.refilter();
+        }
+        KeyCode::Char(c) if palette.query.len() < MAX_PALETTE_QUERY_LEN => {
+            palette.query.push(c);
+            palette.refilter();
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Close the palette, leaving the screen as it was
+fn close(app: &mut App) {
+    app.state.command_palette = CommandPaletteState::default();
+    app.state.input_mode = InputMode::Normal;
+}
+
+/// Go wherever `target` points
+fn go_to(app: &mut App, target: PaletteTarget) -> Result<()> {
+    match target {
+        PaletteTarget::Session(session_id) => {
+            // The session may have exited and been cleaned up while the
+            // palette was open
+            if app.sessions.get(session_id).is_some() {
+                app.activate_session(session_id)?;
+            }
+        }
+        PaletteTarget::Branch(project_id, branch_id) => {
+            focus_pane(app, Tab::Projects);
+            select_project_row(app, project_id);
+            app.state.navigate_to_branch(project_id, branch_id);
+        }
+        PaletteTarget::Project(project_id) => {
+            focus_pane(app, Tab::Projects);
+            select_project_row(app, project_id);
+            app.state.navigate_to_project(project_id);
+        }
+        PaletteTarget::Folder(path) => {
+            focus_pane(app, Tab::Projects);
+            app.state.projects_nav = ProjectsNav::Overview;
+            reveal_folder(app, &path);
+            if let Some(index) = project::row_index_of_folder(&app.project_store, &path) {
+                app.state.selected_project_index = index;
+            }
+        }
+        PaletteTarget::Action(action) => run_action(app, action)?,
+    }
+    Ok(())
+}
+
+fn run_action(app: &mut App, action: PaletteAction) -> Result<()> {
+    match action {
+        PaletteAction::NewWorktree(project_id) => {
+            focus_pane(app, Tab::Projects);
+            select_project_row(app, project_id);
+            app.state.navigate_to_project(project_id);
+            app.start_worktree_wizard(project_id)?;
+        }
+        PaletteAction::RefreshGit => {
+            app.refresh_all_git_state();
+            app.state
+                .header_notifications
+                .push("Git state refreshed".to_string());
+        }
+        PaletteAction::AddProject => {
+            focus_pane(app, Tab::Projects);
+            app.state.projects_nav = ProjectsNav::Overview;
+            app.state.new_project_path.clear();
+            app.state.input_mode = InputMode::AddingProject;
+        }
+        PaletteAction::OpenSettings(section) => {
+            focus_pane(app, Tab::Settings);
+            super::normal::settings_pane::open_section(app, section);
+        }
+        PaletteAction::ShowTimeline => super::timeline::open_timeline(app),
+        PaletteAction::ShowStats => super::timeline::open_stats(app),
+        PaletteAction::ShowSpend => super::timeline::open_spend(app),
+        PaletteAction::ToggleDoNotDisturb => {
+            app.toggle_do_not_disturb();
+            let message = if app.config.notify_on.do_not_disturb {
+                "Do not disturb is on - notifications are silenced"
+            } else {
+                "Do not disturb is off"
+            };
+            app.state.header_notifications.push(message.to_string());
+        }
+        PaletteAction::MoveToCodexAccount(session_id, config_id) => {
+            if app.move_to_codex_account(session_id, config_id)? {
+                app.state
+                    .header_notifications
+                    .push("Moved the session to the other Codex account".to_string());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Bring the panes back, with `tab` focused
+///
+/// From the session view this leaves the session the same way `Esc` does.
+fn focus_pane(app: &mut App, tab: Tab) {
+    if app.state.focus == Focus::Session {
+        app.state.return_from_session(&app.sessions);
+    }
+    app.state.focus = Focus::Panes(tab);
+    app.sync_pane_focus();
+}
+
+/// Select a project's row in the overview, so `Esc` out of it lands there
+fn select_project_row(app: &mut App, project_id: project::ProjectId) {
+    let folder = app
+        .project_store
+        .get_project(project_id)
+        .map(|p| p.folder.clone())
+        .unwrap_or_default();
+    reveal_folder(app, &folder);
+    if let Some(index) = project::row_index_of_project(&app.project_store, project_id) {
+        app.state.selected_project_index = index;
+    }
+}
+
+/// Expand the folder at `path` and every folder above it
+fn reveal_folder(app: &mut App, path: &[String]) {
+    let mut changed = false;
+    for end in 1..=path.len() {
+        if app.project_store.is_folder_collapsed(&path[..end]) {
+            app.project_store.set_folder_collapsed(&path[..end], false);
+            changed = true;
+        }
+    }
+    if changed {
+        if let Err(e) = app.project_store.save() {
+            tracing::warn!("Failed to persist folder collapse state: {}", e);
+        }
+    }
+}
@@ -136,6 +136,17 @@ fn handle_session_mode_esc(app: &mut App, key: KeyEvent) -> Result<()> {
     Ok(())
 }
 
+/// Forward a built-in session-mode key whose action has been rebound
+///
+/// Those keys are `Esc` and `Shift+Esc`. With `detach` moved off `Esc`, a
+/// plain `Esc` is just a key the agent should get.
+pub fn forward_unbound_key(app: &mut App, key: KeyEvent) -> Result<()> {
+    if key.kind != KeyEventKind::Press {
+        return Ok(());
+    }
+    forward_esc_to_pty(app)
+}
+
 /// Forward an Esc key press to the active session's PTY
 fn forward_esc_to_pty(app: &mut App) -> Result<()> {
     if let Some(session_id) = app.state.active_session {
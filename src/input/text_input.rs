@@ -77,6 +77,12 @@ pub(crate) fn create_session(
         .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
     let project_id = draft.project_id.unwrap_or(Uuid::nil());
     let branch_id = draft.branch_id.unwrap_or(Uuid::nil());
+    let model = app
+        .project_store
+        .get_project(project_id)
+        .and_then(|project| project.repo_config.as_ref())
+        .and_then(|config| config.agent.model_for(agent))
+        .map(String::from);
 
     // Size the new PTY exactly like the session view renders it (and like
     // resize_active_session_pty computes it), so the session never starts
@@ -99,6 +105,7 @@ pub(crate) fn create_session(
             initial_prompt: None,
             account,
             auto_close: false,
+            model,
         },
         rows,
         cols,
@@ -239,14 +246,13 @@ pub fn handle_adding_project_key(app: &mut App, key: KeyEvent) -> Result<()> {
                     app.state.pending_project_path = repo_path;
                     app.state.pending_session_subdir = session_subdir;
                     app.state.pending_default_branch = default_branch;
+                    app.state.pending_project_kind = crate::project::ProjectKind::Git;
                     app.state.new_project_name = default_name;
                     app.state.input_mode = InputMode::AddingProjectName;
                 }
                 Err(e) => {
-                    app.state.error_message =
-                        Some(format!("Not a git repository: {}", user_path.display()));
-                    tracing::error!("Not a git repository: {} ({})", user_path.display(), e);
-                    app.state.input_mode = InputMode::Normal;
+                    tracing::debug!("Not a git repository: {} ({})", user_path.display(), e);
+                    stage_non_git_project(app, user_path);
                 }
             }
         }
@@ -266,6 +272,46 @@ pub fn handle_adding_project_key(app: &mut App, key: KeyEvent) -> Result<()> {
     Ok(())
 }
 
+/// Stage a project for a directory that is not inside a git repository
+///
+/// A directory holding two or more repositories becomes a multi-repository
+/// project; anything else is a plain directory, worked on in place.
+fn stage_non_git_project(app: &mut App, path: PathBuf) {
+    use crate::project::ProjectKind;
+
+    if !path.is_dir() {
+        app.state.error_message = Some(format!("Not a directory: {}", path.display()));
+        app.state.input_mode = InputMode::Normal;
+        return;
+    }
+    if app.project_store.find_by_repo_path(&path).is_some() {
+        app.state.error_message = Some(format!("Project already exists: {}", path.display()));
+        app.state.input_mode = InputMode::Normal;
+        return;
+    }
+
+    let repos = crate::git::multi_repo::child_repositories(&path).unwrap_or_else(|e| {
+        tracing::warn!("Failed to look for repositories in {:?}: {}", path, e);
+        Vec::new()
+    });
+    let kind = if repos.len() >= 2 {
+        ProjectKind::MultiRepo { repos }
+    } else {
+        ProjectKind::Directory
+    };
+
+    app.state.new_project_name = path
+        .file_name()
+        .and_then(|s| s.to_str())
+        .unwrap_or("unknown")
+        .to_string();
+    app.state.pending_project_path = path;
+    app.state.pending_session_subdir = None;
+    app.state.pending_default_branch = crate::project::LOCAL_BRANCH_NAME.to_string();
+    app.state.pending_project_kind = kind;
+    app.state.input_mode = InputMode::AddingProjectName;
+}
+
 /// Handle key when entering project name (second step of project addition)
 pub fn handle_adding_project_name_key(app: &mut App, key: KeyEvent) -> Result<()> {
     // Only process key press events (not release/repeat)
@@ -306,6 +352,25 @@ pub fn handle_adding_project_name_key(app: &mut App, key: KeyEvent) -> Result<()
                 default_branch.clone(),
             );
             project.session_subdir = session_subdir;
+            project.kind = std::mem::take(&mut app.state.pending_project_kind);
+            match crate::project::RepoConfig::load(&repo_path) {
+                Ok(repo_config) => {
+                    // A subfolder the user added the project from wins over
+                    // the repository's suggestion
+                    if project.session_subdir.is_none() {
+                        project.session_subdir = repo_config
+                            .as_ref()
+                            .and_then(|config| config.session_subdir.clone());
+                    }
+                    project.apply_repo_config(repo_config);
+                }
+                Err(e) => {
+                    tracing::warn!("Ignoring .panoptes.toml: {:#}", e);
+                    app.state
+                        .header_notifications
+                        .push(format!("Ignored .panoptes.toml: {:#}", e));
+                }
+            }
             let project_id = project.id;
             app.project_store.add_project(project);
 
@@ -330,6 +395,16 @@ pub fn handle_adding_project_name_key(app: &mut App, key: KeyEvent) -> Result<()
             }
 
             tracing::info!("Added project: {}", name);
+            if app
+                .project_store
+                .get_project(project_id)
+                .is_some_and(|project| project.repo_commands_pending())
+            {
+                app.state.header_notifications.push(format!(
+                    "'{}' has setup commands or shortcuts - allow them in its settings (,)",
+                    name
+                ));
+            }
 
             // Select the newly added project
             let project_count = app.project_store.project_count();
@@ -0,0 +1,115 @@
+//! Activity timeline input
+//!
+//! `Tab` steps through the live timeline, the stats page and the spending
+//! page. On the timeline, `↑`/`↓` scroll through the sessions and `+`/`-` widen and narrow
+//! the window. `Esc` (or the key that opened it) closes the overlay.
+
+use anyhow::Result;
+use chrono::Utc;
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind};
+
+use crate::app::{App, InputMode, TimelinePage};
+use crate::keymap::{Action, KeyContext, Keymap};
+use crate::session::{HistoryStats, SpendReport};
+
+/// Open the timeline over whatever is on screen
+pub fn open_timeline(app: &mut App) {
+    app.state.timeline.scroll = 0;
+    app.state.timeline.page = TimelinePage::Activity;
+    app.state.input_mode = InputMode::ViewingTimeline;
+}
+
+/// Open the overlay straight on its stats page
+pub fn open_stats(app: &mut App) {
+    open_timeline(app);
+    show_page(app, TimelinePage::Stats);
+}
+
+/// Open the overlay straight on its spending page
+pub fn open_spend(app: &mut App) {
+    open_timeline(app);
+    show_page(app, TimelinePage::Spend);
+}
+
+/// Switch page, reading the history log afresh for the pages drawn from it
+///
+/// Read on every visit rather than kept up to date, since the figures are
+/// only looked at here and the log is only ever appended to. Only the months
+/// the page covers are read, at most two.
+fn show_page(app: &mut App, page: TimelinePage) {
+    let dir = app.sessions.history_dir();
+    let loaded = match page {
+        TimelinePage::Activity => Ok(()),
+        TimelinePage::Stats => {
+            HistoryStats::load(dir, Utc::now()).map(|stats| app.state.timeline.stats = Some(stats))
+        }
+        TimelinePage::Spend => SpendReport::load(dir, &app.config.pricing, Utc::now())
+            .map(|spend| app.state.timeline.spend = Some(spend)),
+    };
+    match loaded {
+        Ok(()) => app.state.timeline.page = page,
+        Err(e) => {
+            app.state.error_message = Some(format!("Failed to read session history: {:#}", e))
+        }
+    }
+}
+
+/// Handle a key while the timeline is open
+pub fn handle_timeline_key(app: &mut App, key: KeyEvent) -> Result<()> {
+    if key.kind == KeyEventKind::Release {
+        return Ok(());
+    }
+    let count = app.sessions.sessions_in_order().len();
+
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => close(app),
+        _ if opens_timeline(&app.state.keymap, &key) => close(app),
+        KeyCode::Tab => show_page(app, app.state.timeline.page.next()),
+        KeyCode::BackTab => show_page(app, app.state.timeline.page.previous()),
+        _ if app.state.timeline.page != TimelinePage::Activity => {}
+        KeyCode::Up => app.state.timeline.scroll = app.state.timeline.scroll.saturating_sub(1),
+        KeyCode::Down if app.state.timeline.scroll + 1 < count => {
+            app.state.timeline.scroll += 1;
+        }
+        KeyCode::Char('+') | KeyCode::Char('=') => {
+            app.state.timeline.zoom_out();
+        }
+        KeyCode::Char('-') => {
+            app.state.timeline.zoom_in();
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Whether `key` is bound to opening the timeline, which also closes it
+fn opens_timeline(keymap: &Keymap, key: &KeyEvent) -> bool {
+    keymap.action_for(KeyContext::Normal, key) == Some(Action::Timeline)
+}
+
+fn close(app: &mut App) {
+    app.state.timeline.stats = None;
+    app.state.timeline.spend = None;
+    app.state.input_mode = InputMode::Normal;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    #[test]
+    fn test_the_timeline_closes_on_whatever_key_opens_it() {
+        let ctrl_l = KeyEvent::new(KeyCode::Char('l'), KeyModifiers::CONTROL);
+        let f2 = KeyEvent::new(KeyCode::F(2), KeyModifiers::NONE);
+        assert!(opens_timeline(&Keymap::default(), &ctrl_l));
+
+        let (keymap, warnings) = Keymap::from_config(
+            &toml::from_str("[normal]\ntimeline = \"f2\"\n").unwrap(),
+            &[],
+        );
+        assert!(warnings.is_empty(), "{warnings:?}");
+        assert!(opens_timeline(&keymap, &f2));
+        assert!(!opens_timeline(&keymap, &ctrl_l));
+    }
+}
@@ -0,0 +1,836 @@
+//! Remappable key bindings
+//!
+//! Every built-in key that means the same thing wherever it works is an
+//! [`Action`] with default chords, and the `[keys]` tables of `config.toml`
+//! can rebind any of them:
+//!
+//! ```toml
+//! [keys.normal]
+//! up = ["up", "k"]
+//! down = ["down", "j"]
+//!
+//! [keys.session]
+//! detach = "ctrl+]"
+//! send_esc = "esc"
+//! ```
+//!
+//! Bindings are applied by translation, before the mode handlers see a key:
+//! a chord bound to an action becomes that action's default key, and a
+//! default key whose action has been rebound away stops meaning anything.
+//! The handlers therefore keep matching the keys they always have, and a
+//! remapped key behaves exactly like the key it stands in for, guards and
+//! all.
+//!
+//! Translation only covers the actions of the screen the key lands on, which
+//! the dispatcher passes in. `s` is a new shell in the branch list but "set as
+//! default" in the config lists, so moving `new_shell` elsewhere leaves the
+//! config lists' `s` alone, and a chord bound to `rename` means nothing special
+//! outside the projects overview.
+//!
+//! Only normal mode and session mode are remappable. Every other mode is a
+//! text field, a dialog or a wizard step, where letters are typing.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use anyhow::{bail, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{Deserialize, Serialize};
+
+use crate::config::CustomShortcut;
+
+/// The `[keys]` section of `config.toml`: context name, then action name, then chords
+pub type KeysConfig = BTreeMap<String, BTreeMap<String, ChordList>>;
+
+/// One chord or several, so `quit = "ctrl+q"` and `up = ["up", "k"]` both load
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ChordList {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl ChordList {
+    fn as_slice(&self) -> &[String] {
+        match self {
+            Self::One(chord) => std::slice::from_ref(chord),
+            Self::Many(chords) => chords,
+        }
+    }
+}
+
+/// Where a binding applies
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyContext {
+    /// Normal mode: the three panes and the session view
+    Normal,
+    /// Session mode, where every other key goes to the agent
+    Session,
+}
+
+impl KeyContext {
+    pub const ALL: [KeyContext; 2] = [KeyContext::Normal, KeyContext::Session];
+
+    /// The table name under `[keys]`
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Normal => "normal",
+            Self::Session => "session",
+        }
+    }
+}
+
+/// Something a built-in key does
+///
+/// The order of [`Action::ALL`] is also the precedence when two actions are
+/// configured onto the same chord: the earlier one keeps it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    // Normal mode, from every pane
+    NextPane,
+    PrevPane,
+    Quit,
+    Help,
+    JumpToAttention,
+    CommandPalette,
+    Timeline,
+    Up,
+    Down,
+    Select,
+    Back,
+    // Normal mode, where the focused pane or view binds them
+    New,
+    NewShell,
+    Delete,
+    Move,
+    Rename,
+    Refresh,
+    Commit,
+    ProjectSettings,
+    Tile,
+    // Session mode
+    Detach,
+    SendEsc,
+}
+
+impl Action {
+    pub const ALL: [Action; 22] = [
+        Action::NextPane,
+        Action::PrevPane,
+        Action::Quit,
+        Action::Help,
+        Action::JumpToAttention,
+        Action::CommandPalette,
+        Action::Timeline,
+        Action::Up,
+        Action::Down,
+        Action::Select,
+        Action::Back,
+        Action::New,
+        Action::NewShell,
+        Action::Delete,
+        Action::Move,
+        Action::Rename,
+        Action::Refresh,
+        Action::Commit,
+        Action::ProjectSettings,
+        Action::Tile,
+        Action::Detach,
+        Action::SendEsc,
+    ];
+
+    /// The name used in `config.toml`
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::NextPane => "next_pane",
+            Self::PrevPane => "prev_pane",
+            Self::Quit => "quit",
+            Self::Help => "help",
+            Self::JumpToAttention => "jump_to_attention",
+            Self::CommandPalette => "command_palette",
+            Self::Timeline => "timeline",
+            Self::Up => "up",
+            Self::Down => "down",
+            Self::Select => "select",
+            Self::Back => "back",
+            Self::New => "new",
+            Self::NewShell => "new_shell",
+            Self::Delete => "delete",
+            Self::Move => "move",
+            Self::Rename => "rename",
+            Self::Refresh => "refresh",
+            Self::Commit => "commit",
+            Self::ProjectSettings => "project_settings",
+            Self::Tile => "tile",
+            Self::Detach => "detach",
+            Self::SendEsc => "send_esc",
+        }
+    }
+
+    pub fn context(self) -> KeyContext {
+        match self {
+            Self::Detach | Self::SendEsc => KeyContext::Session,
+            _ => KeyContext::Normal,
+        }
+    }
+
+    /// The built-in chords, first one canonical
+    ///
+    /// The canonical chord is the key the handlers match on, and what a
+    /// remapped chord is translated into. `next_pane` and `prev_pane` lead
+    /// with the arrows because the tiled session view claims those for
+    /// moving between tiles.
+    fn default_chords(self) -> &'static [&'static str] {
+        match self {
+            Self::NextPane => &["right", "tab"],
+            Self::PrevPane => &["left", "shift+tab"],
+            Self::Quit => &["q"],
+            Self::Help => &["?"],
+            Self::JumpToAttention => &["space"],
+            Self::CommandPalette => &["ctrl+p"],
+            Self::Timeline => &["ctrl+l"],
+            Self::Up => &["up"],
+            Self::Down => &["down"],
+            Self::Select => &["enter"],
+            Self::Back => &["esc"],
+            Self::New => &["n"],
+            Self::NewShell => &["s"],
+            Self::Delete => &["d"],
+            Self::Move => &["m"],
+            Self::Rename => &["r"],
+            Self::Refresh => &["R"],
+            Self::Commit => &["c"],
+            Self::ProjectSettings => &[","],
+            Self::Tile => &["ctrl+t"],
+            Self::Detach => &["esc"],
+            Self::SendEsc => &["shift+esc"],
+        }
+    }
+
+    fn defaults(self) -> Vec<KeyChord> {
+        self.default_chords()
+            .iter()
+            .map(|chord| KeyChord::parse(chord).expect("built-in chords parse"))
+            .collect()
+    }
+
+    /// Whether the action is live in the branch list or the session view,
+    /// the two places custom shortcuts fire
+    ///
+    /// The rest belong to the projects overview and the project level, which
+    /// is why `m` and `r` can be custom shortcuts at all.
+    fn fires_beside_custom_shortcuts(self) -> bool {
+        !matches!(
+            self,
+            Self::Move | Self::Rename | Self::Refresh | Self::Commit | Self::ProjectSettings
+        )
+    }
+
+    fn from_name(context: KeyContext, name: &str) -> Option<Action> {
+        Self::ALL
+            .into_iter()
+            .find(|action| action.context() == context && action.name() == name)
+    }
+}
+
+/// A key plus modifiers, normalised so one physical chord compares equal
+/// however the terminal reports it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyChord {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl KeyChord {
+    fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        let mut modifiers =
+            modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT);
+        let code = match code {
+            KeyCode::Char(c) if modifiers.contains(KeyModifiers::CONTROL) => {
+                // Terminals cannot tell Ctrl+P from Ctrl+Shift+P
+                modifiers.remove(KeyModifiers::SHIFT);
+                KeyCode::Char(c.to_ascii_lowercase())
+            }
+            // A character already carries its shift: `R`, `?`
+            KeyCode::Char(c) if modifiers.contains(KeyModifiers::SHIFT) => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                KeyCode::Char(c.to_ascii_uppercase())
+            }
+            // kitty and Ghostty report Shift+Tab as Tab+SHIFT, others as BackTab
+            KeyCode::Tab if modifiers.contains(KeyModifiers::SHIFT) => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                KeyCode::BackTab
+            }
+            KeyCode::BackTab => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                KeyCode::BackTab
+            }
+            other => other,
+        };
+        Self { code, modifiers }
+    }
+
+    /// The chord a key event was
+    pub fn from_event(key: &KeyEvent) -> Self {
+        Self::new(key.code, key.modifiers)
+    }
+
+    /// Parse a chord as written in `config.toml`: `j`, `R`, `space`,
+    /// `ctrl+p`, `shift+esc`, `alt+left`, `f1`, `+`
+    pub fn parse(text: &str) -> Result<Self> {
+        let text = text.trim();
+        let (mods, key) = if text == "+" {
+            ("", "+")
+        } else if let Some(mods) = text.strip_suffix("++") {
+            (mods, "+")
+        } else {
+            match text.rsplit_once('+') {
+                Some((mods, key)) => (mods, key),
+                None => ("", text),
+            }
+        };
+
+        let mut modifiers = KeyModifiers::NONE;
+        for part in mods.split('+').filter(|part| !part.is_empty()) {
+            modifiers |= match part.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" | "meta" | "option" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => bail!("unknown modifier '{}' in '{}'", part, text),
+            };
+        }
+
+        let mut chars = key.chars();
+        let code = match (chars.next(), chars.next()) {
+            (None, _) => bail!("no key in '{}'", text),
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match key.to_ascii_lowercase().as_str() {
+                "esc" | "escape" => KeyCode::Esc,
+                "enter" | "return" => KeyCode::Enter,
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "space" => KeyCode::Char(' '),
+                "backspace" => KeyCode::Backspace,
+                "delete" | "del" => KeyCode::Delete,
+                "insert" => KeyCode::Insert,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                lower => match lower.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                    Some(n @ 1..=12) => KeyCode::F(n),
+                    _ => bail!("unknown key '{}' in '{}'", key, text),
+                },
+            },
+        };
+        Ok(Self::new(code, modifiers))
+    }
+
+    /// The key event the handlers would have received for this chord
+    fn to_event(self, like: &KeyEvent) -> KeyEvent {
+        let mut modifiers = self.modifiers;
+        if self.code == KeyCode::BackTab {
+            modifiers |= KeyModifiers::SHIFT;
+        }
+        let mut key = KeyEvent::new(self.code, modifiers);
+        key.kind = like.kind;
+        key.state = like.state;
+        key
+    }
+
+    /// The plain character this chord types, if it is one
+    fn plain_char(self) -> Option<char> {
+        match self.code {
+            KeyCode::Char(c) if self.modifiers.is_empty() => Some(c),
+            _ => None,
+        }
+    }
+
+    /// How the chord is shown in the help overlay: `↑`, `⇧Tab`, `Ctrl+P`
+    pub fn label(self) -> String {
+        let key = match self.code {
+            KeyCode::Up => "↑".to_string(),
+            KeyCode::Down => "↓".to_string(),
+            KeyCode::Left => "←".to_string(),
+            KeyCode::Right => "→".to_string(),
+            KeyCode::BackTab => "⇧Tab".to_string(),
+            KeyCode::Char(' ') => "Space".to_string(),
+            KeyCode::Char(c) => c.to_string(),
+            KeyCode::PageUp => "PgUp".to_string(),
+            KeyCode::PageDown => "PgDn".to_string(),
+            KeyCode::F(n) => format!("F{}", n),
+            other => format!("{:?}", other),
+        };
+        let mut label = String::new();
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            label.push_str("Ctrl+");
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            label.push_str("Alt+");
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            label.push('⇧');
+        }
+        if self.modifiers.contains(KeyModifiers::CONTROL) && key.chars().count() == 1 {
+            label.push_str(&key.to_uppercase());
+        } else {
+            label.push_str(&key);
+        }
+        label
+    }
+}
+
+/// Written the way `config.toml` spells it, for messages
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "alt+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "shift+")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::BackTab => write!(f, "shift+tab"),
+            KeyCode::F(n) => write!(f, "f{}", n),
+            other => write!(f, "{}", format!("{:?}", other).to_ascii_lowercase()),
+        }
+    }
+}
+
+/// What the bindings make of a key event
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Remapped {
+    /// Hand this key to the mode handler: the key itself, or the canonical
+    /// key of the action it is bound to
+    Key(KeyEvent),
+    /// A built-in key whose action has been bound elsewhere: it means
+    /// nothing here any more
+    Unbound,
+}
+
+/// The active bindings
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    /// Each action's chords, in [`Action::ALL`] order
+    bindings: Vec<(Action, Vec<KeyChord>)>,
+    /// The built-in chords, to recognise keys that have been rebound away
+    defaults: Vec<(Action, Vec<KeyChord>)>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let defaults: Vec<_> = Action::ALL.into_iter().map(|a| (a, a.defaults())).collect();
+        Self {
+            bindings: defaults.clone(),
+            defaults,
+        }
+    }
+}
+
+impl Keymap {
+    /// Build the keymap from the `[keys]` tables
+    ///
+    /// Nothing here fails the load. Unknown names, chords that do not parse,
+    /// and chords claimed twice are reported in the returned warnings and
+    /// skipped, and so is a chord that would shadow one of the custom
+    /// `shortcuts` - both are plain keys in the same places.
+    pub fn from_config(keys: &KeysConfig, shortcuts: &[CustomShortcut]) -> (Self, Vec<String>) {
+        let mut keymap = Self::default();
+        let mut warnings = Vec::new();
+
+        for (context_name, table) in keys {
+            let Some(context) = KeyContext::ALL
+                .into_iter()
+                .find(|c| c.name() == context_name)
+            else {
+                warnings.push(format!(
+                    "[keys.{}] is not a key context (use normal or session); ignored",
+                    context_name
+                ));
+                continue;
+            };
+            for (action_name, chords) in table {
+                let Some(action) = Action::from_name(context, action_name) else {
+                    warnings.push(format!(
+                        "keys.{}.{} is not an action; ignored",
+                        context_name, action_name
+                    ));
+                    continue;
+                };
+                let mut parsed = Vec::new();
+                for chord in chords.as_slice() {
+                    match KeyChord::parse(chord) {
+                        Ok(chord) if !parsed.contains(&chord) => parsed.push(chord),
+                        Ok(_) => {}
+                        Err(e) => warnings.push(format!(
+                            "keys.{}.{}: {}; ignored",
+                            context_name, action_name, e
+                        )),
+                    }
+                }
+                keymap.set(action, parsed);
+            }
+        }
+
+        // Earlier actions keep a chord claimed twice
+        for context in KeyContext::ALL {
+            let mut claimed: Vec<(KeyChord, Action)> = Vec::new();
+            for (action, chords) in keymap
+                .bindings
+                .iter_mut()
+                .filter(|(action, _)| action.context() == context)
+            {
+                chords.retain(|chord| {
+                    if let Some((_, owner)) = claimed.iter().find(|(c, _)| c == chord) {
+                        warnings.push(format!(
+                            "'{}' is bound to both {}.{} and {}.{}; keeping {}",
+                            chord,
+                            context.name(),
+                            owner.name(),
+                            context.name(),
+                            action.name(),
+                            owner.name()
+                        ));
+                        return false;
+                    }
+                    claimed.push((*chord, *action));
+                    true
+                });
+            }
+        }
+
+        // A shortcut's key is lost when it is translated into another key,
+        // or when it lands on an action that is live where shortcuts fire
+        for shortcut in shortcuts {
+            let shadowing = keymap.bindings.iter().find(|(action, chords)| {
+                action.context() == KeyContext::Normal
+                    && chords.iter().any(|c| {
+                        c.plain_char() == Some(shortcut.key)
+                            && (action.fires_beside_custom_shortcuts()
+                                || keymap.canonical(*action) != Some(*c))
+                    })
+            });
+            if let Some((action, _)) = shadowing {
+                warnings.push(format!(
+                    "'{}' is bound to normal.{}, so the custom shortcut '{}' ({}) cannot run",
+                    shortcut.key,
+                    action.name(),
+                    shortcut.key,
+                    shortcut.display_name()
+                ));
+            }
+        }
+
+        (keymap, warnings)
+    }
+
+    fn set(&mut self, action: Action, chords: Vec<KeyChord>) {
+        if let Some((_, bound)) = self.bindings.iter_mut().find(|(a, _)| *a == action) {
+            *bound = chords;
+        }
+    }
+
+    /// The chords currently bound to `action`
+    pub fn chords(&self, action: Action) -> &[KeyChord] {
+        self.bindings
+            .iter()
+            .find(|(a, _)| *a == action)
+            .map(|(_, chords)| chords.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// The action `key` is bound to in `context`, if any
+    pub fn action_for(&self, context: KeyContext, key: &KeyEvent) -> Option<Action> {
+        let chord = KeyChord::from_event(key);
+        self.bindings
+            .iter()
+            .find(|(action, chords)| action.context() == context && chords.contains(&chord))
+            .map(|(action, _)| *action)
+    }
+
+    /// The key the handlers match on for `action`
+    fn canonical(&self, action: Action) -> Option<KeyChord> {
+        self.defaults
+            .iter()
+            .find(|(a, _)| *a == action)
+            .and_then(|(_, chords)| chords.first().copied())
+    }
+
+    /// Translate a key event through the bindings of the actions in `here`,
+    /// the ones the screen it lands on implements
+    ///
+    /// Every other action is left out, so its chords and its default keys
+    /// reach the handler as they are.
+    pub fn remap(&self, here: &[Action], key: KeyEvent) -> Remapped {
+        let chord = KeyChord::from_event(&key);
+        let bound = self
+            .bindings
+            .iter()
+            .find(|(action, chords)| here.contains(action) && chords.contains(&chord));
+        if let Some((action, _)) = bound {
+            return match self.canonical(*action) {
+                Some(chord) => Remapped::Key(chord.to_event(&key)),
+                None => Remapped::Key(key),
+            };
+        }
+        let is_builtin = self
+            .defaults
+            .iter()
+            .any(|(action, chords)| here.contains(action) && chords.contains(&chord));
+        if is_builtin {
+            Remapped::Unbound
+        } else {
+            Remapped::Key(key)
+        }
+    }
+
+    /// The keys for `actions`, for the help overlay: each action's chords
+    /// space-separated, actions separated by `/`
+    pub fn label(&self, actions: &[Action]) -> String {
+        actions
+            .iter()
+            .map(|&action| {
+                let chords = self.chords(action);
+                if chords.is_empty() {
+                    "unbound".to_string()
+                } else {
+                    chords
+                        .iter()
+                        .map(|chord| chord.label())
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" / ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(toml_text: &str) -> KeysConfig {
+        toml::from_str(toml_text).unwrap()
+    }
+
+    fn press(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    /// Every action of `context`, as if one screen implemented them all
+    fn everywhere(context: KeyContext) -> Vec<Action> {
+        Action::ALL
+            .into_iter()
+            .filter(|action| action.context() == context)
+            .collect()
+    }
+
+    #[test]
+    fn test_chords_parse_and_normalise() {
+        let parse = |text| KeyChord::parse(text).unwrap();
+        assert_eq!(
+            parse("ctrl+p"),
+            KeyChord::new(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        );
+        assert_eq!(parse("Ctrl+P").to_string(), "ctrl+p");
+        assert_eq!(parse("shift+tab"), parse("backtab"));
+        assert_eq!(parse("shift+r"), parse("R"));
+        assert_eq!(
+            parse("space"),
+            KeyChord::new(KeyCode::Char(' '), KeyModifiers::NONE)
+        );
+        assert_eq!(
+            parse("+"),
+            KeyChord::new(KeyCode::Char('+'), KeyModifiers::NONE)
+        );
+        assert_eq!(
+            parse("ctrl++"),
+            KeyChord::new(KeyCode::Char('+'), KeyModifiers::CONTROL)
+        );
+        assert_eq!(
+            parse("f5"),
+            KeyChord::new(KeyCode::F(5), KeyModifiers::NONE)
+        );
+        assert!(KeyChord::parse("hyper+x").is_err());
+        assert!(KeyChord::parse("banana").is_err());
+        assert!(KeyChord::parse("").is_err());
+
+        // However the terminal reports them
+        assert_eq!(
+            KeyChord::from_event(&press(KeyCode::Tab, KeyModifiers::SHIFT)),
+            parse("shift+tab")
+        );
+        assert_eq!(
+            KeyChord::from_event(&press(KeyCode::Char('?'), KeyModifiers::SHIFT)),
+            parse("?")
+        );
+    }
+
+    #[test]
+    fn test_every_default_chord_parses_without_conflicts() {
+        let (keymap, warnings) = Keymap::from_config(&KeysConfig::new(), &[]);
+        assert!(warnings.is_empty(), "{warnings:?}");
+        for action in Action::ALL {
+            assert!(!keymap.chords(action).is_empty(), "{action:?}");
+        }
+    }
+
+    #[test]
+    fn test_defaults_pass_keys_through_untouched() {
+        let keymap = Keymap::default();
+        for code in [
+            KeyCode::Up,
+            KeyCode::Char('j'),
+            KeyCode::Char('1'),
+            KeyCode::Esc,
+        ] {
+            let key = press(code, KeyModifiers::NONE);
+            assert_eq!(
+                keymap.remap(&everywhere(KeyContext::Normal), key),
+                Remapped::Key(key)
+            );
+        }
+    }
+
+    #[test]
+    fn test_vim_keys_translate_to_the_arrows() {
+        let (keymap, warnings) = Keymap::from_config(
+            &keys("[normal]\nup = [\"up\", \"k\"]\ndown = [\"down\", \"j\"]\nprev_pane = \"h\"\n"),
+            &[],
+        );
+        assert!(warnings.is_empty(), "{warnings:?}");
+
+        let j = press(KeyCode::Char('j'), KeyModifiers::NONE);
+        assert_eq!(
+            keymap.remap(&everywhere(KeyContext::Normal), j),
+            Remapped::Key(press(KeyCode::Down, KeyModifiers::NONE))
+        );
+        let down = press(KeyCode::Down, KeyModifiers::NONE);
+        assert_eq!(
+            keymap.remap(&everywhere(KeyContext::Normal), down),
+            Remapped::Key(down)
+        );
+
+        // Rebinding replaces: the old keys for prev_pane are free now
+        let h = press(KeyCode::Char('h'), KeyModifiers::NONE);
+        assert_eq!(
+            keymap.remap(&everywhere(KeyContext::Normal), h),
+            Remapped::Key(press(KeyCode::Left, KeyModifiers::NONE))
+        );
+        for key in [
+            press(KeyCode::Left, KeyModifiers::NONE),
+            press(KeyCode::BackTab, KeyModifiers::SHIFT),
+        ] {
+            assert_eq!(
+                keymap.remap(&everywhere(KeyContext::Normal), key),
+                Remapped::Unbound
+            );
+        }
+        assert_eq!(keymap.label(&[Action::Up, Action::Down]), "↑ k / ↓ j");
+    }
+
+    #[test]
+    fn test_esc_can_go_to_the_agent() {
+        let (keymap, warnings) = Keymap::from_config(
+            &keys("[session]\ndetach = \"ctrl+]\"\nsend_esc = \"esc\"\n"),
+            &[],
+        );
+        assert!(warnings.is_empty(), "{warnings:?}");
+
+        let esc = press(KeyCode::Esc, KeyModifiers::NONE);
+        assert_eq!(
+            keymap.remap(&everywhere(KeyContext::Session), esc),
+            Remapped::Key(press(KeyCode::Esc, KeyModifiers::SHIFT))
+        );
+        let detach = press(KeyCode::Char(']'), KeyModifiers::CONTROL);
+        assert_eq!(
+            keymap.remap(&everywhere(KeyContext::Session), detach),
+            Remapped::Key(esc)
+        );
+        // Session bindings say nothing about normal mode
+        assert_eq!(
+            keymap.remap(&everywhere(KeyContext::Normal), esc),
+            Remapped::Key(esc)
+        );
+    }
+
+    #[test]
+    fn test_only_the_actions_here_are_remapped() {
+        let (keymap, warnings) =
+            Keymap::from_config(&keys("[normal]\nnew_shell = \"S\"\nrename = \"e\"\n"), &[]);
+        assert!(warnings.is_empty(), "{warnings:?}");
+        let s = press(KeyCode::Char('s'), KeyModifiers::NONE);
+        let shift_s = press(KeyCode::Char('S'), KeyModifiers::SHIFT);
+        let e = press(KeyCode::Char('e'), KeyModifiers::NONE);
+
+        // Where the actions live, their keys move
+        let branch = [Action::New, Action::NewShell, Action::Delete];
+        assert_eq!(keymap.remap(&branch, s), Remapped::Unbound);
+        assert_eq!(keymap.remap(&branch, shift_s), Remapped::Key(s));
+        let overview = [Action::New, Action::Delete, Action::Move, Action::Rename];
+        assert_eq!(
+            keymap.remap(&overview, e),
+            Remapped::Key(press(KeyCode::Char('r'), KeyModifiers::NONE))
+        );
+
+        // Anywhere else, the keys are what they always were
+        let configs = [Action::New, Action::Delete];
+        assert_eq!(keymap.remap(&configs, s), Remapped::Key(s));
+        assert_eq!(keymap.remap(&configs, shift_s), Remapped::Key(shift_s));
+        assert_eq!(keymap.remap(&configs, e), Remapped::Key(e));
+    }
+
+    #[test]
+    fn test_conflicts_and_mistakes_are_reported_not_fatal() {
+        let shortcut = CustomShortcut::new('x', "Editor".to_string(), "code .".to_string(), false);
+        let (keymap, warnings) = Keymap::from_config(
+            &keys(
+                "[normal]\nquit = \"j\"\ndown = \"j\"\ndelete = \"x\"\nfly = \"f\"\nup = \"banana\"\n\n[visual]\nup = \"k\"\n",
+            ),
+            &[shortcut],
+        );
+
+        assert_eq!(warnings.len(), 5, "{warnings:?}");
+        assert!(warnings
+            .iter()
+            .any(|w| w.contains("'j' is bound to both normal.quit and normal.down; keeping quit")));
+        assert!(warnings
+            .iter()
+            .any(|w| w.contains("custom shortcut 'x' (Editor)")));
+        assert!(warnings.iter().any(|w| w.contains("keys.normal.fly")));
+        assert!(warnings.iter().any(|w| w.contains("banana")));
+        assert!(warnings.iter().any(|w| w.contains("[keys.visual]")));
+
+        assert_eq!(keymap.chords(Action::Quit), [KeyChord::parse("j").unwrap()]);
+        assert!(keymap.chords(Action::Down).is_empty());
+        assert!(keymap.chords(Action::Up).is_empty());
+        assert_eq!(keymap.label(&[Action::Down]), "unbound");
+    }
+
+    #[test]
+    fn test_overview_keys_leave_custom_shortcuts_alone() {
+        // `m` moves projects in the overview, but custom shortcuts fire in the
+        // branch list and the session view, where `m` means nothing
+        let shortcut = CustomShortcut::new('m', String::new(), "make".to_string(), false);
+        let (_, warnings) =
+            Keymap::from_config(&KeysConfig::new(), std::slice::from_ref(&shortcut));
+        assert!(warnings.is_empty(), "{warnings:?}");
+
+        // ...unless it is rebound onto something that is live there
+        let (_, warnings) = Keymap::from_config(&keys("[normal]\ndown = \"m\"\n"), &[shortcut]);
+        assert!(
+            warnings.iter().any(|w| w.contains("custom shortcut 'm'")),
+            "{warnings:?}"
+        );
+    }
+}
@@ -5,16 +5,22 @@
 pub mod agent;
 pub mod agent_profiles;
 pub mod app;
+pub mod backup;
 pub mod claude_config;
 pub mod claude_json;
 pub mod codex_config;
 pub mod config;
+pub mod config_edit;
 pub mod git;
 pub mod hooks;
 pub mod input;
+pub mod keymap;
 pub mod logging;
+pub mod notify;
 pub mod path_complete;
 pub mod persistence;
+pub mod pricing;
+pub mod process;
 pub mod project;
 pub mod session;
 pub mod transcript;
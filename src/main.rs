@@ -1,11 +1,18 @@
 use anyhow::Result;
 
 use panoptes::app::App;
+use panoptes::backup;
 use panoptes::config;
 use panoptes::logging;
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    // `panoptes export` / `panoptes import` run and exit without the dashboard
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Some(result) = backup::run_cli(&args) {
+        return result;
+    }
+
     // Ensure config directory exists (creates logs dir too)
     config::ensure_directories()?;
 
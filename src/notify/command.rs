@@ -0,0 +1,240 @@
+//! User commands run when a session wants attention
+//!
+//! The escape hatch for anything the built-in methods do not cover: a
+//! different sound for an approval than for a finished turn, a status LED, a
+//! line in a journal. Each attention reason may name a shell command, run
+//! through `sh -c` alongside `notification_method`:
+//!
+//! ```toml
+//! [notify_commands]
+//! approval = "paplay ~/sounds/knock.oga"
+//! turn_complete = "paplay ~/sounds/done.oga"
+//! timeout_secs = 10
+//! ```
+//!
+//! The command learns what happened from `PANOPTES_*` environment variables
+//! (see [`run`]). It runs on a worker thread and is killed if it outlives
+//! `timeout_secs`, so a hung script cannot pile up a process per notification.
+
+use std::process::{ExitStatus, Stdio};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::Notification;
+use crate::process::{self, Finished, Limits};
+use crate::session::AttentionReason;
+
+/// The `[notify_commands]` section of `config.toml`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotifyCommands {
+    /// Run when a permission dialog or question blocks a turn
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approval: Option<String>,
+    /// Run when an agent finishes its turn
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub turn_complete: Option<String>,
+    /// Run when a tool has been in flight far longer than expected
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stalled: Option<String>,
+    /// Run when a session's process dies unexpectedly
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub crashed: Option<String>,
+    /// Run when a session goes over a `[budgets]` limit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub over_budget: Option<String>,
+    /// Seconds a command may run before it is killed
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+impl Default for NotifyCommands {
+    fn default() -> Self {
+        Self {
+            approval: None,
+            turn_complete: None,
+            stalled: None,
+            crashed: None,
+            over_budget: None,
+            timeout_secs: default_timeout_secs(),
+        }
+    }
+}
+
+fn default_timeout_secs() -> u64 {
+    10
+}
+
+impl NotifyCommands {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// The command configured for this reason, if any
+    pub fn command_for(&self, reason: &AttentionReason) -> Option<&str> {
+        let command = match reason {
+            AttentionReason::Approval { .. } => &self.approval,
+            AttentionReason::TurnComplete => &self.turn_complete,
+            AttentionReason::Stalled { .. } => &self.stalled,
+            AttentionReason::Crashed { .. } => &self.crashed,
+            AttentionReason::OverBudget { .. } => &self.over_budget,
+        };
+        command.as_deref().filter(|c| !c.trim().is_empty())
+    }
+}
+
+/// Run the command for this reason on a worker thread, if one is configured
+///
+/// The command sees:
+/// - `PANOPTES_SESSION` - the session's name
+/// - `PANOPTES_PROJECT`, `PANOPTES_BRANCH` - where it lives, empty if unknown
+/// - `PANOPTES_REASON` - the `[notify_on]` name, e.g. `approval`
+/// - `PANOPTES_SUMMARY` - the reason as the session list shows it
+/// - `PANOPTES_LAST_MESSAGE` - the agent's last reply, empty if none yet
+pub fn run(commands: &NotifyCommands, reason: &AttentionReason, notification: &Notification) {
+    let Some(command) = commands.command_for(reason) else {
+        return;
+    };
+    let command = command.to_string();
+    let env = environment(reason, notification);
+    let timeout = Duration::from_secs(commands.timeout_secs);
+    std::thread::spawn(move || match execute(&command, &env, timeout) {
+        Ok(status) if status.success() => {}
+        Ok(status) => tracing::warn!("Notify command `{}` exited with {}", command, status),
+        Err(e) => tracing::warn!("Notify command `{}` failed: {:#}", command, e),
+    });
+}
+
+fn environment(
+    reason: &AttentionReason,
+    notification: &Notification,
+) -> Vec<(&'static str, String)> {
+    vec![
+        ("PANOPTES_SESSION", notification.session.clone()),
+        (
+            "PANOPTES_PROJECT",
+            notification.project.clone().unwrap_or_default(),
+        ),
+        (
+            "PANOPTES_BRANCH",
+            notification.branch.clone().unwrap_or_default(),
+        ),
+        ("PANOPTES_REASON", reason.key().to_string()),
+        ("PANOPTES_SUMMARY", notification.summary.clone()),
+        (
+            "PANOPTES_LAST_MESSAGE",
+            notification.last_message.clone().unwrap_or_default(),
+        ),
+    ]
+}
+
+/// Run `command` through `sh -c`, killing it once `timeout` has passed
+///
+/// Its output goes nowhere, stderr included. The timeout also reaches
+/// whatever the shell started (`sleep 60; paplay ...` would otherwise outlive
+/// it), see [`crate::process`].
+fn execute(command: &str, env: &[(&str, String)], timeout: Duration) -> Result<ExitStatus> {
+    let mut shell = process::shell(command);
+    shell
+        .envs(env.iter().map(|(k, v)| (k, v)))
+        .stderr(Stdio::null());
+    let limits = Limits {
+        timeout: Some(timeout),
+        cancel: None,
+    };
+    match process::run(&mut shell, limits).context("Failed to start sh")? {
+        Finished::Exited { status, .. } => Ok(status),
+        Finished::TimedOut | Finished::Cancelled => {
+            anyhow::bail!("killed after {}s", timeout.as_secs())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    fn notification() -> Notification {
+        Notification {
+            session: "api-fix".to_string(),
+            summary: "approve Bash".to_string(),
+            project: Some("web".to_string()),
+            branch: None,
+            last_message: Some("Shall I run the migration?".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_command_sees_the_notification_in_its_environment() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("env");
+        let reason = AttentionReason::Approval {
+            tool: Some("Bash".to_string()),
+        };
+        let command = format!(
+            "printf '%s|%s|%s|%s|%s|%s' \"$PANOPTES_SESSION\" \"$PANOPTES_PROJECT\" \
+             \"$PANOPTES_BRANCH\" \"$PANOPTES_REASON\" \"$PANOPTES_SUMMARY\" \
+             \"$PANOPTES_LAST_MESSAGE\" > {}",
+            out.display()
+        );
+
+        let status = execute(
+            &command,
+            &environment(&reason, &notification()),
+            Duration::from_secs(5),
+        )
+        .unwrap();
+
+        assert!(status.success());
+        assert_eq!(
+            std::fs::read_to_string(&out).unwrap(),
+            "api-fix|web||approval|approve Bash|Shall I run the migration?"
+        );
+    }
+
+    #[test]
+    fn test_command_is_killed_at_the_timeout() {
+        let started = Instant::now();
+
+        let result = execute("sleep 5", &[], Duration::from_millis(200));
+
+        assert!(result.is_err());
+        assert!(started.elapsed() < Duration::from_secs(3));
+    }
+
+    #[test]
+    fn test_timeout_kills_what_the_command_started() {
+        let dir = tempfile::tempdir().unwrap();
+        let marker = dir.path().join("late");
+        let command = format!("(sleep 1; touch {}) & sleep 5", marker.display());
+
+        let result = execute(&command, &[], Duration::from_millis(200));
+        std::thread::sleep(Duration::from_millis(1500));
+
+        assert!(result.is_err());
+        assert!(!marker.exists());
+    }
+
+    #[test]
+    fn test_command_for_picks_the_reason_and_skips_blank_ones() {
+        let commands = NotifyCommands {
+            approval: Some("knock".to_string()),
+            turn_complete: Some("  ".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            commands.command_for(&AttentionReason::Approval { tool: None }),
+            Some("knock")
+        );
+        assert_eq!(commands.command_for(&AttentionReason::TurnComplete), None);
+        assert_eq!(
+            commands.command_for(&AttentionReason::Crashed {
+                reason: "signal 9".to_string()
+            }),
+            None
+        );
+    }
+}
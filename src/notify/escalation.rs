@@ -0,0 +1,279 @@
+//! Re-notifying, louder each time, while an approval goes unanswered
+//!
+//! A single bell is easy to miss, and an agent blocked on a permission dialog
+//! does nothing until someone answers it. While a session's attention sits
+//! unacknowledged, each step fires once its time comes, optionally through a
+//! different channel than the first notification:
+//!
+//! ```toml
+//! [escalation]
+//! on = ["approval", "crashed"]
+//!
+//! [[escalation.steps]]
+//! after_mins = 5
+//! method = "bell"
+//!
+//! [[escalation.steps]]
+//! after_mins = 15
+//! method = "title"
+//!
+//! [[escalation.steps]]
+//! after_mins = 30
+//! method = "desktop"
+//! webhooks = true
+//! ```
+//!
+//! Looking at the session acknowledges it and stops the escalation; a new
+//! reason starts it over.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::NotificationMethod;
+use crate::session::{AttentionReason, SessionId};
+
+/// The `[escalation]` section of `config.toml`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EscalationConfig {
+    /// Which attention reasons escalate, named as in `[notify_on]`
+    #[serde(default = "default_on")]
+    pub on: Vec<String>,
+    /// What to do, and when, while the attention goes unacknowledged; none
+    /// means escalation is off
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub steps: Vec<EscalationStep>,
+}
+
+impl Default for EscalationConfig {
+    fn default() -> Self {
+        Self {
+            on: default_on(),
+            steps: Vec::new(),
+        }
+    }
+}
+
+fn default_on() -> Vec<String> {
+    vec!["approval".to_string(), "crashed".to_string()]
+}
+
+/// One `[[escalation.steps]]` entry
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EscalationStep {
+    /// Minutes after the attention was raised; steps are listed in the
+    /// order they fire
+    pub after_mins: u64,
+    /// How to notify; `notification_method` when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub method: Option<NotificationMethod>,
+    /// Also run the reason's `[notify_commands]` entry
+    #[serde(default)]
+    pub command: bool,
+    /// Also fire the `[[webhooks]]` that want the reason
+    #[serde(default)]
+    pub webhooks: bool,
+}
+
+impl EscalationConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Whether attention for this reason escalates
+    pub fn covers(&self, reason: &AttentionReason) -> bool {
+        !self.steps.is_empty() && self.on.iter().any(|r| r == reason.key())
+    }
+}
+
+/// A step whose time has come
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Due {
+    pub session_id: SessionId,
+    /// Index into `[escalation] steps`
+    pub step: usize,
+    /// How long the attention has gone unacknowledged
+    pub waited: Duration,
+}
+
+/// Attention being watched for escalation
+#[derive(Debug)]
+struct Pending {
+    reason: AttentionReason,
+    since: Instant,
+    /// Steps already fired
+    fired: usize,
+}
+
+/// How far each unacknowledged session has escalated
+///
+/// Kept in memory only: after a restart a session still waiting starts over
+/// from the first step, which errs on the side of telling the user again.
+#[derive(Debug, Default)]
+pub struct Escalations {
+    pending: HashMap<SessionId, Pending>,
+}
+
+impl Escalations {
+    /// The steps due now, given every session currently waiting for the user
+    /// on a reason the config covers
+    ///
+    /// Sessions missing from `waiting` have been acknowledged and are
+    /// forgotten. When several steps fall due at once - the event loop was
+    /// blocked, or the steps share a time - only the last of them fires.
+    pub fn due(
+        &mut self,
+        config: &EscalationConfig,
+        waiting: &[(SessionId, AttentionReason)],
+        now: Instant,
+    ) -> Vec<Due> {
+        self.pending
+            .retain(|id, _| waiting.iter().any(|(waiting_id, _)| waiting_id == id));
+
+        let mut due = Vec::new();
+        for (session_id, reason) in waiting {
+            let pending = self.pending.entry(*session_id).or_insert_with(|| Pending {
+                reason: reason.clone(),
+                since: now,
+                fired: 0,
+            });
+            if pending.reason != *reason {
+                *pending = Pending {
+                    reason: reason.clone(),
+                    since: now,
+                    fired: 0,
+                };
+            }
+
+            let waited = now.saturating_duration_since(pending.since);
+            let reached = config
+                .steps
+                .iter()
+                .take_while(|step| waited >= Duration::from_secs(step.after_mins * 60))
+                .count();
+            if reached > pending.fired {
+                pending.fired = reached;
+                due.push(Due {
+                    session_id: *session_id,
+                    step: reached - 1,
+                    waited,
+                });
+            }
+        }
+        due
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(after_mins: u64) -> EscalationStep {
+        EscalationStep {
+            after_mins,
+            method: None,
+            command: false,
+            webhooks: false,
+        }
+    }
+
+    fn config() -> EscalationConfig {
+        EscalationConfig {
+            steps: vec![step(5), step(15)],
+            ..Default::default()
+        }
+    }
+
+    fn mins(n: u64) -> Duration {
+        Duration::from_secs(n * 60)
+    }
+
+    #[test]
+    fn test_steps_fire_once_each_as_time_passes() {
+        let config = config();
+        let id = SessionId::new_v4();
+        let waiting = vec![(id, AttentionReason::Approval { tool: None })];
+        let start = Instant::now();
+        let mut escalations = Escalations::default();
+
+        assert!(escalations.due(&config, &waiting, start).is_empty());
+        assert!(escalations
+            .due(&config, &waiting, start + mins(4))
+            .is_empty());
+
+        let due = escalations.due(&config, &waiting, start + mins(5));
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].step, 0);
+        assert_eq!(due[0].waited, mins(5));
+        assert!(escalations
+            .due(&config, &waiting, start + mins(6))
+            .is_empty());
+
+        let due = escalations.due(&config, &waiting, start + mins(15));
+        assert_eq!(due[0].step, 1);
+        assert!(escalations
+            .due(&config, &waiting, start + mins(60))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_missed_steps_collapse_into_the_latest() {
+        let config = config();
+        let id = SessionId::new_v4();
+        let waiting = vec![(
+            id,
+            AttentionReason::Crashed {
+                reason: "signal 9".to_string(),
+            },
+        )];
+        let start = Instant::now();
+        let mut escalations = Escalations::default();
+        escalations.due(&config, &waiting, start);
+
+        let due = escalations.due(&config, &waiting, start + mins(20));
+
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].step, 1);
+    }
+
+    #[test]
+    fn test_acknowledging_or_a_new_reason_starts_over() {
+        let config = config();
+        let id = SessionId::new_v4();
+        let bash = vec![(
+            id,
+            AttentionReason::Approval {
+                tool: Some("Bash".to_string()),
+            },
+        )];
+        let start = Instant::now();
+        let mut escalations = Escalations::default();
+        escalations.due(&config, &bash, start);
+
+        // A different dialog is a fresh wait
+        let edit = vec![(
+            id,
+            AttentionReason::Approval {
+                tool: Some("Edit".to_string()),
+            },
+        )];
+        assert!(escalations.due(&config, &edit, start + mins(10)).is_empty());
+        assert!(escalations.due(&config, &edit, start + mins(14)).is_empty());
+        assert_eq!(escalations.due(&config, &edit, start + mins(15)).len(), 1);
+
+        // Acknowledged, then asked again
+        escalations.due(&config, &[], start + mins(16));
+        escalations.due(&config, &edit, start + mins(17));
+        assert!(escalations.due(&config, &edit, start + mins(21)).is_empty());
+        assert_eq!(escalations.due(&config, &edit, start + mins(22)).len(), 1);
+    }
+
+    #[test]
+    fn test_covers_only_the_named_reasons_and_only_with_steps() {
+        let config = config();
+        assert!(config.covers(&AttentionReason::Approval { tool: None }));
+        assert!(!config.covers(&AttentionReason::TurnComplete));
+        assert!(!EscalationConfig::default().covers(&AttentionReason::Approval { tool: None }));
+    }
+}
@@ -0,0 +1,138 @@
+//! Telling the user a session wants them
+//!
+//! [`send`] delivers one [`Notification`] by the configured
+//! [`NotificationMethod`]. The bell and the title rewrite only work while the
+//! terminal is in front of you; the desktop and OSC methods raise a native
+//! notification that reaches you on another workspace too. [`webhook`]
+//! reaches you when you are not at the machine at all.
+
+pub mod command;
+#[cfg(unix)]
+pub mod dbus;
+pub mod escalation;
+pub mod osc;
+pub mod rules;
+pub mod webhook;
+
+use std::io::Write;
+
+use crate::config::NotificationMethod;
+
+/// What a notification says: which session, why, and where it lives
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notification {
+    /// Session name
+    pub session: String,
+    /// Why it wants attention, e.g. [`crate::session::AttentionReason::summary`]
+    pub summary: String,
+    /// Owning project's name, when it is still known
+    pub project: Option<String>,
+    /// Owning branch's name, when it is still known
+    pub branch: Option<String>,
+    /// The agent's last reply, when it has made one
+    pub last_message: Option<String>,
+}
+
+impl Notification {
+    /// The headline: which session
+    pub fn title(&self) -> String {
+        format!("Panoptes: {}", self.session)
+    }
+
+    /// The detail: why, then where
+    pub fn body(&self) -> String {
+        match self.place() {
+            Some(place) => format!("{}\n{}", self.summary, place),
+            None => self.summary.clone(),
+        }
+    }
+
+    /// `project / branch`, or whichever half is known
+    fn place(&self) -> Option<String> {
+        match (&self.project, &self.branch) {
+            (Some(project), Some(branch)) => Some(format!("{} / {}", project, branch)),
+            (Some(name), None) | (None, Some(name)) => Some(name.clone()),
+            (None, None) => None,
+        }
+    }
+}
+
+/// Send a notification via the configured method
+pub fn send(method: NotificationMethod, notification: &Notification) {
+    match method {
+        NotificationMethod::Bell => {
+            print!("\x07"); // ASCII bell character
+            std::io::stdout().flush().ok();
+        }
+        NotificationMethod::Title => {
+            // Update terminal title using OSC escape sequence
+            // Format: ESC ] 0 ; title BEL
+            print!("\x1b]0;[!] {} needs attention\x07", notification.session);
+            std::io::stdout().flush().ok();
+        }
+        NotificationMethod::Desktop => send_desktop(notification),
+        NotificationMethod::Osc => {
+            let seq = osc::sequence(
+                osc::Flavor::detect(),
+                &notification.title(),
+                &notification.body(),
+            );
+            print!("{}", seq);
+            std::io::stdout().flush().ok();
+        }
+        NotificationMethod::None => {
+            // Do nothing
+        }
+    }
+}
+
+/// Raise a freedesktop notification on a worker thread
+///
+/// The bus round trip is quick but not free, and a wedged notification daemon
+/// would otherwise stall the event loop for the whole timeout. A failure is
+/// only logged: falling back to the bell from another thread could land its
+/// byte in the middle of a frame being drawn.
+#[cfg(unix)]
+fn send_desktop(notification: &Notification) {
+    let (summary, body) = (notification.title(), notification.body());
+    std::thread::spawn(move || {
+        let sent =
+            dbus::session_bus_address().and_then(|address| dbus::notify(&address, &summary, &body));
+        if let Err(e) = sent {
+            tracing::warn!("Desktop notification failed: {:#}", e);
+        }
+    });
+}
+
+#[cfg(not(unix))]
+fn send_desktop(_notification: &Notification) {
+    tracing::warn!("Desktop notifications need a D-Bus session bus; none on this platform");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn notification(project: Option<&str>, branch: Option<&str>) -> Notification {
+        Notification {
+            session: "api-fix".to_string(),
+            summary: "approve Bash".to_string(),
+            project: project.map(str::to_string),
+            branch: branch.map(str::to_string),
+            last_message: None,
+        }
+    }
+
+    #[test]
+    fn test_body_names_project_and_branch() {
+        let n = notification(Some("web"), Some("main"));
+        assert_eq!(n.title(), "Panoptes: api-fix");
+        assert_eq!(n.body(), "approve Bash\nweb / main");
+    }
+
+    #[test]
+    fn test_body_without_a_known_place_is_just_the_summary() {
+        assert_eq!(notification(None, None).body(), "approve Bash");
+        assert_eq!(notification(Some("web"), None).body(), "approve Bash\nweb");
+    }
+}
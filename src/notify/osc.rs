@@ -0,0 +1,105 @@
+//! Native terminal notifications through OSC escape sequences
+//!
+//! Two competing sequences ask the terminal itself to raise a desktop
+//! notification, which reaches the user even when the terminal is on another
+//! workspace and its bell goes unheard:
+//! - OSC 9 (`ESC ] 9 ; text BEL`), from iTerm2 and also understood by kitty,
+//!   WezTerm, Ghostty and Windows Terminal
+//! - OSC 777 (`ESC ] 777 ; notify ; title ; body BEL`), from rxvt-unicode and
+//!   understood by foot, WezTerm and Ghostty
+//!
+//! Only one is sent. Terminals that speak both would show the notification
+//! twice, so OSC 777 is reserved for the terminals that speak nothing else.
+
+/// Which notification sequence the terminal understands
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flavor {
+    Osc9,
+    Osc777,
+}
+
+impl Flavor {
+    /// Pick the sequence for the terminal named by `TERM`
+    pub fn detect() -> Self {
+        Self::for_term(std::env::var("TERM").ok().as_deref())
+    }
+
+    fn for_term(term: Option<&str>) -> Self {
+        match term {
+            Some(term) if term.starts_with("rxvt") || term.starts_with("foot") => Self::Osc777,
+            _ => Self::Osc9,
+        }
+    }
+}
+
+/// The escape sequence raising a notification with this title and body
+pub fn sequence(flavor: Flavor, title: &str, body: &str) -> String {
+    match flavor {
+        // One line of text: the title leads, as it does in a desktop popup
+        Flavor::Osc9 => format!("\x1b]9;{}: {}\x07", clean(title), clean(body)),
+        // `;` separates the fields, so the title cannot contain one; the body
+        // is the last field and runs to the terminator
+        Flavor::Osc777 => format!(
+            "\x1b]777;notify;{};{}\x07",
+            clean(title).replace(';', ","),
+            clean(body)
+        ),
+    }
+}
+
+/// Flatten text for an escape sequence's payload
+///
+/// A control character would end the sequence early - BEL and ESC are its
+/// terminators - and let whatever follows reach the terminal as raw input.
+/// Newlines become spaces rather than vanishing, so words stay apart.
+fn clean(text: &str) -> String {
+    text.chars()
+        .filter_map(|c| match c {
+            '\n' | '\t' => Some(' '),
+            c if c.is_control() => None,
+            c => Some(c),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_osc9_carries_title_and_body_on_one_line() {
+        assert_eq!(
+            sequence(Flavor::Osc9, "Panoptes: api", "approve Bash\nweb / main"),
+            "\x1b]9;Panoptes: api: approve Bash web / main\x07"
+        );
+    }
+
+    #[test]
+    fn test_osc777_keeps_semicolons_out_of_the_title() {
+        assert_eq!(
+            sequence(Flavor::Osc777, "a;b", "turn complete; web"),
+            "\x1b]777;notify;a,b;turn complete; web\x07"
+        );
+    }
+
+    /// Text from an agent must not be able to close the sequence and write
+    /// its own escapes to the terminal
+    #[test]
+    fn test_control_characters_cannot_end_the_sequence() {
+        let seq = sequence(Flavor::Osc9, "x\x07", "crashed: \x1b]0;owned\x07");
+        assert_eq!(seq.matches('\x07').count(), 1);
+        assert_eq!(seq.matches('\x1b').count(), 1);
+    }
+
+    #[test]
+    fn test_flavor_follows_term() {
+        assert_eq!(
+            Flavor::for_term(Some("rxvt-unicode-256color")),
+            Flavor::Osc777
+        );
+        assert_eq!(Flavor::for_term(Some("foot")), Flavor::Osc777);
+        assert_eq!(Flavor::for_term(Some("xterm-kitty")), Flavor::Osc9);
+        assert_eq!(Flavor::for_term(Some("wezterm")), Flavor::Osc9);
+        assert_eq!(Flavor::for_term(None), Flavor::Osc9);
+    }
+}
@@ -0,0 +1,203 @@
+//! Which notifications stay silent, beyond which reasons ring
+//!
+//! `[notify_on]`'s booleans say which *reasons* are worth interrupting for.
+//! Background experiments still interrupt as much as production work that
+//! way, so the rules here narrow it by *when* and by *which session*:
+//!
+//! ```toml
+//! [notify_on]
+//! do_not_disturb = false   # toggled live from Settings or Ctrl+P
+//! recent_hours = 8         # only sessions started in the last 8 hours
+//!
+//! [notify_on.quiet_hours]
+//! start = "22:00"
+//! end = "07:30"
+//!
+//! [notify_on.projects."scratch"]
+//! mute = true
+//!
+//! [notify_on.folders."Acme/Platform"]
+//! only = ["approval", "crashed"]
+//! ```
+//!
+//! Rules only ever silence. A session they silence still raises its badge in
+//! the session list - nothing is lost, it just does not reach out for you.
+
+use chrono::{DateTime, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::session::AttentionReason;
+
+/// `[notify_on.quiet_hours]`: a daily window, in local time, with no
+/// notifications
+///
+/// Times are `"HH:MM"` strings rather than parsed on load, so a typo costs
+/// the quiet hours and not the whole config file. [`QuietHours::problem`]
+/// names it once, when the config is loaded or reloaded.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuietHours {
+    pub start: String,
+    pub end: String,
+}
+
+impl QuietHours {
+    /// Whether `now` falls inside the window
+    ///
+    /// A window whose end comes before its start runs through midnight. A
+    /// window with a malformed time contains nothing.
+    pub fn contains(&self, now: NaiveTime) -> bool {
+        let (Some(start), Some(end)) = (parse_time(&self.start), parse_time(&self.end)) else {
+            return false;
+        };
+        if start <= end {
+            start <= now && now < end
+        } else {
+            now >= start || now < end
+        }
+    }
+
+    /// What is wrong with the window, for the startup notice, or `None` when
+    /// both times parse
+    pub fn problem(&self) -> Option<String> {
+        let bad: Vec<&str> = [&self.start, &self.end]
+            .into_iter()
+            .filter(|time| parse_time(time).is_none())
+            .map(String::as_str)
+            .collect();
+        (!bad.is_empty()).then(|| {
+            format!(
+                "Ignoring [notify_on.quiet_hours]: '{}' is not an HH:MM time.",
+                bad.join("' and '")
+            )
+        })
+    }
+}
+
+fn parse_time(text: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(text.trim(), "%H:%M").ok()
+}
+
+/// A `[notify_on.projects."name"]` or `[notify_on.folders."path"]` rule
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotifyRule {
+    /// Never notify for sessions here
+    #[serde(default)]
+    pub mute: bool,
+    /// Notify only for these reasons, named as in `[notify_on]`; empty means
+    /// every reason that rings
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub only: Vec<String>,
+}
+
+impl NotifyRule {
+    /// Whether this rule silences a notification for `reason`
+    pub fn silences(&self, reason: &AttentionReason) -> bool {
+        self.mute || (!self.only.is_empty() && !self.only.iter().any(|r| r == reason.key()))
+    }
+}
+
+/// The session a notification is about, as the rules see it
+#[derive(Debug, Clone, Copy)]
+pub struct NotifyTarget<'a> {
+    /// Its project's name, if the project still exists
+    pub project: Option<&'a str>,
+    /// The folder its project is filed under; empty at the root
+    pub folder: &'a [String],
+    /// The `[notify]` rule from its project's `.panoptes.toml`, which a
+    /// `[notify_on.projects]` entry for the project replaces
+    pub repo_rule: Option<&'a NotifyRule>,
+    /// When the session was created
+    pub created_at: DateTime<Utc>,
+}
+
+/// Whether a `[notify_on.folders]` key names `folder` or one of its parents
+///
+/// The key is the folder path as the projects overview shows it,
+/// `/`-separated, so `"Acme"` covers `Acme/Platform` too.
+pub fn folder_matches(key: &str, folder: &[String]) -> bool {
+    let segments: Vec<&str> = key
+        .split('/')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .collect();
+    !segments.is_empty()
+        && segments.len() <= folder.len()
+        && segments.iter().zip(folder).all(|(a, b)| *a == b.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(text: &str) -> NaiveTime {
+        parse_time(text).unwrap()
+    }
+
+    fn quiet(start: &str, end: &str) -> QuietHours {
+        QuietHours {
+            start: start.to_string(),
+            end: end.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_quiet_hours_within_one_day() {
+        let lunch = quiet("12:00", "13:00");
+        assert!(lunch.contains(time("12:00")));
+        assert!(lunch.contains(time("12:59")));
+        assert!(!lunch.contains(time("13:00")));
+        assert!(!lunch.contains(time("11:59")));
+    }
+
+    #[test]
+    fn test_quiet_hours_through_midnight() {
+        let night = quiet("22:00", "07:30");
+        assert!(night.contains(time("23:15")));
+        assert!(night.contains(time("00:00")));
+        assert!(night.contains(time("07:29")));
+        assert!(!night.contains(time("07:30")));
+        assert!(!night.contains(time("12:00")));
+    }
+
+    /// A typo costs the quiet hours, never a notification that should ring
+    #[test]
+    fn test_malformed_quiet_hours_silence_nothing() {
+        let typo = quiet("10pm", "07:00");
+        assert!(!typo.contains(time("23:00")));
+        assert!(typo.problem().unwrap().contains("'10pm'"));
+        assert_eq!(quiet("22:00", "07:30").problem(), None);
+    }
+
+    #[test]
+    fn test_rule_mute_and_only() {
+        let approval = AttentionReason::Approval { tool: None };
+        let done = AttentionReason::TurnComplete;
+
+        let muted = NotifyRule {
+            mute: true,
+            only: Vec::new(),
+        };
+        assert!(muted.silences(&approval));
+
+        let only_approval = NotifyRule {
+            mute: false,
+            only: vec!["approval".to_string()],
+        };
+        assert!(!only_approval.silences(&approval));
+        assert!(only_approval.silences(&done));
+
+        assert!(!NotifyRule::default().silences(&done));
+    }
+
+    #[test]
+    fn test_folder_key_covers_its_subfolders() {
+        let folder = vec!["Acme".to_string(), "Platform".to_string()];
+        assert!(folder_matches("Acme", &folder));
+        assert!(folder_matches("Acme/Platform", &folder));
+        assert!(folder_matches(" Acme / Platform ", &folder));
+        assert!(!folder_matches("Acme/Platform/Infra", &folder));
+        assert!(!folder_matches("Platform", &folder));
+        assert!(!folder_matches("", &folder));
+        assert!(!folder_matches("Acme", &[]));
+    }
+}
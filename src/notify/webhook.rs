@@ -0,0 +1,409 @@
+//! Outgoing webhooks: a POST to a URL when a session wants the user
+//!
+//! Meant for the user who is not at the terminal at all - a phone ping from
+//! ntfy, a Slack message, a Home Assistant automation. Each `[[webhooks]]`
+//! entry is fired for every reason `[notify_on]` rings, or only the reasons
+//! its `on` list names:
+//!
+//! ```toml
+//! [[webhooks]]
+//! url = "https://ntfy.sh/my-panoptes"
+//! on = ["approval", "crashed"]
+//! content_type = "text/plain"
+//! template = "{session}: {summary} ({project} / {branch})"
+//! ```
+//!
+//! Without a `template` the body is a JSON object carrying every field, with
+//! a `text` key so Slack-compatible incoming webhooks take it as it is. A
+//! template's `{placeholder}`s are JSON-escaped when `content_type` is JSON,
+//! so `{"text": "{summary}"}` stays valid whatever the agent said.
+//!
+//! Unlike the other notification methods, a webhook fires for the session on
+//! screen too: the screen being open says nothing about anyone watching it.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+use super::Notification;
+use crate::session::AttentionReason;
+
+/// Per-attempt timeout for a POST
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Pause before each retry of a failed delivery
+const RETRY_DELAYS: [Duration; 3] = [
+    Duration::from_secs(2),
+    Duration::from_secs(10),
+    Duration::from_secs(30),
+];
+
+/// One `[[webhooks]]` entry in `config.toml`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebhookConfig {
+    /// Where to POST
+    pub url: String,
+    /// Reasons that fire this webhook, named as in `[notify_on]`; empty means
+    /// every reason that rings
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub on: Vec<String>,
+    /// Request body with `{placeholder}`s; the JSON default when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    /// `Content-Type` of the request
+    #[serde(default = "default_content_type")]
+    pub content_type: String,
+    /// Extra request headers, e.g. an `Authorization` token
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+}
+
+fn default_content_type() -> String {
+    "application/json".to_string()
+}
+
+impl WebhookConfig {
+    /// Whether this webhook wants to hear about this reason
+    pub fn fires_on(&self, reason: &AttentionReason) -> bool {
+        self.on.is_empty() || self.on.iter().any(|name| name == reason.key())
+    }
+
+    /// The request body for one notification
+    pub fn payload(&self, reason: &AttentionReason, notification: &Notification) -> String {
+        let fields = Fields::new(reason, notification);
+        match &self.template {
+            Some(template) => render(template, &fields, self.content_type.contains("json")),
+            None => serde_json::json!({
+                "text": format!("{}: {}", fields.title, fields.body.replace('\n', " · ")),
+                "title": fields.title,
+                "message": fields.body,
+                "session": fields.session,
+                "reason": fields.reason,
+                "summary": fields.summary,
+                "project": fields.project,
+                "branch": fields.branch,
+            })
+            .to_string(),
+        }
+    }
+}
+
+/// Fire every webhook that wants this reason, each on its own worker thread
+///
+/// Delivery retries with backoff, so it can take the better part of a minute
+/// and must never hold up the event loop. A webhook that still fails after
+/// its last retry is logged and dropped.
+pub fn send(webhooks: &[WebhookConfig], reason: &AttentionReason, notification: &Notification) {
+    for webhook in webhooks.iter().filter(|w| w.fires_on(reason)) {
+        let webhook = webhook.clone();
+        let body = webhook.payload(reason, notification);
+        std::thread::spawn(move || {
+            if let Err(e) = deliver(&webhook, &body, &RETRY_DELAYS) {
+                tracing::warn!("Webhook to {} failed: {:#}", webhook.url, e);
+            }
+        });
+    }
+}
+
+/// POST `body`, retrying transport failures, 429s and 5xx responses after
+/// each of `delays` in turn
+///
+/// Any other 4xx is the request's own fault and would fail the same way
+/// again, so it is returned at once.
+fn deliver(webhook: &WebhookConfig, body: &str, delays: &[Duration]) -> Result<()> {
+    let agent = ureq::AgentBuilder::new().timeout(TIMEOUT).build();
+    let mut delays = delays.iter();
+    loop {
+        let mut request = agent
+            .post(&webhook.url)
+            .set("Content-Type", &webhook.content_type);
+        for (name, value) in &webhook.headers {
+            request = request.set(name, value);
+        }
+        let error = match request.send_string(body) {
+            Ok(_) => return Ok(()),
+            Err(ureq::Error::Status(code, _)) if code != 429 && code < 500 => {
+                return Err(anyhow!("server answered {}", code))
+            }
+            Err(ureq::Error::Status(code, _)) => anyhow!("server answered {}", code),
+            Err(ureq::Error::Transport(e)) => anyhow!(e),
+        };
+        match delays.next() {
+            Some(delay) => {
+                tracing::debug!("Webhook to {} failed ({}); retrying", webhook.url, error);
+                std::thread::sleep(*delay);
+            }
+            None => return Err(error),
+        }
+    }
+}
+
+/// The values a template can refer to
+struct Fields {
+    session: String,
+    reason: &'static str,
+    summary: String,
+    project: String,
+    branch: String,
+    title: String,
+    body: String,
+}
+
+impl Fields {
+    fn new(reason: &AttentionReason, notification: &Notification) -> Self {
+        Self {
+            session: notification.session.clone(),
+            reason: reason.key(),
+            summary: notification.summary.clone(),
+            project: notification.project.clone().unwrap_or_default(),
+            branch: notification.branch.clone().unwrap_or_default(),
+            title: notification.title(),
+            body: notification.body(),
+        }
+    }
+
+    fn get(&self, name: &str) -> Option<&str> {
+        Some(match name {
+            "session" => &self.session,
+            "reason" => self.reason,
+            "summary" => &self.summary,
+            "project" => &self.project,
+            "branch" => &self.branch,
+            "title" => &self.title,
+            "body" => &self.body,
+            _ => return None,
+        })
+    }
+}
+
+/// Substitute `{name}` placeholders in one pass, so a value that happens to
+/// contain `{branch}` is not substituted again
+///
+/// Anything in braces that is not a known name - the braces of a JSON
+/// template, say - is left exactly as written.
+fn render(template: &str, fields: &Fields, json: bool) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        out.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+        let value = after
+            .find('}')
+            .and_then(|close| Some((close, fields.get(&after[..close])?)));
+        match value {
+            Some((close, value)) => {
+                if json {
+                    out.push_str(&json_escape(value));
+                } else {
+                    out.push_str(value);
+                }
+                rest = &after[close + 1..];
+            }
+            None => {
+                out.push('{');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// A string's JSON encoding without the surrounding quotes
+fn json_escape(value: &str) -> String {
+    let quoted = serde_json::Value::from(value).to_string();
+    quoted[1..quoted.len() - 1].to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+
+    fn notification() -> Notification {
+        Notification {
+            session: "api-fix".to_string(),
+            summary: "approve Bash".to_string(),
+            project: Some("web".to_string()),
+            branch: Some("main".to_string()),
+            last_message: None,
+        }
+    }
+
+    fn approval() -> AttentionReason {
+        AttentionReason::Approval {
+            tool: Some("Bash".to_string()),
+        }
+    }
+
+    fn webhook(url: String) -> WebhookConfig {
+        WebhookConfig {
+            url,
+            on: Vec::new(),
+            template: None,
+            content_type: default_content_type(),
+            headers: BTreeMap::new(),
+        }
+    }
+
+    /// A stand-in HTTP server answering successive requests with `statuses`,
+    /// returning each request's head and body
+    fn stand_in(
+        statuses: &'static [u16],
+    ) -> (String, std::thread::JoinHandle<Vec<(String, String)>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            let mut requests = Vec::new();
+            for status in statuses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(&stream);
+                let mut head = String::new();
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line == "\r\n" {
+                        break;
+                    }
+                    head.push_str(&line);
+                }
+                let length = head
+                    .lines()
+                    .find_map(|l| {
+                        l.to_ascii_lowercase()
+                            .strip_prefix("content-length:")
+                            .map(|v| v.trim().parse::<usize>().unwrap())
+                    })
+                    .unwrap_or(0);
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).unwrap();
+                (&stream)
+                    .write_all(
+                        format!(
+                            "HTTP/1.1 {status} X\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                        )
+                        .as_bytes(),
+                    )
+                    .unwrap();
+                requests.push((head, String::from_utf8(body).unwrap()));
+            }
+            requests
+        });
+        (url, server)
+    }
+
+    #[test]
+    fn test_default_payload_posts_json_with_every_field() {
+        let (url, server) = stand_in(&[200]);
+        let hook = webhook(url);
+
+        deliver(&hook, &hook.payload(&approval(), &notification()), &[]).unwrap();
+
+        let requests = server.join().unwrap();
+        let (head, body) = &requests[0];
+        assert!(head.starts_with("POST /hook "), "{head}");
+        assert!(head
+            .to_ascii_lowercase()
+            .contains("content-type: application/json"));
+        let json: serde_json::Value = serde_json::from_str(body).unwrap();
+        assert_eq!(json["reason"], "approval");
+        assert_eq!(json["session"], "api-fix");
+        assert_eq!(json["project"], "web");
+        assert_eq!(json["branch"], "main");
+        assert_eq!(json["text"], "Panoptes: api-fix: approve Bash · web / main");
+    }
+
+    #[test]
+    fn test_delivery_retries_server_errors_then_succeeds() {
+        let (url, server) = stand_in(&[503, 500, 200]);
+        let hook = webhook(url);
+
+        deliver(
+            &hook,
+            "{}",
+            &[Duration::ZERO, Duration::ZERO, Duration::ZERO],
+        )
+        .unwrap();
+
+        assert_eq!(server.join().unwrap().len(), 3);
+    }
+
+    /// A 4xx other than 429 would fail the same way again
+    #[test]
+    fn test_delivery_gives_up_at_once_on_a_client_error() {
+        let (url, server) = stand_in(&[404]);
+        let hook = webhook(url);
+
+        let error = deliver(&hook, "{}", &[Duration::ZERO]).unwrap_err();
+
+        assert!(error.to_string().contains("404"), "{error}");
+        assert_eq!(server.join().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_delivery_fails_after_the_last_retry() {
+        let (url, server) = stand_in(&[500, 500]);
+        let hook = webhook(url);
+
+        assert!(deliver(&hook, "{}", &[Duration::ZERO]).is_err());
+        assert_eq!(server.join().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_headers_are_sent() {
+        let (url, server) = stand_in(&[200]);
+        let mut hook = webhook(url);
+        hook.headers
+            .insert("Authorization".to_string(), "Bearer tk_1".to_string());
+
+        deliver(&hook, "{}", &[]).unwrap();
+
+        let (head, _) = &server.join().unwrap()[0];
+        assert!(head.contains("Authorization: Bearer tk_1"), "{head}");
+    }
+
+    #[test]
+    fn test_on_filters_reasons_by_notify_on_name() {
+        let mut hook = webhook("http://unused".to_string());
+        assert!(hook.fires_on(&AttentionReason::TurnComplete));
+
+        hook.on = vec!["approval".to_string(), "crashed".to_string()];
+        assert!(hook.fires_on(&approval()));
+        assert!(hook.fires_on(&AttentionReason::Crashed {
+            reason: "signal 9".to_string()
+        }));
+        assert!(!hook.fires_on(&AttentionReason::TurnComplete));
+    }
+
+    /// A JSON template stays valid JSON whatever the agent's text contains
+    #[test]
+    fn test_json_template_escapes_values() {
+        let mut hook = webhook("http://unused".to_string());
+        hook.template = Some(r#"{"text": "{session} {summary}", "tag": "{nope}"}"#.to_string());
+        let mut n = notification();
+        n.summary = "crashed: \"quoted\"\nline".to_string();
+
+        let body = hook.payload(&approval(), &n);
+
+        let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(json["text"], "api-fix crashed: \"quoted\"\nline");
+        assert_eq!(json["tag"], "{nope}");
+    }
+
+    #[test]
+    fn test_plain_template_substitutes_once() {
+        let mut hook = webhook("http://unused".to_string());
+        hook.content_type = "text/plain".to_string();
+        hook.template = Some("{session} on {branch}: {summary}".to_string());
+        let mut n = notification();
+        n.session = "{branch}".to_string();
+
+        assert_eq!(
+            hook.payload(&approval(), &n),
+            "{branch} on main: approve Bash"
+        );
+    }
+}
@@ -0,0 +1,199 @@
+//! Model pricing, for turning tokens into an estimated spend
+//!
+//! Prices are US dollars per million tokens. A built-in table covers the
+//! model families the agents ship with, and the `[pricing]` tables of
+//! `config.toml` add to it or override it:
+//!
+//! ```toml
+//! [pricing."claude-sonnet"]
+//! input = 3.0
+//! output = 15.0
+//! cache_write = 3.75
+//! cache_read = 0.3
+//! ```
+//!
+//! A table's name is matched anywhere in the model's identifier, and the
+//! longest match wins, so `opus-4-1` can be priced apart from `opus` in
+//! general. A configured entry beats a built-in one of any length.
+//!
+//! Everything here is an estimate: list prices change, and long-context and
+//! batch pricing are not modelled. A model nothing matches is left unpriced
+//! rather than guessed at, and reports say how many tokens that left out.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::agent::events::TokenCounts;
+
+/// The `[pricing]` section of `config.toml`: model pattern, then its prices
+pub type PricingConfig = BTreeMap<String, ModelPrice>;
+
+/// What a model charges, in dollars per million tokens
+///
+/// The cache prices default to the input price when omitted, which can only
+/// overstate a bill - the safer mistake when the point is staying under a
+/// budget.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ModelPrice {
+    pub input: f64,
+    pub output: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_write: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_read: Option<f64>,
+}
+
+impl ModelPrice {
+    const fn new(input: f64, output: f64, cache_write: f64, cache_read: f64) -> Self {
+        Self {
+            input,
+            output,
+            cache_write: Some(cache_write),
+            cache_read: Some(cache_read),
+        }
+    }
+
+    /// The dollar cost of `tokens` at these prices
+    pub fn cost(&self, tokens: &TokenCounts) -> f64 {
+        let per = |count: u64, price: f64| count as f64 * price / 1_000_000.0;
+        per(tokens.input, self.input)
+            + per(tokens.output, self.output)
+            + per(tokens.cache_write, self.cache_write.unwrap_or(self.input))
+            + per(tokens.cache_read, self.cache_read.unwrap_or(self.input))
+    }
+}
+
+/// List prices at the time of writing, most specific first within a family
+const BUILT_IN: &[(&str, ModelPrice)] = &[
+    ("opus-4-1", ModelPrice::new(15.0, 75.0, 18.75, 1.5)),
+    ("opus-4-2025", ModelPrice::new(15.0, 75.0, 18.75, 1.5)),
+    ("opus", ModelPrice::new(5.0, 25.0, 6.25, 0.5)),
+    ("sonnet", ModelPrice::new(3.0, 15.0, 3.75, 0.3)),
+    ("haiku", ModelPrice::new(1.0, 5.0, 1.25, 0.1)),
+    ("gpt-5-mini", ModelPrice::new(0.25, 2.0, 0.25, 0.025)),
+    ("gpt-5-nano", ModelPrice::new(0.05, 0.4, 0.05, 0.005)),
+    ("gpt-5", ModelPrice::new(1.25, 10.0, 1.25, 0.125)),
+    ("o4-mini", ModelPrice::new(1.1, 4.4, 1.1, 0.275)),
+    ("o3", ModelPrice::new(2.0, 8.0, 2.0, 0.5)),
+];
+
+/// The price of `model`, from the configured table first and the built-in one
+/// after
+pub fn price_for(model: &str, configured: &PricingConfig) -> Option<ModelPrice> {
+    longest_match(
+        model,
+        configured
+            .iter()
+            .map(|(pattern, price)| (pattern.as_str(), price)),
+    )
+    .or_else(|| {
+        longest_match(
+            model,
+            BUILT_IN.iter().map(|(pattern, price)| (*pattern, price)),
+        )
+    })
+}
+
+fn longest_match<'a>(
+    model: &str,
+    table: impl Iterator<Item = (&'a str, &'a ModelPrice)>,
+) -> Option<ModelPrice> {
+    table
+        .filter(|(pattern, _)| !pattern.is_empty() && model.contains(pattern))
+        .max_by_key(|(pattern, _)| pattern.len())
+        .map(|(_, price)| *price)
+}
+
+/// The estimated cost of `tokens` spent on `model`, if the model is priced
+pub fn estimate(
+    model: Option<&str>,
+    tokens: &TokenCounts,
+    configured: &PricingConfig,
+) -> Option<f64> {
+    price_for(model?, configured).map(|price| price.cost(tokens))
+}
+
+/// `$0.42`, `$12.30`, `$1,204`
+pub fn format_dollars(amount: f64) -> String {
+    if amount < 1_000.0 {
+        return format!("${:.2}", amount);
+    }
+    let whole = format!("{:.0}", amount);
+    let mut grouped = String::new();
+    for (i, c) in whole.chars().enumerate() {
+        if i > 0 && (whole.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(c);
+    }
+    format!("${}", grouped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_the_longest_matching_pattern_prices_the_model() {
+        let none = PricingConfig::new();
+        assert_eq!(
+            price_for("claude-opus-4-1-20250805", &none).map(|p| p.input),
+            Some(15.0)
+        );
+        assert_eq!(
+            price_for("claude-opus-4-8", &none).map(|p| p.input),
+            Some(5.0)
+        );
+        assert_eq!(price_for("gpt-5-codex", &none).map(|p| p.input), Some(1.25));
+        assert_eq!(price_for("gpt-5-mini", &none).map(|p| p.input), Some(0.25));
+        assert_eq!(price_for("some-future-model", &none), None);
+    }
+
+    #[test]
+    fn test_configured_prices_beat_built_in_ones() {
+        let mut configured = PricingConfig::new();
+        configured.insert(
+            "sonnet".to_string(),
+            ModelPrice {
+                input: 2.0,
+                output: 10.0,
+                cache_write: None,
+                cache_read: None,
+            },
+        );
+        let price = price_for("claude-sonnet-4-5", &configured).unwrap();
+        assert_eq!(price.input, 2.0);
+
+        // Cache prices left out fall back to the input price
+        let tokens = TokenCounts {
+            input: 1_000_000,
+            output: 1_000_000,
+            cache_write: 1_000_000,
+            cache_read: 1_000_000,
+        };
+        assert_eq!(price.cost(&tokens), 16.0);
+    }
+
+    #[test]
+    fn test_cost_prices_each_kind_separately() {
+        let tokens = TokenCounts {
+            input: 100_000,
+            output: 20_000,
+            cache_write: 0,
+            cache_read: 1_000_000,
+        };
+        let cost = estimate(Some("claude-sonnet-4-5"), &tokens, &PricingConfig::new()).unwrap();
+        // 0.30 input + 0.30 output + 0.30 cache reads
+        assert!((cost - 0.9).abs() < 1e-9, "{cost}");
+        assert_eq!(estimate(None, &tokens, &PricingConfig::new()), None);
+    }
+
+    #[test]
+    fn test_format_dollars() {
+        assert_eq!(format_dollars(0.4249), "$0.42");
+        assert_eq!(format_dollars(12.3), "$12.30");
+        assert_eq!(format_dollars(1204.4), "$1,204");
+        assert_eq!(format_dollars(1_234_567.0), "$1,234,567");
+    }
+}
@@ -0,0 +1,149 @@
+//! Child processes that are killed along with everything they start
+//!
+//! Notify commands, worktree setup commands and `git push` can all outlive
+//! their welcome: a hung script, a dev server left running in the
+//! background, a remote that never answers. Each runs as the leader of a
+//! process group of its own and is watched by [`run`], which kills the whole
+//! group at a timeout or on cancellation, so nothing it started is left
+//! behind.
+
+use std::io::Read;
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+/// How often a running process is checked for exit, timeout or cancellation
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// When to stop waiting for a process and kill its group
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Limits<'a> {
+    /// How long it may run
+    pub timeout: Option<Duration>,
+    /// Set from another thread to stop it early
+    pub cancel: Option<&'a AtomicBool>,
+}
+
+/// How a watched process ended
+#[derive(Debug)]
+pub enum Finished {
+    /// It exited by itself. `stderr` holds what it wrote there if it failed
+    /// and stderr was piped, and is empty otherwise
+    Exited { status: ExitStatus, stderr: String },
+    /// It was killed at the timeout
+    TimedOut,
+    /// It was killed because `cancel` was set
+    Cancelled,
+}
+
+/// `sh -c command`, with nothing on stdin and stdout going nowhere
+///
+/// Stdout belongs to the TUI: anything written there would land in the
+/// middle of the next frame. Stderr is left for the caller to route.
+pub fn shell(command: &str) -> Command {
+    let mut shell = Command::new("sh");
+    shell
+        .arg("-c")
+        .arg(command)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null());
+    shell
+}
+
+/// Start `command` as the leader of a process group and wait for it
+///
+/// A piped stderr is drained on its own thread, so a chatty process cannot
+/// fill the pipe and stall while this polls for its exit. It is only
+/// collected after a failure: on success a background process the command
+/// started may still hold the pipe open, and nothing needs the text.
+pub fn run(command: &mut Command, limits: Limits) -> std::io::Result<Finished> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        command.process_group(0);
+    }
+    let mut child = command.spawn()?;
+
+    let stderr_pipe = child.stderr.take();
+    let drain = std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        if let Some(mut pipe) = stderr_pipe {
+            let _ = pipe.read_to_end(&mut buf);
+        }
+        String::from_utf8_lossy(&buf).into_owned()
+    });
+
+    let deadline = limits.timeout.map(|timeout| Instant::now() + timeout);
+    loop {
+        if let Some(status) = child.try_wait()? {
+            let stderr = if status.success() {
+                String::new()
+            } else {
+                drain.join().unwrap_or_default()
+            };
+            return Ok(Finished::Exited { status, stderr });
+        }
+        let stopped = if limits.cancel.is_some_and(|c| c.load(Ordering::Relaxed)) {
+            Some(Finished::Cancelled)
+        } else if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            Some(Finished::TimedOut)
+        } else {
+            None
+        };
+        if let Some(finished) = stopped {
+            kill_group(&mut child);
+            let _ = child.wait();
+            return Ok(finished);
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Kill a process started by [`run`] and every process in its group
+#[cfg(unix)]
+fn kill_group(child: &mut Child) {
+    // The group id is the leader's pid, given `process_group(0)`
+    let pgid = child.id() as libc::pid_t;
+    if unsafe { libc::killpg(pgid, libc::SIGKILL) } != 0 {
+        let _ = child.kill();
+    }
+}
+
+#[cfg(not(unix))]
+fn kill_group(child: &mut Child) {
+    let _ = child.kill();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_a_failure_brings_its_stderr() {
+        let mut command = shell("echo 'no such target' >&2; exit 3");
+        command.stderr(Stdio::piped());
+
+        match run(&mut command, Limits::default()).unwrap() {
+            Finished::Exited { status, stderr } => {
+                assert_eq!(status.code(), Some(3));
+                assert_eq!(stderr.trim(), "no such target");
+            }
+            other => panic!("{other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_cancel_wins_over_a_timeout_not_yet_reached() {
+        let cancel = AtomicBool::new(true);
+        let limits = Limits {
+            timeout: Some(Duration::from_secs(30)),
+            cancel: Some(&cancel),
+        };
+        let started = Instant::now();
+
+        let finished = run(&mut shell("sleep 5"), limits).unwrap();
+
+        assert!(matches!(finished, Finished::Cancelled), "{finished:?}");
+        assert!(started.elapsed() < Duration::from_secs(3));
+    }
+}
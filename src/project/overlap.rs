@@ -0,0 +1,214 @@
+//! Files touched by more than one branch of the same project
+//!
+//! The git half - what a branch changed, and which changes collide - lives in
+//! [`crate::git::overlap`]. This half decides which branches are compared and
+//! turns the result into something the panes can look up per branch.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver};
+
+use super::{BranchId, ProjectId, ProjectStore};
+
+/// One project's worth of branches to compare
+#[derive(Debug, Clone)]
+pub struct OverlapScan {
+    /// Project the branches belong to
+    pub project_id: ProjectId,
+    /// Ref each branch's changes are measured from (e.g. "main")
+    pub base_ref: String,
+    /// Branch and the directory its checkout lives in
+    pub branches: Vec<(BranchId, PathBuf)>,
+}
+
+/// A file one branch changed that other branches changed too
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SharedFile {
+    /// Repository-relative path
+    pub path: String,
+    /// The other branches that also changed it
+    pub other_branches: Vec<BranchId>,
+}
+
+/// The latest overlap scan, looked up per branch
+#[derive(Debug, Clone, Default)]
+pub struct FileOverlaps {
+    by_branch: HashMap<BranchId, Vec<SharedFile>>,
+}
+
+impl FileOverlaps {
+    /// Files this branch shares with another branch, sorted by path
+    pub fn for_branch(&self, branch_id: BranchId) -> &[SharedFile] {
+        self.by_branch
+            .get(&branch_id)
+            .map(Vec::as_slice)
+            .unwrap_or(&[])
+    }
+
+    /// Number of files this branch shares with another branch
+    pub fn count_for_branch(&self, branch_id: BranchId) -> usize {
+        self.for_branch(branch_id).len()
+    }
+
+    /// Whether no branch shares a file with another
+    pub fn is_empty(&self) -> bool {
+        self.by_branch.is_empty()
+    }
+
+    /// Record the shared files of one project's branches
+    pub fn record_project(&mut self, overlaps: std::collections::BTreeMap<String, Vec<BranchId>>) {
+        for (path, owners) in overlaps {
+            for &branch_id in &owners {
+                self.by_branch
+                    .entry(branch_id)
+                    .or_default()
+                    .push(SharedFile {
+                        path: path.clone(),
+                        other_branches: owners
+                            .iter()
+                            .copied()
+                            .filter(|&other| other != branch_id)
+                            .collect(),
+                    });
+            }
+        }
+    }
+}
+
+/// The comparisons worth running for the projects in `store`
+///
+/// Only worktrees that still exist on disk are compared, and only projects
+/// with at least two of them: one branch has nothing to collide with. The
+/// local checkout is left out - it normally sits on the base branch itself.
+/// Only single-repository projects are scanned: a multi-repository branch's
+/// directory is not itself a checkout to diff.
+pub fn scans_for(store: &ProjectStore) -> Vec<OverlapScan> {
+    store
+        .projects()
+        .filter(|project| project.kind.is_git())
+        .filter_map(|project| {
+            let branches: Vec<(BranchId, PathBuf)> = store
+                .branches_for_project(project.id)
+                .into_iter()
+                .filter(|b| b.is_worktree && !b.stale)
+                .map(|b| (b.id, b.working_dir.clone()))
+                .collect();
+            (branches.len() > 1).then(|| OverlapScan {
+                project_id: project.id,
+                base_ref: project
+                    .default_base_branch
+                    .clone()
+                    .unwrap_or_else(|| project.default_branch.clone()),
+                branches,
+            })
+        })
+        .collect()
+}
+
+/// Run the comparisons, skipping any branch git cannot answer for
+///
+/// A branch whose checkout is mid-rebase or whose base ref has gone is only
+/// logged: one unreadable worktree must not hide collisions between the rest.
+pub fn run_scans(scans: &[OverlapScan]) -> FileOverlaps {
+    let mut overlaps = FileOverlaps::default();
+    for scan in scans {
+        let changes: Vec<(BranchId, _)> = scan
+            .branches
+            .iter()
+            .filter_map(|(branch_id, checkout)| {
+                match crate::git::overlap::changed_files(checkout, &scan.base_ref) {
+                    Ok(files) => Some((*branch_id, files)),
+                    Err(e) => {
+                        tracing::debug!(
+                            branch_id = %branch_id,
+                            error = %e,
+                            "Skipping branch in overlap scan"
+                        );
+                        None
+                    }
+                }
+            })
+            .collect();
+        overlaps.record_project(crate::git::overlap::find_overlaps(&changes));
+    }
+    overlaps
+}
+
+/// Run `scans` on a worker thread; the receiver yields exactly one result
+///
+/// Diffing a large worktree against its base can take a while, and this runs
+/// on a timer rather than in answer to a key, so it must never stall a frame.
+pub fn spawn_scans(scans: Vec<OverlapScan>) -> Receiver<FileOverlaps> {
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let _ = tx.send(run_scans(&scans));
+    });
+    rx
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project::{Branch, Project};
+
+    #[test]
+    fn test_scans_skip_stale_and_lonely_worktrees() {
+        let mut store = ProjectStore::new();
+        let busy = Project::new("busy".into(), "/tmp/busy".into(), "main".into());
+        let lonely = Project::new("lonely".into(), "/tmp/lonely".into(), "main".into());
+        let (busy_id, lonely_id) = (busy.id, lonely.id);
+        store.add_project(busy);
+        store.add_project(lonely);
+
+        store.add_branch(Branch::default_for_project(
+            busy_id,
+            "main".into(),
+            "/tmp/busy".into(),
+        ));
+        for name in ["a", "b"] {
+            store.add_branch(Branch::new(
+                busy_id,
+                name.into(),
+                format!("/tmp/wt/{name}").into(),
+                false,
+                true,
+            ));
+        }
+        let mut stale = Branch::new(busy_id, "gone".into(), "/tmp/gone".into(), false, true);
+        stale.stale = true;
+        store.add_branch(stale);
+        store.add_branch(Branch::new(
+            lonely_id,
+            "solo".into(),
+            "/tmp/wt/solo".into(),
+            false,
+            true,
+        ));
+
+        let scans = scans_for(&store);
+
+        assert_eq!(scans.len(), 1);
+        assert_eq!(scans[0].project_id, busy_id);
+        assert_eq!(scans[0].base_ref, "main");
+        assert_eq!(scans[0].branches.len(), 2);
+    }
+
+    #[test]
+    fn test_each_owner_sees_the_others() {
+        let (a, b, c) = (
+            uuid::Uuid::new_v4(),
+            uuid::Uuid::new_v4(),
+            uuid::Uuid::new_v4(),
+        );
+        let mut overlaps = FileOverlaps::default();
+        overlaps.record_project(
+            [("src/manager.rs".to_string(), vec![a, b, c])]
+                .into_iter()
+                .collect(),
+        );
+
+        assert_eq!(overlaps.count_for_branch(a), 1);
+        assert_eq!(overlaps.for_branch(b)[0].other_branches, vec![a, c]);
+        assert!(overlaps.for_branch(uuid::Uuid::new_v4()).is_empty());
+    }
+}
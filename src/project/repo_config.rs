@@ -0,0 +1,255 @@
+//! A repository's own `.panoptes.toml`
+//!
+//! Project settings normally live in `projects.json`, set up by each person
+//! through the UI. A repository can ship the sensible ones itself, so everyone
+//! who adds it starts from the same place:
+//!
+//! ```toml
+//! default_base_branch = "origin/develop"
+//! session_subdir = "packages/app"
+//!
+//! [agent]
+//! recommended = "claude"
+//! claude_model = "sonnet"
+//! codex_model = "gpt-5-codex"
+//!
+//! [worktree]
+//! submodules = true
+//! setup = ["npm ci", "cp .env.example .env"]
+//!
+//! [notify]
+//! only = ["approval", "crashed"]
+//!
+//! [[custom_shortcuts]]
+//! key = "t"
+//! name = "Test"
+//! command = "npm test"
+//! ```
+//!
+//! The file is read when the project is added and again on every refresh
+//! (`R`). It supplies defaults rather than overriding anything: a setting
+//! still at the value the file last gave it follows the file, and one changed
+//! in the UI stays as it was changed. `session_subdir` is only read on add,
+//! since the branches already registered start where it pointed then.
+//!
+//! Setup commands and shortcuts are the exception to "just read it": one
+//! runs by itself in every new worktree, the other runs in place of a key
+//! the user may have bound. They only take effect once allowed from the
+//! project settings (`,`), and a change to either in the file needs allowing
+//! again.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Component, Path, PathBuf};
+
+use crate::agent::AgentType;
+use crate::config::{is_reserved_key, CustomShortcut};
+use crate::notify::rules::NotifyRule;
+
+/// Name of the file, at the project's `repo_path`
+pub const REPO_CONFIG_FILE: &str = ".panoptes.toml";
+
+/// The contents of a `.panoptes.toml`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RepoConfig {
+    /// Base for new worktrees, e.g. `"origin/develop"`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_base_branch: Option<String>,
+    /// Where sessions start, relative to the repository root
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_subdir: Option<PathBuf>,
+    /// Which agent, and which model, the repository is meant to be worked on with
+    #[serde(default)]
+    pub agent: RepoAgent,
+    /// How new worktrees are set up
+    #[serde(default)]
+    pub worktree: RepoWorktree,
+    /// Notification preferences for the project's sessions, as a
+    /// `[notify_on.projects]` rule; one in `config.toml` takes precedence
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notify: Option<NotifyRule>,
+    /// Shortcuts that fire only in this project, ahead of the global ones
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub custom_shortcuts: Vec<CustomShortcut>,
+}
+
+/// The `[agent]` table
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RepoAgent {
+    /// Preselected when starting a new session
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recommended: Option<RecommendedAgent>,
+    /// Passed to Claude Code as `--model`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub claude_model: Option<String>,
+    /// Passed to Codex as `--model`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub codex_model: Option<String>,
+}
+
+/// An agent a repository can recommend
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RecommendedAgent {
+    Claude,
+    Codex,
+}
+
+/// The `[worktree]` table
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RepoWorktree {
+    /// Initialise submodules in new worktrees
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub submodules: Option<bool>,
+    /// Carry the main checkout's sparse-checkout patterns over
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sparse_checkout: Option<bool>,
+    /// Shell commands run, in order, in each new worktree once it is checked
+    /// out - if the user has allowed them
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub setup: Vec<String>,
+}
+
+impl RepoAgent {
+    /// The model the repository asks for with this agent, if any
+    pub fn model_for(&self, agent: AgentType) -> Option<&str> {
+        match agent {
+            AgentType::ClaudeCode => self.claude_model.as_deref(),
+            AgentType::OpenAICodex => self.codex_model.as_deref(),
+            AgentType::Shell => None,
+        }
+    }
+}
+
+impl RepoConfig {
+    /// Read the `.panoptes.toml` in `dir`, or `None` if there is none
+    ///
+    /// Shortcuts on reserved keys are dropped, as they could never fire, and
+    /// so is a `session_subdir` that would lead out of the repository.
+    pub fn load(dir: &Path) -> Result<Option<Self>> {
+        let path = dir.join(REPO_CONFIG_FILE);
+        let text = match std::fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e).with_context(|| format!("Failed to read {:?}", path)),
+        };
+        let mut config: Self =
+            toml::from_str(&text).with_context(|| format!("Failed to parse {}", path.display()))?;
+        config.custom_shortcuts.retain(|shortcut| {
+            let usable = !is_reserved_key(shortcut.key);
+            if !usable {
+                tracing::warn!(
+                    "Ignoring shortcut '{}' in {:?}: the key is reserved",
+                    shortcut.key,
+                    path
+                );
+            }
+            usable
+        });
+        if let Some(subdir) = &config.session_subdir {
+            if !is_inside_repo(subdir) {
+                tracing::warn!(
+                    "Ignoring session_subdir {:?} in {:?}: it must be a path inside the repository",
+                    subdir,
+                    path
+                );
+                config.session_subdir = None;
+            }
+        }
+        Ok(Some(config))
+    }
+}
+
+/// Whether `subdir`, joined to a checkout, stays inside it: relative, and
+/// with no `..` to climb out by
+fn is_inside_repo(subdir: &Path) -> bool {
+    subdir
+        .components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+}
+
+/// Move `field` from the file's previous value to its next one, unless the
+/// user has set it to something else in the meantime
+pub(super) fn follow<T: PartialEq + Clone>(field: &mut T, previous: &T, next: &T) {
+    if field == previous {
+        *field = next.clone();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_file_is_no_config() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(RepoConfig::load(dir.path()).unwrap(), None);
+    }
+
+    #[test]
+    fn test_file_parses_and_drops_reserved_shortcuts() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join(REPO_CONFIG_FILE),
+            "default_base_branch = \"origin/develop\"\n\
+             [agent]\nrecommended = \"codex\"\ncodex_model = \"gpt-5-codex\"\n\
+             [worktree]\nsetup = [\"npm ci\"]\n\
+             [notify]\nmute = true\n\
+             [[custom_shortcuts]]\nkey = \"t\"\ncommand = \"npm test\"\n\
+             [[custom_shortcuts]]\nkey = \"q\"\ncommand = \"exit\"\n",
+        )
+        .unwrap();
+
+        let config = RepoConfig::load(dir.path()).unwrap().unwrap();
+
+        assert_eq!(
+            config.default_base_branch.as_deref(),
+            Some("origin/develop")
+        );
+        assert_eq!(config.agent.recommended, Some(RecommendedAgent::Codex));
+        assert_eq!(
+            config.agent.model_for(AgentType::OpenAICodex),
+            Some("gpt-5-codex")
+        );
+        assert_eq!(config.agent.model_for(AgentType::ClaudeCode), None);
+        assert_eq!(config.worktree.setup, vec!["npm ci".to_string()]);
+        assert!(config.notify.is_some_and(|rule| rule.mute));
+        assert_eq!(config.custom_shortcuts.len(), 1);
+        assert_eq!(config.custom_shortcuts[0].key, 't');
+    }
+
+    #[test]
+    fn test_session_subdir_outside_the_repo_is_dropped() {
+        let dir = tempfile::tempdir().unwrap();
+        let subdir_of = |value: &str| {
+            std::fs::write(
+                dir.path().join(REPO_CONFIG_FILE),
+                format!("session_subdir = {value:?}\n"),
+            )
+            .unwrap();
+            RepoConfig::load(dir.path())
+                .unwrap()
+                .unwrap()
+                .session_subdir
+        };
+
+        assert_eq!(
+            subdir_of("packages/app"),
+            Some(PathBuf::from("packages/app"))
+        );
+        assert_eq!(subdir_of("/etc"), None);
+        assert_eq!(subdir_of("../elsewhere"), None);
+        assert_eq!(subdir_of("packages/../../elsewhere"), None);
+    }
+
+    #[test]
+    fn test_malformed_file_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join(REPO_CONFIG_FILE),
+            "[agent]\nrecommended = 3\n",
+        )
+        .unwrap();
+        assert!(RepoConfig::load(dir.path()).is_err());
+    }
+}
@@ -2,8 +2,11 @@
 //!
 //! Handles saving and loading projects and branches to/from disk.
 
-use super::{folder_path_key, Branch, BranchId, Project, ProjectId, MAX_FOLDER_DEPTH};
-use crate::config::config_dir;
+use super::{
+    folder_path_key, Branch, BranchId, Project, ProjectId, RepoConfig, ShortcutScope,
+    MAX_FOLDER_DEPTH,
+};
+use crate::config::{config_dir, is_reserved_key, CustomShortcut};
 use crate::persistence::{self, LoadOutcome};
 use anyhow::{bail, Context, Result};
 use serde::{Deserialize, Serialize};
@@ -18,6 +21,9 @@ struct StoreData {
     /// Display paths of folders the user has collapsed in the projects overview
     #[serde(default)]
     collapsed_folders: Vec<String>,
+    /// Custom shortcuts shared by every project under a folder, by display path
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    folder_shortcuts: HashMap<String, Vec<CustomShortcut>>,
 }
 
 /// Store for persisting projects and branches
@@ -29,6 +35,8 @@ pub struct ProjectStore {
     branches: HashMap<BranchId, Branch>,
     /// Display paths of folders collapsed in the projects overview
     collapsed_folders: HashSet<String>,
+    /// Custom shortcuts of folders, by display path
+    folder_shortcuts: HashMap<String, Vec<CustomShortcut>>,
     /// Path to the projects.json file
     store_path: PathBuf,
 }
@@ -39,6 +47,7 @@ impl Default for ProjectStore {
             projects: HashMap::new(),
             branches: HashMap::new(),
             collapsed_folders: HashSet::new(),
+            folder_shortcuts: HashMap::new(),
             store_path: projects_file_path(),
         }
     }
@@ -56,6 +65,7 @@ impl ProjectStore {
             projects: HashMap::new(),
             branches: HashMap::new(),
             collapsed_folders: HashSet::new(),
+            folder_shortcuts: HashMap::new(),
             store_path: path,
         }
     }
@@ -221,7 +231,7 @@ impl ProjectStore {
 
         let mut new_path = parent.to_vec();
         new_path.push(new_name.to_string());
-        self.remap_collapsed(path, Some(&new_path));
+        self.remap_folder_keys(path, Some(&new_path));
 
         Ok(renamed)
     }
@@ -271,7 +281,7 @@ impl ProjectStore {
                 moved += 1;
             }
         }
-        self.remap_collapsed(path, Some(&new_path));
+        self.remap_folder_keys(path, Some(&new_path));
 
         Ok(moved)
     }
@@ -294,7 +304,7 @@ impl ProjectStore {
                 moved += 1;
             }
         }
-        self.remap_collapsed(path, None);
+        self.remap_folder_keys(path, None);
 
         moved
     }
@@ -330,24 +340,42 @@ impl ProjectStore {
         &self.collapsed_folders
     }
 
-    /// Rewrite collapse keys for a subtree that moved (`Some`) or vanished (`None`)
-    fn remap_collapsed(&mut self, old_path: &[String], new_path: Option<&[String]>) {
+    /// Rewrite folder keys for a subtree that moved (`Some`) or vanished (`None`)
+    ///
+    /// Covers the collapsed state and the folder shortcuts alike; a dissolved
+    /// folder's shortcuts go with it.
+    fn remap_folder_keys(&mut self, old_path: &[String], new_path: Option<&[String]>) {
         let old_key = folder_path_key(old_path);
         let prefix = format!("{}/", old_key);
+        let in_subtree = |key: &String| *key == old_key || key.starts_with(&prefix);
+        let remapped = |key: &str| {
+            new_path
+                .map(|new_path| format!("{}{}", folder_path_key(new_path), &key[old_key.len()..]))
+        };
 
         let affected: Vec<String> = self
             .collapsed_folders
             .iter()
-            .filter(|k| **k == old_key || k.starts_with(&prefix))
+            .filter(|k| in_subtree(k))
             .cloned()
             .collect();
-
         for key in affected {
             self.collapsed_folders.remove(&key);
-            if let Some(new_path) = new_path {
-                let suffix = &key[old_key.len()..];
-                self.collapsed_folders
-                    .insert(format!("{}{}", folder_path_key(new_path), suffix));
+            if let Some(new_key) = remapped(&key) {
+                self.collapsed_folders.insert(new_key);
+            }
+        }
+
+        let affected: Vec<String> = self
+            .folder_shortcuts
+            .keys()
+            .filter(|k| in_subtree(k))
+            .cloned()
+            .collect();
+        for key in affected {
+            let shortcuts = self.folder_shortcuts.remove(&key).unwrap_or_default();
+            if let Some(new_key) = remapped(&key) {
+                self.folder_shortcuts.insert(new_key, shortcuts);
             }
         }
     }
@@ -415,6 +443,172 @@ impl ProjectStore {
         stale_count
     }
 
+    /// Re-read a project's `.panoptes.toml` and merge it into the project
+    ///
+    /// Returns whether the file changed since it was last read. A file that
+    /// does not parse is an error and leaves the project as it was.
+    pub fn reload_repo_config(&mut self, project_id: ProjectId) -> Result<bool> {
+        let Some(project) = self.projects.get_mut(&project_id) else {
+            return Ok(false);
+        };
+        let config = RepoConfig::load(&project.repo_path)?;
+        Ok(project.apply_repo_config(config))
+    }
+
+    /// The custom shortcuts that fire in a project, most specific first
+    ///
+    /// The project's own shortcuts come first, then its `.panoptes.toml`
+    /// ones, then those of its folders from the innermost out, and last the
+    /// global ones. Each layer only adds the keys the ones before it left
+    /// free, so a project entry shadows a global one on the same key.
+    /// The file's shortcuts take part only once allowed (see
+    /// [`Project::allowed_repo_shortcuts`]). Without a project, the global
+    /// ones alone.
+    pub fn shortcuts_for<'a>(
+        &'a self,
+        project_id: ProjectId,
+        global: &'a [CustomShortcut],
+    ) -> Vec<&'a CustomShortcut> {
+        let mut layers: Vec<&[CustomShortcut]> = Vec::new();
+        if let Some(project) = self.projects.get(&project_id) {
+            layers.push(&project.custom_shortcuts);
+            layers.push(project.allowed_repo_shortcuts());
+            for depth in (1..=project.folder.len()).rev() {
+                if let Some(shortcuts) = self
+                    .folder_shortcuts
+                    .get(&folder_path_key(&project.folder[..depth]))
+                {
+                    layers.push(shortcuts);
+                }
+            }
+        }
+        layers.push(global);
+
+        let mut shortcuts: Vec<&CustomShortcut> = Vec::new();
+        for shortcut in layers.into_iter().flatten() {
+            if !shortcuts.iter().any(|s| s.key == shortcut.key) {
+                shortcuts.push(shortcut);
+            }
+        }
+        shortcuts
+    }
+
+    /// The custom shortcut bound to `key` in a project, if any
+    pub fn shortcut_for<'a>(
+        &'a self,
+        project_id: ProjectId,
+        global: &'a [CustomShortcut],
+        key: char,
+    ) -> Option<&'a CustomShortcut> {
+        self.shortcuts_for(project_id, global)
+            .into_iter()
+            .find(|shortcut| shortcut.key == key)
+    }
+
+    /// The scopes a project's settings can edit shortcuts in: the project
+    /// itself, then each folder it sits under from the innermost out
+    pub fn shortcut_scopes(&self, project_id: ProjectId) -> Vec<ShortcutScope> {
+        let mut scopes = vec![ShortcutScope::Project(project_id)];
+        if let Some(project) = self.projects.get(&project_id) {
+            for depth in (1..=project.folder.len()).rev() {
+                scopes.push(ShortcutScope::Folder(project.folder[..depth].to_vec()));
+            }
+        }
+        scopes
+    }
+
+    /// The shortcuts kept in one scope
+    pub fn scoped_shortcuts(&self, scope: &ShortcutScope) -> &[CustomShortcut] {
+        match scope {
+            ShortcutScope::Project(id) => self
+                .projects
+                .get(id)
+                .map(|p| p.custom_shortcuts.as_slice())
+                .unwrap_or_default(),
+            ShortcutScope::Folder(path) => self
+                .folder_shortcuts
+                .get(&folder_path_key(path))
+                .map(Vec::as_slice)
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Every shortcut a project's settings list, with the scope it is kept
+    /// in and its index there
+    pub fn editable_shortcuts(
+        &self,
+        project_id: ProjectId,
+    ) -> Vec<(ShortcutScope, usize, &CustomShortcut)> {
+        self.shortcut_scopes(project_id)
+            .into_iter()
+            .flat_map(|scope| {
+                self.scoped_shortcuts(&scope)
+                    .iter()
+                    .enumerate()
+                    .map(move |(i, shortcut)| (scope.clone(), i, shortcut))
+            })
+            .collect()
+    }
+
+    /// Add a shortcut to a scope
+    ///
+    /// Like [`crate::config::Config::add_shortcut`], the key must be free in
+    /// that scope and not reserved. It may shadow a key of a wider scope.
+    pub fn add_scoped_shortcut(
+        &mut self,
+        scope: &ShortcutScope,
+        shortcut: CustomShortcut,
+    ) -> Result<()> {
+        if is_reserved_key(shortcut.key) {
+            bail!("Key '{}' is reserved", shortcut.key);
+        }
+        if self
+            .scoped_shortcuts(scope)
+            .iter()
+            .any(|s| s.key == shortcut.key)
+        {
+            bail!("Key '{}' is already in use", shortcut.key);
+        }
+        match scope {
+            ShortcutScope::Project(id) => self
+                .projects
+                .get_mut(id)
+                .context("Project not found while adding a shortcut")?
+                .custom_shortcuts
+                .push(shortcut),
+            ShortcutScope::Folder(path) => self
+                .folder_shortcuts
+                .entry(folder_path_key(path))
+                .or_default()
+                .push(shortcut),
+        }
+        Ok(())
+    }
+
+    /// Remove a shortcut from a scope by index
+    pub fn remove_scoped_shortcut(
+        &mut self,
+        scope: &ShortcutScope,
+        index: usize,
+    ) -> Option<CustomShortcut> {
+        let shortcuts = match scope {
+            ShortcutScope::Project(id) => &mut self.projects.get_mut(id)?.custom_shortcuts,
+            ShortcutScope::Folder(path) => {
+                let key = folder_path_key(path);
+                let shortcuts = self.folder_shortcuts.get_mut(&key)?;
+                if index >= shortcuts.len() {
+                    return None;
+                }
+                let removed = shortcuts.remove(index);
+                if shortcuts.is_empty() {
+                    self.folder_shortcuts.remove(&key);
+                }
+                return Some(removed);
+            }
+        };
+        (index < shortcuts.len()).then(|| shortcuts.remove(index))
+    }
+
     /// Get stale branch count for a project
     pub fn stale_branch_count(&self, project_id: ProjectId) -> usize {
         self.branches
@@ -447,6 +641,7 @@ impl ProjectStore {
             projects: data.projects.into_iter().map(|p| (p.id, p)).collect(),
             branches: data.branches.into_iter().map(|b| (b.id, b)).collect(),
             collapsed_folders: data.collapsed_folders.into_iter().collect(),
+            folder_shortcuts: data.folder_shortcuts,
             store_path: path.to_path_buf(),
         }
     }
@@ -473,11 +668,21 @@ impl ProjectStore {
             .cloned()
             .collect();
         collapsed_folders.sort();
+        let folder_shortcuts = self
+            .folder_shortcuts
+            .iter()
+            .filter(|(key, _)| {
+                let segments: Vec<String> = key.split('/').map(|s| s.to_string()).collect();
+                self.projects.values().any(|p| p.is_under_folder(&segments))
+            })
+            .map(|(key, shortcuts)| (key.clone(), shortcuts.clone()))
+            .collect();
 
         let data = StoreData {
             projects: self.projects.values().cloned().collect(),
             branches: self.branches.values().cloned().collect(),
             collapsed_folders,
+            folder_shortcuts,
         };
 
         persistence::save_json_atomic(path, &data, "projects")
@@ -663,6 +868,88 @@ mod tests {
         assert!(loaded.get_branch(branch_id).is_some());
     }
 
+    #[test]
+    fn test_reload_repo_config_merges_and_survives_a_broken_file() {
+        let repo = TempDir::new().unwrap();
+        let mut store = ProjectStore::new();
+        let project = Project::new("app".to_string(), repo.path().into(), "main".to_string());
+        let project_id = project.id;
+        store.add_project(project);
+        assert!(!store.reload_repo_config(project_id).unwrap());
+
+        let file = repo.path().join(crate::project::REPO_CONFIG_FILE);
+        std::fs::write(&file, "default_base_branch = \"origin/develop\"\n").unwrap();
+        assert!(store.reload_repo_config(project_id).unwrap());
+        let project = store.get_project(project_id).unwrap();
+        assert_eq!(
+            project.default_base_branch.as_deref(),
+            Some("origin/develop")
+        );
+
+        std::fs::write(&file, "default_base_branch = [\n").unwrap();
+        assert!(store.reload_repo_config(project_id).is_err());
+        let project = store.get_project(project_id).unwrap();
+        assert!(project.repo_config.is_some());
+    }
+
+    #[test]
+    fn test_project_shortcuts_shadow_global_ones() {
+        let mut store = ProjectStore::new();
+        let mut project = Project::new("web".to_string(), "/tmp/web".into(), "main".to_string());
+        let project_id = project.id;
+        project.repo_config = Some(RepoConfig {
+            custom_shortcuts: vec![CustomShortcut::new(
+                't',
+                "Test".to_string(),
+                "npm test".to_string(),
+                false,
+            )],
+            ..Default::default()
+        });
+        store.add_project(project);
+        let global = vec![
+            CustomShortcut::new('t', "Test".to_string(), "cargo test".to_string(), false),
+            CustomShortcut::new('v', "Code".to_string(), "code .".to_string(), false),
+        ];
+
+        // Until the file's shortcuts are allowed, the global key stands
+        assert_eq!(
+            store
+                .shortcut_for(project_id, &global, 't')
+                .unwrap()
+                .command,
+            "cargo test"
+        );
+
+        store
+            .get_project_mut(project_id)
+            .unwrap()
+            .allow_repo_commands();
+        let shortcuts = store.shortcuts_for(project_id, &global);
+        assert_eq!(shortcuts.len(), 2);
+        assert_eq!(
+            store
+                .shortcut_for(project_id, &global, 't')
+                .unwrap()
+                .command,
+            "npm test"
+        );
+        assert_eq!(
+            store
+                .shortcut_for(project_id, &global, 'v')
+                .unwrap()
+                .command,
+            "code ."
+        );
+        assert_eq!(
+            store
+                .shortcut_for(uuid::Uuid::nil(), &global, 't')
+                .unwrap()
+                .command,
+            "cargo test"
+        );
+    }
+
     #[test]
     fn test_store_load_nonexistent() {
         let temp_dir = TempDir::new().unwrap();
@@ -993,6 +1280,7 @@ mod tests {
 
         assert_eq!(store.project_count(), 1);
         assert!(store.projects().next().unwrap().folder.is_empty());
+        assert!(store.projects().next().unwrap().kind.is_git());
         assert!(store.collapsed_folders().is_empty());
     }
 
@@ -1016,4 +1304,77 @@ mod tests {
 
         assert_eq!(entries.len(), 1);
     }
+
+    #[test]
+    fn test_project_and_folder_shortcuts_layer_over_the_file_and_global() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut store = ProjectStore::with_path(temp_dir.path().join("projects.json"));
+        let mut project = Project::new("web".to_string(), "/tmp/web".into(), "main".to_string());
+        let project_id = project.id;
+        project.folder = vec!["Acme".to_string(), "Front".to_string()];
+        project.repo_config = Some(RepoConfig {
+            custom_shortcuts: vec![CustomShortcut::new(
+                'l',
+                String::new(),
+                "npm run lint".to_string(),
+                false,
+            )],
+            ..Default::default()
+        });
+        project.allow_repo_commands();
+        store.add_project(project);
+        let shortcut = |key, command: &str| {
+            CustomShortcut::new(key, String::new(), command.to_string(), false)
+        };
+        let project_scope = ShortcutScope::Project(project_id);
+        let outer = ShortcutScope::Folder(vec!["Acme".to_string()]);
+        let inner = ShortcutScope::Folder(vec!["Acme".to_string(), "Front".to_string()]);
+        store
+            .add_scoped_shortcut(&project_scope, shortcut('t', "npm test"))
+            .unwrap();
+        store
+            .add_scoped_shortcut(&inner, shortcut('l', "eslint ."))
+            .unwrap();
+        store
+            .add_scoped_shortcut(&inner, shortcut('v', "npm run dev"))
+            .unwrap();
+        store
+            .add_scoped_shortcut(&outer, shortcut('v', "make dev"))
+            .unwrap();
+        store
+            .add_scoped_shortcut(&outer, shortcut('o', "open ."))
+            .unwrap();
+        assert!(store
+            .add_scoped_shortcut(&project_scope, shortcut('t', "again"))
+            .is_err());
+        let global = vec![shortcut('t', "cargo test"), shortcut('g', "lazygit")];
+
+        let command = |key| {
+            store
+                .shortcut_for(project_id, &global, key)
+                .map(|s| s.command.clone())
+        };
+        assert_eq!(command('t').as_deref(), Some("npm test"));
+        assert_eq!(command('l').as_deref(), Some("npm run lint"));
+        assert_eq!(command('v').as_deref(), Some("npm run dev"));
+        assert_eq!(command('o').as_deref(), Some("open ."));
+        assert_eq!(command('g').as_deref(), Some("lazygit"));
+        assert_eq!(store.shortcuts_for(project_id, &global).len(), 5);
+        assert_eq!(store.editable_shortcuts(project_id).len(), 5);
+
+        // Folder shortcuts follow their folder, and survive a reload
+        store
+            .rename_folder(&["Acme".to_string()], "Globex")
+            .unwrap();
+        store.save().unwrap();
+        let (store, warning) = ProjectStore::load_from_with_status(&store.store_path);
+        assert!(warning.is_none());
+        assert_eq!(
+            store
+                .shortcut_for(project_id, &global, 'o')
+                .map(|s| s.command.as_str()),
+            Some("open .")
+        );
+        assert!(store.scoped_shortcuts(&outer).is_empty());
+    }
 }
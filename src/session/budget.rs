@@ -0,0 +1,390 @@
+//! Spending limits
+//!
+//! The `[budgets]` section of `config.toml` caps what a session, a project or
+//! an account may spend before Panoptes flags it:
+//!
+//! ```toml
+//! [budgets]
+//! interrupt = true          # also press Esc in the offending session
+//!
+//! [budgets.session]
+//! cost = 5.0                # dollars this run
+//! turn_minutes = 30         # any single turn
+//!
+//! [budgets.projects.web]
+//! cost = 200.0              # dollars this calendar month
+//!
+//! [budgets.accounts.work]
+//! tokens = 50_000_000       # tokens this calendar month
+//! ```
+//!
+//! A session's budget covers what it has spent since Panoptes started it; a
+//! project's or account's covers the local calendar month, read back from the
+//! history log at startup. `turn_minutes` applies to each turn of any session
+//! in scope. Costs are estimates (see [`crate::pricing`]) and tokens on a model
+//! with no known price count towards a token limit but not a cost limit.
+//!
+//! Limits are checked only while a session is working, and each turn is
+//! flagged at most once. With `interrupt` set, every turn that starts over a
+//! monthly limit is stopped as well, until the limit is raised or the month
+//! turns over.
+
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{DateTime, Datelike, Duration, Local, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::history::{HistoryEvent, HistoryRecord};
+use super::SessionId;
+use crate::agent::events::TokenCounts;
+use crate::pricing::{estimate, format_dollars, PricingConfig};
+use crate::project::ProjectId;
+
+/// The key in `[budgets.accounts]` for sessions on the agent's default login
+pub const DEFAULT_ACCOUNT: &str = "default";
+
+/// The `[budgets]` section of `config.toml`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BudgetsConfig {
+    /// Press Esc in a session that goes over, as well as flagging it
+    #[serde(default)]
+    pub interrupt: bool,
+    /// Limits on each session, over its current run
+    #[serde(default, skip_serializing_if = "BudgetLimit::is_empty")]
+    pub session: BudgetLimit,
+    /// Limits per project, by project name, over the calendar month
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub projects: BTreeMap<String, BudgetLimit>,
+    /// Limits per account, by account name, over the calendar month
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub accounts: BTreeMap<String, BudgetLimit>,
+}
+
+impl BudgetsConfig {
+    /// Whether no limit is set anywhere
+    pub fn is_empty(&self) -> bool {
+        self.session.is_empty()
+            && self.projects.values().all(BudgetLimit::is_empty)
+            && self.accounts.values().all(BudgetLimit::is_empty)
+    }
+}
+
+/// One scope's limits; any left out are not enforced
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BudgetLimit {
+    /// Tokens of every kind, cache reads and writes included
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tokens: Option<u64>,
+    /// Estimated dollars
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost: Option<f64>,
+    /// Wall-clock minutes any one turn may run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub turn_minutes: Option<u64>,
+}
+
+impl BudgetLimit {
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_none() && self.cost.is_none() && self.turn_minutes.is_none()
+    }
+
+    /// The first of these limits that `spent` or the current `turn` is over,
+    /// described for the session list
+    pub fn exceeded(&self, spent: &Tally, turn: Option<Duration>) -> Option<String> {
+        if let Some(cost) = self.cost {
+            if spent.cost >= cost {
+                return Some(format_dollars(cost));
+            }
+        }
+        if let Some(tokens) = self.tokens {
+            if spent.tokens >= tokens {
+                return Some(format!("{} tokens", tokens));
+            }
+        }
+        if let (Some(minutes), Some(turn)) = (self.turn_minutes, turn) {
+            if turn >= Duration::minutes(minutes as i64) {
+                return Some(format!("{}m turn", minutes));
+            }
+        }
+        None
+    }
+}
+
+/// Tokens spent and what they are estimated to cost
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Tally {
+    pub tokens: u64,
+    pub cost: f64,
+}
+
+impl Tally {
+    /// Count `tokens` spent on `model`; an unpriced model adds no cost
+    pub fn add(&mut self, model: Option<&str>, tokens: &TokenCounts, pricing: &PricingConfig) {
+        self.tokens += tokens.total();
+        self.cost += estimate(model, tokens, pricing).unwrap_or(0.0);
+    }
+}
+
+/// A session that went over budget during a check
+#[derive(Debug, Clone, PartialEq)]
+pub struct BudgetAlert {
+    pub session_id: SessionId,
+    pub session_name: String,
+    /// Which limit, e.g. `project web $200.00`
+    pub limit: String,
+    /// Whether Esc was sent to the session
+    pub interrupted: bool,
+    /// Whether the alert should sound a notification
+    pub rings: bool,
+}
+
+/// Who a session's spending is charged to
+#[derive(Debug, Clone, PartialEq)]
+struct Owner {
+    project_id: ProjectId,
+    account: Option<String>,
+}
+
+/// The calendar month's recorded spending, per session and model
+///
+/// Kept beside the history log so the monthly limits survive a restart
+/// without rereading the file on every check.
+#[derive(Debug, Default)]
+pub struct MonthLedger {
+    /// The local `(year, month)` the figures are for
+    month: Option<(i32, u32)>,
+    owners: HashMap<SessionId, Owner>,
+    spent: HashMap<SessionId, HashMap<Option<String>, TokenCounts>>,
+}
+
+impl MonthLedger {
+    /// Rebuild the month so far from the history log
+    pub fn load(records: &[HistoryRecord], now: DateTime<Utc>) -> Self {
+        let mut ledger = Self {
+            month: Some(month_of(now)),
+            ..Self::default()
+        };
+        for record in records {
+            match &record.event {
+                HistoryEvent::SessionStarted {
+                    project_id,
+                    account,
+                    ..
+                } => ledger.set_owner(record.session_id, *project_id, account.clone()),
+                HistoryEvent::Spend { model, tokens } if month_of(record.at) == month_of(now) => {
+                    ledger.add(record.session_id, model.clone(), tokens, record.at)
+                }
+                _ => {}
+            }
+        }
+        ledger
+    }
+
+    /// Say who a session's spending is charged to
+    pub fn set_owner(
+        &mut self,
+        session_id: SessionId,
+        project_id: ProjectId,
+        account: Option<String>,
+    ) {
+        self.owners.insert(
+            session_id,
+            Owner {
+                project_id,
+                account,
+            },
+        );
+    }
+
+    /// Record spending, starting afresh when the month has turned over
+    pub fn add(
+        &mut self,
+        session_id: SessionId,
+        model: Option<String>,
+        tokens: &TokenCounts,
+        at: DateTime<Utc>,
+    ) {
+        let month = month_of(at);
+        if self.month != Some(month) {
+            self.month = Some(month);
+            self.spent.clear();
+        }
+        *self
+            .spent
+            .entry(session_id)
+            .or_default()
+            .entry(model)
+            .or_default() += *tokens;
+    }
+
+    /// This month's spending per project and per account, as of `now`
+    ///
+    /// Accounts are keyed by name, with [`DEFAULT_ACCOUNT`] for the default
+    /// login, matching `[budgets.accounts]`.
+    pub fn tallies(
+        &self,
+        pricing: &PricingConfig,
+        now: DateTime<Utc>,
+    ) -> (HashMap<ProjectId, Tally>, HashMap<String, Tally>) {
+        let mut projects: HashMap<ProjectId, Tally> = HashMap::new();
+        let mut accounts: HashMap<String, Tally> = HashMap::new();
+        if self.month != Some(month_of(now)) {
+            return (projects, accounts);
+        }
+        for (session_id, by_model) in &self.spent {
+            let Some(owner) = self.owners.get(session_id) else {
+                continue;
+            };
+            for (model, tokens) in by_model {
+                projects.entry(owner.project_id).or_default().add(
+                    model.as_deref(),
+                    tokens,
+                    pricing,
+                );
+                accounts
+                    .entry(account_key(owner.account.as_deref()))
+                    .or_default()
+                    .add(model.as_deref(), tokens, pricing);
+            }
+        }
+        (projects, accounts)
+    }
+}
+
+/// The `[budgets.accounts]` key for an account name
+pub fn account_key(account: Option<&str>) -> String {
+    account.unwrap_or(DEFAULT_ACCOUNT).to_string()
+}
+
+/// The local calendar month `at` falls in, as (year, month)
+pub(super) fn month_of(at: DateTime<Utc>) -> (i32, u32) {
+    let local = at.with_timezone(&Local);
+    (local.year(), local.month())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::SessionType;
+    use uuid::Uuid;
+
+    fn output(n: u64) -> TokenCounts {
+        TokenCounts {
+            output: n,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_budgets_parse_from_toml() {
+        let budgets: BudgetsConfig = toml::from_str(
+            r#"
+            interrupt = true
+            [session]
+            cost = 5.0
+            turn_minutes = 30
+            [projects.web]
+            cost = 200.0
+            [accounts.default]
+            tokens = 1000
+            "#,
+        )
+        .unwrap();
+        assert!(budgets.interrupt);
+        assert_eq!(budgets.session.cost, Some(5.0));
+        assert_eq!(budgets.session.turn_minutes, Some(30));
+        assert_eq!(budgets.projects["web"].cost, Some(200.0));
+        assert_eq!(budgets.accounts[DEFAULT_ACCOUNT].tokens, Some(1000));
+        assert!(!budgets.is_empty());
+        assert!(BudgetsConfig::default().is_empty());
+    }
+
+    #[test]
+    fn test_the_first_limit_crossed_is_reported() {
+        let limit = BudgetLimit {
+            tokens: Some(1_000),
+            cost: Some(2.0),
+            turn_minutes: Some(30),
+        };
+        let under = Tally {
+            tokens: 999,
+            cost: 1.99,
+        };
+        assert_eq!(limit.exceeded(&under, Some(Duration::minutes(29))), None);
+        assert_eq!(limit.exceeded(&under, None), None);
+        assert_eq!(
+            limit.exceeded(
+                &Tally {
+                    tokens: 0,
+                    cost: 2.0
+                },
+                None
+            ),
+            Some("$2.00".to_string())
+        );
+        assert_eq!(
+            limit.exceeded(
+                &Tally {
+                    tokens: 1_000,
+                    cost: 0.0
+                },
+                None
+            ),
+            Some("1000 tokens".to_string())
+        );
+        assert_eq!(
+            limit.exceeded(&under, Some(Duration::minutes(30))),
+            Some("30m turn".to_string())
+        );
+    }
+
+    #[test]
+    fn test_the_ledger_keeps_this_month_per_project_and_account() {
+        let now = Utc::now();
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        let (web, api) = (Uuid::new_v4(), Uuid::new_v4());
+        let started = |session_id, project_id, account: Option<&str>| HistoryRecord {
+            at: now - Duration::days(400),
+            session_id,
+            event: HistoryEvent::SessionStarted {
+                name: "s".to_string(),
+                project_id,
+                branch_id: Uuid::new_v4(),
+                session_type: SessionType::ClaudeCode,
+                resumed: false,
+                account: account.map(str::to_string),
+            },
+        };
+        let spend = |session_id, at, n| HistoryRecord {
+            at,
+            session_id,
+            event: HistoryEvent::Spend {
+                model: Some("claude-sonnet-4-5".to_string()),
+                tokens: output(n),
+            },
+        };
+        let records = vec![
+            started(a, web, Some("work")),
+            started(b, api, None),
+            // Sonnet output is $15 per million
+            spend(a, now, 1_000_000),
+            spend(b, now, 200_000),
+            // Last year's spending is not this month's
+            spend(a, now - Duration::days(400), 1_000_000),
+        ];
+
+        let mut ledger = MonthLedger::load(&records, now);
+        ledger.add(b, None, &output(50), now);
+        let pricing = PricingConfig::new();
+        let (projects, accounts) = ledger.tallies(&pricing, now);
+        assert!((projects[&web].cost - 15.0).abs() < 1e-9);
+        assert_eq!(projects[&api].tokens, 200_050);
+        assert!((projects[&api].cost - 3.0).abs() < 1e-9);
+        assert_eq!(accounts["work"].tokens, 1_000_000);
+        assert_eq!(accounts[DEFAULT_ACCOUNT].tokens, 200_050);
+
+        // Next month starts from nothing
+        let (projects, _) = ledger.tallies(&pricing, now + Duration::days(40));
+        assert!(projects.is_empty());
+    }
+}
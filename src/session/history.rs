@@ -0,0 +1,978 @@
+//! Session history
+//!
+//! An append-only log of what sessions did, kept beside `sessions.json` in one
+//! file per local calendar month, `history-YYYY-MM.jsonl`. The session store is
+//! an index of what can be brought back and forgets a session the moment it is
+//! closed; this log forgets nothing, so the stats view can answer where agent
+//! time actually went last week.
+//!
+//! Splitting by month keeps what is read small however long the log grows:
+//! the stats and spending pages read the months their last week and current
+//! month fall in, the budgets the current month alone. A session still open
+//! when the month turns has its `session_started` line written again at the
+//! top of the new month's file, with its original time, so every file can say
+//! whose spending it holds. A `history.jsonl` from before the split is divided
+//! into monthly files the first time the log is opened.
+//!
+//! One JSON object per line, each stamped with its session and time:
+//!
+//! - `session_started` / `session_ended`, with the final [`UsageSnapshot`]
+//! - `turn`: a prompt and how long the agent took to finish (or be stopped)
+//! - `tool`: one tool invocation and how long it ran
+//! - `approval`: how long a permission dialog waited on the user
+//! - `spend`: tokens billed since the session's last `spend` line, by model
+//!
+//! Appending is the only write, so a crash loses at most the line being
+//! written, and a torn or unreadable line is skipped on read rather than
+//! failing the whole file.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Local, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::budget::{month_of, MonthLedger};
+use super::{AttentionReason, SessionId, SessionInfo, SessionState, SessionType};
+use crate::agent::events::{AgentEvent, TokenCounts, UsageSnapshot};
+use crate::project::{BranchId, ProjectId};
+
+/// The single file the log was kept in before it was split by month
+pub const LEGACY_HISTORY_FILE_NAME: &str = "history.jsonl";
+
+/// A local calendar month, as (year, month)
+type Month = (i32, u32);
+
+/// File name of one month of the log, in the same directory as the session
+/// store
+pub fn history_file_name((year, month): Month) -> String {
+    format!("history-{:04}-{:02}.jsonl", year, month)
+}
+
+/// One line of the log
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryRecord {
+    pub at: DateTime<Utc>,
+    pub session_id: SessionId,
+    #[serde(flatten)]
+    pub event: HistoryEvent,
+}
+
+/// What happened
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum HistoryEvent {
+    /// A session was created, or resumed from a previous run
+    SessionStarted {
+        name: String,
+        project_id: ProjectId,
+        branch_id: BranchId,
+        session_type: SessionType,
+        resumed: bool,
+        /// The account it runs as, by name
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        account: Option<String>,
+    },
+    /// A session was closed, exited, or went down with Panoptes
+    SessionEnded {
+        reason: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        usage: Option<UsageSnapshot>,
+    },
+    /// A turn, from the prompt to the agent finishing or being interrupted
+    Turn { duration_ms: u64, aborted: bool },
+    /// A tool invocation, from start to finish
+    Tool { name: String, duration_ms: u64 },
+    /// A permission dialog, from raised to answered
+    Approval {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        tool: Option<String>,
+        waited_ms: u64,
+    },
+    /// Tokens billed since the last `spend` line, written as each turn ends
+    Spend {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        model: Option<String>,
+        tokens: TokenCounts,
+    },
+}
+
+/// What an event is about to change, captured before the state machine runs
+///
+/// The interesting facts - which tool a `ToolFinished` retires, how long the
+/// approval dialog has been up - are gone once the event is applied.
+#[derive(Debug, Clone)]
+pub struct Prior {
+    state: SessionState,
+    state_entered_at: DateTime<Utc>,
+    approval_tool: Option<String>,
+    kind: PriorKind,
+}
+
+#[derive(Debug, Clone)]
+enum PriorKind {
+    TurnStarted,
+    TurnEnded {
+        aborted: bool,
+    },
+    ToolFinished {
+        name: String,
+        started_at: DateTime<Utc>,
+    },
+    Other,
+}
+
+impl Prior {
+    pub fn capture(info: &SessionInfo, event: &AgentEvent) -> Self {
+        let kind = match event {
+            AgentEvent::TurnStarted { .. } => PriorKind::TurnStarted,
+            AgentEvent::TurnCompleted { .. } => PriorKind::TurnEnded { aborted: false },
+            AgentEvent::TurnAborted => PriorKind::TurnEnded { aborted: true },
+            AgentEvent::ToolFinished { key } => match info.in_flight.get(key) {
+                Some(tool) => PriorKind::ToolFinished {
+                    name: tool.name.clone(),
+                    started_at: tool.started_at,
+                },
+                None => PriorKind::Other,
+            },
+            _ => PriorKind::Other,
+        };
+        let approval_tool = match &info.attention {
+            Some(AttentionReason::Approval { tool }) => tool.clone(),
+            _ => None,
+        };
+        Self {
+            state: info.state,
+            state_entered_at: info.state_entered_at,
+            approval_tool,
+            kind,
+        }
+    }
+}
+
+/// The log, open for appending
+#[derive(Debug)]
+pub struct HistoryLog {
+    dir: PathBuf,
+    /// The month's file being appended to; opened on the first append, so a
+    /// manager that records nothing never creates one
+    file: Option<(Month, File)>,
+    /// The `session_started` line of each session not yet ended, carried
+    /// into the next month's file
+    open_starts: HashMap<SessionId, HistoryRecord>,
+    /// When each session's current turn began
+    open_turns: HashMap<SessionId, DateTime<Utc>>,
+    /// Each session's spending as of its last `spend` line
+    recorded_spend: HashMap<SessionId, TokenCounts>,
+    /// This month's `spend` lines, for the monthly budgets
+    month: MonthLedger,
+    /// Set after the first failed write, so a full disk warns once rather
+    /// than on every event
+    failed: bool,
+}
+
+impl HistoryLog {
+    /// Open the log kept in `dir`, reading this month's file back for the
+    /// monthly budgets
+    pub fn new(dir: PathBuf) -> Self {
+        if let Err(e) = split_legacy_log(&dir) {
+            tracing::warn!(
+                dir = %dir.display(),
+                error = %e,
+                "Failed to split {} by month; its history is left out", LEGACY_HISTORY_FILE_NAME
+            );
+        }
+        let now = Utc::now();
+        let month = match read_months(&dir, now, now) {
+            Ok((records, _)) => MonthLedger::load(&records, now),
+            Err(e) => {
+                tracing::warn!(
+                    dir = %dir.display(),
+                    error = %e,
+                    "Failed to read the session history; monthly budgets start from zero"
+                );
+                MonthLedger::default()
+            }
+        };
+        Self {
+            dir,
+            file: None,
+            open_starts: HashMap::new(),
+            open_turns: HashMap::new(),
+            recorded_spend: HashMap::new(),
+            month,
+            failed: false,
+        }
+    }
+
+    /// The directory the monthly files are kept in
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// When a session's current turn began, if it is in one
+    pub fn turn_started(&self, session_id: SessionId) -> Option<DateTime<Utc>> {
+        self.open_turns.get(&session_id).copied()
+    }
+
+    /// This month's recorded spending
+    pub fn month(&self) -> &MonthLedger {
+        &self.month
+    }
+
+    /// What a session has spent since its last `spend` line
+    pub fn unrecorded_spend(&self, info: &SessionInfo) -> TokenCounts {
+        let spent = info.usage.spent;
+        match self.recorded_spend.get(&info.id) {
+            Some(recorded) if spent.total() >= recorded.total() => spent.since(recorded),
+            _ => spent,
+        }
+    }
+
+    /// Record a session starting
+    pub fn session_started(&mut self, info: &SessionInfo, resumed: bool, now: DateTime<Utc>) {
+        self.month.set_owner(
+            info.id,
+            info.project_id,
+            info.account_name().map(str::to_string),
+        );
+        let event = HistoryEvent::SessionStarted {
+            name: info.name.clone(),
+            project_id: info.project_id,
+            branch_id: info.branch_id,
+            session_type: info.session_type,
+            resumed,
+            account: info.account_name().map(str::to_string),
+        };
+        self.append(info.id, now, event.clone());
+        self.open_starts.insert(
+            info.id,
+            HistoryRecord {
+                at: now,
+                session_id: info.id,
+                event,
+            },
+        );
+    }
+
+    /// Record a session ending, with whatever usage it last reported
+    pub fn session_ended(&mut self, info: &SessionInfo, reason: &str, now: DateTime<Utc>) {
+        self.open_turns.remove(&info.id);
+        self.record_spend(info, now);
+        self.recorded_spend.remove(&info.id);
+        let usage = (!info.usage.is_empty()).then(|| info.usage.clone());
+        self.append(
+            info.id,
+            now,
+            HistoryEvent::SessionEnded {
+                reason: reason.to_string(),
+                usage,
+            },
+        );
+        // Kept until now, so an end in a new month still carries the start
+        self.open_starts.remove(&info.id);
+    }
+
+    /// Record whatever an applied event finished: a turn, a tool, an approval
+    pub fn observe(&mut self, info: &SessionInfo, prior: Prior, now: DateTime<Utc>) {
+        match prior.kind {
+            PriorKind::TurnStarted => {
+                // A turn that never reported its end is dropped, not counted
+                // as lasting until this one began
+                self.open_turns.insert(info.id, now);
+            }
+            PriorKind::TurnEnded { aborted } => {
+                if let Some(started) = self.open_turns.remove(&info.id) {
+                    self.append(
+                        info.id,
+                        now,
+                        HistoryEvent::Turn {
+                            duration_ms: millis(now - started),
+                            aborted,
+                        },
+                    );
+                }
+                self.record_spend(info, now);
+            }
+            PriorKind::ToolFinished { name, started_at } => self.append(
+                info.id,
+                now,
+                HistoryEvent::Tool {
+                    name,
+                    duration_ms: millis(now - started_at),
+                },
+            ),
+            PriorKind::Other => {}
+        }
+
+        if prior.state == SessionState::AwaitingApproval
+            && info.state != SessionState::AwaitingApproval
+        {
+            self.append(
+                info.id,
+                now,
+                HistoryEvent::Approval {
+                    tool: prior.approval_tool,
+                    waited_ms: millis(now - prior.state_entered_at),
+                },
+            );
+        }
+    }
+
+    /// Record what a session has billed since its last `spend` line, if anything
+    ///
+    /// Written per turn rather than per API call: a busy session makes dozens
+    /// of calls a turn, and a day's spending only needs to land on the right
+    /// day. Tokens the agent reports after its turn ends are picked up by the
+    /// next turn, or by the session ending.
+    fn record_spend(&mut self, info: &SessionInfo, now: DateTime<Utc>) {
+        let spent = info.usage.spent;
+        let recorded = self.recorded_spend.entry(info.id).or_default();
+        // Usage starts again from zero when the process is respawned
+        if spent.total() < recorded.total() {
+            *recorded = TokenCounts::default();
+        }
+        let tokens = spent.since(recorded);
+        *recorded = spent;
+        if !tokens.is_zero() {
+            self.month
+                .add(info.id, info.usage.model.clone(), &tokens, now);
+            self.append(
+                info.id,
+                now,
+                HistoryEvent::Spend {
+                    model: info.usage.model.clone(),
+                    tokens,
+                },
+            );
+        }
+    }
+
+    /// Append one line; failures are logged, never raised
+    ///
+    /// Losing a line of statistics is strictly better than failing the event
+    /// that produced it.
+    fn append(&mut self, session_id: SessionId, at: DateTime<Utc>, event: HistoryEvent) {
+        let record = HistoryRecord {
+            at,
+            session_id,
+            event,
+        };
+        if let Err(e) = self.write(&record) {
+            if !self.failed {
+                tracing::warn!(
+                    dir = %self.dir.display(),
+                    error = %e,
+                    "Failed to append to the session history; statistics will be incomplete"
+                );
+            }
+            self.failed = true;
+            self.file = None;
+        }
+    }
+
+    fn write(&mut self, record: &HistoryRecord) -> Result<()> {
+        let month = month_of(record.at);
+        if self.file.as_ref().map(|(open, _)| *open) != Some(month) {
+            std::fs::create_dir_all(&self.dir)?;
+            let mut file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(self.dir.join(history_file_name(month)))?;
+            let mut carried = String::new();
+            for start in self
+                .open_starts
+                .values()
+                .filter(|start| month_of(start.at) < month)
+            {
+                carried.push_str(&serde_json::to_string(start)?);
+                carried.push('\n');
+            }
+            file.write_all(carried.as_bytes())?;
+            self.file = Some((month, file));
+        }
+        let mut line = serde_json::to_string(record)?;
+        line.push('\n');
+        // One write per line, so concurrent appends cannot interleave mid-line
+        if let Some((_, file)) = self.file.as_mut() {
+            file.write_all(line.as_bytes())?;
+        }
+        Ok(())
+    }
+}
+
+/// Read the months of the log from `from`'s to `to`'s, oldest first
+///
+/// Months with no file are empty; see [`read_history`] for the lines skipped.
+pub fn read_months(
+    dir: &Path,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Result<(Vec<HistoryRecord>, usize)> {
+    let (mut records, mut skipped) = (Vec::new(), 0);
+    let (mut month, last) = (month_of(from), month_of(to));
+    while month <= last {
+        let (mut more, more_skipped) = read_history(&dir.join(history_file_name(month)))?;
+        records.append(&mut more);
+        skipped += more_skipped;
+        month = match month {
+            (year, 12) => (year + 1, 1),
+            (year, m) => (year, m + 1),
+        };
+    }
+    Ok((records, skipped))
+}
+
+/// Divide a `history.jsonl` from before the split into monthly files, then
+/// remove it
+///
+/// Each month's file starts with the `session_started` lines of the sessions
+/// still open at the time, as the live log writes them.
+fn split_legacy_log(dir: &Path) -> Result<()> {
+    let legacy = dir.join(LEGACY_HISTORY_FILE_NAME);
+    if !legacy.exists() {
+        return Ok(());
+    }
+    let (records, skipped) = read_history(&legacy)?;
+    let mut open_starts: HashMap<SessionId, &HistoryRecord> = HashMap::new();
+    let mut months: BTreeMap<Month, String> = BTreeMap::new();
+    for record in &records {
+        let text = months.entry(month_of(record.at)).or_insert_with(|| {
+            open_starts
+                .values()
+                .filter_map(|start| serde_json::to_string(start).ok())
+                .map(|line| line + "\n")
+                .collect()
+        });
+        text.push_str(&serde_json::to_string(record)?);
+        text.push('\n');
+        match record.event {
+            HistoryEvent::SessionStarted { .. } => {
+                open_starts.insert(record.session_id, record);
+            }
+            HistoryEvent::SessionEnded { .. } => {
+                open_starts.remove(&record.session_id);
+            }
+            _ => {}
+        }
+    }
+    for (month, text) in months {
+        let path = dir.join(history_file_name(month));
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .and_then(|mut file| file.write_all(text.as_bytes()))
+            .with_context(|| format!("writing {}", path.display()))?;
+    }
+    std::fs::remove_file(&legacy).with_context(|| format!("removing {}", legacy.display()))?;
+    tracing::info!(
+        records = records.len(),
+        skipped,
+        "Split {} into monthly files",
+        LEGACY_HISTORY_FILE_NAME
+    );
+    Ok(())
+}
+
+/// Read every record in one file of the log, oldest first
+///
+/// A missing file is an empty history. Lines that do not parse - a write torn
+/// by a crash, or a record from a newer version - are skipped and counted.
+pub fn read_history(path: &Path) -> Result<(Vec<HistoryRecord>, usize)> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok((Vec::new(), 0)),
+        Err(e) => {
+            return Err(e).with_context(|| format!("reading {}", path.display()));
+        }
+    };
+    let mut records = Vec::new();
+    let mut skipped = 0;
+    for line in BufReader::new(file).lines() {
+        let line = line.with_context(|| format!("reading {}", path.display()))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str(&line) {
+            Ok(record) => records.push(record),
+            Err(_) => skipped += 1,
+        }
+    }
+    Ok((records, skipped))
+}
+
+fn millis(d: Duration) -> u64 {
+    d.num_milliseconds().max(0) as u64
+}
+
+/// How many days the stats view covers
+pub const STATS_DAYS: i64 = 7;
+
+/// The figures the stats view shows, over the last [`STATS_DAYS`] days
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HistoryStats {
+    /// Turns started on each local day, oldest first, every day present
+    pub turns_per_day: Vec<(NaiveDate, usize)>,
+    /// Turns that ran to completion
+    pub turns: usize,
+    /// Turns the user interrupted
+    pub aborted_turns: usize,
+    /// Median completed-turn duration
+    pub median_turn: Option<Duration>,
+    /// Total time agents spent inside turns
+    pub working: Duration,
+    /// Permission dialogs answered
+    pub approvals: usize,
+    /// Total time those dialogs waited on the user
+    pub awaiting_approval: Duration,
+    /// Median wait per dialog
+    pub median_approval: Option<Duration>,
+    /// The tools that ran longest in total: name, invocations, total time
+    pub top_tools: Vec<(String, usize, Duration)>,
+    /// Tokens per project, most first
+    pub tokens_per_project: Vec<(ProjectId, u64)>,
+    /// Lines of the log that could not be read
+    pub skipped: usize,
+}
+
+/// Most tools the stats view lists
+const TOP_TOOLS: usize = 5;
+
+impl HistoryStats {
+    /// Read the log in `dir` and summarise the last [`STATS_DAYS`] days
+    pub fn load(dir: &Path, now: DateTime<Utc>) -> Result<Self> {
+        let (records, skipped) = read_months(dir, now - Duration::days(STATS_DAYS), now)?;
+        Ok(Self {
+            skipped,
+            ..Self::compute(&records, now)
+        })
+    }
+
+    pub fn compute(records: &[HistoryRecord], now: DateTime<Utc>) -> Self {
+        let from = now - Duration::days(STATS_DAYS);
+        let today = now.with_timezone(&Local).date_naive();
+        let mut turns_per_day: Vec<(NaiveDate, usize)> = (0..STATS_DAYS)
+            .rev()
+            .map(|back| (today - Duration::days(back), 0))
+            .collect();
+
+        // Project membership comes from the start record, which may predate
+        // the window for a long-lived session
+        let projects: HashMap<SessionId, ProjectId> = records
+            .iter()
+            .filter_map(|r| match r.event {
+                HistoryEvent::SessionStarted { project_id, .. } => Some((r.session_id, project_id)),
+                _ => None,
+            })
+            .collect();
+
+        let mut stats = Self::default();
+        let mut turn_times = Vec::new();
+        let mut approval_waits = Vec::new();
+        let mut tools: HashMap<&str, (usize, Duration)> = HashMap::new();
+        // Usage is cumulative per conversation and a resumed session reports
+        // it again, so each session counts once, at its highest figure
+        let mut session_tokens: HashMap<SessionId, u64> = HashMap::new();
+
+        for record in records.iter().filter(|r| r.at >= from && r.at <= now) {
+            match &record.event {
+                HistoryEvent::Turn {
+                    duration_ms,
+                    aborted,
+                } => {
+                    let duration = Duration::milliseconds(*duration_ms as i64);
+                    let started = (record.at - duration).with_timezone(&Local).date_naive();
+                    if let Some(day) = turns_per_day.iter_mut().find(|(d, _)| *d == started) {
+                        day.1 += 1;
+                    }
+                    stats.working += duration;
+                    if *aborted {
+                        stats.aborted_turns += 1;
+                    } else {
+                        stats.turns += 1;
+                        turn_times.push(duration);
+                    }
+                }
+                HistoryEvent::Tool { name, duration_ms } => {
+                    let entry = tools.entry(name.as_str()).or_default();
+                    entry.0 += 1;
+                    entry.1 += Duration::milliseconds(*duration_ms as i64);
+                }
+                HistoryEvent::Approval { waited_ms, .. } => {
+                    let waited = Duration::milliseconds(*waited_ms as i64);
+                    stats.approvals += 1;
+                    stats.awaiting_approval += waited;
+                    approval_waits.push(waited);
+                }
+                HistoryEvent::SessionEnded {
+                    usage: Some(usage), ..
+                } => {
+                    if let Some(tokens) = usage.total_tokens {
+                        let best = session_tokens.entry(record.session_id).or_default();
+                        *best = (*best).max(tokens);
+                    }
+                }
+                HistoryEvent::SessionStarted { .. }
+                | HistoryEvent::SessionEnded { .. }
+                | HistoryEvent::Spend { .. } => {}
+            }
+        }
+
+        stats.turns_per_day = turns_per_day;
+        stats.median_turn = median(turn_times);
+        stats.median_approval = median(approval_waits);
+
+        let mut top_tools: Vec<(String, usize, Duration)> = tools
+            .into_iter()
+            .map(|(name, (count, total))| (name.to_string(), count, total))
+            .collect();
+        top_tools.sort_by(|a, b| b.2.cmp(&a.2).then_with(|| a.0.cmp(&b.0)));
+        top_tools.truncate(TOP_TOOLS);
+        stats.top_tools = top_tools;
+
+        let mut per_project: HashMap<ProjectId, u64> = HashMap::new();
+        for (session_id, tokens) in session_tokens {
+            if let Some(project_id) = projects.get(&session_id) {
+                *per_project.entry(*project_id).or_default() += tokens;
+            }
+        }
+        let mut tokens_per_project: Vec<(ProjectId, u64)> = per_project.into_iter().collect();
+        tokens_per_project.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        stats.tokens_per_project = tokens_per_project;
+
+        stats
+    }
+}
+
+fn median(mut values: Vec<Duration>) -> Option<Duration> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort();
+    let mid = values.len() / 2;
+    Some(if values.len() % 2 == 0 {
+        (values[mid - 1] + values[mid]) / 2
+    } else {
+        values[mid]
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use uuid::Uuid;
+
+    fn info() -> SessionInfo {
+        SessionInfo::new(
+            "auth-fix".to_string(),
+            PathBuf::from("/tmp"),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+        )
+    }
+
+    fn record(session_id: SessionId, at: DateTime<Utc>, event: HistoryEvent) -> HistoryRecord {
+        HistoryRecord {
+            at,
+            session_id,
+            event,
+        }
+    }
+
+    #[test]
+    fn test_records_round_trip_through_the_file_and_torn_lines_are_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut log = HistoryLog::new(dir.path().to_path_buf());
+        let info = info();
+        let now = Utc::now();
+        let path = dir.path().join(history_file_name(month_of(now)));
+
+        log.session_started(&info, false, now);
+        log.session_ended(&info, "closed", now);
+        std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(b"{\"at\":\"2026-")
+            .unwrap();
+
+        let (records, skipped) = read_history(&path).unwrap();
+        assert_eq!(skipped, 1);
+        assert_eq!(records.len(), 2);
+        assert!(matches!(
+            &records[0].event,
+            HistoryEvent::SessionStarted { name, resumed: false, .. } if name == "auth-fix"
+        ));
+        assert!(matches!(
+            &records[1].event,
+            HistoryEvent::SessionEnded { reason, usage: None } if reason == "closed"
+        ));
+    }
+
+    #[test]
+    fn test_a_missing_log_is_an_empty_history() {
+        let dir = tempfile::tempdir().unwrap();
+        let now = Utc::now();
+        let (records, skipped) =
+            read_months(dir.path(), now - Duration::days(STATS_DAYS), now).unwrap();
+        assert!(records.is_empty());
+        assert_eq!(skipped, 0);
+    }
+
+    #[test]
+    fn test_observe_times_turns_tools_and_approvals() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut log = HistoryLog::new(dir.path().to_path_buf());
+        let mut info = info();
+        let t0 = Utc::now();
+        let config = crate::config::Config::default();
+        let apply = |info: &mut SessionInfo, log: &mut HistoryLog, event, secs| {
+            let now = t0 + Duration::seconds(secs);
+            let prior = Prior::capture(info, &event);
+            crate::session::state_machine::apply(info, event, now, &config);
+            log.observe(info, prior, now);
+        };
+
+        apply(
+            &mut info,
+            &mut log,
+            AgentEvent::TurnStarted { title: None },
+            0,
+        );
+        apply(
+            &mut info,
+            &mut log,
+            AgentEvent::ToolStarted {
+                key: "t1".to_string(),
+                name: "Bash".to_string(),
+            },
+            5,
+        );
+        apply(
+            &mut info,
+            &mut log,
+            AgentEvent::ApprovalRequested {
+                tool: Some("Bash".to_string()),
+            },
+            6,
+        );
+        apply(
+            &mut info,
+            &mut log,
+            AgentEvent::ToolFinished {
+                key: "t1".to_string(),
+            },
+            36,
+        );
+        apply(
+            &mut info,
+            &mut log,
+            AgentEvent::TurnCompleted { last_message: None },
+            60,
+        );
+
+        let (records, _) = read_months(dir.path(), t0, t0 + Duration::seconds(60)).unwrap();
+        let events: Vec<&HistoryEvent> = records.iter().map(|r| &r.event).collect();
+        assert_eq!(
+            events,
+            vec![
+                &HistoryEvent::Tool {
+                    name: "Bash".to_string(),
+                    duration_ms: 31_000
+                },
+                &HistoryEvent::Turn {
+                    duration_ms: 60_000,
+                    aborted: false
+                },
+                // The dialog is up until the state machine leaves
+                // AwaitingApproval, which here is the end of the turn
+                &HistoryEvent::Approval {
+                    tool: Some("Bash".to_string()),
+                    waited_ms: 54_000
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_each_turn_records_only_what_it_spent() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut log = HistoryLog::new(dir.path().to_path_buf());
+        let mut info = info();
+        info.usage.model = Some("claude-sonnet-4-5".to_string());
+        let now = Utc::now();
+        let turn_ended = |info: &SessionInfo| Prior::capture(info, &AgentEvent::TurnAborted);
+
+        info.usage.spent.output = 400;
+        log.observe(&info, turn_ended(&info), now);
+        // Nothing new since: no line
+        log.observe(&info, turn_ended(&info), now);
+        info.usage.spent.output = 1_000;
+        log.session_ended(&info, "closed", now);
+
+        let (records, _) = read_months(dir.path(), now, now).unwrap();
+        let spends: Vec<u64> = records
+            .iter()
+            .filter_map(|r| match &r.event {
+                HistoryEvent::Spend { tokens, model } => {
+                    assert_eq!(model.as_deref(), Some("claude-sonnet-4-5"));
+                    Some(tokens.output)
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(spends, vec![400, 600]);
+    }
+
+    fn local(year: i32, month: u32, day: u32) -> DateTime<Utc> {
+        Local
+            .with_ymd_and_hms(year, month, day, 12, 0, 0)
+            .single()
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn started_in(records: &[HistoryRecord]) -> Vec<SessionId> {
+        records
+            .iter()
+            .filter(|r| matches!(r.event, HistoryEvent::SessionStarted { .. }))
+            .map(|r| r.session_id)
+            .collect()
+    }
+
+    #[test]
+    fn test_each_month_has_its_own_file_and_open_sessions_carry_over() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut log = HistoryLog::new(dir.path().to_path_buf());
+        let (long, short) = (info(), info());
+        let (jan, feb) = (local(2026, 1, 30), local(2026, 2, 2));
+
+        log.session_started(&long, false, jan);
+        log.session_started(&short, false, jan);
+        log.session_ended(&short, "closed", jan);
+        log.session_ended(&long, "closed", feb);
+
+        let (january, _) = read_months(dir.path(), jan, jan).unwrap();
+        let (february, _) = read_months(dir.path(), feb, feb).unwrap();
+        assert_eq!(january.len(), 3);
+        // Only the session still open crosses over, with its original time
+        assert_eq!(started_in(&february), vec![long.id]);
+        assert_eq!(february[0].at, jan);
+        let (both, _) = read_months(dir.path(), jan, feb).unwrap();
+        assert_eq!(both.len(), january.len() + february.len());
+    }
+
+    #[test]
+    fn test_a_single_file_log_is_split_by_month() {
+        let dir = tempfile::tempdir().unwrap();
+        let (long, short) = (Uuid::new_v4(), Uuid::new_v4());
+        let (jan, feb) = (local(2026, 1, 30), local(2026, 2, 2));
+        let started = || HistoryEvent::SessionStarted {
+            name: "s".to_string(),
+            project_id: Uuid::new_v4(),
+            branch_id: Uuid::new_v4(),
+            session_type: SessionType::ClaudeCode,
+            resumed: false,
+            account: None,
+        };
+        let ended = || HistoryEvent::SessionEnded {
+            reason: "closed".to_string(),
+            usage: None,
+        };
+        let legacy: String = [
+            record(long, jan, started()),
+            record(short, jan, started()),
+            record(short, jan, ended()),
+            record(long, feb, ended()),
+        ]
+        .iter()
+        .map(|r| serde_json::to_string(r).unwrap() + "\n")
+        .collect();
+        std::fs::write(dir.path().join(LEGACY_HISTORY_FILE_NAME), legacy).unwrap();
+
+        HistoryLog::new(dir.path().to_path_buf());
+
+        assert!(!dir.path().join(LEGACY_HISTORY_FILE_NAME).exists());
+        let (january, _) = read_months(dir.path(), jan, jan).unwrap();
+        let (february, _) = read_months(dir.path(), feb, feb).unwrap();
+        assert_eq!(january.len(), 3);
+        assert_eq!(started_in(&february), vec![long]);
+        assert_eq!(february.len(), 2);
+    }
+
+    #[test]
+    fn test_stats_summarise_the_week() {
+        let now = Utc::now();
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        let project = Uuid::new_v4();
+        let started = || HistoryEvent::SessionStarted {
+            name: "s".to_string(),
+            project_id: project,
+            branch_id: Uuid::new_v4(),
+            session_type: SessionType::ClaudeCode,
+            resumed: false,
+            account: None,
+        };
+        let ended = |tokens| HistoryEvent::SessionEnded {
+            reason: "closed".to_string(),
+            usage: Some(UsageSnapshot {
+                total_tokens: Some(tokens),
+                ..Default::default()
+            }),
+        };
+        let turn = |secs: u64, aborted| HistoryEvent::Turn {
+            duration_ms: secs * 1000,
+            aborted,
+        };
+        let records = vec![
+            // Older than the window: ignored, but its project still counts
+            record(a, now - Duration::days(30), started()),
+            record(a, now - Duration::days(30), turn(999, false)),
+            record(b, now - Duration::hours(3), started()),
+            record(a, now - Duration::hours(2), turn(60, false)),
+            record(a, now - Duration::hours(2), turn(120, false)),
+            record(b, now - Duration::hours(2), turn(600, false)),
+            record(b, now - Duration::hours(2), turn(5, true)),
+            record(
+                b,
+                now - Duration::hours(1),
+                HistoryEvent::Approval {
+                    tool: None,
+                    waited_ms: 90_000,
+                },
+            ),
+            record(
+                b,
+                now - Duration::hours(1),
+                HistoryEvent::Tool {
+                    name: "Bash".to_string(),
+                    duration_ms: 4_000,
+                },
+            ),
+            record(a, now - Duration::minutes(30), ended(1_000)),
+            // Resumed and ended again: counted once, at the higher figure
+            record(a, now - Duration::minutes(10), ended(1_500)),
+            record(b, now - Duration::minutes(10), ended(500)),
+        ];
+
+        let stats = HistoryStats::compute(&records, now);
+        assert_eq!(stats.turns, 3);
+        assert_eq!(stats.aborted_turns, 1);
+        assert_eq!(stats.median_turn, Some(Duration::seconds(120)));
+        assert_eq!(stats.working, Duration::seconds(785));
+        assert_eq!(stats.approvals, 1);
+        assert_eq!(stats.awaiting_approval, Duration::seconds(90));
+        assert_eq!(
+            stats.top_tools,
+            vec![("Bash".to_string(), 1, Duration::seconds(4))]
+        );
+        assert_eq!(stats.tokens_per_project, vec![(project, 2_000)]);
+        assert_eq!(stats.turns_per_day.len(), STATS_DAYS as usize);
+        assert_eq!(stats.turns_per_day.iter().map(|(_, n)| n).sum::<usize>(), 4);
+    }
+}
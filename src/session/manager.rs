@@ -14,13 +14,14 @@ use uuid::Uuid;
 use crate::agent::adapter::SpawnConfig;
 use crate::agent::events::AgentEvent;
 use crate::agent::AgentType;
-use crate::config::{Config, NotificationMethod};
+use crate::config::Config;
 use crate::hooks::HookEvent;
-use crate::project::{BranchId, ProjectId};
+use crate::project::{BranchId, ProjectId, ProjectStore};
 
+use super::budget::{account_key, BudgetAlert, Tally};
 use super::{
-    state_machine, AttentionReason, Session, SessionId, SessionInfo, SessionState, SessionStore,
-    SessionType,
+    history, state_machine, AttentionReason, CodexLimits, HistoryLog, Session, SessionId,
+    SessionInfo, SessionState, SessionStore, SessionType,
 };
 
 /// Everything needed to create a brand-new session
@@ -49,6 +50,8 @@ pub struct NewSessionSpec {
     /// Set at creation so the flag is in place before the command can finish,
     /// rather than patched on afterwards.
     pub auto_close: bool,
+    /// Model to ask the agent for (agents only); None = its own default
+    pub model: Option<String>,
 }
 
 /// The account profile a new session runs under
@@ -83,6 +86,13 @@ pub struct SessionManager {
     /// inert until the user opens one, at which point the entry moves from here
     /// into `sessions`.
     recovered: HashMap<SessionId, SessionInfo>,
+    /// Append-only log of what sessions did, for the stats view
+    history: HistoryLog,
+    /// The turn each session was last flagged over budget in, keyed by when
+    /// it started (`None` for a turn begun before Panoptes was watching)
+    budget_alerts: HashMap<SessionId, Option<DateTime<Utc>>>,
+    /// The latest Codex rate-limit reading per account
+    codex_limits: CodexLimits,
     /// Run every session on a shell process, whatever its record says
     ///
     /// The one thing a test cannot do is launch a real Claude Code or Codex,
@@ -128,12 +138,23 @@ impl SessionManager {
         let mut store = store;
         Self::drop_unpersistable(&mut store);
         let recovered = Self::reconcile(&store);
+        // Beside the store, so a test's temp store brings a temp log with it
+        let history = HistoryLog::new(
+            store
+                .path()
+                .parent()
+                .map(std::path::Path::to_path_buf)
+                .unwrap_or_default(),
+        );
         Self {
             sessions: HashMap::new(),
             session_order: Vec::new(),
             config,
             store,
             recovered,
+            history,
+            budget_alerts: HashMap::new(),
+            codex_limits: CodexLimits::default(),
             #[cfg(test)]
             spawn_as_shell: false,
         }
@@ -193,7 +214,8 @@ impl SessionManager {
             .collect()
     }
 
-    /// Adopt the settings the Settings pane can change while Panoptes runs
+    /// Adopt the settings that can change while Panoptes runs, from the
+    /// Settings pane or a reloaded `config.toml`
     ///
     /// The manager keeps its own copy of the config, and the state machine
     /// reads *that* copy on every event - so a toggle in `App::config` would
@@ -204,6 +226,8 @@ impl SessionManager {
         self.config.notification_method = config.notification_method;
         self.config.notify_on = config.notify_on.clone();
         self.config.attention_on_idle = config.attention_on_idle;
+        self.config.budgets = config.budgets.clone();
+        self.config.pricing = config.pricing.clone();
     }
 
     /// Access the durable session store
@@ -221,6 +245,16 @@ impl SessionManager {
         self.recovered.len()
     }
 
+    /// The latest Codex rate-limit reading per account
+    pub fn codex_limits(&self) -> &CodexLimits {
+        &self.codex_limits
+    }
+
+    /// The directory the session history is written to
+    pub fn history_dir(&self) -> &std::path::Path {
+        self.history.dir()
+    }
+
     /// Look up a recovered session by ID
     pub fn get_recovered(&self, session_id: SessionId) -> Option<&SessionInfo> {
         self.recovered.get(&session_id)
@@ -315,30 +349,9 @@ impl SessionManager {
             return Err(anyhow!("Cannot resume '{}': {}", info.name, reason));
         }
 
-        let agent_type = AgentType::from(info.session_type);
-
-        let spawn_config = SpawnConfig {
-            session_id,
-            session_name: info.name.clone(),
-            working_dir: info.working_dir.clone(),
-            initial_prompt: None,
-            rows: rows as u16,
-            cols: cols as u16,
-            claude_config_dir,
-            codex_home,
-            resume: info.resume_cursor(),
-        };
-
-        let mut info = info;
-        info.state = SessionState::Starting;
-        info.state_entered_at = Utc::now();
-        info.last_activity = Utc::now();
-        // Its transcript holds a whole prior conversation; reading from the
-        // start would replay it as if it were happening now
-        info.resumed_conversation = true;
-
         // A failed spawn returns here with the recovery entry untouched
-        self.spawn_and_register(info, spawn_config, agent_type, rows, cols)?;
+        self.relaunch(info, rows, cols, claude_config_dir, codex_home)?;
+        self.record_started(session_id, true);
 
         // Only now that the process exists does the session stop being "recovered"
         self.recovered.remove(&session_id);
@@ -497,6 +510,7 @@ impl SessionManager {
             initial_prompt,
             account,
             auto_close,
+            model,
         } = spec;
 
         let mut info = match agent {
@@ -505,6 +519,9 @@ impl SessionManager {
             AgentType::Shell => SessionInfo::shell(name, working_dir, project_id, branch_id),
         };
         info.auto_close_after_command = auto_close;
+        if agent != AgentType::Shell {
+            info.model = model;
+        }
 
         let mut claude_config_dir = None;
         let mut codex_home = None;
@@ -544,9 +561,11 @@ impl SessionManager {
             claude_config_dir,
             codex_home,
             resume: None,
+            model: info.model.clone(),
         };
 
         let session_id = self.spawn_and_register(info, spawn_config, agent, rows, cols)?;
+        self.record_started(session_id, false);
 
         if is_shell {
             if let Some(command) = initial_prompt {
@@ -582,6 +601,68 @@ impl SessionManager {
         agent.create_adapter()
     }
 
+    /// Start a session's agent again on its recorded conversation
+    ///
+    /// The shared body of resuming, waking and moving to another account:
+    /// `info` goes back to Starting, with the relaunch on its timeline and no
+    /// tools in flight, and its transcript is read as a resumed one (it holds
+    /// a whole prior conversation; reading from the start would replay it as
+    /// if it were happening now). A process the session still has is killed
+    /// first; should the spawn then fail, the session is left suspended so
+    /// waking it retries. Without one, a failure leaves the manager untouched.
+    fn relaunch(
+        &mut self,
+        mut info: SessionInfo,
+        rows: usize,
+        cols: usize,
+        claude_config_dir: Option<PathBuf>,
+        codex_home: Option<PathBuf>,
+    ) -> Result<()> {
+        let session_id = info.id;
+        let spawn_config = SpawnConfig {
+            session_id,
+            session_name: info.name.clone(),
+            working_dir: info.working_dir.clone(),
+            initial_prompt: None,
+            rows: rows as u16,
+            cols: cols as u16,
+            claude_config_dir,
+            codex_home,
+            resume: info.resume_cursor(),
+            model: info.model.clone(),
+        };
+        let now = Utc::now();
+        info.state = SessionState::Starting;
+        info.state_entered_at = now;
+        info.last_activity = now;
+        info.timeline.record(SessionState::Starting, now);
+        info.in_flight.clear();
+        info.resumed_conversation = true;
+
+        let running = self
+            .sessions
+            .get_mut(&session_id)
+            .filter(|session| session.info.state.has_process());
+        let killed = match running {
+            Some(session) => {
+                session.kill()?;
+                true
+            }
+            None => false,
+        };
+
+        let agent = AgentType::from(info.session_type);
+        if let Err(e) = self.spawn_and_register(info, spawn_config, agent, rows, cols) {
+            if killed {
+                if let Some(session) = self.sessions.get_mut(&session_id) {
+                    session.set_state(SessionState::Suspended);
+                }
+            }
+            return Err(e);
+        }
+        Ok(())
+    }
+
     /// Spawn the agent process and take ownership of the resulting session
     ///
     /// The shared tail of every path that brings a process to life - create,
@@ -624,6 +705,13 @@ impl SessionManager {
         Ok(session_id)
     }
 
+    fn record_started(&mut self, session_id: SessionId, resumed: bool) {
+        if let Some(session) = self.sessions.get(&session_id) {
+            self.history
+                .session_started(&session.info, resumed, Utc::now());
+        }
+    }
+
     /// Take ownership of a session, keeping the twin collections consistent
     ///
     /// `sessions` and `session_order` must always agree; every insertion goes
@@ -654,6 +742,10 @@ impl SessionManager {
             if session.is_alive() {
                 session.kill()?;
             }
+            if session.info.state != SessionState::Exited {
+                self.history
+                    .session_ended(&session.info, "closed", Utc::now());
+            }
 
             // Closing a session is an explicit discard, so drop its durable
             // record too. Quitting Panoptes deliberately does not do this.
@@ -796,6 +888,9 @@ impl SessionManager {
                         crashed_sessions.push((session.info.id, session.info.name.clone(), reason));
                     }
                     session.set_state(SessionState::Exited);
+                    let reason = session.info.exit_reason.as_deref().unwrap_or("exited");
+                    self.history
+                        .session_ended(&session.info, reason, Utc::now());
                 }
             }
         }
@@ -863,6 +958,133 @@ impl SessionManager {
         changed
     }
 
+    /// Flag, and optionally interrupt, working sessions over a `[budgets]` limit
+    ///
+    /// Project and account totals are this month's recorded spending plus
+    /// whatever live sessions have spent since they last recorded any, so a
+    /// runaway turn is caught while it runs rather than when it ends. The
+    /// session's own limit is checked first, then its project's, then its
+    /// account's, and the first one crossed is the one reported.
+    ///
+    /// Each turn is flagged at most once. Returns the sessions flagged.
+    pub fn check_budgets(
+        &mut self,
+        projects: &ProjectStore,
+        now: DateTime<Utc>,
+    ) -> Vec<BudgetAlert> {
+        let budgets = &self.config.budgets;
+        if budgets.is_empty() {
+            return Vec::new();
+        }
+        let pricing = &self.config.pricing;
+
+        let (mut project_spend, mut account_spend) = self.history.month().tallies(pricing, now);
+        for session in self.sessions.values() {
+            let unrecorded = self.history.unrecorded_spend(&session.info);
+            let model = session.info.usage.model.as_deref();
+            project_spend
+                .entry(session.info.project_id)
+                .or_default()
+                .add(model, &unrecorded, pricing);
+            account_spend
+                .entry(account_key(session.info.account_name()))
+                .or_default()
+                .add(model, &unrecorded, pricing);
+        }
+
+        self.budget_alerts
+            .retain(|id, _| self.sessions.contains_key(id));
+        let mut alerts = Vec::new();
+        for session in self.sessions.values_mut() {
+            let info = &session.info;
+            if info.session_type == SessionType::Shell
+                || !matches!(info.state, SessionState::Thinking | SessionState::Executing)
+            {
+                continue;
+            }
+            let turn_started = self.history.turn_started(info.id);
+            if self.budget_alerts.get(&info.id) == Some(&turn_started) {
+                continue;
+            }
+            let turn = turn_started.map(|started| now - started);
+
+            let mut spent = Tally::default();
+            spent.add(info.usage.model.as_deref(), &info.usage.spent, pricing);
+            let project_name = projects
+                .get_project(info.project_id)
+                .map(|p| p.name.as_str());
+            let account = account_key(info.account_name());
+            let project_limit = project_name.and_then(|name| {
+                let limit = budgets.projects.get(name)?;
+                let spent = project_spend
+                    .get(&info.project_id)
+                    .copied()
+                    .unwrap_or_default();
+                Some(format!(
+                    "project {} {}",
+                    name,
+                    limit.exceeded(&spent, turn)?
+                ))
+            });
+            let account_limit = || {
+                let limit = budgets.accounts.get(&account)?;
+                let spent = account_spend.get(&account).copied().unwrap_or_default();
+                Some(format!(
+                    "account {} {}",
+                    account,
+                    limit.exceeded(&spent, turn)?
+                ))
+            };
+            let Some(limit) = budgets
+                .session
+                .exceeded(&spent, turn)
+                .map(|limit| format!("session {}", limit))
+                .or(project_limit)
+                .or_else(account_limit)
+            else {
+                continue;
+            };
+
+            tracing::warn!(
+                session_id = %info.id,
+                session_name = %info.name,
+                limit = %limit,
+                interrupt = budgets.interrupt,
+                "Session over budget"
+            );
+            self.budget_alerts.insert(info.id, turn_started);
+            let reason = AttentionReason::OverBudget {
+                limit: limit.clone(),
+            };
+            let rings =
+                info.attention.as_ref() != Some(&reason) && self.config.notify_on.rings(&reason);
+            let mut interrupted = false;
+            if budgets.interrupt {
+                let esc = crossterm::event::KeyEvent::new(
+                    crossterm::event::KeyCode::Esc,
+                    crossterm::event::KeyModifiers::NONE,
+                );
+                match session.send_key(esc) {
+                    Ok(()) => interrupted = true,
+                    Err(e) => tracing::warn!(
+                        session_id = %session.info.id,
+                        error = %e,
+                        "Failed to interrupt a session over budget"
+                    ),
+                }
+            }
+            session.info.attention = Some(reason);
+            alerts.push(BudgetAlert {
+                session_id: session.info.id,
+                session_name: session.info.name.clone(),
+                limit,
+                interrupted,
+                rings,
+            });
+        }
+        alerts
+    }
+
     /// Check shell session states by polling foreground process detection
     ///
     /// For shell sessions (SessionType::Shell), this checks whether a command
@@ -1064,39 +1286,84 @@ impl SessionManager {
             return Err(anyhow!("Cannot wake '{}': {}", info.name, reason));
         }
 
-        let agent_type = AgentType::from(info.session_type);
-
-        let spawn_config = SpawnConfig {
-            session_id,
-            session_name: info.name.clone(),
-            working_dir: info.working_dir.clone(),
-            initial_prompt: None,
-            rows: rows as u16,
-            cols: cols as u16,
-            claude_config_dir,
-            codex_home,
-            resume: info.resume_cursor(),
-        };
-
         let mut info = info;
-        info.state = SessionState::Starting;
-        info.state_entered_at = Utc::now();
-        info.last_activity = Utc::now();
         info.last_engagement = Utc::now();
-        info.in_flight.clear();
-        info.resumed_conversation = true;
 
         // Spawning happens before the existing entry is touched, so a failure
         // leaves the suspended session exactly as it was. On success the new
         // session replaces the suspended entry; its position in
         // `session_order` is keyed by ID and so is untouched.
-        self.spawn_and_register(info, spawn_config, agent_type, rows, cols)?;
+        self.relaunch(info, rows, cols, claude_config_dir, codex_home)?;
 
         tracing::info!(session_id = %session_id, "Woke suspended session");
 
         Ok(())
     }
 
+    /// Move a Codex session's conversation to another account
+    ///
+    /// The rollout is copied into the other account's `CODEX_HOME` first,
+    /// since `codex resume` only looks in its own, and the agent is then
+    /// relaunched there on the same conversation, exactly as waking does. A
+    /// suspended session just changes account and wakes on the new one.
+    ///
+    /// A session in the middle of a turn is refused rather than cut off. On a
+    /// failed relaunch the session is left suspended on its old account, so
+    /// waking it brings it back where it was.
+    pub fn switch_codex_account(
+        &mut self,
+        session_id: SessionId,
+        rows: usize,
+        cols: usize,
+        account: AgentAccount,
+        from_home: &std::path::Path,
+        to_home: &std::path::Path,
+    ) -> Result<()> {
+        let info = self
+            .sessions
+            .get(&session_id)
+            .ok_or_else(|| anyhow!("No session with ID {}", session_id))?
+            .info
+            .clone();
+        if info.session_type != SessionType::OpenAICodex {
+            return Err(anyhow!("'{}' is not a Codex session", info.name));
+        }
+        if let Some(reason) = info.resume_blocker() {
+            return Err(anyhow!("Cannot move '{}': {}", info.name, reason));
+        }
+        if info.state.is_mid_turn() {
+            return Err(anyhow!("'{}' is mid-turn", info.name));
+        }
+        let conversation_id = info.resume_cursor().unwrap_or_default();
+        crate::agent::codex::copy_rollout(from_home, to_home, &conversation_id)?;
+
+        let mut info = info;
+        info.codex_config_id = Some(account.id);
+        info.codex_config_name = Some(account.name.clone());
+        // The readings were the old account's
+        info.usage.rate_limit_used_percent = None;
+        info.usage.rate_limit_resets_at = None;
+
+        if !info.state.has_process() {
+            if let Some(session) = self.sessions.get_mut(&session_id) {
+                session.info = info;
+            }
+            self.persist_session(session_id);
+            return Ok(());
+        }
+
+        self.relaunch(info, rows, cols, None, account.dir.clone())?;
+        // Spending from here on is the new account's
+        self.record_started(session_id, true);
+
+        tracing::info!(
+            session_id = %session_id,
+            account = %account.name,
+            "Moved Codex session to another account"
+        );
+        Ok(())
+    }
+
     /// Clean up exited sessions that have been exited longer than retention_secs
     /// Returns the number of sessions cleaned up
     pub fn cleanup_exited_sessions(&mut self, retention_secs: u64) -> usize {
@@ -1169,27 +1436,17 @@ impl SessionManager {
             }
         };
 
-        let applied = state_machine::apply(&mut session.info, event, Utc::now(), &self.config);
-        applied.rang.then_some(session_id)
-    }
-
-    /// Send a notification via the configured method
-    pub fn send_notification(method: NotificationMethod, session_name: &str) {
-        match method {
-            NotificationMethod::Bell => {
-                print!("\x07"); // ASCII bell character
-                std::io::stdout().flush().ok();
-            }
-            NotificationMethod::Title => {
-                // Update terminal title using OSC escape sequence
-                // Format: ESC ] 0 ; title BEL
-                print!("\x1b]0;[!] {} needs attention\x07", session_name);
-                std::io::stdout().flush().ok();
-            }
-            NotificationMethod::None => {
-                // Do nothing
+        let now = Utc::now();
+        if let AgentEvent::Usage(usage) = &event {
+            if session.info.session_type == SessionType::OpenAICodex {
+                self.codex_limits
+                    .record(session.info.codex_config_id, usage);
             }
         }
+        let prior = history::Prior::capture(&session.info, &event);
+        let applied = state_machine::apply(&mut session.info, event, now, &self.config);
+        self.history.observe(&session.info, prior, now);
+        applied.rang.then_some(session_id)
     }
 
     /// Reset the terminal title to default (used after "title" notification mode)
@@ -1245,6 +1502,14 @@ impl SessionManager {
             );
         }
 
+        let now = Utc::now();
+        for session in self.sessions.values() {
+            if session.info.state != SessionState::Exited {
+                self.history
+                    .session_ended(&session.info, "Panoptes quit", now);
+            }
+        }
+
         for (id, session) in self.sessions.iter_mut() {
             if session.is_alive() {
                 tracing::debug!("Killing session {}", id);
@@ -1375,6 +1640,7 @@ impl SessionManager {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::NotificationMethod;
     use crate::hooks::HookEventType;
     use crate::session::SessionType;
     use tempfile::TempDir;
@@ -1399,7 +1665,7 @@ mod tests {
         }
     }
 
-    /// The Settings pane's six live fields must reach the copy the state
+    /// The Settings pane's seven live fields must reach the copy the state
     /// machine reads, and the startup-only ones must not follow them
     #[test]
     fn test_apply_runtime_config_copies_the_live_fields_only() {
@@ -1412,7 +1678,9 @@ mod tests {
         edited.notify_on.turn_complete = false;
         edited.notify_on.stalled = true;
         edited.notify_on.crashed = false;
+        edited.notify_on.over_budget = false;
         edited.attention_on_idle = true;
+        edited.budgets.session.cost = Some(5.0);
         // Startup-only: changing this mid-run would make one session's
         // scrollback disagree with the session beside it
         edited.scrollback_lines = 42;
@@ -1427,7 +1695,9 @@ mod tests {
         assert!(!manager.config.notify_on.turn_complete);
         assert!(manager.config.notify_on.stalled);
         assert!(!manager.config.notify_on.crashed);
+        assert!(!manager.config.notify_on.over_budget);
         assert!(manager.config.attention_on_idle);
+        assert_eq!(manager.config.budgets.session.cost, Some(5.0));
         assert_eq!(
             manager.config.scrollback_lines,
             Config::default().scrollback_lines,
@@ -1484,6 +1754,7 @@ mod tests {
                     initial_prompt: None,
                     account: None,
                     auto_close: false,
+                    model: None,
                 },
                 24,
                 80,
@@ -1509,6 +1780,7 @@ mod tests {
                     initial_prompt: None,
                     account: None,
                     auto_close: false,
+                    model: None,
                 },
                 24,
                 80,
@@ -1627,6 +1899,93 @@ mod tests {
         session_id
     }
 
+    #[test]
+    fn test_switching_codex_account_relaunches_under_the_other_home() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = test_config(&temp_dir);
+        let mut manager = test_manager(&temp_dir, config);
+        let (work, spare) = (TempDir::new().unwrap(), TempDir::new().unwrap());
+
+        // A Codex conversation, recorded under the work account's home
+        let conversation_id = Uuid::new_v4().to_string();
+        let day = work.path().join("sessions/2026/10/18");
+        std::fs::create_dir_all(&day).unwrap();
+        let meta = serde_json::json!({
+            "type": "session_meta",
+            "payload": { "id": conversation_id, "cwd": "/tmp" }
+        });
+        std::fs::write(
+            day.join(format!("rollout-1-{conversation_id}.jsonl")),
+            format!("{meta}\n"),
+        )
+        .unwrap();
+
+        manager.spawn_as_shell = true;
+        let session_id = manager
+            .create_session(
+                AgentType::OpenAICodex,
+                NewSessionSpec {
+                    name: "codex".to_string(),
+                    working_dir: PathBuf::from("/tmp"),
+                    project_id: Uuid::new_v4(),
+                    branch_id: Uuid::new_v4(),
+                    initial_prompt: None,
+                    account: None,
+                    auto_close: false,
+                    model: None,
+                },
+                24,
+                80,
+            )
+            .unwrap();
+        let session = manager.get_mut(session_id).unwrap();
+        session.info.agent_session_id = Some(conversation_id.clone());
+        session.info.usage.rate_limit_used_percent = Some(95.0);
+        session.set_state(SessionState::Executing);
+
+        let spare_account = AgentAccount {
+            id: Uuid::new_v4(),
+            name: "Spare".to_string(),
+            dir: Some(spare.path().to_path_buf()),
+        };
+        // Never in the middle of a turn
+        assert!(manager
+            .switch_codex_account(
+                session_id,
+                24,
+                80,
+                spare_account.clone(),
+                work.path(),
+                spare.path()
+            )
+            .is_err());
+
+        manager
+            .get_mut(session_id)
+            .unwrap()
+            .set_state(SessionState::Waiting);
+        manager
+            .switch_codex_account(
+                session_id,
+                24,
+                80,
+                spare_account.clone(),
+                work.path(),
+                spare.path(),
+            )
+            .unwrap();
+
+        let info = &manager.get(session_id).unwrap().info;
+        assert_eq!(info.codex_config_id, Some(spare_account.id));
+        assert_eq!(info.codex_config_name.as_deref(), Some("Spare"));
+        assert_eq!(info.usage.rate_limit_used_percent, None);
+        assert_eq!(info.state, SessionState::Starting);
+        assert!(info.resumed_conversation);
+        assert!(crate::agent::codex::rollout_path(spare.path(), &conversation_id).is_some());
+
+        manager.shutdown_all();
+    }
+
     #[test]
     fn test_suspend_kills_the_process_but_keeps_the_session() {
         let temp_dir = TempDir::new().unwrap();
@@ -1957,6 +2316,91 @@ mod tests {
         }
     }
 
+    /// A session with `spent` tokens of Sonnet output, mid-turn
+    fn insert_spending_session(
+        manager: &mut SessionManager,
+        project_id: ProjectId,
+        output: u64,
+    ) -> SessionId {
+        let session_id = manager
+            .insert_test_session("spender", project_id, uuid::Uuid::new_v4())
+            .expect("Failed to spawn test process");
+        let session = manager.get_mut(session_id).unwrap();
+        session.set_state(SessionState::Thinking);
+        session.info.usage.model = Some("claude-sonnet-4-5".to_string());
+        session.info.usage.spent.output = output;
+        session_id
+    }
+
+    #[test]
+    fn test_a_session_over_its_budget_is_flagged_and_interrupted_once_per_turn() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = test_config(&temp_dir);
+        config.budgets.interrupt = true;
+        config.budgets.session.cost = Some(5.0);
+        let mut manager = test_manager(&temp_dir, config);
+        let projects = ProjectStore::new();
+        // Sonnet output is $15 per million
+        let under = insert_spending_session(&mut manager, uuid::Uuid::new_v4(), 100_000);
+        let over = insert_spending_session(&mut manager, uuid::Uuid::new_v4(), 1_000_000);
+
+        let alerts = manager.check_budgets(&projects, Utc::now());
+        assert_eq!(alerts.len(), 1, "{alerts:?}");
+        assert_eq!(alerts[0].session_id, over);
+        assert_eq!(alerts[0].limit, "session $5.00");
+        assert!(alerts[0].interrupted);
+        assert!(alerts[0].rings);
+        assert_eq!(
+            manager.get(over).unwrap().info.attention,
+            Some(AttentionReason::OverBudget {
+                limit: "session $5.00".to_string()
+            })
+        );
+        assert_eq!(manager.get(under).unwrap().info.attention, None);
+
+        // Still over, but this turn has already been dealt with
+        assert!(manager.check_budgets(&projects, Utc::now()).is_empty());
+    }
+
+    #[test]
+    fn test_a_project_budget_counts_every_session_in_the_project() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = test_config(&temp_dir);
+        config.budgets.projects.insert(
+            "web".to_string(),
+            super::super::budget::BudgetLimit {
+                cost: Some(2.0),
+                ..Default::default()
+            },
+        );
+        let mut manager = test_manager(&temp_dir, config);
+        let mut projects = ProjectStore::new();
+        let project = crate::project::Project::new(
+            "web".to_string(),
+            PathBuf::from("/tmp/web"),
+            "main".to_string(),
+        );
+        let web = project.id;
+        projects.add_project(project);
+
+        // $1.50 each: neither is over on its own, together they are
+        let first = insert_spending_session(&mut manager, web, 100_000);
+        let second = insert_spending_session(&mut manager, web, 100_000);
+        let elsewhere = insert_spending_session(&mut manager, uuid::Uuid::new_v4(), 100_000);
+        // Not working, so not checked
+        manager
+            .get_mut(second)
+            .unwrap()
+            .set_state(SessionState::Waiting);
+
+        let alerts = manager.check_budgets(&projects, Utc::now());
+        assert_eq!(alerts.len(), 1, "{alerts:?}");
+        assert_eq!(alerts[0].session_id, first);
+        assert_eq!(alerts[0].limit, "project web $2.00");
+        assert!(!alerts[0].interrupted, "interrupting was not asked for");
+        assert_eq!(manager.get(elsewhere).unwrap().info.attention, None);
+    }
+
     #[test]
     fn test_stalled_codex_tools_are_evicted_too() {
         let temp_dir = TempDir::new().unwrap();
@@ -3,15 +3,25 @@
 //! This module handles Claude Code session lifecycle, PTY management,
 //! and session state tracking.
 
+pub mod budget;
+pub mod history;
 pub mod manager;
 pub mod pty;
+pub mod rate_limits;
+pub mod spend;
 pub mod state_machine;
 pub mod store;
+pub mod timeline;
 pub mod vterm;
 
+pub use budget::{BudgetAlert, BudgetsConfig};
+pub use history::{HistoryLog, HistoryStats};
 pub use manager::{AgentAccount, NewSessionSpec, SessionManager};
 pub use pty::{mouse_event_to_bytes, ExitInfo, PtyHandle, PtyWriteTimedOut};
+pub use rate_limits::{CodexLimits, RotationConfig, RotationOffer};
+pub use spend::SpendReport;
 pub use store::{sessions_file_path, SessionStore};
+pub use timeline::{Segment, StateTimeline};
 pub use vterm::{VirtualTerminal, DEFAULT_SCROLLBACK_ROWS};
 
 use std::collections::{HashMap, VecDeque};
@@ -169,6 +179,15 @@ impl SessionState {
         )
     }
 
+    /// Whether a turn is under way, counting one paused on a permission
+    /// dialog - cutting the process off now would lose the turn
+    pub fn is_mid_turn(&self) -> bool {
+        matches!(
+            self,
+            SessionState::Thinking | SessionState::Executing | SessionState::AwaitingApproval
+        )
+    }
+
     /// Whether a live child process is expected to be attached
     ///
     /// Polling a session without one reads EOF from a dead PTY, which
@@ -298,6 +317,11 @@ pub enum AttentionReason {
         /// Exit code or signal description
         reason: String,
     },
+    /// The session went over a `[budgets]` limit mid-turn
+    OverBudget {
+        /// Which limit, e.g. `session $5.00` or `project web 30m turn`
+        limit: String,
+    },
 }
 
 impl AttentionReason {
@@ -311,6 +335,18 @@ impl AttentionReason {
                 format!("{} stalled {}m", tool, secs / 60)
             }
             AttentionReason::Crashed { reason } => format!("crashed: {}", reason),
+            AttentionReason::OverBudget { limit } => format!("over budget: {}", limit),
+        }
+    }
+
+    /// Stable name for this kind of reason, matching its `[notify_on]` key
+    pub fn key(&self) -> &'static str {
+        match self {
+            AttentionReason::Approval { .. } => "approval",
+            AttentionReason::TurnComplete => "turn_complete",
+            AttentionReason::Stalled { .. } => "stalled",
+            AttentionReason::Crashed { .. } => "crashed",
+            AttentionReason::OverBudget { .. } => "over_budget",
         }
     }
 }
@@ -376,6 +412,13 @@ pub struct SessionInfo {
     /// subagents share the parent's session and show up in `in_flight` instead.
     #[serde(skip)]
     pub subagents: usize,
+    /// Every state this session has entered, with when, for the activity
+    /// timeline
+    ///
+    /// Not persisted: a restored session comes back as `Resumable`, and a
+    /// history ending in `Thinking` would claim it had been thinking ever since.
+    #[serde(skip)]
+    pub timeline: StateTimeline,
     /// Whether this session reattached to a conversation that already existed
     ///
     /// Decides where transcript reading starts. A fresh session's transcript
@@ -410,6 +453,9 @@ pub struct SessionInfo {
     /// Codex configuration name (cached for display)
     #[serde(default)]
     pub codex_config_name: Option<String>,
+    /// Model the agent was started with, kept so a resume asks for it again
+    #[serde(default)]
+    pub model: Option<String>,
     /// Whether to automatically close this session after its command finishes
     #[serde(default)]
     pub auto_close_after_command: bool,
@@ -522,6 +568,7 @@ impl SessionInfo {
             self.in_flight.clear();
         }
         self.state = state;
+        self.timeline.record(state, now);
         self.last_activity = now;
         self.state_entered_at = now;
         self.last_engagement = now;
@@ -630,6 +677,7 @@ impl SessionInfo {
             last_message: None,
             usage: crate::agent::events::UsageSnapshot::default(),
             subagents: 0,
+            timeline: StateTimeline::starting_at(now),
             resumed_conversation: false,
             auto_named: false,
             exit_reason: None,
@@ -638,6 +686,7 @@ impl SessionInfo {
             claude_config_name: None,
             codex_config_id: None,
             codex_config_name: None,
+            model: None,
             auto_close_after_command: false,
             agent_session_id: None,
         }
@@ -0,0 +1,257 @@
+//! Codex rate limits per account, and moving sessions off an exhausted one
+//!
+//! Codex reports how much of its plan's rate-limit window a conversation has
+//! used on every `token_count` line, and the figure belongs to the account,
+//! not the conversation. The latest reading per account is kept here, so
+//! Settings → Codex configs can show it even after every session on that
+//! account has moved elsewhere.
+//!
+//! Once an account crosses the `[codex_rotation]` threshold, each Codex
+//! session on it is offered a move to the least-used other account: its
+//! conversation is relaunched with `codex resume` under the other
+//! `CODEX_HOME`. With `auto` set the move happens by itself, at the session's
+//! next pause between turns.
+//!
+//! ```toml
+//! [codex_rotation]
+//! threshold_percent = 90.0
+//! auto = false
+//! ```
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{SessionId, SessionInfo, SessionType};
+use crate::agent::events::UsageSnapshot;
+use crate::codex_config::{CodexConfig, CodexConfigId};
+
+/// The `[codex_rotation]` section of `config.toml`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RotationConfig {
+    /// Rate-limit use, in percent, at which an account counts as exhausted
+    #[serde(default = "default_threshold")]
+    pub threshold_percent: f64,
+    /// Move sessions without asking
+    #[serde(default)]
+    pub auto: bool,
+}
+
+impl Default for RotationConfig {
+    fn default() -> Self {
+        Self {
+            threshold_percent: default_threshold(),
+            auto: false,
+        }
+    }
+}
+
+impl RotationConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+fn default_threshold() -> f64 {
+    90.0
+}
+
+/// An account's latest rate-limit reading
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccountLimit {
+    /// Percent of the window used when last reported
+    pub used_percent: f64,
+    /// When the window resets, if Codex said
+    pub resets_at: Option<DateTime<Utc>>,
+}
+
+impl AccountLimit {
+    /// Percent used as of `now`; a window that has since reset is empty again
+    pub fn used_at(&self, now: DateTime<Utc>) -> f64 {
+        match self.resets_at {
+            Some(resets_at) if resets_at <= now => 0.0,
+            _ => self.used_percent,
+        }
+    }
+}
+
+/// The latest reading for every Codex account seen this run
+///
+/// Keyed by config, with `None` for sessions started without one, which run
+/// on Codex's own `~/.codex` login.
+#[derive(Debug, Default)]
+pub struct CodexLimits {
+    accounts: HashMap<Option<CodexConfigId>, AccountLimit>,
+}
+
+impl CodexLimits {
+    /// Take a usage report's reading, if it has one
+    pub fn record(&mut self, account: Option<CodexConfigId>, usage: &UsageSnapshot) {
+        let Some(used_percent) = usage.rate_limit_used_percent else {
+            return;
+        };
+        let resets_at = usage
+            .rate_limit_resets_at
+            .as_deref()
+            .and_then(|at| DateTime::parse_from_rfc3339(at).ok())
+            .map(|at| at.with_timezone(&Utc));
+        self.accounts.insert(
+            account,
+            AccountLimit {
+                used_percent,
+                resets_at,
+            },
+        );
+    }
+
+    pub fn get(&self, account: Option<CodexConfigId>) -> Option<&AccountLimit> {
+        self.accounts.get(&account)
+    }
+
+    /// Percent used as of `now`; an account never heard from counts as unused
+    pub fn used_at(&self, account: Option<CodexConfigId>, now: DateTime<Utc>) -> f64 {
+        self.get(account).map_or(0.0, |limit| limit.used_at(now))
+    }
+}
+
+/// A session on an exhausted account, and where it could go
+#[derive(Debug, Clone, PartialEq)]
+pub struct RotationOffer {
+    pub session_id: SessionId,
+    pub session_name: String,
+    /// The exhausted account's name
+    pub from_name: String,
+    /// How much of its window that account has used
+    pub used_percent: f64,
+    /// The least-used other account under the threshold, if any is
+    pub to: Option<(CodexConfigId, String)>,
+}
+
+/// Every live Codex session whose account is at or over `threshold`
+///
+/// Accounts sharing a `CODEX_HOME` share a login, so one is never offered as
+/// the way out of the other. Ties go to the account listed first.
+pub fn rotation_offers(
+    sessions: &[&SessionInfo],
+    limits: &CodexLimits,
+    accounts: &[&CodexConfig],
+    threshold: f64,
+    now: DateTime<Utc>,
+) -> Vec<RotationOffer> {
+    let home_of = |id: Option<CodexConfigId>| {
+        id.and_then(|id| accounts.iter().find(|c| c.id == id))
+            .and_then(|c| c.codex_home.clone())
+    };
+
+    sessions
+        .iter()
+        .filter(|info| info.session_type == SessionType::OpenAICodex && info.state.has_process())
+        .filter_map(|info| {
+            let from = info.codex_config_id;
+            let used_percent = limits.used_at(from, now);
+            if used_percent < threshold {
+                return None;
+            }
+            let from_home = home_of(from);
+            let to = accounts
+                .iter()
+                .filter(|c| Some(c.id) != from && c.codex_home != from_home)
+                .map(|c| (c, limits.used_at(Some(c.id), now)))
+                .filter(|(_, used)| *used < threshold)
+                .fold(
+                    None,
+                    |best: Option<(&&CodexConfig, f64)>, (c, used)| match best {
+                        Some((_, best_used)) if best_used <= used => best,
+                        _ => Some((c, used)),
+                    },
+                )
+                .map(|(c, _)| (c.id, c.name.clone()));
+            Some(RotationOffer {
+                session_id: info.id,
+                session_name: info.name.clone(),
+                from_name: info
+                    .codex_config_name
+                    .clone()
+                    .unwrap_or_else(|| "default".to_string()),
+                used_percent,
+                to,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use std::path::PathBuf;
+
+    fn usage(percent: f64, resets_at: DateTime<Utc>) -> UsageSnapshot {
+        UsageSnapshot {
+            rate_limit_used_percent: Some(percent),
+            rate_limit_resets_at: Some(resets_at.to_rfc3339()),
+            ..Default::default()
+        }
+    }
+
+    fn codex_session(account: &CodexConfig) -> SessionInfo {
+        let mut info = SessionInfo::new(
+            "codex".to_string(),
+            PathBuf::from("/tmp"),
+            uuid::Uuid::new_v4(),
+            uuid::Uuid::new_v4(),
+        );
+        info.session_type = SessionType::OpenAICodex;
+        info.codex_config_id = Some(account.id);
+        info.codex_config_name = Some(account.name.clone());
+        info
+    }
+
+    #[test]
+    fn test_a_window_that_has_reset_counts_as_unused() {
+        let now = Utc::now();
+        let mut limits = CodexLimits::default();
+        let account = Some(uuid::Uuid::new_v4());
+        limits.record(account, &usage(95.0, now + Duration::hours(1)));
+        assert_eq!(limits.used_at(account, now), 95.0);
+        assert_eq!(limits.used_at(account, now + Duration::hours(2)), 0.0);
+        assert_eq!(limits.used_at(None, now), 0.0);
+
+        // A report with no reading keeps the last one
+        limits.record(account, &UsageSnapshot::default());
+        assert_eq!(limits.used_at(account, now), 95.0);
+    }
+
+    #[test]
+    fn test_sessions_on_an_exhausted_account_are_offered_the_least_used_other() {
+        let now = Utc::now();
+        let later = now + Duration::hours(1);
+        let work = CodexConfig::new("Work".to_string(), Some(PathBuf::from("/codex/work")));
+        let busy = CodexConfig::new("Busy".to_string(), Some(PathBuf::from("/codex/busy")));
+        let spare = CodexConfig::new("Spare".to_string(), Some(PathBuf::from("/codex/spare")));
+        // Same login as Work under another name: never a way out of it
+        let alias = CodexConfig::new("Alias".to_string(), Some(PathBuf::from("/codex/work")));
+        let accounts = [&work, &busy, &spare, &alias];
+
+        let mut limits = CodexLimits::default();
+        limits.record(Some(work.id), &usage(93.0, later));
+        limits.record(Some(busy.id), &usage(60.0, later));
+        limits.record(Some(spare.id), &usage(10.0, later));
+
+        let on_work = codex_session(&work);
+        let on_spare = codex_session(&spare);
+        let offers = rotation_offers(&[&on_work, &on_spare], &limits, &accounts, 90.0, now);
+        assert_eq!(offers.len(), 1, "{offers:?}");
+        assert_eq!(offers[0].session_id, on_work.id);
+        assert_eq!(offers[0].from_name, "Work");
+        assert_eq!(offers[0].used_percent, 93.0);
+        assert_eq!(offers[0].to, Some((spare.id, "Spare".to_string())));
+
+        // With every other account exhausted too, there is nowhere to go
+        limits.record(Some(busy.id), &usage(99.0, later));
+        limits.record(Some(spare.id), &usage(91.0, later));
+        let offers = rotation_offers(&[&on_work], &limits, &accounts, 90.0, now);
+        assert_eq!(offers[0].to, None);
+    }
+}
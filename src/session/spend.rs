@@ -0,0 +1,186 @@
+//! Estimated spending, from the history log's `spend` lines
+//!
+//! Tokens are stored and prices are applied when the report is built, so a
+//! corrected `[pricing]` table reprices the past as well as the future.
+//! Periods are local calendar days and the local calendar month, which is what
+//! a monthly API budget is measured against.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use anyhow::Result;
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, Utc};
+
+use super::history::{read_months, HistoryEvent, HistoryRecord, STATS_DAYS};
+use super::SessionId;
+use crate::pricing::{estimate, PricingConfig};
+use crate::project::ProjectId;
+
+/// Estimated spending over the current month, broken down
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SpendReport {
+    /// Dollars spent today
+    pub today: f64,
+    /// Dollars spent this calendar month
+    pub month: f64,
+    /// Dollars per local day over the last [`STATS_DAYS`] days, oldest first
+    pub per_day: Vec<(NaiveDate, f64)>,
+    /// This month's dollars per project, most first
+    pub per_project: Vec<(ProjectId, f64)>,
+    /// This month's dollars per account, most first; `None` is the agent's
+    /// default login
+    pub per_account: Vec<(Option<String>, f64)>,
+    /// Tokens this month on models with no known price, left out of every total
+    pub unpriced_tokens: u64,
+    /// Lines of the log that could not be read
+    pub skipped: usize,
+}
+
+impl SpendReport {
+    /// Read the log in `dir` and price this month's spending, and the last
+    /// [`STATS_DAYS`] days' should they reach into the month before
+    pub fn load(dir: &Path, pricing: &PricingConfig, now: DateTime<Utc>) -> Result<Self> {
+        let (records, skipped) = read_months(dir, now - Duration::days(STATS_DAYS), now)?;
+        Ok(Self {
+            skipped,
+            ..Self::compute(&records, pricing, now)
+        })
+    }
+
+    pub fn compute(records: &[HistoryRecord], pricing: &PricingConfig, now: DateTime<Utc>) -> Self {
+        let today = now.with_timezone(&Local).date_naive();
+        let month_start = today.with_day(1).unwrap_or(today);
+        let mut per_day: Vec<(NaiveDate, f64)> = (0..STATS_DAYS)
+            .rev()
+            .map(|back| (today - Duration::days(back), 0.0))
+            .collect();
+
+        // Who a session belonged to is only said when it started, which may
+        // be long before the spending
+        let owners: HashMap<SessionId, (ProjectId, Option<&str>)> = records
+            .iter()
+            .filter_map(|r| match &r.event {
+                HistoryEvent::SessionStarted {
+                    project_id,
+                    account,
+                    ..
+                } => Some((r.session_id, (*project_id, account.as_deref()))),
+                _ => None,
+            })
+            .collect();
+
+        let mut report = Self::default();
+        let mut projects: HashMap<ProjectId, f64> = HashMap::new();
+        let mut accounts: HashMap<Option<&str>, f64> = HashMap::new();
+
+        for record in records.iter().filter(|r| r.at <= now) {
+            let HistoryEvent::Spend { model, tokens } = &record.event else {
+                continue;
+            };
+            let day = record.at.with_timezone(&Local).date_naive();
+            let Some(cost) = estimate(model.as_deref(), tokens, pricing) else {
+                if day >= month_start {
+                    report.unpriced_tokens += tokens.total();
+                }
+                continue;
+            };
+
+            if let Some(entry) = per_day.iter_mut().find(|(d, _)| *d == day) {
+                entry.1 += cost;
+            }
+            if day == today {
+                report.today += cost;
+            }
+            if day >= month_start {
+                report.month += cost;
+                if let Some((project_id, account)) = owners.get(&record.session_id) {
+                    *projects.entry(*project_id).or_default() += cost;
+                    *accounts.entry(*account).or_default() += cost;
+                }
+            }
+        }
+
+        report.per_day = per_day;
+        report.per_project = most_first(projects.into_iter().collect());
+        report.per_account = most_first(
+            accounts
+                .into_iter()
+                .map(|(account, cost)| (account.map(str::to_string), cost))
+                .collect(),
+        );
+        report
+    }
+}
+
+fn most_first<K>(mut entries: Vec<(K, f64)>) -> Vec<(K, f64)> {
+    entries.sort_by(|a, b| b.1.total_cmp(&a.1));
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::events::TokenCounts;
+    use crate::session::SessionType;
+    use uuid::Uuid;
+
+    fn started(
+        session_id: SessionId,
+        project_id: ProjectId,
+        account: Option<&str>,
+    ) -> HistoryRecord {
+        HistoryRecord {
+            at: Utc::now() - Duration::days(90),
+            session_id,
+            event: HistoryEvent::SessionStarted {
+                name: "s".to_string(),
+                project_id,
+                branch_id: Uuid::new_v4(),
+                session_type: SessionType::ClaudeCode,
+                resumed: false,
+                account: account.map(str::to_string),
+            },
+        }
+    }
+
+    fn spend(session_id: SessionId, at: DateTime<Utc>, model: &str, output: u64) -> HistoryRecord {
+        HistoryRecord {
+            at,
+            session_id,
+            event: HistoryEvent::Spend {
+                model: Some(model.to_string()),
+                tokens: TokenCounts {
+                    output,
+                    ..Default::default()
+                },
+            },
+        }
+    }
+
+    #[test]
+    fn test_spending_is_priced_and_split_by_day_project_and_account() {
+        let now = Utc::now();
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        let (web, api) = (Uuid::new_v4(), Uuid::new_v4());
+        let records = vec![
+            started(a, web, Some("work")),
+            started(b, api, None),
+            // Sonnet output is $15 per million
+            spend(a, now, "claude-sonnet-4-5", 1_000_000),
+            spend(b, now, "claude-sonnet-4-5", 200_000),
+            spend(b, now, "mystery-model", 5_000),
+            // Last year: outside the month and the week alike
+            spend(a, now - Duration::days(400), "claude-sonnet-4-5", 1_000_000),
+        ];
+
+        let report = SpendReport::compute(&records, &PricingConfig::new(), now);
+        assert!((report.today - 18.0).abs() < 1e-9, "{report:?}");
+        assert!((report.month - 18.0).abs() < 1e-9, "{report:?}");
+        assert_eq!(report.per_day.len(), STATS_DAYS as usize);
+        assert!((report.per_day.last().unwrap().1 - 18.0).abs() < 1e-9);
+        assert_eq!(report.per_project[0].0, web);
+        assert_eq!(report.per_account[0].0.as_deref(), Some("work"));
+        assert_eq!(report.per_account[1].0, None);
+        assert_eq!(report.unpriced_tokens, 5_000);
+    }
+}
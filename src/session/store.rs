@@ -66,6 +66,11 @@ impl SessionStore {
         }
     }
 
+    /// Where the store is saved
+    pub fn path(&self) -> &Path {
+        &self.store_path
+    }
+
     /// Get all persisted sessions
     pub fn sessions(&self) -> impl Iterator<Item = &SessionInfo> {
         self.sessions.values()
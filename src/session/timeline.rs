@@ -0,0 +1,180 @@
+//! Per-session state history
+//!
+//! Every state change a session goes through is appended here with its
+//! timestamp, so the activity timeline can show how a session's recent hours
+//! split between working and waiting on the user. The history is a plain list
+//! of transitions: a state lasts from its own entry until the next one, and the
+//! newest lasts until now.
+
+use std::collections::VecDeque;
+
+use chrono::{DateTime, Duration, Utc};
+
+use super::SessionState;
+
+/// How far back a timeline remembers
+pub const TIMELINE_RETENTION_HOURS: i64 = 24;
+
+/// Most transitions a timeline keeps, whatever their age
+///
+/// A session flapping between Thinking and Executing on every tool call
+/// records several transitions a second at worst; this caps what a runaway
+/// one can hold.
+const MAX_TRANSITIONS: usize = 4096;
+
+/// One stretch of time spent in a single state
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Segment {
+    pub state: SessionState,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+impl Segment {
+    pub fn duration(&self) -> Duration {
+        self.end - self.start
+    }
+}
+
+/// The transitions a session has gone through, oldest first
+#[derive(Debug, Clone, Default)]
+pub struct StateTimeline {
+    transitions: VecDeque<(DateTime<Utc>, SessionState)>,
+}
+
+impl StateTimeline {
+    /// A timeline whose first state is `Starting`, for a session spawned at `at`
+    pub fn starting_at(at: DateTime<Utc>) -> Self {
+        let mut timeline = Self::default();
+        timeline.record(SessionState::Starting, at);
+        timeline
+    }
+
+    /// Record that the session entered `state` at `at`
+    ///
+    /// Re-entering the current state is not a transition and is dropped, so
+    /// the callers can report every state they set without checking first.
+    pub fn record(&mut self, state: SessionState, at: DateTime<Utc>) {
+        if self.current() == Some(state) {
+            return;
+        }
+        self.transitions.push_back((at, state));
+
+        // The newest transition older than the cutoff is kept: it says what
+        // the session was doing when the retained window opens
+        let cutoff = at - Duration::hours(TIMELINE_RETENTION_HOURS);
+        while self.transitions.len() > MAX_TRANSITIONS
+            || self.transitions.get(1).is_some_and(|(t, _)| *t <= cutoff)
+        {
+            self.transitions.pop_front();
+        }
+    }
+
+    /// The most recently entered state, if anything has been recorded
+    pub fn current(&self) -> Option<SessionState> {
+        self.transitions.back().map(|(_, state)| *state)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.transitions.is_empty()
+    }
+
+    /// The stretches overlapping `from..to`, clipped to it
+    ///
+    /// Time before the first recorded transition has no segment: the session
+    /// did not exist yet, or Panoptes was not watching it.
+    pub fn segments(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Vec<Segment> {
+        let mut segments = Vec::new();
+        for (i, (start, state)) in self.transitions.iter().enumerate() {
+            let end = self.transitions.get(i + 1).map_or(to, |(next, _)| *next);
+            let (start, end) = ((*start).max(from), end.min(to));
+            if start < end {
+                segments.push(Segment {
+                    state: *state,
+                    start,
+                    end,
+                });
+            }
+        }
+        segments
+    }
+
+    /// Total time spent in each state within `from..to`, in first-seen order
+    pub fn totals(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Vec<(SessionState, Duration)> {
+        let mut totals: Vec<(SessionState, Duration)> = Vec::new();
+        for segment in self.segments(from, to) {
+            match totals.iter_mut().find(|(state, _)| *state == segment.state) {
+                Some((_, total)) => *total += segment.duration(),
+                None => totals.push((segment.state, segment.duration())),
+            }
+        }
+        totals
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(minutes: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp(1_700_000_000, 0).unwrap() + Duration::minutes(minutes)
+    }
+
+    #[test]
+    fn test_segments_run_until_the_next_transition_and_clip_to_the_window() {
+        let mut timeline = StateTimeline::default();
+        timeline.record(SessionState::Starting, at(0));
+        timeline.record(SessionState::Thinking, at(10));
+        timeline.record(SessionState::Thinking, at(15));
+        timeline.record(SessionState::Waiting, at(40));
+
+        let segments = timeline.segments(at(5), at(100));
+        let spans: Vec<(SessionState, i64)> = segments
+            .iter()
+            .map(|s| (s.state, s.duration().num_minutes()))
+            .collect();
+        assert_eq!(
+            spans,
+            vec![
+                (SessionState::Starting, 5),
+                (SessionState::Thinking, 30),
+                (SessionState::Waiting, 60),
+            ]
+        );
+        assert_eq!(timeline.current(), Some(SessionState::Waiting));
+    }
+
+    #[test]
+    fn test_totals_add_up_repeated_states() {
+        let mut timeline = StateTimeline::default();
+        timeline.record(SessionState::Executing, at(0));
+        timeline.record(SessionState::Waiting, at(10));
+        timeline.record(SessionState::Executing, at(30));
+        timeline.record(SessionState::Waiting, at(35));
+
+        let totals = timeline.totals(at(0), at(60));
+        assert_eq!(
+            totals,
+            vec![
+                (SessionState::Executing, Duration::minutes(15)),
+                (SessionState::Waiting, Duration::minutes(45)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_old_transitions_are_dropped_but_the_opening_state_is_kept() {
+        let mut timeline = StateTimeline::default();
+        timeline.record(SessionState::Waiting, at(0));
+        timeline.record(SessionState::Thinking, at(60));
+        let day = TIMELINE_RETENTION_HOURS * 60;
+        timeline.record(SessionState::Waiting, at(day + 120));
+
+        // The first transition is older than the window and superseded by a
+        // second that is also older, so it goes; the second opens the window
+        assert_eq!(timeline.transitions.len(), 2);
+        let segments = timeline.segments(at(120), at(day + 180));
+        assert_eq!(segments[0].state, SessionState::Thinking);
+        assert_eq!(segments[0].start, at(120));
+    }
+}
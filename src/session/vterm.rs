@@ -191,6 +191,22 @@ impl VirtualTerminal {
         lines
     }
 
+    /// The last `count` lines of the screen that have anything on them
+    ///
+    /// For a preview smaller than the screen: the top rows of a tall screen
+    /// are often the banner an agent printed at startup, and the bottom rows
+    /// below a shell prompt are empty, so neither says what the session is
+    /// doing now. Blank rows at the bottom are skipped before counting.
+    pub fn tail_styled_lines(&self, count: usize) -> Vec<Line<'static>> {
+        let (rows, _) = self.size();
+        let lines = self.visible_styled_lines(rows);
+        let end = lines
+            .iter()
+            .rposition(|line| line.spans.iter().any(|s| !s.content.trim().is_empty()))
+            .map_or(0, |last| last + 1);
+        lines[end.saturating_sub(count)..end].to_vec()
+    }
+
     /// Get dimensions
     pub fn size(&self) -> (usize, usize) {
         let size = self.parser.screen().size();
@@ -465,4 +481,19 @@ mod tests {
             .collect();
         assert_eq!(back_text, live_text);
     }
+
+    #[test]
+    fn test_tail_skips_the_blank_rows_below_the_output() {
+        let mut vt = VirtualTerminal::new(10, 20);
+        vt.process(b"one\r\ntwo\r\nthree\r\n");
+
+        let text: Vec<String> = vt
+            .tail_styled_lines(2)
+            .iter()
+            .map(|line| line.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+        assert_eq!(text, vec!["two", "three"]);
+
+        assert!(VirtualTerminal::new(10, 20).tail_styled_lines(3).is_empty());
+    }
 }
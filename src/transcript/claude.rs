@@ -6,14 +6,15 @@
 //! **This tailer contributes usage figures only, never state.** Claude's hooks
 //! already report state, they arrive sooner, and two producers writing the same
 //! field would fight over it. The transcript is read for the one thing hooks do
-//! not carry: how full the context window is and which model is answering.
+//! not carry: how full the context window is, which model is answering, and
+//! what each API call billed.
 //!
 //! There is no rate-limit data anywhere in a Claude transcript, so those fields
 //! stay empty for Claude sessions rather than being guessed at.
 
 use serde_json::Value;
 
-use crate::agent::events::{AgentEvent, UsageSnapshot};
+use crate::agent::events::{AgentEvent, TokenCounts, TokenReport, UsageSnapshot};
 
 /// Translate one transcript line into a usage event
 ///
@@ -41,6 +42,22 @@ pub fn parse_line(line: &str) -> Option<AgentEvent> {
     .filter_map(|field| usage.get(*field).and_then(Value::as_u64))
     .sum();
 
+    let count = |field: &str| usage.get(field).and_then(Value::as_u64).unwrap_or(0);
+    let tokens = TokenCounts {
+        input: count("input_tokens"),
+        output: count("output_tokens"),
+        cache_write: count("cache_creation_input_tokens"),
+        cache_read: count("cache_read_input_tokens"),
+    };
+    // One message streamed as several content blocks is written as several
+    // lines, each repeating the whole message's usage; the message ID is what
+    // lets it be billed once
+    let call_id = message
+        .get("id")
+        .or_else(|| record.get("requestId"))
+        .or_else(|| record.get("uuid"))
+        .and_then(Value::as_str);
+
     let snapshot = UsageSnapshot {
         total_tokens: (context_tokens > 0).then_some(context_tokens),
         // Claude never states its context window in the transcript, so it is
@@ -54,6 +71,13 @@ pub fn parse_line(line: &str) -> Option<AgentEvent> {
             .get("model")
             .and_then(Value::as_str)
             .map(str::to_string),
+        report: match call_id {
+            Some(id) if !tokens.is_zero() => Some(TokenReport::Call {
+                id: id.to_string(),
+                tokens,
+            }),
+            _ => None,
+        },
         ..Default::default()
     };
 
@@ -126,6 +150,30 @@ mod tests {
         assert_eq!(usage.plan, None);
     }
 
+    #[test]
+    fn test_assistant_usage_reports_the_call_by_kind() {
+        let line = r#"{"type":"assistant","requestId":"req_1","message":{"id":"msg_1",
+            "model":"claude-opus-4-8","usage":{"input_tokens":100,
+            "cache_creation_input_tokens":2000,"cache_read_input_tokens":48000,
+            "output_tokens":400}}}"#;
+
+        let Some(AgentEvent::Usage(usage)) = parse_line(line) else {
+            panic!("expected usage");
+        };
+        assert_eq!(
+            usage.report,
+            Some(TokenReport::Call {
+                id: "msg_1".to_string(),
+                tokens: TokenCounts {
+                    input: 100,
+                    output: 400,
+                    cache_write: 2000,
+                    cache_read: 48000,
+                },
+            })
+        );
+    }
+
     #[test]
     fn test_one_million_context_variant() {
         let line = r#"{"message":{"model":"claude-opus-4-8[1m]","usage":{"input_tokens":10}}}"#;
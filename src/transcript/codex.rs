@@ -20,7 +20,7 @@ use std::path::{Path, PathBuf};
 
 use serde_json::Value;
 
-use crate::agent::events::{AgentEvent, UsageSnapshot};
+use crate::agent::events::{AgentEvent, TokenCounts, TokenReport, UsageSnapshot};
 
 /// The `session_meta` header of a Codex rollout file
 ///
@@ -215,6 +215,18 @@ fn parse_token_count(payload: &Value) -> UsageSnapshot {
     let window = info
         .and_then(|i| i.get("model_context_window"))
         .and_then(Value::as_u64);
+    let spent = info.and_then(|i| i.get("total_token_usage")).map(|usage| {
+        let count = |field: &str| usage.get(field).and_then(Value::as_u64).unwrap_or(0);
+        // Codex counts cached input inside `input_tokens`, and reasoning inside
+        // `output_tokens`; it has no notion of a cache write
+        let cached = count("cached_input_tokens");
+        TokenCounts {
+            input: count("input_tokens").saturating_sub(cached),
+            output: count("output_tokens"),
+            cache_write: 0,
+            cache_read: cached,
+        }
+    });
 
     let primary = payload.get("rate_limits").and_then(|r| r.get("primary"));
 
@@ -230,12 +242,27 @@ fn parse_token_count(payload: &Value) -> UsageSnapshot {
             .and_then(Value::as_f64),
         rate_limit_resets_at: primary
             .and_then(|p| p.get("resets_at"))
-            .and_then(Value::as_str)
-            .map(str::to_string),
+            .and_then(reset_time),
         plan: primary
             .and_then(|p| p.get("plan_type"))
             .and_then(Value::as_str)
             .map(str::to_string),
+        report: spent
+            .filter(|tokens| !tokens.is_zero())
+            .map(TokenReport::Total),
+        ..Default::default()
+    }
+}
+
+/// A rate-limit reset time as RFC 3339, whichever way Codex wrote it
+///
+/// Older releases wrote a timestamp string, newer ones Unix seconds.
+fn reset_time(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => chrono::DateTime::from_timestamp(n.as_i64()?, 0)
+            .map(|at| at.to_rfc3339_opts(chrono::SecondsFormat::Secs, true)),
+        _ => None,
     }
 }
 
@@ -379,6 +406,40 @@ mod tests {
         assert_eq!(usage.model.as_deref(), Some("gpt-5-codex"));
         assert_eq!(usage.rate_limit_used_percent, Some(12.5));
         assert_eq!(usage.plan.as_deref(), Some("pro"));
+        assert_eq!(
+            usage.rate_limit_resets_at.as_deref(),
+            Some("2026-07-22T18:00:00Z")
+        );
+
+        // Newer releases give the reset as Unix seconds
+        let line = line.replace("\"2026-07-22T18:00:00Z\"", "1784743200");
+        let Some(AgentEvent::Usage(usage)) = parse_line(&line) else {
+            panic!("expected a usage event");
+        };
+        assert_eq!(
+            usage.rate_limit_resets_at.as_deref(),
+            Some("2026-07-22T18:00:00Z")
+        );
+    }
+
+    #[test]
+    fn test_token_count_splits_cached_input_from_fresh() {
+        let line = r#"{"type":"event_msg","payload":{"type":"token_count",
+            "info":{"total_token_usage":{"input_tokens":30000,"cached_input_tokens":24000,
+                    "output_tokens":1500,"reasoning_output_tokens":900,"total_tokens":31500}}}}"#;
+
+        let Some(AgentEvent::Usage(usage)) = parse_line(line) else {
+            panic!("expected a usage event");
+        };
+        assert_eq!(
+            usage.report,
+            Some(TokenReport::Total(TokenCounts {
+                input: 6_000,
+                output: 1_500,
+                cache_write: 0,
+                cache_read: 24_000,
+            }))
+        );
     }
 
     #[test]
@@ -23,7 +23,7 @@ pub mod watcher;
 use std::io::{Read, Seek, SeekFrom};
 use std::path::{Path, PathBuf};
 
-use crate::agent::events::{AgentEvent, UsageSnapshot};
+use crate::agent::events::{AgentEvent, TokenCounts, UsageSnapshot};
 
 pub use watcher::{TranscriptWatcher, WatchTarget};
 
@@ -291,7 +291,13 @@ fn seed_usage(kind: TranscriptKind, path: &Path, len: u64) -> Option<UsageSnapsh
             }
         }
     }
-    merged
+    // Whatever the file already holds was spent before this session was
+    // watching: the latest report is where counting starts, not a bill
+    merged.map(|usage| UsageSnapshot {
+        spent: TokenCounts::default(),
+        baseline: true,
+        ..usage
+    })
 }
 
 #[cfg(test)]
@@ -412,6 +418,25 @@ mod tests {
         assert_eq!(seed.context_window, Some(272_000));
     }
 
+    #[test]
+    fn test_seed_is_a_baseline_not_a_bill() {
+        // A resumed conversation's earlier spending was counted when it
+        // happened; only what it spends from here on belongs to this session
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("rollout.jsonl");
+        append(&path, "{\"type\":\"event_msg\",\"payload\":{\"type\":\"token_count\",\"info\":{\"model\":\"gpt-5-codex\",\"total_token_usage\":{\"input_tokens\":9000,\"output_tokens\":1000,\"total_tokens\":10000}}}}\n");
+
+        let (_, seed) = Tailer::attach(TranscriptKind::Codex, path);
+        let seed = seed.expect("usage should be seeded");
+        assert!(seed.baseline);
+        assert!(seed.spent.is_zero());
+
+        let mut usage = UsageSnapshot::default();
+        usage.merge(seed);
+        assert!(usage.spent.is_zero());
+        assert!(usage.report.is_some());
+    }
+
     #[test]
     fn test_seed_merges_model_from_an_older_record_backwards() {
         // Codex's newest token_count does not always name the model; an older
@@ -84,6 +84,48 @@ impl FrameLayout {
     pub fn pty_size(&self) -> (u16, u16) {
         (self.content.height, self.content.width)
     }
+
+    /// Split the frame into `count` tiles, each drawn with its own border
+    ///
+    /// Two tiles sit side by side; a third stacks under the second, leaving
+    /// the first at full height; four make a 2x2 grid. Tiles are listed
+    /// left-to-right, then top-to-bottom, which is also focus order.
+    pub fn tiles(&self, count: usize) -> Vec<Rect> {
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)])
+            .split(self.frame);
+        let stacked = |area: Rect| {
+            Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)])
+                .split(area)
+        };
+
+        match count {
+            0 | 1 => vec![self.frame],
+            2 => vec![columns[0], columns[1]],
+            3 => {
+                let right = stacked(columns[1]);
+                vec![columns[0], right[0], right[1]]
+            }
+            _ => {
+                let left = stacked(columns[0]);
+                let right = stacked(columns[1]);
+                vec![left[0], right[0], left[1], right[1]]
+            }
+        }
+    }
+}
+
+/// The content area inside a tile's border
+pub fn tile_content(tile: Rect) -> Rect {
+    Rect {
+        x: tile.x + 1,
+        y: tile.y + 1,
+        width: tile.width.saturating_sub(2),
+        height: tile.height.saturating_sub(2),
+    }
 }
 
 /// Render frame border separately from content
@@ -167,4 +209,31 @@ mod tests {
         assert_eq!(rows, 16);
         assert_eq!(cols, 78);
     }
+
+    #[test]
+    fn test_tiles_cover_the_frame_without_overlap() {
+        let layout = FrameLayout::calculate(Rect::new(0, 0, 81, 25), &FrameConfig::default());
+        let frame = layout.frame;
+
+        for count in 1..=4 {
+            let tiles = layout.tiles(count);
+            assert_eq!(tiles.len(), count);
+            let area: u32 = tiles.iter().map(|t| t.width as u32 * t.height as u32).sum();
+            assert_eq!(
+                area,
+                frame.width as u32 * frame.height as u32,
+                "{count} tiles"
+            );
+            for (i, a) in tiles.iter().enumerate() {
+                for b in &tiles[i + 1..] {
+                    assert!(!a.intersects(*b), "{count} tiles: {a:?} overlaps {b:?}");
+                }
+            }
+        }
+
+        // Three tiles: the first keeps the full height
+        let tiles = layout.tiles(3);
+        assert_eq!(tiles[0].height, frame.height);
+        assert_eq!(tile_content(tiles[0]).height, frame.height - 2);
+    }
 }
@@ -79,6 +79,8 @@ pub struct Header<'a> {
     notifications: Option<&'a HeaderNotificationManager>,
     /// Number of sessions needing attention
     attention_count: usize,
+    /// Whether do-not-disturb or the quiet hours are silencing notifications
+    quiet: bool,
     /// Optional custom style (for session view state-based coloring)
     custom_style: Option<Style>,
     /// How much of the wordmark this header draws
@@ -93,6 +95,7 @@ impl<'a> Header<'a> {
             suffix: None,
             notifications: None,
             attention_count: 0,
+            quiet: false,
             custom_style: None,
             logo: LogoKind::None,
         }
@@ -167,6 +170,13 @@ impl<'a> Header<'a> {
         self
     }
 
+    /// Mark notifications as silenced, so a quiet dashboard is not mistaken
+    /// for one with nothing to say
+    pub fn with_quiet(mut self, quiet: bool) -> Self {
+        self.quiet = quiet;
+        self
+    }
+
     /// Set a custom style (overrides default header style)
     pub fn with_custom_style(mut self, style: Style) -> Self {
         self.custom_style = Some(style);
@@ -365,12 +375,18 @@ impl<'a> Header<'a> {
         Some(Span::styled(msg, style))
     }
 
-    /// The blinking attention badge, empty when nothing needs attention
+    /// The blinking attention badge, empty when nothing needs attention,
+    /// preceded by a quiet marker while notifications are silenced
     fn badge_spans(&self) -> Vec<Span<'static>> {
+        let t = theme();
+        let mut spans = Vec::new();
+        if self.quiet {
+            spans.push(Span::styled("[quiet]", Style::default().fg(t.text_dim)));
+            spans.push(Span::raw(" "));
+        }
         if self.attention_count == 0 {
-            return Vec::new();
+            return spans;
         }
-        let t = theme();
         // The count stays put through the blink-off phase so it does not jitter
         let (text, style) = if Self::should_show_blink() {
             (
@@ -383,7 +399,9 @@ impl<'a> Header<'a> {
                 Style::default().fg(t.attention_badge),
             )
         };
-        vec![Span::styled(text, style), Span::raw(" ")]
+        spans.push(Span::styled(text, style));
+        spans.push(Span::raw(" "));
+        spans
     }
 
     /// Check if the blink indicator should be visible (500ms on/off cycle)
@@ -422,6 +440,23 @@ mod tests {
         assert_eq!(header.attention_count, 5);
     }
 
+    #[test]
+    fn test_quiet_marker_shows_with_or_without_attention() {
+        let text = |header: &Header| -> String {
+            header
+                .badge_spans()
+                .iter()
+                .map(|s| s.content.to_string())
+                .collect()
+        };
+
+        assert_eq!(text(&Header::new(Breadcrumb::new())), "");
+        let quiet = Header::new(Breadcrumb::new()).with_quiet(true);
+        assert_eq!(text(&quiet), "[quiet] ");
+        let quiet = quiet.with_attention_count(2);
+        assert!(text(&quiet).starts_with("[quiet] [2"), "{}", text(&quiet));
+    }
+
     fn area(width: u16, height: u16) -> Rect {
         Rect {
             x: 0,
@@ -10,11 +10,35 @@
 //! in how fine a grey ramp they can express - so the worst case is exactly
 //! the classic appearance. The tier is detected from `COLORTERM`/`TERM` at
 //! startup, and can be forced with the `theme` key in `config.toml`.
+//!
+//! On top of the tiers sit named themes: the built-in `dark` (the palette
+//! above) and `light`, plus any `~/.panoptes/themes/<name>.toml`, which starts
+//! from one of the two and overrides tokens by field name:
+//!
+//! ```toml
+//! base = "light"
+//!
+//! [colors]
+//! accent = "#005f87"
+//! state_thinking = "136"
+//! border_dim = "gray"
+//! ```
+//!
+//! The theme can be switched while Panoptes runs, from Settings.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::{OnceLock, RwLock};
 
+use anyhow::{anyhow, bail, Context, Result};
 use ratatui::style::{Color, Modifier, Style};
+use serde::Deserialize;
 
 use crate::config::ThemeMode;
 
+/// The themes that need no file, in the order Settings lists them
+pub const BUILT_IN_THEMES: [&str; 2] = ["dark", "light"];
+
 /// How rich a palette the terminal can display
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ColorSupport {
@@ -269,6 +293,140 @@ impl Theme {
         }
     }
 
+    /// The built-in light theme at a capability tier
+    ///
+    /// For light-background terminals: dark text, and the yellows and cyan
+    /// that wash out on white pulled darker wherever the tier can express
+    /// it. Like the dark tiers, the 16-colour version leaves the chromatic
+    /// tokens to the terminal's own palette, which on a light scheme is
+    /// already tuned for the background.
+    pub fn light(support: ColorSupport) -> Self {
+        let base = Self {
+            accent: Color::Blue,
+            text: Color::Black,
+            text_dim: Color::DarkGray,
+            text_faint: Color::DarkGray,
+            text_inverted: Color::White,
+            selected: Color::Black,
+            state_awaiting_approval: Color::LightRed,
+            border_focus: Color::Blue,
+            border: Color::DarkGray,
+            border_dim: Color::Gray,
+            ..Self::ansi16()
+        };
+        match support {
+            ColorSupport::Ansi16 => base,
+            ColorSupport::Ansi256 => Self {
+                text_faint: Color::Indexed(246),
+                border_dim: Color::Indexed(250),
+                bg_surface: Color::Indexed(254),
+                state_suspended: Color::Indexed(243),
+                state_thinking: Color::Indexed(136),
+                state_executing: Color::Indexed(30),
+                attention_idle: Color::Indexed(136),
+                attention_badge: Color::Indexed(136),
+                warning: Color::Indexed(136),
+                border_warning: Color::Indexed(136),
+                default_marker: Color::Indexed(136),
+                ..base
+            },
+            ColorSupport::TrueColor => {
+                let amber = Color::Rgb(0x9a, 0x6a, 0x00);
+                Self {
+                    text_faint: Color::Rgb(0x9e, 0x9e, 0x9e),
+                    border_dim: Color::Rgb(0xc8, 0xcc, 0xd0),
+                    bg_surface: Color::Rgb(0xe6, 0xe9, 0xec),
+                    state_suspended: Color::Rgb(0x80, 0x80, 0x80),
+                    state_thinking: amber,
+                    state_executing: Color::Rgb(0x00, 0x7a, 0x8a),
+                    attention_idle: amber,
+                    attention_badge: amber,
+                    warning: amber,
+                    border_warning: amber,
+                    default_marker: amber,
+                    ..base
+                }
+            }
+        }
+    }
+
+    /// The built-in theme called `name` at a capability tier
+    pub fn built_in(name: &str, support: ColorSupport) -> Option<Self> {
+        match name {
+            "dark" => Some(Self::for_support(support)),
+            "light" => Some(Self::light(support)),
+            _ => None,
+        }
+    }
+
+    /// The token a theme file calls `name`
+    fn token_mut(&mut self, name: &str) -> Option<&mut Color> {
+        Some(match name {
+            "state_starting" => &mut self.state_starting,
+            "state_thinking" => &mut self.state_thinking,
+            "state_executing" => &mut self.state_executing,
+            "state_waiting" => &mut self.state_waiting,
+            "state_awaiting_approval" => &mut self.state_awaiting_approval,
+            "state_suspended" => &mut self.state_suspended,
+            "state_exited" => &mut self.state_exited,
+            "state_resumable" => &mut self.state_resumable,
+            "accent" => &mut self.accent,
+            "text" => &mut self.text,
+            "text_dim" => &mut self.text_dim,
+            "text_faint" => &mut self.text_faint,
+            "text_inverted" => &mut self.text_inverted,
+            "selected" => &mut self.selected,
+            "active" => &mut self.active,
+            "bg_base" => &mut self.bg_base,
+            "bg_surface" => &mut self.bg_surface,
+            "input_prompt" => &mut self.input_prompt,
+            "attention_waiting" => &mut self.attention_waiting,
+            "attention_idle" => &mut self.attention_idle,
+            "attention_badge" => &mut self.attention_badge,
+            "success" => &mut self.success,
+            "warning" => &mut self.warning,
+            "danger" => &mut self.danger,
+            "error_bg" => &mut self.error_bg,
+            "error_fg" => &mut self.error_fg,
+            "warning_bg" => &mut self.warning_bg,
+            "warning_fg" => &mut self.warning_fg,
+            "border_focus" => &mut self.border_focus,
+            "border" => &mut self.border,
+            "border_dim" => &mut self.border_dim,
+            "border_warning" => &mut self.border_warning,
+            "confirm_key" => &mut self.confirm_key,
+            "cancel_key" => &mut self.cancel_key,
+            "default_marker" => &mut self.default_marker,
+            _ => return None,
+        })
+    }
+
+    /// Parse a theme file's text: a built-in base plus token overrides
+    ///
+    /// Strict, unlike `config.toml`: a theme is applied on request, so naming
+    /// the bad token is more useful than half-applying the file.
+    pub fn from_toml(text: &str, support: ColorSupport) -> Result<Self> {
+        let file: ThemeFile = toml::from_str(text)?;
+        let base = file.base.as_deref().unwrap_or("dark");
+        let mut theme = Self::built_in(base, support).ok_or_else(|| {
+            anyhow!(
+                "base '{}' is not a built-in theme ({})",
+                base,
+                BUILT_IN_THEMES.join(", ")
+            )
+        })?;
+        for (token, value) in &file.colors {
+            let color: Color = value
+                .parse()
+                .map_err(|_| anyhow!("'{}' is not a colour (for {})", value, token))?;
+            match theme.token_mut(token) {
+                Some(slot) => *slot = color,
+                None => bail!("'{}' is not a theme token", token),
+            }
+        }
+        Ok(theme)
+    }
+
     /// Get the color for a session state
     pub fn session_state_color(&self, state: &crate::session::SessionState) -> Color {
         use crate::session::SessionState;
@@ -293,7 +451,7 @@ impl Theme {
         match reason {
             AttentionReason::TurnComplete => self.success,
             AttentionReason::Approval { .. } | AttentionReason::Stalled { .. } => self.warning,
-            AttentionReason::Crashed { .. } => self.danger,
+            AttentionReason::Crashed { .. } | AttentionReason::OverBudget { .. } => self.danger,
         }
     }
 
@@ -345,32 +503,111 @@ impl Theme {
     }
 }
 
-/// Global theme instance
-static THEME: std::sync::OnceLock<Theme> = std::sync::OnceLock::new();
+/// A theme file in `~/.panoptes/themes/`
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ThemeFile {
+    /// Built-in theme to start from, `dark` when absent
+    #[serde(default)]
+    base: Option<String>,
+    /// Token name to colour: a name (`blue`, `lightred`), an index (`136`),
+    /// or `#rrggbb`
+    #[serde(default)]
+    colors: BTreeMap<String, String>,
+}
+
+/// Every theme that can be chosen: the built-ins, then `dir`'s `*.toml` by name
+///
+/// A file named like a built-in is left out; the built-in keeps its name.
+pub fn theme_names(dir: &Path) -> Vec<String> {
+    let mut files: Vec<String> = std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+        .filter_map(|path| path.file_stem()?.to_str().map(str::to_string))
+        .filter(|name| !BUILT_IN_THEMES.contains(&name.as_str()))
+        .collect();
+    files.sort();
+
+    let mut names: Vec<String> = BUILT_IN_THEMES.iter().map(|n| n.to_string()).collect();
+    names.extend(files);
+    names
+}
+
+/// Load the theme called `name`, built-in or from `dir`
+pub fn load_theme(name: &str, support: ColorSupport, dir: &Path) -> Result<Theme> {
+    if let Some(theme) = Theme::built_in(name, support) {
+        return Ok(theme);
+    }
+    let path = dir.join(format!("{}.toml", name));
+    let text = std::fs::read_to_string(&path)
+        .with_context(|| format!("Theme '{}' not found at {}", name, path.display()))?;
+    Theme::from_toml(&text, support).with_context(|| format!("Theme '{}' is invalid", name))
+}
 
-/// Install the theme for this run, from the configured mode
+/// The theme in use
+///
+/// Each switch leaks the theme it installs, so every `&'static Theme` handed
+/// out stays valid. A theme is a few hundred bytes and switching is a
+/// keypress in Settings, so the leak is bounded by the user's patience.
+static THEME: RwLock<Option<&'static Theme>> = RwLock::new(None);
+
+/// The capability tier decided at startup, which later switches keep
+static SUPPORT: OnceLock<ColorSupport> = OnceLock::new();
+
+/// Install the theme for this run, from the configured tier and theme name
 ///
 /// Called once at startup, before the first render. `Auto` detects the tier
 /// from the environment; the other modes force one, for when detection is
-/// wrong. A second call is ignored: the theme is pinned for the process.
-pub fn init(mode: ThemeMode) {
+/// wrong. The tier is pinned for the process. When the named theme cannot be
+/// loaded, `dark` is used and the returned warning says why.
+pub fn init(mode: ThemeMode, name: &str, dir: &Path) -> Option<String> {
     let support = match mode {
         ThemeMode::Auto => detect_color_support(),
         ThemeMode::TrueColor => ColorSupport::TrueColor,
         ThemeMode::Ansi256 => ColorSupport::Ansi256,
         ThemeMode::Ansi16 => ColorSupport::Ansi16,
     };
-    if THEME.set(Theme::for_support(support)).is_err() {
+    if SUPPORT.set(support).is_err() {
         tracing::warn!("Theme already initialised; ignoring re-init");
+        return None;
+    }
+    match load_theme(name, support, dir) {
+        Ok(theme) => {
+            install(theme);
+            None
+        }
+        Err(e) => {
+            install(Theme::for_support(support));
+            Some(format!("{:#}. Using the dark theme.", e))
+        }
     }
 }
 
+/// Switch to the theme called `name`, effective from the next frame
+pub fn switch(name: &str, dir: &Path) -> Result<()> {
+    let support = *SUPPORT.get_or_init(detect_color_support);
+    install(load_theme(name, support, dir)?);
+    Ok(())
+}
+
+fn install(theme: Theme) {
+    let theme: &'static Theme = Box::leak(Box::new(theme));
+    *THEME.write().unwrap_or_else(|e| e.into_inner()) = Some(theme);
+}
+
 /// Get the current theme
 ///
 /// Falls back to the 16-colour baseline when [`init`] has not run - which is
 /// the case in tests, keeping their colours independent of the environment.
 pub fn theme() -> &'static Theme {
-    THEME.get_or_init(Theme::default)
+    if let Some(theme) = *THEME.read().unwrap_or_else(|e| e.into_inner()) {
+        return theme;
+    }
+    static FALLBACK: OnceLock<Theme> = OnceLock::new();
+    FALLBACK.get_or_init(Theme::default)
 }
 
 #[cfg(test)]
@@ -530,6 +767,88 @@ mod tests {
         assert_eq!(theme.session_state_color(&SessionState::Exited), Color::Red);
     }
 
+    #[test]
+    fn test_light_theme_reads_on_a_light_background() {
+        for support in [
+            ColorSupport::Ansi16,
+            ColorSupport::Ansi256,
+            ColorSupport::TrueColor,
+        ] {
+            let light = Theme::light(support);
+            assert_eq!(light.text, Color::Black, "{support:?}");
+            assert_ne!(light.selected, Color::White, "{support:?}");
+            assert_ne!(light.state_awaiting_approval, Color::LightYellow);
+            // The signals keep their meaning: same outcome colours as dark
+            assert_eq!(light.danger, Theme::ansi16().danger);
+            assert_eq!(light.success, Theme::ansi16().success);
+            if support != ColorSupport::Ansi16 {
+                assert_ne!(light.state_suspended, light.text_dim);
+                assert_ne!(light.state_suspended, light.text_faint);
+            }
+        }
+    }
+
+    #[test]
+    fn test_theme_file_overrides_tokens_on_a_base() {
+        let theme = Theme::from_toml(
+            "base = \"light\"\n[colors]\naccent = \"#005f87\"\nstate_thinking = \"136\"\nborder_dim = \"light red\"\n",
+            ColorSupport::Ansi16,
+        )
+        .unwrap();
+        assert_eq!(theme.accent, Color::Rgb(0x00, 0x5f, 0x87));
+        assert_eq!(theme.state_thinking, Color::Indexed(136));
+        assert_eq!(theme.border_dim, Color::LightRed);
+        // Untouched tokens come from the base
+        assert_eq!(theme.text, Color::Black);
+
+        // No base means dark
+        let theme = Theme::from_toml("[colors]\ntext = \"gray\"\n", ColorSupport::Ansi16).unwrap();
+        assert_eq!(theme.text, Color::Gray);
+        assert_eq!(theme.accent, Color::Cyan);
+
+        for bad in [
+            "[colors]\nshiny = \"red\"\n",
+            "[colors]\ntext = \"puce\"\n",
+            "base = \"sepia\"\n",
+            "colours = {}\n",
+        ] {
+            assert!(
+                Theme::from_toml(bad, ColorSupport::Ansi16).is_err(),
+                "{bad}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_themes_are_listed_and_loaded_from_the_directory() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(dir.path().join("solarized.toml"), "base = \"light\"\n").unwrap();
+        std::fs::write(dir.path().join("dark.toml"), "[colors]\ntext = \"red\"\n").unwrap();
+        std::fs::write(dir.path().join("broken.toml"), "[colors]\ntext = 3\n").unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "").unwrap();
+
+        assert_eq!(
+            theme_names(dir.path()),
+            ["dark", "light", "broken", "solarized"]
+        );
+        assert_eq!(theme_names(&dir.path().join("missing")), ["dark", "light"]);
+
+        let support = ColorSupport::Ansi16;
+        let solarized = load_theme("solarized", support, dir.path()).unwrap();
+        assert_eq!(solarized.text, Color::Black);
+        // The built-in wins over a file of the same name
+        assert_eq!(
+            load_theme("dark", support, dir.path()).unwrap().text,
+            Color::White
+        );
+        let broken = load_theme("broken", support, dir.path()).unwrap_err();
+        assert!(
+            format!("{broken:#}").contains("Theme 'broken' is invalid"),
+            "{broken:#}"
+        );
+        assert!(load_theme("nope", support, dir.path()).is_err());
+    }
+
     #[test]
     fn test_global_theme() {
         let t = theme();
@@ -6,14 +6,18 @@
 //! and in which profile type they show. The render functions are written
 //! once, generic over [`AgentProfile`]; [`AgentKind`] picks the wording.
 
+use chrono::{DateTime, Utc};
 use ratatui::prelude::*;
 use ratatui::widgets::{List, ListItem, Paragraph};
 use uuid::Uuid;
 
 use crate::agent_profiles::{AgentProfile, ProfileStore};
 use crate::input::agent_configs::AgentKind;
+use crate::session::rate_limits::AccountLimit;
+use crate::session::CodexLimits;
 use crate::tui::theme::theme;
 use crate::tui::views::confirm::{render_confirm_dialog, ConfirmDialogConfig};
+use crate::tui::views::timeline::short_duration;
 use crate::tui::widgets::dialog::{render_dialog, DialogSize, DialogSpec};
 use crate::tui::widgets::selection::{
     selection_name_style, selection_prefix, selection_style_with_accent,
@@ -55,13 +59,15 @@ fn copy_for(kind: AgentKind) -> &'static AgentConfigCopy {
 /// Render an agent's config list into a settings-pane rect
 ///
 /// The pane owns the border and title, so this draws rows only, truncated
-/// against the pane's current width.
+/// against the pane's current width. Codex passes its accounts' rate-limit
+/// readings and the rotation threshold, which annotate each row.
 pub fn render_agent_config_list<C: AgentProfile>(
     frame: &mut Frame,
     area: Rect,
     config_store: &ProfileStore<C>,
     selected_index: usize,
     focused: bool,
+    rate_limits: Option<(&CodexLimits, f64)>,
 ) {
     let t = theme();
     let configs = config_store.configs_sorted();
@@ -83,7 +89,7 @@ pub fn render_agent_config_list<C: AgentProfile>(
             let is_selected = i == selected_index && focused;
             let is_default = default_id == Some(config.id());
 
-            let line = Line::from(vec![
+            let mut spans = vec![
                 Span::raw(selection_prefix(is_selected)),
                 Span::styled(
                     config.name().to_string(),
@@ -97,7 +103,13 @@ pub fn render_agent_config_list<C: AgentProfile>(
                     format!("  {}", config.home_dir_display()),
                     Style::default().fg(t.text_dim),
                 ),
-            ]);
+            ];
+            if let Some(span) = rate_limits.and_then(|(limits, threshold)| {
+                rate_limit_span(limits.get(Some(config.id()))?, threshold, Utc::now())
+            }) {
+                spans.push(span);
+            }
+            let line = Line::from(spans);
 
             ListItem::new(crate::tui::views::pane_projects::clamp_line(line, width))
         })
@@ -107,6 +119,31 @@ pub fn render_agent_config_list<C: AgentProfile>(
     frame.render_widget(List::new(items), area);
 }
 
+/// `  limit 93% · resets in 2h10m`, in the warning colour once at `threshold`
+///
+/// Nothing once the window has reset: the reading no longer says anything.
+fn rate_limit_span(
+    limit: &AccountLimit,
+    threshold: f64,
+    now: DateTime<Utc>,
+) -> Option<Span<'static>> {
+    let t = theme();
+    let used = limit.used_at(now);
+    if limit.resets_at.is_some() && used == 0.0 {
+        return None;
+    }
+    let mut text = format!("  limit {:.0}%", used);
+    if let Some(resets_at) = limit.resets_at {
+        text.push_str(&format!(" · resets in {}", short_duration(resets_at - now)));
+    }
+    let colour = if used >= threshold {
+        t.warning
+    } else {
+        t.text_dim
+    };
+    Some(Span::styled(text, Style::default().fg(colour)))
+}
+
 /// Render the config name input dialog
 pub fn render_agent_config_name_input_dialog(
     frame: &mut Frame,
@@ -351,7 +388,7 @@ mod tests {
 
     fn render_list<C: AgentProfile>(store: &ProfileStore<C>, selected: usize) -> Vec<String> {
         render_to_lines(60, 12, |frame| {
-            render_agent_config_list(frame, frame.size(), store, selected, true)
+            render_agent_config_list(frame, frame.size(), store, selected, true, None)
         })
     }
 
@@ -369,11 +406,40 @@ mod tests {
         assert!(contains_line(&focused, "▶ "), "{focused:?}");
 
         let unfocused = render_to_lines(60, 12, |frame| {
-            render_agent_config_list(frame, frame.size(), &store, 0, false)
+            render_agent_config_list(frame, frame.size(), &store, 0, false, None)
         });
         assert!(!contains_line(&unfocused, "▶ "), "{unfocused:?}");
     }
 
+    #[test]
+    fn test_codex_list_shows_each_accounts_rate_limit() {
+        let mut store = CodexConfigStore::new();
+        let work = CodexConfig::new("Work".to_string(), Some(PathBuf::from("/tmp/codex-work")));
+        let spare = CodexConfig::new("Spare".to_string(), Some(PathBuf::from("/tmp/spare")));
+        let (work_id, spare_id) = (work.id, spare.id);
+        store.add(work);
+        store.add(spare);
+
+        let mut limits = CodexLimits::default();
+        let in_two_hours = Utc::now() + chrono::Duration::minutes(121);
+        let usage = |percent| crate::agent::events::UsageSnapshot {
+            rate_limit_used_percent: Some(percent),
+            rate_limit_resets_at: Some(in_two_hours.to_rfc3339()),
+            ..Default::default()
+        };
+        limits.record(Some(work_id), &usage(93.0));
+        limits.record(Some(spare_id), &usage(12.0));
+
+        let lines = render_to_lines(80, 12, |frame| {
+            render_agent_config_list(frame, frame.size(), &store, 0, true, Some((&limits, 90.0)))
+        });
+        assert!(
+            contains_line(&lines, "/tmp/codex-work  limit 93% · resets in 2h00m"),
+            "{lines:?}"
+        );
+        assert!(contains_line(&lines, "/tmp/spare  limit 12%"), "{lines:?}");
+    }
+
     #[test]
     fn test_empty_list_points_at_the_add_key() {
         let lines = render_list(&ClaudeConfigStore::new(), 0);
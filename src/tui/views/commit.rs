@@ -0,0 +1,301 @@
+//! Commit wizard overlay
+//!
+//! Step 1 lists the changed files beside the highlighted file's diff; step 2
+//! is the message and the push toggle. Both are centred overlays anchored to
+//! the terminal: a diff squeezed into one pane is not a diff you can review.
+
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Wrap};
+
+use crate::app::{AppState, InputMode};
+use crate::tui::theme::theme;
+use crate::tui::widgets::dialog::{centered_rect, DialogSize};
+use crate::tui::widgets::selection::{selection_prefix, selection_style};
+
+const WIZARD_WIDTH: DialogSize = DialogSize::Percent {
+    pct: 85,
+    min: 40,
+    max: 160,
+};
+const WIZARD_HEIGHT: DialogSize = DialogSize::Percent {
+    pct: 80,
+    min: 10,
+    max: 48,
+};
+
+/// Render whichever commit wizard step is open
+pub fn render_commit_wizard(frame: &mut Frame, area: Rect, state: &AppState) {
+    let overlay = centered_rect(area, WIZARD_WIDTH, WIZARD_HEIGHT);
+    frame.render_widget(Clear, overlay);
+
+    match state.input_mode {
+        InputMode::CommitSelectFiles => render_select_files(frame, overlay, state),
+        InputMode::CommitMessage => render_message(frame, overlay, state),
+        _ => {}
+    }
+}
+
+/// Step 1: the file list on the left, the highlighted file's diff on the right
+fn render_select_files(frame: &mut Frame, area: Rect, state: &AppState) {
+    let t = theme();
+    let wizard = &state.commit_wizard;
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(35), Constraint::Percentage(65)])
+        .split(area);
+
+    let items: Vec<ListItem> = wizard
+        .changes
+        .iter()
+        .enumerate()
+        .map(|(i, change)| {
+            let ticked = wizard.selected.get(i).copied().unwrap_or(false);
+            let highlighted = i == wizard.list_index;
+            let staging = match (change.staged, change.unstaged) {
+                (true, true) => " (partly staged)",
+                (true, false) => " (staged)",
+                _ => "",
+            };
+            ListItem::new(format!(
+                "{}[{}] {} {}{}",
+                selection_prefix(highlighted),
+                if ticked { "x" } else { " " },
+                change.kind.code(),
+                change.path,
+                staging
+            ))
+            .style(selection_style(
+                highlighted,
+                if ticked { t.text } else { t.text_dim },
+            ))
+        })
+        .collect();
+
+    let ticked = wizard.selected.iter().filter(|&&s| s).count();
+    frame.render_widget(
+        List::new(items).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(t.border_focus))
+                .title(format!(
+                    "Commit '{}' ({} of {} files)",
+                    wizard.branch_name,
+                    ticked,
+                    wizard.changes.len()
+                )),
+        ),
+        columns[0],
+    );
+
+    let diff: Vec<Line> = wizard.diff_preview.lines().map(diff_line).collect();
+    frame.render_widget(
+        Paragraph::new(diff).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(t.border))
+                .title("Diff"),
+        ),
+        columns[1],
+    );
+}
+
+/// One line of a patch, coloured the way `git diff` colours it
+fn diff_line(line: &str) -> Line<'static> {
+    let t = theme();
+    let style = if line.starts_with("+++") || line.starts_with("---") {
+        Style::default().fg(t.text).add_modifier(Modifier::BOLD)
+    } else if line.starts_with('+') {
+        Style::default().fg(t.success)
+    } else if line.starts_with('-') {
+        Style::default().fg(t.danger)
+    } else if line.starts_with("@@") {
+        Style::default().fg(t.accent)
+    } else {
+        Style::default().fg(t.text_dim)
+    };
+    Line::from(Span::styled(line.to_string(), style))
+}
+
+/// Step 2: the message, whether it is being drafted, and where it will push
+fn render_message(frame: &mut Frame, area: Rect, state: &AppState) {
+    let t = theme();
+    let wizard = &state.commit_wizard;
+
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled("Committing ", t.muted_style()),
+            Span::styled(
+                format!("{} file(s)", wizard.selected.iter().filter(|&&s| s).count()),
+                Style::default().fg(t.accent).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(" on ", t.muted_style()),
+            Span::styled(
+                wizard.branch_name.clone(),
+                Style::default().fg(t.accent).add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled("Message:", t.muted_style())),
+    ];
+    for (i, text) in wizard.message.split('\n').enumerate() {
+        let prompt = if i == 0 { "> " } else { "  " };
+        lines.push(Line::from(Span::styled(
+            format!("{}{}", prompt, text),
+            t.input_style(),
+        )));
+    }
+    if let Some(last) = lines.last_mut() {
+        last.spans.push(Span::styled("_", t.input_style()));
+    }
+    if let Some(request) = &wizard.drafting {
+        let waiting = if request.sent_at.is_some() {
+            "Waiting for the agent's draft..."
+        } else {
+            "Waiting for the agent's turn to end before asking..."
+        };
+        lines.push(Line::from(Span::styled(
+            waiting,
+            Style::default().fg(t.warning),
+        )));
+    }
+    lines.push(Line::from(""));
+
+    match (&wizard.push_target, &wizard.push_blocker) {
+        (Some(target), _) => lines.push(Line::from(vec![
+            Span::styled(
+                if wizard.push { "[x] " } else { "[ ] " },
+                Style::default().fg(t.text),
+            ),
+            Span::styled(format!("Push to {}", target), Style::default().fg(t.text)),
+        ])),
+        (None, Some(reason)) => lines.push(Line::from(Span::styled(
+            format!("Cannot push: {}", reason),
+            Style::default().fg(t.text_dim),
+        ))),
+        (None, None) => {}
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![
+        Span::styled("Enter", Style::default().fg(t.confirm_key)),
+        Span::styled(": commit  ", t.muted_style()),
+        Span::styled("Tab", Style::default().fg(t.accent)),
+        Span::styled(": push  ", t.muted_style()),
+        Span::styled("Ctrl+G", Style::default().fg(t.accent)),
+        Span::styled(": ask agent  ", t.muted_style()),
+        Span::styled("Esc", Style::default().fg(t.cancel_key)),
+        Span::styled(": back", t.muted_style()),
+    ]));
+
+    frame.render_widget(
+        Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(t.accent))
+                .title("Commit"),
+        ),
+        area,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::CommitWizardState;
+    use crate::git::commit::{ChangeKind, WorkingChange};
+    use crate::tui::views::test_util::{contains_line, render_to_lines};
+
+    fn wizard_state(mode: InputMode) -> AppState {
+        AppState {
+            input_mode: mode,
+            commit_wizard: CommitWizardState {
+                branch_name: "feature".to_string(),
+                changes: vec![
+                    WorkingChange {
+                        path: "src/lib.rs".to_string(),
+                        kind: ChangeKind::Modified,
+                        staged: true,
+                        unstaged: false,
+                    },
+                    WorkingChange {
+                        path: "notes.md".to_string(),
+                        kind: ChangeKind::Added,
+                        staged: false,
+                        unstaged: true,
+                    },
+                ],
+                selected: vec![true, false],
+                diff_preview: "@@ -1 +1 @@\n-old\n+new".to_string(),
+                message: "Fix the parser".to_string(),
+                push: true,
+                push_target: Some("origin/feature".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_file_step_lists_ticks_and_the_diff() {
+        let state = wizard_state(InputMode::CommitSelectFiles);
+        let lines = render_to_lines(120, 30, |frame| {
+            render_commit_wizard(frame, frame.size(), &state)
+        });
+
+        assert!(
+            contains_line(&lines, "Commit 'feature' (1 of 2 files)"),
+            "{lines:?}"
+        );
+        assert!(
+            contains_line(&lines, "[x] M src/lib.rs (staged)"),
+            "{lines:?}"
+        );
+        assert!(contains_line(&lines, "[ ] A notes.md"), "{lines:?}");
+        assert!(contains_line(&lines, "+new"), "{lines:?}");
+    }
+
+    #[test]
+    fn test_message_step_shows_the_push_target_or_why_not() {
+        let mut state = wizard_state(InputMode::CommitMessage);
+        let lines = render_to_lines(120, 30, |frame| {
+            render_commit_wizard(frame, frame.size(), &state)
+        });
+        assert!(contains_line(&lines, "> Fix the parser_"), "{lines:?}");
+        assert!(
+            contains_line(&lines, "[x] Push to origin/feature"),
+            "{lines:?}"
+        );
+
+        state.commit_wizard.push_target = None;
+        state.commit_wizard.push_blocker = Some("no remote configured".to_string());
+        state.commit_wizard.drafting = Some(crate::app::DraftRequest {
+            sent_at: Some(chrono::Utc::now()),
+            ..crate::app::DraftRequest::new(uuid::Uuid::new_v4())
+        });
+        let lines = render_to_lines(120, 30, |frame| {
+            render_commit_wizard(frame, frame.size(), &state)
+        });
+        assert!(
+            contains_line(&lines, "Cannot push: no remote configured"),
+            "{lines:?}"
+        );
+        assert!(
+            contains_line(&lines, "Waiting for the agent's draft..."),
+            "{lines:?}"
+        );
+    }
+
+    /// Centred overlays with `Clear` panic rather than clip when the rect
+    /// escapes the buffer
+    #[test]
+    fn test_commit_overlay_survives_a_tiny_terminal() {
+        for mode in [InputMode::CommitSelectFiles, InputMode::CommitMessage] {
+            let state = wizard_state(mode);
+            for (width, height) in [(1_u16, 1_u16), (10, 3), (36, 8), (60, 12)] {
+                render_to_lines(width, height, |frame| {
+                    render_commit_wizard(frame, frame.size(), &state)
+                });
+            }
+        }
+    }
+}
@@ -6,7 +6,8 @@ use ratatui::prelude::*;
 use ratatui::widgets::{List, ListItem, Paragraph};
 
 use crate::app::{AppState, InputMode};
-use crate::config::{reserved_keys_display, Config};
+use crate::config::{reserved_keys_display, Config, CustomShortcut};
+use crate::project::ProjectStore;
 use crate::tui::theme::theme;
 use crate::tui::widgets::dialog::{render_dialog, yes_no_line, DialogSize, DialogSpec};
 use crate::tui::widgets::selection::{selection_prefix, selection_style_with_accent};
@@ -122,8 +123,23 @@ pub fn render_add_shortcut_key_dialog(frame: &mut Frame, area: Rect, state: &App
         Style::default().fg(t.text_dim),
     )));
     lines.push(Line::from(""));
+
+    // A project's shortcut can be kept for its folders instead
+    let hint = match &state.shortcut_scope {
+        Some(scope) => {
+            lines.insert(
+                1,
+                Line::from(vec![
+                    Span::styled("For: ", Style::default().fg(t.text_dim)),
+                    Span::styled(scope.label(), Style::default().fg(t.accent)),
+                ]),
+            );
+            "Tab: project/folder | Esc: cancel"
+        }
+        None => "Esc: cancel",
+    };
     lines.push(Line::from(Span::styled(
-        "Esc: cancel",
+        hint,
         Style::default().fg(t.text_dim),
     )));
 
@@ -135,7 +151,11 @@ pub fn render_add_shortcut_key_dialog(frame: &mut Frame, area: Rect, state: &App
             border_color: t.accent,
             alignment: Alignment::Center,
             width: NARROW,
-            height: DialogSize::Fixed(10),
+            height: DialogSize::Fixed(if state.shortcut_scope.is_some() {
+                11
+            } else {
+                10
+            }),
         },
         lines,
     );
@@ -316,13 +336,18 @@ pub fn render_delete_shortcut_confirm_dialog(
     area: Rect,
     state: &AppState,
     config: &Config,
+    project_store: &ProjectStore,
 ) {
     let t = theme();
 
-    // Get shortcut info
+    // Get shortcut info from wherever it is kept
+    let shortcuts: &[CustomShortcut] = match &state.shortcut_scope {
+        None => &config.custom_shortcuts,
+        Some(scope) => project_store.scoped_shortcuts(scope),
+    };
     let shortcut_info = state
         .pending_delete_shortcut_index
-        .and_then(|i| config.custom_shortcuts.get(i))
+        .and_then(|i| shortcuts.get(i))
         .map(|s| format!("'{}' ({})", s.key, s.display_name()))
         .unwrap_or_else(|| "?".to_string());
 
@@ -365,6 +390,7 @@ pub fn render_custom_shortcut_dialogs(
     area: Rect,
     state: &AppState,
     config: &Config,
+    project_store: &ProjectStore,
 ) {
     match state.input_mode {
         InputMode::AddingCustomShortcutKey => {
@@ -380,7 +406,7 @@ pub fn render_custom_shortcut_dialogs(
             render_add_shortcut_auto_close_dialog(frame, area, state);
         }
         InputMode::ConfirmingCustomShortcutDelete => {
-            render_delete_shortcut_confirm_dialog(frame, area, state, config);
+            render_delete_shortcut_confirm_dialog(frame, area, state, config, project_store);
         }
         _ => {}
     }
@@ -397,7 +423,13 @@ mod tests {
             ..Default::default()
         };
         render_to_lines(80, 24, |frame| {
-            render_custom_shortcut_dialogs(frame, frame.size(), &state, config)
+            render_custom_shortcut_dialogs(
+                frame,
+                frame.size(),
+                &state,
+                config,
+                &ProjectStore::new(),
+            )
         })
     }
 
@@ -477,6 +509,29 @@ mod tests {
         assert!(contains_line(&lines, "(Reserved:"), "{:?}", lines);
     }
 
+    #[test]
+    fn test_add_key_dialog_names_a_project_scope() {
+        let state = AppState {
+            input_mode: InputMode::AddingCustomShortcutKey,
+            shortcut_scope: Some(crate::project::ShortcutScope::Folder(vec![
+                "Acme".to_string()
+            ])),
+            ..Default::default()
+        };
+        let lines = render_to_lines(80, 24, |frame| {
+            render_custom_shortcut_dialogs(
+                frame,
+                frame.size(),
+                &state,
+                &Config::default(),
+                &ProjectStore::new(),
+            )
+        });
+
+        assert!(contains_line(&lines, "For: folder Acme"), "{lines:?}");
+        assert!(contains_line(&lines, "Tab: project/folder"), "{lines:?}");
+    }
+
     #[test]
     fn test_auto_close_dialog_offers_yes_no() {
         let lines = render_in_mode(InputMode::AddingCustomShortcutAutoClose, &Config::default());
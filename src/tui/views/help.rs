@@ -3,10 +3,14 @@
 //! Structured per pane and per sub-screen, mirroring the navigation model: a
 //! global section that applies everywhere, then the keys of the focused pane's
 //! current level. Dismissible with `?` or `Esc`.
+//!
+//! The remappable keys are read from the active [`Keymap`], so the overlay
+//! shows what the user's bindings actually are.
 
 use ratatui::prelude::*;
 
 use crate::app::{AppState, Focus, ProjectsNav, SettingsNav, Tab};
+use crate::keymap::{Action, Keymap};
 use crate::tui::theme::theme;
 use crate::tui::widgets::dialog::{render_dialog, DialogSize, DialogSpec};
 
@@ -39,42 +43,45 @@ pub fn render_help_overlay(frame: &mut Frame, area: Rect, state: &AppState) {
 
 /// The title and shortcut list for wherever the user currently is
 fn shortcuts_for(state: &AppState) -> (&'static str, Vec<Line<'static>>) {
+    let k = &state.keymap;
     match state.focus {
-        Focus::Session => ("Keyboard Shortcuts - Session", session_shortcuts()),
+        Focus::Session => ("Keyboard Shortcuts - Session", session_shortcuts(k)),
         Focus::Panes(Tab::Projects) => match state.projects_nav {
-            ProjectsNav::Overview => ("Keyboard Shortcuts - Projects", projects_shortcuts()),
-            ProjectsNav::Project(_) => ("Keyboard Shortcuts - Project", project_shortcuts()),
-            ProjectsNav::Branch(_, _) => ("Keyboard Shortcuts - Branch", branch_shortcuts()),
+            ProjectsNav::Overview => ("Keyboard Shortcuts - Projects", projects_shortcuts(k)),
+            ProjectsNav::Project(_) => ("Keyboard Shortcuts - Project", project_shortcuts(k)),
+            ProjectsNav::Branch(_, _) => ("Keyboard Shortcuts - Branch", branch_shortcuts(k)),
             ProjectsNav::ProjectSettings(_) => (
                 "Keyboard Shortcuts - Project settings",
-                project_settings_shortcuts(),
+                project_settings_shortcuts(k),
             ),
         },
-        Focus::Panes(Tab::Sessions) => ("Keyboard Shortcuts - Sessions", sessions_shortcuts()),
+        Focus::Panes(Tab::Sessions) => ("Keyboard Shortcuts - Sessions", sessions_shortcuts(k)),
         Focus::Panes(Tab::Settings) => match state.settings_nav {
-            SettingsNav::Sections => ("Keyboard Shortcuts - Settings", settings_shortcuts()),
-            SettingsNav::ClaudeConfigs | SettingsNav::CodexConfigs => {
-                ("Keyboard Shortcuts - Configs", settings_configs_shortcuts())
-            }
+            SettingsNav::Sections => ("Keyboard Shortcuts - Settings", settings_shortcuts(k)),
+            SettingsNav::ClaudeConfigs | SettingsNav::CodexConfigs => (
+                "Keyboard Shortcuts - Configs",
+                settings_configs_shortcuts(k),
+            ),
             SettingsNav::Shortcuts => (
                 "Keyboard Shortcuts - Custom shortcuts",
-                settings_shortcuts_section(),
+                settings_shortcuts_section(k),
             ),
             SettingsNav::Notifications => (
                 "Keyboard Shortcuts - Notifications",
-                settings_notifications_shortcuts(),
+                settings_notifications_shortcuts(k),
             ),
-            SettingsNav::About => ("Keyboard Shortcuts - About", settings_about_shortcuts()),
+            SettingsNav::Theme => ("Keyboard Shortcuts - Theme", settings_theme_shortcuts(k)),
+            SettingsNav::About => ("Keyboard Shortcuts - About", settings_about_shortcuts(k)),
         },
     }
 }
 
 /// Format a shortcut line with key and description
-fn shortcut_line(key: &'static str, desc: &'static str) -> Line<'static> {
+fn shortcut_line(key: impl AsRef<str>, desc: &'static str) -> Line<'static> {
     let t = theme();
     Line::from(vec![
         Span::styled(
-            format!("{:>12}", key),
+            format!("{:>12}", key.as_ref()),
             Style::default().fg(t.accent).add_modifier(Modifier::BOLD),
         ),
         Span::raw("  "),
@@ -108,22 +115,44 @@ fn footer_hint() -> Line<'static> {
 }
 
 /// The keys that mean the same thing from every pane
-fn global_section() -> Vec<Line<'static>> {
+fn global_section(k: &Keymap) -> Vec<Line<'static>> {
     vec![
         empty_line(),
         section_header("Global"),
-        shortcut_line("→ ← Tab ⇧Tab", "Switch pane (wraps)"),
-        shortcut_line("Esc", "Back one level, then out to Projects"),
-        shortcut_line("q", "Quit (asks to confirm)"),
-        shortcut_line("Space", "Jump to next session needing attention"),
-        shortcut_line("?", "Toggle this help"),
+        shortcut_line(
+            k.label(&[Action::NextPane, Action::PrevPane]),
+            "Switch pane (wraps)",
+        ),
+        shortcut_line(
+            k.label(&[Action::Back]),
+            "Back one level, then out to Projects",
+        ),
+        shortcut_line(k.label(&[Action::Quit]), "Quit (asks to confirm)"),
+        shortcut_line(
+            k.label(&[Action::JumpToAttention]),
+            "Jump to next session needing attention",
+        ),
+        shortcut_line(
+            k.label(&[Action::CommandPalette]),
+            "Go to a session, branch, project or action by name",
+        ),
+        shortcut_line(
+            k.label(&[Action::Timeline]),
+            "Activity timeline: every session's recent states",
+        ),
+        shortcut_line(k.label(&[Action::Help]), "Toggle this help"),
         empty_line(),
     ]
 }
 
+/// The keys that move the selection
+fn updown(k: &Keymap) -> String {
+    k.label(&[Action::Up, Action::Down])
+}
+
 /// Build a pane's help: the global section, then this level's own keys
-fn with_global(header: &'static str, lines: Vec<Line<'static>>) -> Vec<Line<'static>> {
-    let mut all = global_section();
+fn with_global(k: &Keymap, header: &'static str, lines: Vec<Line<'static>>) -> Vec<Line<'static>> {
+    let mut all = global_section(k);
     all.push(section_header(header));
     all.extend(lines);
     all.push(empty_line());
@@ -131,150 +160,213 @@ fn with_global(header: &'static str, lines: Vec<Line<'static>>) -> Vec<Line<'sta
     all
 }
 
-fn projects_shortcuts() -> Vec<Line<'static>> {
+fn projects_shortcuts(k: &Keymap) -> Vec<Line<'static>> {
     with_global(
+        k,
         "Pane 1 - Projects",
         vec![
-            shortcut_line("↑ / ↓", "Move through the tree"),
-            shortcut_line("Enter", "Open project, or expand/collapse folder"),
-            shortcut_line("n", "Add a project"),
-            shortcut_line("d", "Delete project, or ungroup folder"),
-            shortcut_line("m", "Move project or folder into a folder"),
-            shortcut_line("r", "Rename the selected folder"),
-            shortcut_line("R", "Refresh git state"),
+            shortcut_line(updown(k), "Move through the tree"),
+            shortcut_line(
+                k.label(&[Action::Select]),
+                "Open project, or expand/collapse folder",
+            ),
+            shortcut_line(k.label(&[Action::New]), "Add a project"),
+            shortcut_line(
+                k.label(&[Action::Delete]),
+                "Delete project, or ungroup folder",
+            ),
+            shortcut_line(
+                k.label(&[Action::Move]),
+                "Move project or folder into a folder",
+            ),
+            shortcut_line(k.label(&[Action::Rename]), "Rename the selected folder"),
+            shortcut_line(k.label(&[Action::Refresh]), "Refresh git state"),
         ],
     )
 }
 
-fn project_shortcuts() -> Vec<Line<'static>> {
+fn project_shortcuts(k: &Keymap) -> Vec<Line<'static>> {
     with_global(
+        k,
         "Pane 1 - Project",
         vec![
-            shortcut_line("↑ / ↓ / 1-9", "Select a branch"),
-            shortcut_line("Enter", "Open the branch"),
-            shortcut_line("n", "Create a worktree"),
-            shortcut_line("d", "Delete the selected branch"),
-            shortcut_line("R", "Refresh branches"),
-            shortcut_line(",", "Project settings"),
+            shortcut_line(format!("{} / 1-9", updown(k)), "Select a branch"),
+            shortcut_line(k.label(&[Action::Select]), "Open the branch"),
+            shortcut_line(k.label(&[Action::New]), "Create a worktree"),
+            shortcut_line(
+                k.label(&[Action::Commit]),
+                "Commit & push the selected branch",
+            ),
+            shortcut_line(k.label(&[Action::Delete]), "Delete the selected branch"),
+            shortcut_line(k.label(&[Action::Refresh]), "Refresh branches"),
+            shortcut_line(k.label(&[Action::ProjectSettings]), "Project settings"),
         ],
     )
 }
 
-fn branch_shortcuts() -> Vec<Line<'static>> {
+fn branch_shortcuts(k: &Keymap) -> Vec<Line<'static>> {
     with_global(
+        k,
         "Pane 1 - Branch",
         vec![
-            shortcut_line("↑ / ↓ / 1-9", "Select a session (0 = 10)"),
-            shortcut_line("Enter", "Open session (resumes if [Resumable])"),
-            shortcut_line("n", "New AI session (Claude/Codex)"),
-            shortcut_line("s", "New shell session"),
-            shortcut_line("d", "Delete session (or discard a resumable one)"),
+            shortcut_line(format!("{} / 1-9", updown(k)), "Select a session (0 = 10)"),
+            shortcut_line(
+                k.label(&[Action::Select]),
+                "Open session (resumes if [Resumable])",
+            ),
+            shortcut_line(k.label(&[Action::New]), "New AI session (Claude/Codex)"),
+            shortcut_line(k.label(&[Action::NewShell]), "New shell session"),
+            shortcut_line(
+                k.label(&[Action::Delete]),
+                "Delete session (or discard a resumable one)",
+            ),
             shortcut_line("<key>", "Run a custom shortcut"),
         ],
     )
 }
 
-fn project_settings_shortcuts() -> Vec<Line<'static>> {
+fn project_settings_shortcuts(k: &Keymap) -> Vec<Line<'static>> {
     with_global(
+        k,
         "Pane 1 - Project settings",
         vec![
-            shortcut_line("↑ / ↓", "Move through the rows"),
-            shortcut_line("Enter", "Open the selected setting"),
-            shortcut_line("Esc", "Back to the branch list"),
+            shortcut_line(updown(k), "Move through the rows"),
+            shortcut_line(k.label(&[Action::Select]), "Open the selected setting"),
+            shortcut_line(
+                k.label(&[Action::Delete]),
+                "Delete the selected custom shortcut",
+            ),
+            shortcut_line(k.label(&[Action::Back]), "Back to the branch list"),
         ],
     )
 }
 
-fn sessions_shortcuts() -> Vec<Line<'static>> {
+fn sessions_shortcuts(k: &Keymap) -> Vec<Line<'static>> {
     with_global(
+        k,
         "Pane 2 - Sessions",
         vec![
-            shortcut_line("↑ / ↓ / 1-9", "Select a session (0 = 10)"),
-            shortcut_line("Enter", "Open the session full-screen"),
-            shortcut_line("d", "Delete the selected session"),
-            shortcut_line("Esc", "Back to the Projects pane"),
+            shortcut_line(format!("{} / 1-9", updown(k)), "Select a session (0 = 10)"),
+            shortcut_line(k.label(&[Action::Select]), "Open the session full-screen"),
+            shortcut_line(k.label(&[Action::Delete]), "Delete the selected session"),
+            shortcut_line(k.label(&[Action::Back]), "Back to the Projects pane"),
         ],
     )
 }
 
-fn settings_shortcuts() -> Vec<Line<'static>> {
+fn settings_shortcuts(k: &Keymap) -> Vec<Line<'static>> {
     with_global(
+        k,
         "Pane 3 - Settings",
         vec![
-            shortcut_line("↑ / ↓", "Move through the sections"),
-            shortcut_line("Enter", "Open the selected section"),
-            shortcut_line("Esc", "Back to the Projects pane"),
+            shortcut_line(updown(k), "Move through the sections"),
+            shortcut_line(k.label(&[Action::Select]), "Open the selected section"),
+            shortcut_line(k.label(&[Action::Back]), "Back to the Projects pane"),
         ],
     )
 }
 
-fn settings_configs_shortcuts() -> Vec<Line<'static>> {
+fn settings_configs_shortcuts(k: &Keymap) -> Vec<Line<'static>> {
     with_global(
+        k,
         "Settings - Configs",
         vec![
-            shortcut_line("↑ / ↓", "Select a config"),
-            shortcut_line("n", "Add a config"),
-            shortcut_line("d", "Delete the selected config"),
-            shortcut_line("s", "Set as default"),
-            shortcut_line("Esc", "Back to the sections list"),
+            shortcut_line(updown(k), "Select a config"),
+            shortcut_line(k.label(&[Action::New]), "Add a config"),
+            shortcut_line(k.label(&[Action::Delete]), "Delete the selected config"),
+            shortcut_line(k.label(&[Action::NewShell]), "Set as default"),
+            shortcut_line(k.label(&[Action::Back]), "Back to the sections list"),
         ],
     )
 }
 
-fn settings_shortcuts_section() -> Vec<Line<'static>> {
+fn settings_shortcuts_section(k: &Keymap) -> Vec<Line<'static>> {
     with_global(
+        k,
         "Settings - Custom shortcuts",
         vec![
-            shortcut_line("↑ / ↓", "Select a shortcut"),
-            shortcut_line("n", "Bind a key to a shell command"),
-            shortcut_line("d", "Delete the selected shortcut"),
-            shortcut_line("Esc", "Back to the sections list"),
+            shortcut_line(updown(k), "Select a shortcut"),
+            shortcut_line(k.label(&[Action::New]), "Bind a key to a shell command"),
+            shortcut_line(k.label(&[Action::Delete]), "Delete the selected shortcut"),
+            shortcut_line(k.label(&[Action::Back]), "Back to the sections list"),
         ],
     )
 }
 
-fn settings_notifications_shortcuts() -> Vec<Line<'static>> {
+fn settings_notifications_shortcuts(k: &Keymap) -> Vec<Line<'static>> {
     with_global(
+        k,
         "Settings - Notifications",
         vec![
-            shortcut_line("↑ / ↓", "Move through the rows"),
+            shortcut_line(updown(k), "Move through the rows"),
             shortcut_line(
                 "Space / Enter",
                 "Toggle the row, or change how you are notified",
             ),
-            shortcut_line("Esc", "Back to the sections list"),
+            shortcut_line(k.label(&[Action::Back]), "Back to the sections list"),
+        ],
+    )
+}
+
+fn settings_theme_shortcuts(k: &Keymap) -> Vec<Line<'static>> {
+    with_global(
+        k,
+        "Settings - Theme",
+        vec![
+            shortcut_line(updown(k), "Select a theme"),
+            shortcut_line(k.label(&[Action::Select]), "Use it now (and save it)"),
+            shortcut_line(k.label(&[Action::Back]), "Back to the sections list"),
         ],
     )
 }
 
-fn settings_about_shortcuts() -> Vec<Line<'static>> {
+fn settings_about_shortcuts(k: &Keymap) -> Vec<Line<'static>> {
     with_global(
+        k,
         "Settings - About / paths",
         vec![
-            shortcut_line("Esc", "Back to the sections list"),
-            shortcut_line("", "Everything here is read-only; edit config.toml"),
+            shortcut_line("e", "Export all state to ~/panoptes-backup-*.json"),
+            shortcut_line(k.label(&[Action::Back]), "Back to the sections list"),
+            shortcut_line("", "The values here are read-only; edit config.toml"),
         ],
     )
 }
 
-fn session_shortcuts() -> Vec<Line<'static>> {
+fn session_shortcuts(k: &Keymap) -> Vec<Line<'static>> {
     vec![
         empty_line(),
         section_header("Session - normal mode"),
-        shortcut_line("Enter", "Enter session mode (type in the PTY)"),
-        shortcut_line("Esc", "Back to the pane it was opened from"),
-        shortcut_line("q", "Quit (asks to confirm)"),
+        shortcut_line(
+            k.label(&[Action::Select]),
+            "Enter session mode (type in the PTY)",
+        ),
+        shortcut_line(
+            k.label(&[Action::Back]),
+            "Back to the pane it was opened from",
+        ),
+        shortcut_line(k.label(&[Action::Quit]), "Quit (asks to confirm)"),
         shortcut_line("1-9", "Switch to session by number (0 = 10)"),
-        shortcut_line("Space", "Jump to next session needing attention"),
-        shortcut_line("↑ / ↓", "Scroll (3 lines)"),
+        shortcut_line(
+            k.label(&[Action::JumpToAttention]),
+            "Jump to next session needing attention",
+        ),
+        shortcut_line(
+            k.label(&[Action::Tile]),
+            "Tile 2, 3 or 4 sessions side by side",
+        ),
+        shortcut_line(
+            k.label(&[Action::PrevPane, Action::NextPane]),
+            "Focus the previous / next tile",
+        ),
+        shortcut_line(updown(k), "Scroll (3 lines)"),
         shortcut_line("PgUp/PgDn", "Scroll a page"),
         shortcut_line("Home / End", "Jump to top / live view"),
         shortcut_line("<key>", "Run a custom shortcut"),
         empty_line(),
         section_header("Session - attached"),
         shortcut_line("All keys", "Forwarded to the agent"),
-        shortcut_line("Esc", "Detach back to normal mode"),
-        shortcut_line("\u{21E7}Esc", "Send Esc to the agent"),
+        shortcut_line(k.label(&[Action::Detach]), "Detach back to normal mode"),
+        shortcut_line(k.label(&[Action::SendEsc]), "Send Esc to the agent"),
         shortcut_line("Ctrl+Home/End", "Scroll without detaching"),
         shortcut_line("Mouse scroll", "Scroll when the PTY supports it"),
         empty_line(),
@@ -349,6 +441,7 @@ mod tests {
                 SettingsNav::Notifications,
                 "Keyboard Shortcuts - Notifications",
             ),
+            (SettingsNav::Theme, "Keyboard Shortcuts - Theme"),
             (SettingsNav::About, "Keyboard Shortcuts - About"),
         ] {
             cases.push((
@@ -380,6 +473,38 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_help_shows_the_active_bindings() {
+        let keys = toml::from_str(
+            "[normal]\nup = [\"up\", \"k\"]\ndown = [\"down\", \"j\"]\n\n[session]\ndetach = \"ctrl+]\"\nsend_esc = \"esc\"\n",
+        )
+        .unwrap();
+        let (keymap, _) = Keymap::from_config(&keys, &[]);
+
+        let lines = render(&AppState {
+            keymap: keymap.clone(),
+            ..Default::default()
+        });
+        assert!(
+            contains_line(&lines, "↑ k / ↓ j  Move through the tree"),
+            "{lines:?}"
+        );
+
+        let lines = render(&AppState {
+            focus: Focus::Session,
+            keymap,
+            ..Default::default()
+        });
+        assert!(
+            contains_line(&lines, "Ctrl+]  Detach back to normal mode"),
+            "{lines:?}"
+        );
+        assert!(
+            contains_line(&lines, "Esc  Send Esc to the agent"),
+            "{lines:?}"
+        );
+    }
+
     /// The keys that were retired must not be advertised anywhere
     #[test]
     fn test_help_never_mentions_the_retired_keys() {
@@ -13,6 +13,7 @@ use crate::tui::theme::theme;
 mod agent_configs;
 mod agent_select;
 mod claude_settings;
+mod commit;
 mod confirm;
 mod custom_shortcuts;
 mod help;
@@ -22,6 +23,9 @@ pub(crate) mod pane_settings;
 mod panes;
 mod prompts;
 mod session;
+mod spend;
+mod stats;
+mod timeline;
 mod worktree;
 
 #[cfg(test)]
@@ -36,6 +40,7 @@ pub use agent_select::render_agent_type_selector;
 pub use claude_settings::{
     render_claude_settings_copy_dialog, render_claude_settings_migrate_dialog,
 };
+pub use commit::render_commit_wizard;
 pub use confirm::{
     render_confirm_dialog, render_error_overlay, render_loading_indicator,
     render_quit_confirm_dialog, render_session_delete_confirmation, render_startup_notice_overlay,
@@ -45,9 +50,11 @@ pub use custom_shortcuts::{render_custom_shortcut_dialogs, render_shortcuts_list
 pub use help::render_help_overlay;
 pub use panes::{render_panes, PaneContext};
 pub use prompts::{
-    render_folder_move_dialog, render_folder_remove_confirmation, render_project_addition_dialog,
+    render_command_palette, render_folder_move_dialog, render_folder_remove_confirmation,
+    render_project_addition_dialog,
 };
 pub use session::render_session_view;
+pub use timeline::render_activity_timeline;
 pub use worktree::{
     render_branch_delete_confirmation, render_default_base_selector,
     render_project_delete_confirmation, render_worktree_wizard,
@@ -136,12 +143,14 @@ pub fn attention_badge(info: &SessionInfo, needs_attention: bool) -> (&'static s
 /// colour at all
 ///
 /// A full circle is a finished turn waiting on nobody but you; a half circle
-/// is a turn stopped partway, blocked on you; a cross is a process that died.
+/// is a turn stopped partway, blocked on you; a cross is a process that died;
+/// a dollar sign is a session that went over its budget.
 fn attention_symbol(reason: &AttentionReason) -> &'static str {
     match reason {
         AttentionReason::TurnComplete => "● ",
         AttentionReason::Approval { .. } | AttentionReason::Stalled { .. } => "◐ ",
         AttentionReason::Crashed { .. } => "✗ ",
+        AttentionReason::OverBudget { .. } => "$ ",
     }
 }
 
@@ -221,7 +230,7 @@ pub(crate) fn window_rows<T>(rows: Vec<T>, selected_row: usize, height: u16) ->
 }
 
 /// Format custom shortcuts for footer display (e.g., "v:VSCode e:vim | ")
-pub fn format_custom_shortcuts_hint(shortcuts: &[CustomShortcut]) -> String {
+pub fn format_custom_shortcuts_hint(shortcuts: &[&CustomShortcut]) -> String {
     if shortcuts.is_empty() {
         return String::new();
     }
@@ -382,6 +391,11 @@ mod tests {
             reason: "signal 9".to_string(),
         });
         assert_eq!(attention_badge(&info, true).0, "✗ ");
+
+        info.attention = Some(AttentionReason::OverBudget {
+            limit: "session $5.00".to_string(),
+        });
+        assert_eq!(attention_badge(&info, true).0, "$ ");
     }
 
     #[test]
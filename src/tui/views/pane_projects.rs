@@ -12,7 +12,7 @@ use ratatui::widgets::{List, ListItem, Paragraph};
 use crate::app::{AppState, InputMode, ProjectsNav};
 use crate::project::{
     branch_count_label, folder_path_key, project_count_label, Branch, Project, ProjectId,
-    ProjectStore, TreeRow,
+    ProjectKind, ProjectStore, ShortcutScope, TreeRow,
 };
 use crate::session::SessionManager;
 use crate::tui::panes::SideMode;
@@ -21,11 +21,18 @@ use crate::tui::views::{status_parts, truncate_string, window_rows};
 use crate::tui::widgets::selection::{activity_style, selection_prefix, selection_style};
 
 /// The per-project settings rows, in list order
-pub const PROJECT_SETTINGS_ROWS: [&str; 4] = [
+///
+/// The project's own custom shortcuts, and its folders', follow as one row
+/// each after these.
+pub const PROJECT_SETTINGS_ROWS: [&str; 8] = [
     "Default Claude config",
     "Default Codex config",
     "Default base branch",
+    "Initialise submodules",
+    "Sparse checkout",
+    "Repo commands",
     "Rename project",
+    "Custom shortcuts",
 ];
 
 /// Breadcrumb shown in pane 1's own block title, fitted to `width`
@@ -91,7 +98,14 @@ pub fn render_projects_pane(
     // overlays anchored to the terminal instead (see `views::prompts`).
     match state.input_mode {
         InputMode::AddingProjectName => {
-            render_inline_input(frame, area, "Project name", &state.new_project_name);
+            let title = match &state.pending_project_kind {
+                ProjectKind::Git => "Project name".to_string(),
+                ProjectKind::Directory => "Project name (plain directory, no git)".to_string(),
+                ProjectKind::MultiRepo { repos } => {
+                    format!("Project name ({} repositories)", repos.len())
+                }
+            };
+            render_inline_input(frame, area, &title, &state.new_project_name);
             return;
         }
         InputMode::RenamingProject => {
@@ -142,6 +156,7 @@ pub fn render_projects_pane(
             mode,
         ),
         ProjectsNav::Branch(_, branch_id) => {
+            let area = render_shared_files(frame, area, state, branch_id, project_store, mode);
             render_branch_sessions(frame, area, state, branch_id, sessions, mode)
         }
         ProjectsNav::ProjectSettings(project_id) => {
@@ -452,6 +467,7 @@ fn render_branches(
         for branch in &worktrees {
             let active_count = sessions.active_session_count_for_branch(branch.id);
             let attention_count = sessions.attention_count_for_branch(branch.id);
+            let shared_files = state.file_overlaps.count_for_branch(branch.id);
 
             let status = if mode == SideMode::Full {
                 let mut parts = Vec::new();
@@ -461,12 +477,15 @@ fn render_branches(
                 if branch.stale {
                     parts.push("⚠ missing".to_string());
                 }
+                if shared_files > 0 {
+                    parts.push(format!("⚠ {}", shared_file_label(shared_files)));
+                }
                 if parts.is_empty() {
                     String::new()
                 } else {
                     format!("  ({})", parts.join(", "))
                 }
-            } else if branch.stale {
+            } else if branch.stale || shared_files > 0 {
                 " ⚠".to_string()
             } else {
                 String::new()
@@ -494,18 +513,106 @@ fn render_branches(
     frame.render_widget(List::new(items), area);
 }
 
+/// Format a shared-file count for display, e.g. "1 shared file" / "3 shared files"
+pub(crate) fn shared_file_label(count: usize) -> String {
+    if count == 1 {
+        "1 shared file".to_string()
+    } else {
+        format!("{} shared files", count)
+    }
+}
+
 /// The current branch of the main checkout, straight from git
 ///
 /// Falls back to the stored name when git cannot answer, and says so plainly
-/// when HEAD is detached rather than showing a stale branch name.
+/// when HEAD is detached rather than showing a stale branch name. A
+/// multi-repository project lists each repository's branch; a plain
+/// directory has none.
 fn current_branch_name(project: &Project) -> Option<String> {
-    match crate::git::GitOps::open(&project.repo_path) {
+    let branch_of = |repo_path: &std::path::Path| match crate::git::GitOps::open(repo_path) {
         Ok(git) => match git.current_branch() {
             Ok(Some(name)) => Some(name),
             Ok(None) => Some("detached HEAD".to_string()),
             Err(_) => None,
         },
         Err(_) => None,
+    };
+
+    match &project.kind {
+        ProjectKind::Git => branch_of(&project.repo_path),
+        ProjectKind::Directory => Some("plain directory".to_string()),
+        ProjectKind::MultiRepo { repos } => Some(
+            repos
+                .iter()
+                .map(|repo| {
+                    let branch = branch_of(&project.repo_path.join(repo));
+                    format!("{}: {}", repo.display(), branch.as_deref().unwrap_or("?"))
+                })
+                .collect::<Vec<_>>()
+                .join(", "),
+        ),
+    }
+}
+
+/// Rows the shared-files notice may take above a branch's sessions
+const SHARED_FILES_MAX_ROWS: usize = 3;
+
+/// Name the files this branch shares with other worktrees, above its sessions
+///
+/// Returns the area left for the session list. The notice is the detail
+/// behind the branch list's "shared files" count, so it only appears at full
+/// density and only when the list keeps enough height to be usable.
+fn render_shared_files(
+    frame: &mut Frame,
+    area: Rect,
+    state: &AppState,
+    branch_id: crate::project::BranchId,
+    project_store: &ProjectStore,
+    mode: SideMode,
+) -> Rect {
+    let shared = state.file_overlaps.for_branch(branch_id);
+    if shared.is_empty() || mode != SideMode::Full || area.height < 8 {
+        return area;
+    }
+
+    let t = theme();
+    let width = area.width as usize;
+    let shown = shared.len().min(SHARED_FILES_MAX_ROWS);
+    let mut lines: Vec<Line> = shared[..shown]
+        .iter()
+        .map(|file| {
+            let others: Vec<&str> = file
+                .other_branches
+                .iter()
+                .map(|id| {
+                    project_store
+                        .get_branch(*id)
+                        .map(|b| b.name.as_str())
+                        .unwrap_or("?")
+                })
+                .collect();
+            let line = Line::from(vec![
+                Span::styled("⚠ ", Style::default().fg(t.warning)),
+                Span::raw(file.path.clone()),
+                Span::styled(format!(" also in {}", others.join(", ")), t.muted_style()),
+            ]);
+            clamp_line(line, width)
+        })
+        .collect();
+    if shared.len() > shown {
+        lines.push(Line::from(Span::styled(
+            truncate_string(&format!("  +{} more", shared.len() - shown), width),
+            t.muted_style(),
+        )));
+    }
+
+    let height = lines.len() as u16;
+    frame.render_widget(Paragraph::new(lines), Rect { height, ..area });
+    // One blank row between the notice and the list
+    Rect {
+        y: area.y + height + 1,
+        height: area.height - height - 1,
+        ..area
     }
 }
 
@@ -603,7 +710,8 @@ fn render_project_settings(
     let width = area.width as usize;
     let focused = state.is_focused(crate::app::Tab::Projects);
 
-    let values = [
+    let shortcuts = project_store.editable_shortcuts(project_id);
+    let mut values = [
         project
             .and_then(|p| p.default_claude_config)
             .map(|_| "set".to_string())
@@ -615,20 +723,48 @@ fn render_project_settings(
         project
             .and_then(|p| p.default_base_branch.clone())
             .unwrap_or_else(|| "repo default".to_string()),
+        on_off(project.is_some_and(|p| p.worktree_setup.submodules)),
+        on_off(project.is_some_and(|p| p.worktree_setup.sparse_checkout)),
+        project.map(repo_commands_value).unwrap_or_default(),
         project
             .map(|p| p.name.clone())
             .unwrap_or_else(|| "?".to_string()),
+        match shortcuts.len() {
+            0 => "none - Enter to add".to_string(),
+            count => format!("{} - Enter to add", count),
+        },
     ];
+    if project.is_some_and(|p| !p.kind.has_worktrees()) {
+        for value in &mut values[2..=4] {
+            *value = "n/a (no git)".to_string();
+        }
+    }
 
-    let items: Vec<ListItem> = PROJECT_SETTINGS_ROWS
+    let rows = PROJECT_SETTINGS_ROWS
         .iter()
+        .zip(&values)
+        .map(|(label, value)| format!("{}: {}", label, value))
+        .chain(shortcuts.iter().map(|(scope, _, shortcut)| {
+            let from = match scope {
+                ShortcutScope::Project(_) => String::new(),
+                ShortcutScope::Folder(_) => format!(" ({})", scope.label()),
+            };
+            let what = if shortcut.name.is_empty() {
+                shortcut.command.clone()
+            } else {
+                format!("{}: {}", shortcut.name, shortcut.command)
+            };
+            format!("  {}  {}{}", shortcut.key, what, from)
+        }));
+
+    let items: Vec<ListItem> = rows
         .enumerate()
-        .map(|(i, label)| {
+        .map(|(i, row)| {
             let selected = i == state.project_settings_index && focused;
-            let content = if mode == SideMode::Full {
-                format!("{}{}: {}", selection_prefix(selected), label, values[i])
+            let content = if mode == SideMode::Full || i >= PROJECT_SETTINGS_ROWS.len() {
+                format!("{}{}", selection_prefix(selected), row)
             } else {
-                format!("{}{}", selection_prefix(selected), label)
+                format!("{}{}", selection_prefix(selected), PROJECT_SETTINGS_ROWS[i])
             };
             ListItem::new(truncate_string(&content, width)).style(
                 crate::tui::widgets::selection::selection_style_with_accent(selected, t),
@@ -640,6 +776,34 @@ fn render_project_settings(
     frame.render_widget(List::new(items), area);
 }
 
+/// The repo commands row's value: what `.panoptes.toml` would run - setup
+/// commands and shortcuts - and whether it may
+fn repo_commands_value(project: &crate::project::Project) -> String {
+    let plural = |count: usize, what: &str| {
+        format!("{} {}{}", count, what, if count == 1 { "" } else { "s" })
+    };
+    let parts: Vec<String> = [
+        (project.repo_setup().len(), "setup command"),
+        (project.repo_shortcuts().len(), "shortcut"),
+    ]
+    .into_iter()
+    .filter(|(count, _)| *count > 0)
+    .map(|(count, what)| plural(count, what))
+    .collect();
+    if parts.is_empty() {
+        "none".to_string()
+    } else if project.repo_commands_pending() {
+        format!("{} - not allowed", parts.join(", "))
+    } else {
+        format!("{} - allowed", parts.join(", "))
+    }
+}
+
+/// A toggle row's value
+fn on_off(on: bool) -> String {
+    if on { "on" } else { "off" }.to_string()
+}
+
 /// Shorten a state string for the compact density
 ///
 /// `Executing: Bash(ls)` becomes `Exec`, `Waiting - 3m` becomes `Waiting`: the
@@ -689,7 +853,7 @@ pub(crate) fn clamp_line(line: Line<'static>, width: usize) -> Line<'static> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::Config;
+    use crate::config::{Config, CustomShortcut};
     use crate::project::Project;
     use crate::session::store::SessionStore;
     use crate::tui::views::test_util::{column_of, contains_line, style_of_row_with};
@@ -887,7 +1051,7 @@ mod tests {
     }
 
     #[test]
-    fn test_project_settings_lists_the_four_relocated_flows() {
+    fn test_project_settings_lists_every_row() {
         let store = store_with(&[("panoptes", &[][..])]);
         let project_id = store.projects().next().unwrap().id;
         let state = AppState {
@@ -904,6 +1068,67 @@ mod tests {
             contains_line(&lines, "▶ Default Claude config"),
             "{lines:?}"
         );
+        assert!(
+            contains_line(&lines, "Initialise submodules: off"),
+            "{lines:?}"
+        );
+    }
+
+    #[test]
+    fn test_project_settings_list_project_and_folder_shortcuts() {
+        let mut store = store_with(&[("panoptes", &[][..])]);
+        let project_id = store.projects().next().unwrap().id;
+        store
+            .set_project_folder(project_id, vec!["Acme".to_string()])
+            .unwrap();
+        let project_scope = ShortcutScope::Project(project_id);
+        let folder_scope = ShortcutScope::Folder(vec!["Acme".to_string()]);
+        store
+            .add_scoped_shortcut(
+                &project_scope,
+                CustomShortcut::new('t', "Test".into(), "cargo test".into(), false),
+            )
+            .unwrap();
+        store
+            .add_scoped_shortcut(
+                &folder_scope,
+                CustomShortcut::new('l', String::new(), "make lint".into(), false),
+            )
+            .unwrap();
+        let state = AppState {
+            projects_nav: ProjectsNav::ProjectSettings(project_id),
+            ..Default::default()
+        };
+
+        let lines = render(60, &state, &store);
+
+        assert!(contains_line(&lines, "t  Test: cargo test"), "{lines:?}");
+        assert!(
+            contains_line(&lines, "l  make lint (folder Acme)"),
+            "{lines:?}"
+        );
+    }
+
+    #[test]
+    fn test_plain_directory_settings_mark_the_git_rows_unavailable() {
+        let mut store = store_with(&[("notes", &[][..])]);
+        let project_id = store.projects().next().unwrap().id;
+        store.get_project_mut(project_id).unwrap().kind = ProjectKind::Directory;
+        let state = AppState {
+            projects_nav: ProjectsNav::ProjectSettings(project_id),
+            ..Default::default()
+        };
+
+        let lines = render(60, &state, &store);
+
+        assert!(
+            contains_line(&lines, "Default base branch: n/a (no git)"),
+            "{lines:?}"
+        );
+        assert!(
+            contains_line(&lines, "Default Claude config: global default"),
+            "{lines:?}"
+        );
     }
 
     #[test]
@@ -987,6 +1212,56 @@ mod tests {
         assert_eq!(projects_breadcrumb(&state, &store, 10).chars().count(), 10);
     }
 
+    /// Two worktrees editing the same file both say so in the branch list,
+    /// and the branch's own level names the file and the other branch
+    #[test]
+    fn test_shared_files_are_flagged_on_the_branch_and_named_inside_it() {
+        let mut store = store_with(&[("panoptes", &[][..])]);
+        let project_id = store.projects().next().unwrap().id;
+        let ids: Vec<_> = ["feature-a", "feature-b"]
+            .iter()
+            .map(|name| {
+                let branch = crate::project::Branch::new(
+                    project_id,
+                    name.to_string(),
+                    PathBuf::from(format!("/tmp/wt/{name}")),
+                    false,
+                    true,
+                );
+                let id = branch.id;
+                store.add_branch(branch);
+                id
+            })
+            .collect();
+
+        let mut state = AppState {
+            projects_nav: ProjectsNav::Project(project_id),
+            ..Default::default()
+        };
+        state.file_overlaps.record_project(
+            [("src/session/manager.rs".to_string(), ids.clone())]
+                .into_iter()
+                .collect(),
+        );
+
+        let lines = render(60, &state, &store);
+        assert!(
+            contains_line(&lines, "feature-a  (⚠ 1 shared file)"),
+            "{lines:?}"
+        );
+        assert!(
+            contains_line(&lines, "feature-b  (⚠ 1 shared file)"),
+            "{lines:?}"
+        );
+
+        state.projects_nav = ProjectsNav::Branch(project_id, ids[0]);
+        let lines = render(60, &state, &store);
+        assert!(
+            contains_line(&lines, "⚠ src/session/manager.rs also in feature-b"),
+            "{lines:?}"
+        );
+    }
+
     #[test]
     fn test_compact_state_keeps_the_state_and_drops_the_qualifier() {
         assert_eq!(compact_state("Executing: Bash(ls)"), "Exec");
@@ -21,6 +21,12 @@ use crate::tui::widgets::selection::{selection_prefix, selection_style};
 /// Rows the attention section may take, borders included
 const ATTENTION_MAX_HEIGHT: u16 = 8;
 
+/// Rows the selected session's preview may take, borders included
+const PREVIEW_MAX_HEIGHT: u16 = 14;
+
+/// Fewest list rows for the preview to be worth showing beneath them
+const PREVIEW_MIN_LIST_HEIGHT: u16 = 6;
+
 /// Pane 2's block title at the given density
 pub fn sessions_title(sessions: &SessionManager, mode: SideMode) -> String {
     match mode {
@@ -85,9 +91,60 @@ pub fn render_sessions_pane(
     if let Some(attention_area) = attention_area {
         render_attention_section(frame, attention_area, &attention, project_store, mode);
     }
+
+    // The selected session's live output, under the list, while the pane is
+    // focused and wide enough to read it - the "stuck or working?" question
+    // answered without opening the session and losing the place in the list
+    let selected = sessions
+        .get_by_index(state.sessions_pane_index)
+        .filter(|_| mode == SideMode::Full && state.is_focused(Tab::Sessions));
+    let preview_height =
+        PREVIEW_MAX_HEIGHT.min(list_area.height.saturating_sub(PREVIEW_MIN_LIST_HEIGHT));
+    let list_area = match selected {
+        Some(session) if preview_height >= 4 => {
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(0), Constraint::Length(preview_height)])
+                .split(list_area);
+            render_preview(frame, chunks[1], session);
+            chunks[0]
+        }
+        _ => list_area,
+    };
     render_session_list(frame, list_area, state, project_store, sessions, mode);
 }
 
+/// The last lines of a session's terminal, headed by its last message
+fn render_preview(frame: &mut Frame, area: Rect, session: &crate::session::Session) {
+    let t = theme();
+    let info = &session.info;
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(
+            "Preview: {} [{}]",
+            info.name,
+            super::session_state_display(info, Utc::now())
+        ))
+        .border_style(Style::default().fg(t.border_dim));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let mut lines: Vec<Line> = Vec::new();
+    if let Some(message) = &info.last_message {
+        lines.push(Line::from(Span::styled(
+            format!("› {}", message),
+            t.muted_style(),
+        )));
+    }
+    let room = (inner.height as usize).saturating_sub(lines.len());
+    let tail = session.vterm.tail_styled_lines(room);
+    if tail.is_empty() && lines.is_empty() {
+        lines.push(Line::from(Span::styled("No output yet", t.muted_style())));
+    }
+    lines.extend(tail);
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
 /// Below this many columns an elided field is noise, not a name
 const ELIDE_MIN: usize = 12;
 
@@ -342,6 +399,15 @@ fn render_session_list(
             let selected = i == selected_index && focused;
             let (badge, badge_color) = super::attention_badge(info, info.needs_attention());
             let state_display = super::session_state_display(info, now);
+            // Files this session's branch shares with another worktree, as a
+            // trailing marker; its room comes out of the body like the
+            // leading spans' does
+            let shared_files = state.file_overlaps.count_for_branch(info.branch_id);
+            let overlap_marker = if shared_files > 0 {
+                format!(" ⚠{}", shared_files)
+            } else {
+                String::new()
+            };
 
             // Three leading spans, nine columns: selection marker (2),
             // badge (2), agent tag "[CC] " (5). An elided body fills its
@@ -349,7 +415,7 @@ fn render_session_list(
             // columns for clamp_line to cut off the row's tail
             let body = session_body(
                 mode,
-                width.saturating_sub(9),
+                width.saturating_sub(9 + overlap_marker.chars().count()),
                 &SessionRow {
                     prefix: &format!("{}: ", i + 1),
                     project: project_name_of(project_store, info),
@@ -367,6 +433,7 @@ fn render_session_list(
                     t.muted_style(),
                 ),
                 Span::raw(body),
+                Span::styled(overlap_marker, Style::default().fg(t.warning)),
             ]);
 
             ListItem::new(clamp_line(line, width))
@@ -633,6 +700,52 @@ mod tests {
         assert!(contains_line(&lines, "1:"), "{lines:?}");
     }
 
+    /// A session whose branch shares files with another worktree wears a
+    /// warning marker, without costing the row its state
+    #[test]
+    fn test_shared_files_mark_the_session_row() {
+        let temp = TempDir::new().unwrap();
+        let config = Config {
+            worktrees_dir: temp.path().join("worktrees"),
+            hooks_dir: temp.path().join("hooks"),
+            ..Config::default()
+        };
+        let mut sessions = SessionManager::with_store(
+            config,
+            SessionStore::with_path(temp.path().join("sessions.json")),
+        );
+        let (branch_a, branch_b) = (uuid::Uuid::new_v4(), uuid::Uuid::new_v4());
+        sessions
+            .insert_test_session("review", uuid::Uuid::new_v4(), branch_a)
+            .unwrap();
+
+        let mut state = AppState {
+            focus: crate::app::Focus::Panes(Tab::Sessions),
+            ..Default::default()
+        };
+        state.file_overlaps.record_project(
+            [(
+                "src/session/manager.rs".to_string(),
+                vec![branch_a, branch_b],
+            )]
+            .into_iter()
+            .collect(),
+        );
+        let store = ProjectStore::new();
+        let lines = render_to_lines(40, 12, |frame| {
+            render_sessions_pane(
+                frame,
+                frame.size(),
+                &state,
+                &store,
+                &sessions,
+                crate::tui::panes::side_mode(42),
+            )
+        });
+
+        assert!(contains_line(&lines, "] ⚠1"), "{lines:?}");
+    }
+
     #[test]
     fn test_empty_pane_says_so() {
         let temp = TempDir::new().unwrap();
@@ -640,4 +753,44 @@ mod tests {
         let lines = render(40, &sessions);
         assert!(contains_line(&lines, "No sessions yet."), "{lines:?}");
     }
+
+    /// The focused pane previews the selected session's latest output under
+    /// the list; an unfocused one has no selection to preview
+    #[test]
+    fn test_the_selected_session_is_previewed_while_the_pane_has_focus() {
+        let temp = TempDir::new().unwrap();
+        let mut sessions = sessions_with(&temp, &["alpha", "beta"]);
+        let beta = sessions.session_ids()[1];
+        {
+            let session = sessions.get_mut(beta).unwrap();
+            session
+                .vterm
+                .process(b"Compiling panoptes\r\nRunning 812 tests\r\n");
+            session.info.set_last_message("Fixing the flaky test next");
+        }
+        let store = ProjectStore::new();
+        let mode = crate::tui::panes::side_mode(102);
+
+        let mut state = AppState {
+            focus: crate::app::Focus::Panes(Tab::Sessions),
+            sessions_pane_index: 1,
+            ..Default::default()
+        };
+        let lines = render_to_lines(100, 24, |frame| {
+            render_sessions_pane(frame, frame.size(), &state, &store, &sessions, mode)
+        });
+        assert!(contains_line(&lines, "Preview: beta"), "{lines:?}");
+        assert!(
+            contains_line(&lines, "› Fixing the flaky test next"),
+            "{lines:?}"
+        );
+        assert!(contains_line(&lines, "Running 812 tests"), "{lines:?}");
+        assert!(contains_line(&lines, "1:"), "{lines:?}");
+
+        state.focus = crate::app::Focus::Panes(Tab::Projects);
+        let lines = render_to_lines(100, 24, |frame| {
+            render_sessions_pane(frame, frame.size(), &state, &store, &sessions, mode)
+        });
+        assert!(!contains_line(&lines, "Preview"), "{lines:?}");
+    }
 }
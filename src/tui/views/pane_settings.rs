@@ -1,12 +1,14 @@
 //! Pane 3: settings
 //!
-//! Not a config editor. Five sections in a single scrollable drill-down list -
+//! Not a config editor. Six sections in a single scrollable drill-down list -
 //! the btop/weechat shape, one list plus a description of the highlighted item,
 //! rather than htop's two columns, which needs width this pane does not have.
 //!
-//! Only the six Notification rows are editable, and deliberately so: they are
+//! Only the seven Notification rows are editable, and deliberately so: they are
 //! exactly the fields the runtime re-reads on every event, so a toggle takes
 //! effect on the next event with no restart and no "restart required" badge.
+//! The theme list is the other live setting: a theme applies from the next
+//! frame.
 //! Everything numeric or path-shaped is shown read-only under About / paths.
 
 use ratatui::prelude::*;
@@ -17,22 +19,25 @@ use crate::claude_config::ClaudeConfigStore;
 use crate::codex_config::CodexConfigStore;
 use crate::config::{Config, NotificationMethod};
 use crate::logging::LogFileInfo;
+use crate::session::CodexLimits;
 use crate::tui::panes::SideMode;
 use crate::tui::theme::theme;
 use crate::tui::views::{truncate_string, window_rows};
 use crate::tui::widgets::selection::{selection_prefix, selection_style_with_accent};
 
-/// The six editable notification rows, in list order
+/// The eight editable notification rows, in list order
 ///
 /// Editable *only* because these are the fields the runtime re-reads on every
 /// event; nothing here needs a restart, so nothing here can be stale.
-pub const NOTIFICATION_ROWS: [&str; 6] = [
+pub const NOTIFICATION_ROWS: [&str; 8] = [
     "Notify me by",
     "…on approval needed",
     "…on turn finished",
     "…on tool stalled",
     "…on session crashed",
+    "…on session over budget",
     "Idle nudge counts as attention",
+    "Do not disturb",
 ];
 
 /// Pane 3's block title at the given density
@@ -53,6 +58,7 @@ pub fn settings_description(state: &AppState, config: &Config) -> String {
             .map(|section| section.description().to_string())
             .unwrap_or_default(),
         SettingsNav::Notifications => notification_description(state, config),
+        SettingsNav::Theme => format!("Enter to use · currently {}", config.color_theme),
         section => section.description().to_string(),
     }
 }
@@ -64,7 +70,14 @@ fn notification_description(state: &AppState, config: &Config) -> String {
             "Space/Enter to change · currently {}",
             method_label(config.notification_method)
         ),
-        1..=5 => "Space/Enter to toggle · takes effect on the next event".to_string(),
+        1..=6 => "Space/Enter to toggle · takes effect on the next event".to_string(),
+        7 => match &config.notify_on.quiet_hours {
+            Some(quiet) => format!(
+                "Space/Enter to toggle · silences every notification · quiet hours {}-{}",
+                quiet.start, quiet.end
+            ),
+            None => "Space/Enter to toggle · silences every notification, badges stay".to_string(),
+        },
         _ => String::new(),
     }
 }
@@ -73,6 +86,8 @@ fn method_label(method: NotificationMethod) -> &'static str {
     match method {
         NotificationMethod::Bell => "Bell",
         NotificationMethod::Title => "Title",
+        NotificationMethod::Desktop => "Desktop",
+        NotificationMethod::Osc => "Terminal (OSC)",
         NotificationMethod::None => "Silent",
     }
 }
@@ -83,6 +98,8 @@ pub struct SettingsPaneContext<'a> {
     pub config: &'a Config,
     pub claude_config_store: &'a ClaudeConfigStore,
     pub codex_config_store: &'a CodexConfigStore,
+    /// Latest rate-limit reading per Codex account
+    pub codex_limits: &'a CodexLimits,
     pub log_file_info: &'a LogFileInfo,
     pub hook_port: u16,
     pub hook_healthy: bool,
@@ -118,6 +135,7 @@ pub fn render_settings_pane(
             ctx.claude_config_store,
             ctx.state.claude_configs_selected_index,
             focused,
+            None,
         ),
         SettingsNav::CodexConfigs => super::render_agent_config_list(
             frame,
@@ -125,6 +143,10 @@ pub fn render_settings_pane(
             ctx.codex_config_store,
             ctx.state.codex_configs_selected_index,
             focused,
+            Some((
+                ctx.codex_limits,
+                ctx.config.codex_rotation.threshold_percent,
+            )),
         ),
         SettingsNav::Shortcuts => super::render_shortcuts_list(
             frame,
@@ -134,11 +156,12 @@ pub fn render_settings_pane(
             focused,
         ),
         SettingsNav::Notifications => render_notifications(frame, area, ctx.state, ctx.config),
+        SettingsNav::Theme => render_themes(frame, area, ctx.state, ctx.config),
         SettingsNav::About => render_about(frame, area, ctx),
     }
 }
 
-/// The five sections
+/// The six sections
 fn render_sections(frame: &mut Frame, area: Rect, state: &AppState) {
     let t = theme();
     let focused = state.is_focused(Tab::Settings);
@@ -159,7 +182,7 @@ fn render_sections(frame: &mut Frame, area: Rect, state: &AppState) {
     frame.render_widget(List::new(items), area);
 }
 
-/// The six live notification toggles
+/// The seven live notification toggles
 fn render_notifications(frame: &mut Frame, area: Rect, state: &AppState, config: &Config) {
     let t = theme();
     let focused = state.is_focused(Tab::Settings);
@@ -171,7 +194,9 @@ fn render_notifications(frame: &mut Frame, area: Rect, state: &AppState, config:
         checkbox(config.notify_on.turn_complete),
         checkbox(config.notify_on.stalled),
         checkbox(config.notify_on.crashed),
+        checkbox(config.notify_on.over_budget),
         checkbox(config.attention_on_idle),
+        checkbox(config.notify_on.do_not_disturb),
     ];
 
     let items: Vec<ListItem> = NOTIFICATION_ROWS
@@ -189,6 +214,33 @@ fn render_notifications(frame: &mut Frame, area: Rect, state: &AppState, config:
     frame.render_widget(List::new(items), area);
 }
 
+/// The themes to choose from, the one in use marked
+fn render_themes(frame: &mut Frame, area: Rect, state: &AppState, config: &Config) {
+    let t = theme();
+    let focused = state.is_focused(Tab::Settings);
+    let width = area.width as usize;
+
+    let items: Vec<ListItem> = state
+        .available_themes
+        .iter()
+        .enumerate()
+        .map(|(i, name)| {
+            let selected = i == state.theme_index && focused;
+            let marker = if *name == config.color_theme {
+                "  ★ in use"
+            } else {
+                ""
+            };
+            let content = format!("{}{}{}", selection_prefix(selected), name, marker);
+            ListItem::new(truncate_string(&content, width))
+                .style(selection_style_with_accent(selected, t))
+        })
+        .collect();
+
+    let items = window_rows(items, state.theme_index, area.height);
+    frame.render_widget(List::new(items), area);
+}
+
 fn checkbox(on: bool) -> String {
     if on {
         "[x]".to_string()
@@ -243,7 +295,7 @@ fn render_about(frame: &mut Frame, area: Rect, ctx: &SettingsPaneContext) {
         ),
         (
             "log_agent_events".to_string(),
-            format!("{} (startup only)", config.log_agent_events),
+            config.log_agent_events.to_string(),
         ),
     ];
 
@@ -268,6 +320,14 @@ fn render_about(frame: &mut Frame, area: Rect, ctx: &SettingsPaneContext) {
         t.muted_style(),
     ))];
     all.extend(lines);
+    all.push(Line::from(""));
+    all.push(Line::from(Span::styled(
+        truncate_string(
+            "e: export it all to one file; `panoptes import FILE` loads it elsewhere",
+            width,
+        ),
+        t.muted_style(),
+    )));
 
     frame.render_widget(Paragraph::new(all), area);
 }
@@ -283,6 +343,7 @@ mod tests {
         config: &'a Config,
         claude: &'a ClaudeConfigStore,
         codex: &'a CodexConfigStore,
+        limits: &'a CodexLimits,
         log: &'a LogFileInfo,
     ) -> SettingsPaneContext<'a> {
         SettingsPaneContext {
@@ -290,6 +351,7 @@ mod tests {
             config,
             claude_config_store: claude,
             codex_config_store: codex,
+            codex_limits: limits,
             log_file_info: log,
             hook_port: 9999,
             hook_healthy: true,
@@ -302,7 +364,8 @@ mod tests {
         let log = LogFileInfo {
             path: PathBuf::from("/tmp/panoptes/logs/panoptes-now.log"),
         };
-        let ctx = context(state, config, &claude, &codex, &log);
+        let limits = CodexLimits::default();
+        let ctx = context(state, config, &claude, &codex, &limits, &log);
         let mode = crate::tui::panes::side_mode(width + 2);
         render_to_lines(width, 16, |frame| {
             render_settings_pane(frame, frame.size(), mode, &ctx)
@@ -318,7 +381,7 @@ mod tests {
     }
 
     #[test]
-    fn test_sections_list_offers_all_five() {
+    fn test_sections_list_offers_all_six() {
         let lines = render(40, &focused(SettingsNav::Sections), &Config::default());
         for section in SettingsNav::SECTIONS {
             assert!(
@@ -330,6 +393,29 @@ mod tests {
         assert!(contains_line(&lines, "▶ Claude configs"), "{lines:?}");
     }
 
+    #[test]
+    fn test_theme_list_marks_the_theme_in_use() {
+        let config = Config {
+            color_theme: "light".to_string(),
+            ..Config::default()
+        };
+        let mut state = focused(SettingsNav::Theme);
+        state.available_themes = vec![
+            "dark".to_string(),
+            "light".to_string(),
+            "solarized".to_string(),
+        ];
+        state.theme_index = 2;
+        let lines = render(40, &state, &config);
+
+        assert!(contains_line(&lines, "light  ★ in use"), "{lines:?}");
+        assert!(contains_line(&lines, "▶ solarized"), "{lines:?}");
+        assert_eq!(
+            settings_description(&state, &config),
+            "Enter to use · currently light"
+        );
+    }
+
     #[test]
     fn test_notifications_shows_six_editable_rows() {
         let config = Config::default();
@@ -370,7 +456,8 @@ mod tests {
         let log = LogFileInfo {
             path: PathBuf::from("/tmp/x.log"),
         };
-        let mut ctx = context(&state, &config, &claude, &codex, &log);
+        let limits = CodexLimits::default();
+        let mut ctx = context(&state, &config, &claude, &codex, &limits, &log);
         ctx.hook_healthy = false;
 
         let lines = render_to_lines(70, 16, |frame| {
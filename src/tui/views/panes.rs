@@ -52,7 +52,8 @@ pub fn render_panes(frame: &mut Frame, area: Rect, ctx: PaneContext) {
     let header = Header::new(Breadcrumb::new())
         .with_logo(LogoKind::Full)
         .with_notifications(Some(&state.header_notifications))
-        .with_attention_count(attention_count);
+        .with_attention_count(attention_count)
+        .with_quiet(ctx.config.notify_on.is_quiet(chrono::Local::now().time()));
 
     let areas = ScreenLayout::new(area).with_header(header).render(frame);
 
@@ -206,6 +207,7 @@ fn render_pane(frame: &mut Frame, area: Rect, tab: Tab, ctx: &PaneContext) {
                 config: ctx.config,
                 claude_config_store: ctx.claude_config_store,
                 codex_config_store: ctx.codex_config_store,
+                codex_limits: ctx.sessions.codex_limits(),
                 log_file_info: ctx.log_file_info,
                 hook_port: ctx.hook_port,
                 hook_healthy: ctx.hook_healthy,
@@ -260,7 +262,7 @@ fn footer_text(ctx: &PaneContext) -> String {
         None => String::new(),
     };
 
-    let global = "←→/Tab: pane | q: quit | ?: help";
+    let global = "←→/Tab: pane | q: quit | ?: help | Ctrl+P: go to";
     footer_with_attention(format!("{} | {}", base, global), ctx.sessions)
 }
 
@@ -275,6 +277,10 @@ fn prompt_footer(mode: InputMode) -> Option<&'static str> {
         | InputMode::CreatingCodexSession
         | InputMode::CreatingShellSession => "Enter: create | Esc: cancel",
         InputMode::SelectingAgentType => "↑↓: navigate | Enter: select | Esc: cancel",
+        InputMode::CommandPalette => "Type to find | ↑↓: navigate | Enter: go | Esc: close",
+        InputMode::ViewingTimeline => {
+            "Tab: activity/stats/spend | ↑↓: scroll | +/-: wider/narrower window | Esc: close"
+        }
         InputMode::ConfirmingBranchDelete => {
             "w: also delete the directory | y: confirm | n/Esc: cancel"
         }
@@ -285,11 +291,19 @@ fn prompt_footer(mode: InputMode) -> Option<&'static str> {
         InputMode::WorktreeSelectBranch => {
             "Type to search/create | ↑↓: navigate | Enter: select | Esc: cancel"
         }
-        InputMode::WorktreeSelectBase => "Type: filter | ↑↓: navigate | Enter: confirm | Esc: back",
+        InputMode::WorktreeSelectBase => {
+            "Tab: start from | Type: filter | ↑↓: navigate | Enter: confirm | Esc: back"
+        }
         InputMode::WorktreeConfirm => "Enter: create | Esc: back",
         InputMode::SelectingDefaultBase => {
             "Type: filter | ↑↓: navigate | Enter: set default | Esc: cancel"
         }
+        InputMode::CommitSelectFiles => {
+            "↑↓: navigate | Space: tick | a: all | Enter: next | Esc: cancel"
+        }
+        InputMode::CommitMessage => {
+            "Type message | Tab: push | Ctrl+G: ask agent | Enter: commit | Esc: back"
+        }
         InputMode::SelectingClaudeConfig | InputMode::SelectingCodexConfig => {
             "↑↓: navigate | Enter: select | Esc: cancel"
         }
@@ -316,16 +330,19 @@ fn projects_footer(state: &AppState, project_store: &ProjectStore, config: &Conf
             }
         }
         ProjectsNav::Project(_) => {
-            "↑↓/1-9/Enter | n: new worktree | d: delete | R: refresh | ,: settings".to_string()
+            "↑↓/1-9/Enter | n: new worktree | c: commit | d: delete | R: refresh | ,: settings"
+                .to_string()
         }
-        ProjectsNav::Branch(_, _) => {
-            let shortcuts = format_custom_shortcuts_hint(&config.custom_shortcuts);
+        ProjectsNav::Branch(project_id, _) => {
+            let shortcuts = format_custom_shortcuts_hint(
+                &project_store.shortcuts_for(project_id, &config.custom_shortcuts),
+            );
             format!(
                 "↑↓/1-9/Enter | n: new AI | s: shell | d: delete | {}Esc: back",
                 shortcuts
             )
         }
-        ProjectsNav::ProjectSettings(_) => "↑↓/Enter | Esc: back".to_string(),
+        ProjectsNav::ProjectSettings(_) => "↑↓/Enter | d: delete shortcut | Esc: back".to_string(),
     }
 }
 
@@ -338,7 +355,8 @@ fn settings_footer(state: &AppState, config: &Config) -> String {
         }
         SettingsNav::Shortcuts => "↑↓ | n: add | d: delete | Esc: back",
         SettingsNav::Notifications => "↑↓ | Space/Enter: change | Esc: back",
-        SettingsNav::About => "Esc: back",
+        SettingsNav::Theme => "↑↓ | Enter: use theme | Esc: back",
+        SettingsNav::About => "e: export | Esc: back",
     };
     let description = super::pane_settings::settings_description(state, config);
     if description.is_empty() {
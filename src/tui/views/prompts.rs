@@ -197,6 +197,42 @@ pub fn render_folder_move_dialog(frame: &mut Frame, area: Rect, state: &AppState
     render_prompt_overlay(frame, area, " Move to Folder ", lines, title, rows);
 }
 
+/// The `Ctrl+P` command palette: the query, and what it matches
+///
+/// Each row is tagged with what it is and dimmed with where it lives, so two
+/// sessions both called "fix" on different branches can be told apart.
+pub fn render_command_palette(frame: &mut Frame, area: Rect, state: &AppState) {
+    let t = theme();
+    let palette = &state.command_palette;
+
+    let mut lines = vec![Line::from(Span::styled(
+        format!("> {}_", palette.query),
+        t.input_style(),
+    ))];
+    if palette.matches.is_empty() {
+        lines.push(Line::from(Span::styled("No matches", t.muted_style())));
+    }
+
+    let (title, rows) = completion_rows(
+        &palette.matching(),
+        palette.selected,
+        true,
+        "Matches",
+        |entry| {
+            if entry.detail.is_empty() {
+                format!("{:<8} {}", entry.kind, entry.label)
+            } else {
+                format!(
+                    "{:<8} {}  \u{00b7} {}",
+                    entry.kind, entry.label, entry.detail
+                )
+            }
+        },
+    );
+
+    render_prompt_overlay(frame, area, " Go to ", lines, title, rows);
+}
+
 /// The folder-removal confirmation
 ///
 /// Deliberately not the shared delete dialog: dissolving a folder deletes
@@ -394,4 +430,46 @@ mod tests {
             "{lines:?}"
         );
     }
+
+    #[test]
+    fn test_command_palette_tags_each_match_with_where_it_lives() {
+        use crate::app::{CommandPaletteState, PaletteAction, PaletteEntry, PaletteTarget};
+
+        let mut palette = CommandPaletteState::open(vec![
+            PaletteEntry {
+                kind: "session",
+                label: "auth-fix".to_string(),
+                detail: "panoptes / feature/auth".to_string(),
+                target: PaletteTarget::Session(uuid::Uuid::new_v4()),
+            },
+            PaletteEntry {
+                kind: "action",
+                label: "Refresh git state".to_string(),
+                detail: String::new(),
+                target: PaletteTarget::Action(PaletteAction::RefreshGit),
+            },
+        ]);
+        palette.query = "athfx".to_string();
+        palette.refilter();
+        let state = AppState {
+            input_mode: InputMode::CommandPalette,
+            command_palette: palette,
+            ..Default::default()
+        };
+
+        let lines = render_to_lines(120, 24, |frame| {
+            render_command_palette(frame, frame.size(), &state)
+        });
+
+        assert!(contains_line(&lines, "> athfx_"), "{lines:?}");
+        assert!(contains_line(&lines, "Matches (1)"), "{lines:?}");
+        assert!(
+            contains_line(
+                &lines,
+                "session  auth-fix  \u{00b7} panoptes / feature/auth"
+            ),
+            "{lines:?}"
+        );
+        assert!(!contains_line(&lines, "Refresh git state"), "{lines:?}");
+    }
 }
@@ -7,10 +7,13 @@ use ratatui::prelude::*;
 use ratatui::widgets::Paragraph;
 
 use crate::app::{AppState, InputMode};
-use crate::config::Config;
+use crate::config::{Config, CustomShortcut};
+use crate::pricing::{estimate, format_dollars, PricingConfig};
 use crate::project::ProjectStore;
 use crate::session::{Session, SessionInfo, SessionManager, SessionState, SessionType};
-use crate::tui::frame::{render_frame_border, render_pty_content, FrameConfig, FrameLayout};
+use crate::tui::frame::{
+    render_frame_border, render_pty_content, tile_content, FrameConfig, FrameLayout,
+};
 use crate::tui::header::{Header, LogoKind};
 use crate::tui::header_notifications::HeaderNotificationManager;
 use crate::tui::theme::theme;
@@ -34,7 +37,8 @@ pub fn render_session_view(
     // === HEADER ===
     // Built before the layout, because how many rows it needs depends on how
     // much of the wordmark this terminal can afford
-    let (breadcrumb, suffix) = build_header_breadcrumb(session, state, project_store);
+    let (breadcrumb, suffix) =
+        build_header_breadcrumb(session, state, project_store, &config.pricing);
 
     // Session header has custom coloring based on session state
     let header_color = session.map(|s| s.info.state.color()).unwrap_or(t.text_dim);
@@ -47,6 +51,7 @@ pub fn render_session_view(
         .with_suffix(suffix)
         .with_notifications(Some(header_notifications))
         .with_attention_count(attention_count)
+        .with_quiet(config.notify_on.is_quiet(chrono::Local::now().time()))
         .with_custom_style(custom_style);
 
     // Pre-calculate layout using FrameLayout
@@ -59,6 +64,40 @@ pub fn render_session_view(
 
     header.render(frame, layout.header);
 
+    if state.session_tiles.len() > 1 {
+        render_tiles(frame, &layout, state, sessions);
+    } else {
+        render_single_output(frame, &layout, state, session);
+    }
+
+    // === FOOTER ===
+    let is_scrolled = session
+        .map(|s| {
+            if s.info.session_type == SessionType::OpenAICodex {
+                state.session_scroll_offset > 0
+            } else {
+                s.vterm.scrollback_offset() > 0
+            }
+        })
+        .unwrap_or(false);
+    let suspended = session.is_some_and(|s| s.info.state == SessionState::Suspended);
+    let shortcuts = project_store.shortcuts_for(
+        session.map(|s| s.info.project_id).unwrap_or_default(),
+        &config.custom_shortcuts,
+    );
+    let help_text = build_footer_text(state, is_scrolled, suspended, sessions, &shortcuts);
+    render_footer(frame, layout.footer, &help_text);
+}
+
+/// Render the active session's output filling the frame
+fn render_single_output(
+    frame: &mut Frame,
+    layout: &FrameLayout,
+    state: &AppState,
+    session: Option<&Session>,
+) {
+    let t = theme();
+
     // === FRAME BORDER ===
     let frame_color = if state.input_mode == InputMode::Session {
         t.active
@@ -119,26 +158,83 @@ pub fn render_session_view(
         let empty = Paragraph::new("Session not found").style(Style::default().fg(t.error_bg));
         frame.render_widget(empty, layout.content);
     }
+}
 
-    // === FOOTER ===
-    let is_scrolled = session
-        .map(|s| {
-            if s.info.session_type == SessionType::OpenAICodex {
-                state.session_scroll_offset > 0
-            } else {
-                s.vterm.scrollback_offset() > 0
-            }
-        })
-        .unwrap_or(false);
-    let suspended = session.is_some_and(|s| s.info.state == SessionState::Suspended);
-    let help_text = build_footer_text(state, is_scrolled, suspended, sessions, config);
-    render_footer(frame, layout.footer, &help_text);
+/// Render the tiled sessions, each live in its own bordered tile
+///
+/// The focused tile is the one with the active border and, in session mode,
+/// the cursor. A tile whose session wants the user says why in its title, so
+/// a prompt in a corner tile is not missed while typing in another.
+fn render_tiles(
+    frame: &mut Frame,
+    layout: &FrameLayout,
+    state: &AppState,
+    sessions: &SessionManager,
+) {
+    let t = theme();
+    let tiles = layout.tiles(state.session_tiles.len());
+
+    for (session_id, tile) in state.session_tiles.iter().zip(tiles) {
+        let content_area = tile_content(tile);
+        let focused = state.active_session == Some(*session_id);
+        let Some(session) = sessions.get(*session_id) else {
+            render_frame_border(frame, tile, t.text_dim, None);
+            let empty = Paragraph::new("Session not found").style(Style::default().fg(t.error_bg));
+            frame.render_widget(empty, content_area);
+            continue;
+        };
+
+        let color = if focused {
+            t.active
+        } else if session.info.needs_attention() {
+            t.attention_badge
+        } else {
+            t.text_dim
+        };
+        render_frame_border(frame, tile, color, Some(&tile_title(sessions, session)));
+
+        let styled_lines = session.visible_styled_lines(content_area.height as usize);
+        let cursor_visible = focused
+            && state.input_mode == InputMode::Session
+            && session.vterm.cursor_visible()
+            && session.vterm.scrollback_offset() == 0;
+        render_pty_content(
+            frame,
+            content_area,
+            &styled_lines,
+            Some(session.vterm.cursor_position()),
+            cursor_visible,
+        );
+    }
+}
+
+/// A tile's title: the session's number, name and state, and what it wants
+fn tile_title(sessions: &SessionManager, session: &Session) -> String {
+    let number = sessions
+        .session_ids()
+        .iter()
+        .position(|id| *id == session.info.id)
+        .map(|index| format!("{} ", index + 1))
+        .unwrap_or_default();
+    let attention = session
+        .info
+        .attention
+        .as_ref()
+        .map(|reason| format!(" \u{00b7} ! {}", reason.summary()))
+        .unwrap_or_default();
+    format!(
+        "{}{} \u{00b7} {}{}",
+        number,
+        session.info.name,
+        session.info.state.display_name(),
+        attention
+    )
 }
 
 /// What the session header says after the breadcrumb
 ///
 /// Everything the terminal below cannot say for itself, and nothing it can.
-fn header_suffix(info: &SessionInfo, mode: InputMode) -> String {
+fn header_suffix(info: &SessionInfo, mode: InputMode, pricing: &PricingConfig) -> String {
     let mode_indicator = match mode {
         InputMode::Session => "[SESSION]",
         _ => "[NORMAL]",
@@ -176,6 +272,12 @@ fn header_suffix(info: &SessionInfo, mode: InputMode) -> String {
         .summary()
         .map(|summary| format!(" \u{00b7} {}", summary))
         .unwrap_or_default();
+    // What this run of the session has spent, at list prices. Left out until
+    // it has spent something, and for a model with no known price.
+    let cost_display = estimate(info.usage.model.as_deref(), &info.usage.spent, pricing)
+        .filter(|cost| *cost > 0.0)
+        .map(|cost| format!(" \u{00b7} ~{}", format_dollars(cost)))
+        .unwrap_or_default();
     let subagent_display = match info.subagents {
         0 => String::new(),
         1 => " \u{00b7} 1 subagent".to_string(),
@@ -183,8 +285,8 @@ fn header_suffix(info: &SessionInfo, mode: InputMode) -> String {
     };
 
     format!(
-        "{}{}{}{} {}",
-        agent_display, state_display, subagent_display, usage_display, mode_indicator
+        "{}{}{}{}{} {}",
+        agent_display, state_display, subagent_display, usage_display, cost_display, mode_indicator
     )
 }
 
@@ -193,6 +295,7 @@ fn build_header_breadcrumb(
     session: Option<&Session>,
     state: &AppState,
     project_store: &ProjectStore,
+    pricing: &PricingConfig,
 ) -> (Breadcrumb, String) {
     let Some(session) = session else {
         return (
@@ -214,7 +317,10 @@ fn build_header_breadcrumb(
         .push(branch_name)
         .push(&session.info.name);
 
-    (breadcrumb, header_suffix(&session.info, state.input_mode))
+    (
+        breadcrumb,
+        header_suffix(&session.info, state.input_mode, pricing),
+    )
 }
 
 fn build_footer_text(
@@ -222,7 +328,7 @@ fn build_footer_text(
     is_scrolled: bool,
     suspended: bool,
     sessions: &SessionManager,
-    config: &Config,
+    shortcuts: &[&CustomShortcut],
 ) -> String {
     // Say what a suspended session is before saying what to do with it: the
     // scrollback still reads as a live session, so without this the missing
@@ -250,11 +356,17 @@ fn build_footer_text(
         _ => {
             let scroll_hint = if is_scrolled { "End: live view | " } else { "" };
             // Build custom shortcuts hint
-            let shortcuts_hint = format_custom_shortcuts_hint(&config.custom_shortcuts);
+            let shortcuts_hint = format_custom_shortcuts_hint(shortcuts);
+
+            let tile_hint = if state.session_tiles.is_empty() {
+                "Ctrl+T: tile | "
+            } else {
+                "\u{2190}\u{2192}: focus tile | Ctrl+T: tiles | "
+            };
 
             let base = format!(
-                "{}{}Enter: session mode | 1-9: switch | \u{2191}\u{2193}/PgUp/Dn: scroll | q: quit | ?: help | Esc: back",
-                scroll_hint, shortcuts_hint
+                "{}{}Enter: session mode | 1-9: switch | {}\u{2191}\u{2193}/PgUp/Dn: scroll | q: quit | ?: help | Esc: back",
+                scroll_hint, shortcuts_hint, tile_hint
             );
             footer_with_attention(base, sessions)
         }
@@ -317,7 +429,7 @@ mod tests {
             let mut info = info(SessionType::ClaudeCode);
             info.state = state;
 
-            let suffix = header_suffix(&info, InputMode::Session);
+            let suffix = header_suffix(&info, InputMode::Session, &PricingConfig::new());
             assert_eq!(suffix, "[CC] [SESSION]", "{state:?} leaked into {suffix:?}");
         }
     }
@@ -331,21 +443,21 @@ mod tests {
         exited.state = SessionState::Exited;
         exited.exit_reason = Some("killed by signal 9".to_string());
         assert_eq!(
-            header_suffix(&exited, InputMode::Session),
+            header_suffix(&exited, InputMode::Session, &PricingConfig::new()),
             "[CC] - Exited (killed by signal 9) [SESSION]"
         );
 
         // A crash Panoptes could not explain still reports the crash
         exited.exit_reason = None;
         assert_eq!(
-            header_suffix(&exited, InputMode::Session),
+            header_suffix(&exited, InputMode::Session, &PricingConfig::new()),
             "[CC] - Exited [SESSION]"
         );
 
         let mut suspended = info(SessionType::ClaudeCode);
         suspended.state = SessionState::Suspended;
         assert_eq!(
-            header_suffix(&suspended, InputMode::Session),
+            header_suffix(&suspended, InputMode::Session, &PricingConfig::new()),
             "[CC] - Suspended [SESSION]"
         );
     }
@@ -357,7 +469,7 @@ mod tests {
         let mut claude = info(SessionType::ClaudeCode);
         claude.claude_config_name = Some("dot-lambda".to_string());
         assert_eq!(
-            header_suffix(&claude, InputMode::Session),
+            header_suffix(&claude, InputMode::Session, &PricingConfig::new()),
             "[CC \u{00b7} dot-lambda] [SESSION]"
         );
 
@@ -365,13 +477,17 @@ mod tests {
         let mut codex = info(SessionType::OpenAICodex);
         codex.codex_config_name = Some("work".to_string());
         assert_eq!(
-            header_suffix(&codex, InputMode::Session),
+            header_suffix(&codex, InputMode::Session, &PricingConfig::new()),
             "[CX \u{00b7} work] [SESSION]"
         );
 
         // A session with no account named keeps the bare tag
         assert_eq!(
-            header_suffix(&info(SessionType::Shell), InputMode::Session),
+            header_suffix(
+                &info(SessionType::Shell),
+                InputMode::Session,
+                &PricingConfig::new()
+            ),
             "[SH] [SESSION]"
         );
     }
@@ -385,11 +501,31 @@ mod tests {
         info.subagents = 2;
 
         assert_eq!(
-            header_suffix(&info, InputMode::Normal),
+            header_suffix(&info, InputMode::Normal, &PricingConfig::new()),
             "[CC \u{00b7} dot-lambda] - Suspended \u{00b7} 2 subagents [NORMAL]"
         );
     }
 
+    /// Once a priced model has spent something, the header says roughly what
+    #[test]
+    fn test_the_suffix_estimates_what_the_session_has_spent() {
+        let mut info = info(SessionType::ClaudeCode);
+        info.usage.model = Some("claude-sonnet-4-5".to_string());
+        assert_eq!(
+            header_suffix(&info, InputMode::Session, &PricingConfig::new()),
+            "[CC] \u{00b7} sonnet-4-5 [SESSION]"
+        );
+
+        info.usage.spent = crate::agent::events::TokenCounts {
+            output: 100_000,
+            ..Default::default()
+        };
+        assert_eq!(
+            header_suffix(&info, InputMode::Session, &PricingConfig::new()),
+            "[CC] \u{00b7} sonnet-4-5 \u{00b7} ~$1.50 [SESSION]"
+        );
+    }
+
     /// The session header wears the wordmark but not the tagline or version:
     /// every row it takes is a row of agent output the user cannot read
     #[test]
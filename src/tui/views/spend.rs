@@ -0,0 +1,197 @@
+//! The spending page of the activity overlay
+//!
+//! Estimated dollars for today and the month so far, then the last week day by
+//! day, then the month split by project and by account, then what each running
+//! session has spent since Panoptes started watching it. Every figure is list
+//! price times tokens (see [`crate::pricing`]), not an invoice.
+
+use ratatui::prelude::*;
+
+use crate::pricing::{estimate, format_dollars, PricingConfig};
+use crate::project::ProjectStore;
+use crate::session::{SessionManager, SpendReport};
+use crate::tui::theme::theme;
+use crate::tui::views::truncate_string;
+
+/// Width of the label column
+const LABEL_WIDTH: usize = 14;
+
+/// Longest bar in the dollars-per-day chart
+const MAX_DAY_BAR: usize = 30;
+
+/// Most rows each breakdown lists
+const MAX_ROWS: usize = 5;
+
+/// The page's title
+pub(super) fn spend_title() -> String {
+    " Spending - estimated at list prices ".to_string()
+}
+
+/// The page's lines
+pub(super) fn spend_lines(
+    report: &SpendReport,
+    store: &ProjectStore,
+    sessions: &SessionManager,
+    pricing: &PricingConfig,
+) -> Vec<Line<'static>> {
+    let t = theme();
+    let mut lines = vec![
+        row("Today", format_dollars(report.today)),
+        row("This month", format_dollars(report.month)),
+        Line::from(""),
+        Line::from(Span::styled("Per day", t.header_style())),
+    ];
+
+    let busiest = report
+        .per_day
+        .iter()
+        .map(|(_, cost)| *cost)
+        .fold(0.0, f64::max);
+    for (day, cost) in &report.per_day {
+        let bar = if busiest > 0.0 {
+            (cost / busiest * MAX_DAY_BAR as f64).ceil() as usize
+        } else {
+            0
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("  {:<LABEL_WIDTH$}", day.format("%a %d %b").to_string()),
+                t.muted_style(),
+            ),
+            Span::styled("█".repeat(bar), Style::default().fg(t.accent)),
+            Span::styled(
+                format!(" {}", format_dollars(*cost)),
+                Style::default().fg(t.text),
+            ),
+        ]));
+    }
+    lines.push(Line::from(""));
+
+    let projects = report.per_project.iter().map(|(project_id, cost)| {
+        let name = store
+            .get_project(*project_id)
+            .map(|p| p.name.clone())
+            .unwrap_or_else(|| "(removed project)".to_string());
+        (name, *cost)
+    });
+    lines.push(row("Projects", breakdown(projects)));
+
+    let accounts = report.per_account.iter().map(|(account, cost)| {
+        (
+            account.clone().unwrap_or_else(|| "default".to_string()),
+            *cost,
+        )
+    });
+    lines.push(row("Accounts", breakdown(accounts)));
+
+    let mut live: Vec<(String, f64)> = sessions
+        .sessions_in_order()
+        .iter()
+        .filter_map(|session| {
+            let usage = &session.info.usage;
+            let cost = estimate(usage.model.as_deref(), &usage.spent, pricing)?;
+            (cost > 0.0).then(|| (session.info.name.clone(), cost))
+        })
+        .collect();
+    live.sort_by(|a, b| b.1.total_cmp(&a.1));
+    lines.push(row("Live sessions", breakdown(live.into_iter())));
+
+    if report.unpriced_tokens > 0 {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!(
+                "{} tokens this month were on models with no known price and are not counted - add them under [pricing]",
+                report.unpriced_tokens
+            ),
+            Style::default().fg(t.warning),
+        )));
+    }
+    if report.skipped > 0 {
+        lines.push(Line::from(Span::styled(
+            format!("{} unreadable history lines were skipped", report.skipped),
+            Style::default().fg(t.warning),
+        )));
+    }
+    lines
+}
+
+fn row(label: &str, value: String) -> Line<'static> {
+    let t = theme();
+    Line::from(vec![
+        Span::styled(format!("{:<LABEL_WIDTH$}", label), t.muted_style()),
+        Span::styled(value, Style::default().fg(t.text)),
+    ])
+}
+
+/// `web $12.40 · api $3.10`, the first few only
+fn breakdown(entries: impl Iterator<Item = (String, f64)>) -> String {
+    let parts: Vec<String> = entries
+        .take(MAX_ROWS)
+        .map(|(name, cost)| format!("{} {}", truncate_string(&name, 24), format_dollars(cost)))
+        .collect();
+    if parts.is_empty() {
+        "none recorded".to_string()
+    } else {
+        parts.join(" · ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::views::test_util::{contains_line, render_to_lines};
+    use ratatui::widgets::Paragraph;
+
+    #[test]
+    fn test_spend_page_shows_totals_and_breakdowns() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let sessions = SessionManager::with_store(
+            crate::config::Config {
+                worktrees_dir: temp.path().join("worktrees"),
+                hooks_dir: temp.path().join("hooks"),
+                ..crate::config::Config::default()
+            },
+            crate::session::SessionStore::with_path(temp.path().join("sessions.json")),
+        );
+        let mut store = ProjectStore::new();
+        let project = crate::project::Project::new(
+            "web".to_string(),
+            std::path::PathBuf::from("/tmp/web"),
+            "main".to_string(),
+        );
+        let project_id = project.id;
+        store.add_project(project);
+
+        let report = SpendReport {
+            today: 4.2,
+            month: 61.5,
+            per_project: vec![(project_id, 50.0), (uuid::Uuid::new_v4(), 11.5)],
+            per_account: vec![(Some("work".to_string()), 61.5)],
+            unpriced_tokens: 1200,
+            ..Default::default()
+        };
+
+        let lines = render_to_lines(140, 20, |frame| {
+            frame.render_widget(
+                Paragraph::new(spend_lines(
+                    &report,
+                    &store,
+                    &sessions,
+                    &PricingConfig::new(),
+                )),
+                frame.size(),
+            )
+        });
+        assert!(contains_line(&lines, "Today         $4.20"), "{lines:#?}");
+        assert!(contains_line(&lines, "This month    $61.50"), "{lines:#?}");
+        assert!(
+            contains_line(&lines, "web $50.00 · (removed project) $11.50"),
+            "{lines:#?}"
+        );
+        assert!(contains_line(&lines, "work $61.50"), "{lines:#?}");
+        assert!(
+            contains_line(&lines, "1200 tokens this month"),
+            "{lines:#?}"
+        );
+    }
+}
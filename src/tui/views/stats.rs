@@ -0,0 +1,192 @@
+//! The stats page of the activity overlay
+//!
+//! Figures from the session history log (see [`crate::session::history`]):
+//! how many turns a day, how long they take, how long the agents sat on a
+//! permission dialog waiting for someone, which tools the time went to, and
+//! which projects the tokens went to.
+
+use chrono::Duration;
+use ratatui::prelude::*;
+
+use crate::project::ProjectStore;
+use crate::session::history::STATS_DAYS;
+use crate::session::HistoryStats;
+use crate::tui::theme::theme;
+use crate::tui::views::timeline::short_duration;
+use crate::tui::views::truncate_string;
+
+/// Width of the label column
+const LABEL_WIDTH: usize = 12;
+
+/// Longest bar in the turns-per-day chart
+const MAX_DAY_BAR: usize = 30;
+
+/// The page's title
+pub(super) fn stats_title() -> String {
+    format!(" Stats - last {} days ", STATS_DAYS)
+}
+
+/// The page's lines
+pub(super) fn stats_lines(stats: &HistoryStats, store: &ProjectStore) -> Vec<Line<'static>> {
+    let t = theme();
+    let busiest = stats
+        .turns_per_day
+        .iter()
+        .map(|(_, n)| *n)
+        .max()
+        .unwrap_or(0);
+
+    let mut lines = vec![Line::from(Span::styled("Turns per day", t.header_style()))];
+    for (day, turns) in &stats.turns_per_day {
+        let bar = (turns * MAX_DAY_BAR)
+            .checked_add(busiest.saturating_sub(1))
+            .and_then(|n| n.checked_div(busiest))
+            .unwrap_or(0);
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("  {:<LABEL_WIDTH$}", day.format("%a %d %b").to_string()),
+                t.muted_style(),
+            ),
+            Span::styled("█".repeat(bar), Style::default().fg(t.accent)),
+            Span::styled(format!(" {}", turns), Style::default().fg(t.text)),
+        ]));
+    }
+    lines.push(Line::from(""));
+
+    lines.push(row(
+        "Turns",
+        format!(
+            "{} completed · {} interrupted · median {}",
+            stats.turns,
+            stats.aborted_turns,
+            optional_duration(stats.median_turn)
+        ),
+    ));
+    lines.push(row(
+        "Agent time",
+        format!("{} working", short_duration(stats.working)),
+    ));
+    lines.push(row(
+        "Approvals",
+        format!(
+            "{} answered · {} waiting on you · median {}",
+            stats.approvals,
+            short_duration(stats.awaiting_approval),
+            optional_duration(stats.median_approval)
+        ),
+    ));
+
+    let tools = if stats.top_tools.is_empty() {
+        "none recorded".to_string()
+    } else {
+        stats
+            .top_tools
+            .iter()
+            .map(|(name, count, total)| format!("{} {}× {}", name, count, short_duration(*total)))
+            .collect::<Vec<_>>()
+            .join(" · ")
+    };
+    lines.push(row("Tools", tools));
+
+    let tokens = if stats.tokens_per_project.is_empty() {
+        "none recorded".to_string()
+    } else {
+        stats
+            .tokens_per_project
+            .iter()
+            .map(|(project_id, tokens)| {
+                let name = store
+                    .get_project(*project_id)
+                    .map(|p| truncate_string(&p.name, 24))
+                    .unwrap_or_else(|| "(removed project)".to_string());
+                format!("{} {}", name, compact_count(*tokens))
+            })
+            .collect::<Vec<_>>()
+            .join(" · ")
+    };
+    lines.push(row("Tokens", tokens));
+
+    if stats.skipped > 0 {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!("{} unreadable history lines were skipped", stats.skipped),
+            Style::default().fg(t.warning),
+        )));
+    }
+    lines
+}
+
+fn row(label: &str, value: String) -> Line<'static> {
+    let t = theme();
+    Line::from(vec![
+        Span::styled(format!("{:<LABEL_WIDTH$}", label), t.muted_style()),
+        Span::styled(value, Style::default().fg(t.text)),
+    ])
+}
+
+fn optional_duration(d: Option<Duration>) -> String {
+    d.map(short_duration).unwrap_or_else(|| "-".to_string())
+}
+
+/// `950`, `12.3k`, `4.1M`
+fn compact_count(n: u64) -> String {
+    match n {
+        0..=999 => n.to_string(),
+        1_000..=999_999 => format!("{:.1}k", n as f64 / 1_000.0),
+        _ => format!("{:.1}M", n as f64 / 1_000_000.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::views::test_util::{contains_line, render_to_lines};
+    use ratatui::widgets::Paragraph;
+
+    #[test]
+    fn test_compact_count() {
+        assert_eq!(compact_count(950), "950");
+        assert_eq!(compact_count(12_340), "12.3k");
+        assert_eq!(compact_count(4_100_000), "4.1M");
+    }
+
+    #[test]
+    fn test_stats_page_names_projects_and_survives_a_removed_one() {
+        let mut store = ProjectStore::new();
+        let project = crate::project::Project::new(
+            "panoptes".to_string(),
+            std::path::PathBuf::from("/tmp/panoptes"),
+            "main".to_string(),
+        );
+        let project_id = project.id;
+        store.add_project(project);
+
+        let stats = HistoryStats {
+            turns: 3,
+            aborted_turns: 1,
+            median_turn: Some(Duration::seconds(130)),
+            approvals: 2,
+            awaiting_approval: Duration::minutes(47),
+            top_tools: vec![("Bash".to_string(), 12, Duration::minutes(62))],
+            tokens_per_project: vec![(project_id, 1_200_000), (uuid::Uuid::new_v4(), 300)],
+            ..Default::default()
+        };
+
+        let lines = render_to_lines(100, 20, |frame| {
+            frame.render_widget(Paragraph::new(stats_lines(&stats, &store)), frame.size())
+        });
+        assert!(
+            contains_line(&lines, "3 completed · 1 interrupted · median 2m"),
+            "{lines:#?}"
+        );
+        assert!(
+            contains_line(&lines, "2 answered · 47m waiting on you"),
+            "{lines:#?}"
+        );
+        assert!(contains_line(&lines, "Bash 12× 1h02m"), "{lines:#?}");
+        assert!(
+            contains_line(&lines, "panoptes 1.2M · (removed project) 300"),
+            "{lines:#?}"
+        );
+    }
+}
@@ -0,0 +1,364 @@
+//! The activity timeline overlay
+//!
+//! One bar per session across the last few hours, coloured by state, with the
+//! time split between working and waiting on the user written underneath.
+//! Each column of a bar covers an equal slice of the window and takes the
+//! colour of whichever state held most of that slice, so a thirty-second
+//! approval prompt in a six-hour window is honestly invisible rather than
+//! drawn a column wide.
+
+use chrono::{DateTime, Duration, Utc};
+use ratatui::prelude::*;
+
+use crate::app::{AppState, TimelinePage};
+use crate::pricing::PricingConfig;
+use crate::project::ProjectStore;
+use crate::session::{Segment, SessionInfo, SessionManager, SessionState};
+use crate::tui::theme::theme;
+use crate::tui::views::spend::{spend_lines, spend_title};
+use crate::tui::views::stats::{stats_lines, stats_title};
+use crate::tui::views::truncate_string;
+use crate::tui::widgets::dialog::{centered_rect, render_dialog, DialogSize, DialogSpec};
+
+/// Columns given to session names
+const NAME_WIDTH: usize = 18;
+
+/// How long a session must sit in one state before the timeline calls it out
+const STALLED_AFTER_MINUTES: i64 = 60;
+
+/// The states the legend names, in the order a turn goes through them
+const LEGEND: [SessionState; 5] = [
+    SessionState::Thinking,
+    SessionState::Executing,
+    SessionState::AwaitingApproval,
+    SessionState::Waiting,
+    SessionState::Suspended,
+];
+
+const WIDTH: DialogSize = DialogSize::Percent {
+    pct: 90,
+    min: 50,
+    max: 140,
+};
+
+const HEIGHT: DialogSize = DialogSize::Percent {
+    pct: 85,
+    min: 12,
+    max: 60,
+};
+
+/// Render the activity timeline over whatever is on screen
+pub fn render_activity_timeline(
+    frame: &mut Frame,
+    area: Rect,
+    state: &AppState,
+    sessions: &SessionManager,
+    project_store: &ProjectStore,
+    pricing: &PricingConfig,
+    now: DateTime<Utc>,
+) {
+    let t = theme();
+    let page = match (
+        state.timeline.page,
+        &state.timeline.stats,
+        &state.timeline.spend,
+    ) {
+        (TimelinePage::Stats, Some(stats), _) => {
+            Some((stats_title(), stats_lines(stats, project_store)))
+        }
+        (TimelinePage::Spend, _, Some(spend)) => Some((
+            spend_title(),
+            spend_lines(spend, project_store, sessions, pricing),
+        )),
+        _ => None,
+    };
+    if let Some((title, lines)) = page {
+        render_dialog(
+            frame,
+            area,
+            DialogSpec {
+                title: &title,
+                border_color: t.accent,
+                alignment: Alignment::Left,
+                width: WIDTH,
+                height: HEIGHT,
+            },
+            lines,
+        );
+        return;
+    }
+
+    let hours = state.timeline.window_hours();
+    let from = now - Duration::hours(hours);
+
+    let dialog = centered_rect(area, WIDTH, HEIGHT);
+    // Borders, the name column and the gap after it
+    let bar_width = (dialog.width as usize).saturating_sub(2 + NAME_WIDTH + 1);
+    // Everything but the two bordered rows, the axis, the legend and a spacer
+    let session_rows = (dialog.height as usize).saturating_sub(2 + 3) / 2;
+
+    let mut lines = vec![
+        legend_line(),
+        Line::from(Span::styled(
+            axis_label(hours, bar_width, NAME_WIDTH + 1),
+            t.muted_style(),
+        )),
+    ];
+
+    let listed = sessions.sessions_in_order();
+    if listed.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "No sessions are running.",
+            t.muted_style(),
+        )));
+    }
+    let scroll = state.timeline.scroll.min(listed.len().saturating_sub(1));
+    for session in listed.iter().skip(scroll).take(session_rows.max(1)) {
+        lines.push(bar_line(&session.info, from, now, bar_width));
+        lines.push(summary_line(&session.info, from, now));
+    }
+
+    render_dialog(
+        frame,
+        area,
+        DialogSpec {
+            title: &format!(" Activity - last {}h ", hours),
+            border_color: t.accent,
+            alignment: Alignment::Left,
+            width: WIDTH,
+            height: HEIGHT,
+        },
+        lines,
+    );
+}
+
+fn legend_line() -> Line<'static> {
+    let t = theme();
+    let mut spans = vec![Span::raw(" ".repeat(NAME_WIDTH + 1))];
+    for state in LEGEND {
+        spans.push(Span::styled(
+            "█ ",
+            Style::default().fg(t.session_state_color(&state)),
+        ));
+        spans.push(Span::styled(
+            format!("{}  ", state.display_name()),
+            t.muted_style(),
+        ));
+    }
+    Line::from(spans)
+}
+
+/// `-6h` at the left edge of the bars, `now` at the right, the midpoint between
+fn axis_label(hours: i64, bar_width: usize, indent: usize) -> String {
+    let mut axis: Vec<char> = " ".repeat(bar_width).chars().collect();
+    let mut put = |at: usize, text: &str| {
+        for (i, c) in text.chars().enumerate() {
+            if let Some(cell) = axis.get_mut(at + i) {
+                *cell = c;
+            }
+        }
+    };
+    put(0, &format!("-{}h", hours));
+    let middle = if hours % 2 == 0 {
+        format!("-{}h", hours / 2)
+    } else {
+        format!("-{}m", hours * 30)
+    };
+    put(bar_width.saturating_sub(middle.len()) / 2, &middle);
+    put(bar_width.saturating_sub(3), "now");
+    format!(
+        "{}{}",
+        " ".repeat(indent),
+        axis.into_iter().collect::<String>()
+    )
+}
+
+fn bar_line(
+    info: &SessionInfo,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    width: usize,
+) -> Line<'static> {
+    let t = theme();
+    let mut spans = vec![Span::styled(
+        format!(
+            "{:<width$} ",
+            truncate_string(&info.name, NAME_WIDTH),
+            width = NAME_WIDTH
+        ),
+        Style::default().fg(t.text),
+    )];
+    let segments = info.timeline.segments(from, to);
+    for cell in bar_cells(&segments, from, to, width) {
+        spans.push(match cell {
+            Some(state) => Span::styled("█", Style::default().fg(t.session_state_color(&state))),
+            None => Span::styled("·", t.muted_style()),
+        });
+    }
+    Line::from(spans)
+}
+
+/// Working versus waiting over the window, and how long the current state has
+/// lasted once that is long enough to be worth noticing
+fn summary_line(info: &SessionInfo, from: DateTime<Utc>, to: DateTime<Utc>) -> Line<'static> {
+    let t = theme();
+    let (mut working, mut waiting) = (Duration::zero(), Duration::zero());
+    for (state, total) in info.timeline.totals(from, to) {
+        match state {
+            SessionState::Thinking | SessionState::Executing => working += total,
+            SessionState::Waiting | SessionState::AwaitingApproval => waiting += total,
+            _ => {}
+        }
+    }
+
+    let mut spans = vec![
+        Span::raw(" ".repeat(NAME_WIDTH + 1)),
+        Span::styled(
+            format!(
+                "working {} · waiting on you {}",
+                short_duration(working),
+                short_duration(waiting)
+            ),
+            t.muted_style(),
+        ),
+    ];
+    let held = to - info.state_entered_at;
+    if held >= Duration::minutes(STALLED_AFTER_MINUTES) && info.state.has_process() {
+        spans.push(Span::styled(
+            format!(
+                " · {} for {}",
+                info.state.display_name(),
+                short_duration(held)
+            ),
+            Style::default().fg(t.warning),
+        ));
+    }
+    Line::from(spans)
+}
+
+/// The state each of `width` equal slices of `from..to` spent longest in
+///
+/// `None` is a slice no segment touches: before the session existed.
+fn bar_cells(
+    segments: &[Segment],
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    width: usize,
+) -> Vec<Option<SessionState>> {
+    let span_ms = (to - from).num_milliseconds().max(1);
+    (0..width)
+        .map(|i| {
+            let start = from + Duration::milliseconds(span_ms * i as i64 / width as i64);
+            let end = from + Duration::milliseconds(span_ms * (i as i64 + 1) / width as i64);
+            let mut best: Option<(SessionState, Duration)> = None;
+            for segment in segments {
+                let overlap = segment.end.min(end) - segment.start.max(start);
+                if overlap > Duration::zero() && best.map_or(true, |(_, d)| overlap > d) {
+                    best = Some((segment.state, overlap));
+                }
+            }
+            best.map(|(state, _)| state)
+        })
+        .collect()
+}
+
+/// `45s`, `34m`, `1h05m`
+pub(super) fn short_duration(d: Duration) -> String {
+    let minutes = d.num_minutes();
+    if minutes < 1 {
+        format!("{}s", d.num_seconds().max(0))
+    } else if minutes < 60 {
+        format!("{}m", minutes)
+    } else {
+        format!("{}h{:02}m", minutes / 60, minutes % 60)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::StateTimeline;
+    use crate::tui::views::test_util::{contains_line, render_to_lines};
+
+    fn at(minutes: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp(1_700_000_000, 0).unwrap() + Duration::minutes(minutes)
+    }
+
+    #[test]
+    fn test_each_cell_takes_the_state_that_held_most_of_it() {
+        let mut timeline = StateTimeline::default();
+        timeline.record(SessionState::Thinking, at(10));
+        timeline.record(SessionState::Waiting, at(25));
+        timeline.record(SessionState::AwaitingApproval, at(59));
+
+        let cells = bar_cells(&timeline.segments(at(0), at(60)), at(0), at(60), 6);
+        assert_eq!(
+            cells,
+            vec![
+                None,
+                Some(SessionState::Thinking),
+                // Thinking 5 minutes of the slice, Waiting the other 5: a tie
+                // goes to the earlier state
+                Some(SessionState::Thinking),
+                Some(SessionState::Waiting),
+                Some(SessionState::Waiting),
+                Some(SessionState::Waiting),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_short_duration_reads_at_a_glance() {
+        assert_eq!(short_duration(Duration::seconds(45)), "45s");
+        assert_eq!(short_duration(Duration::minutes(34)), "34m");
+        assert_eq!(short_duration(Duration::minutes(65)), "1h05m");
+    }
+
+    #[test]
+    fn test_timeline_splits_working_from_waiting_and_flags_a_long_wait() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let mut sessions = SessionManager::with_store(
+            crate::config::Config {
+                worktrees_dir: temp.path().join("worktrees"),
+                hooks_dir: temp.path().join("hooks"),
+                ..crate::config::Config::default()
+            },
+            crate::session::SessionStore::with_path(temp.path().join("sessions.json")),
+        );
+        let id = sessions
+            .insert_test_session("fix-login", uuid::Uuid::new_v4(), uuid::Uuid::new_v4())
+            .unwrap();
+
+        let now = Utc::now();
+        let info = &mut sessions.get_mut(id).unwrap().info;
+        info.timeline = StateTimeline::default();
+        info.timeline
+            .record(SessionState::Executing, now - Duration::minutes(150));
+        info.timeline
+            .record(SessionState::Waiting, now - Duration::minutes(90));
+        info.state = SessionState::Waiting;
+        info.state_entered_at = now - Duration::minutes(90);
+
+        let state = AppState::default();
+
+        let lines = render_to_lines(120, 30, |frame| {
+            render_activity_timeline(
+                frame,
+                frame.size(),
+                &state,
+                &sessions,
+                &ProjectStore::new(),
+                &PricingConfig::new(),
+                now,
+            )
+        });
+        assert!(contains_line(&lines, "Activity - last 6h"), "{lines:#?}");
+        assert!(contains_line(&lines, "fix-login"), "{lines:#?}");
+        assert!(
+            contains_line(&lines, "working 1h00m · waiting on you 1h30m"),
+            "{lines:#?}"
+        );
+        assert!(contains_line(&lines, "Waiting for 1h30m"), "{lines:#?}");
+    }
+}
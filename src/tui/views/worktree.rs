@@ -208,12 +208,34 @@ fn render_select_branch(frame: &mut Frame, area: Rect, state: &AppState) {
     );
 }
 
-/// Step 2: pick the base for a new branch
+/// Step 2: pick where a new branch starts
+///
+/// A row of source tabs (cycled with `Tab`), then the input and list for the
+/// chosen source: a base branch, a commit or tag, a stash, or a patch file.
 fn render_select_base(frame: &mut Frame, area: Rect, state: &AppState) {
     let t = theme();
     let wizard = &state.worktree_wizard;
+    let source = wizard.creation_type;
 
-    let header = vec![
+    let mut tabs = vec![Span::styled("Start from: ", t.muted_style())];
+    if wizard.multi_repo {
+        tabs.push(Span::styled(
+            "base branch, in every repository that has it (the rest start from HEAD)",
+            Style::default().fg(t.text_dim),
+        ));
+    } else {
+        for option in WorktreeCreationType::NEW_BRANCH_SOURCES {
+            let label = format!(" {} ", option.source_label());
+            tabs.push(if option == source {
+                Span::styled(label, t.selected_style())
+            } else {
+                Span::styled(label, Style::default().fg(t.text_dim))
+            });
+        }
+        tabs.push(Span::styled("  (Tab)", t.muted_style()));
+    }
+
+    let mut header = vec![
         Line::from(vec![
             Span::styled("Branch name: ", t.muted_style()),
             Span::styled(
@@ -221,38 +243,83 @@ fn render_select_base(frame: &mut Frame, area: Rect, state: &AppState) {
                 Style::default().fg(t.accent).add_modifier(Modifier::BOLD),
             ),
         ]),
-        Line::from(Span::styled(
-            format!("> {}_", wizard.base_search_text),
-            t.input_style(),
-        )),
+        Line::from(tabs),
     ];
+    let prompt = match source {
+        WorktreeCreationType::FromRevision => Some("Commit SHA or tag"),
+        WorktreeCreationType::FromPatch => Some("Path to the .patch file"),
+        WorktreeCreationType::FromStash => None,
+        _ => Some("Filter base branches"),
+    };
+    if let Some(prompt) = prompt {
+        header.push(Line::from(vec![
+            Span::styled(format!("{}: ", prompt), t.muted_style()),
+            Span::styled(format!("{}_", wizard.base_search_text), t.input_style()),
+        ]));
+    }
+    if let Some(error) = &wizard.source_error {
+        header.push(Line::from(Span::styled(
+            format!("⚠ {}", error),
+            Style::default().fg(t.error_bg),
+        )));
+    }
 
-    // The input handlers keep the cached filter in step with the search text
-    let items: Vec<ListItem> = wizard
-        .filtered_base_branches
-        .iter()
-        .enumerate()
-        .map(|(i, branch)| {
-            branch_ref_item(
-                branch,
-                i == wizard.base_list_index,
-                " * (default)",
-                true,
-                t.selected_style(),
-            )
-        })
-        .collect();
-
-    render_selector_overlay(
-        frame,
-        area,
-        header,
-        format!(
-            "Select Base Branch ({} options)",
-            wizard.filtered_base_branches.len()
+    let (title, items): (String, Vec<ListItem>) = match source {
+        WorktreeCreationType::FromStash => (
+            format!("Stashes in the main checkout ({})", wizard.stashes.len()),
+            wizard
+                .stashes
+                .iter()
+                .enumerate()
+                .map(|(i, stash)| {
+                    let selected = i == wizard.stash_index;
+                    ListItem::new(format!(
+                        "{}stash@{{{}}} {}",
+                        selection_prefix(selected),
+                        stash.index,
+                        stash.message
+                    ))
+                    .style(selection_style(selected, t.text))
+                })
+                .collect(),
         ),
-        items,
-    );
+        // A commit needs no list; the base a patch lands on is the branch
+        // chosen on the Branch tab
+        WorktreeCreationType::FromRevision => (String::new(), Vec::new()),
+        WorktreeCreationType::FromPatch => (
+            "Applied onto".to_string(),
+            wizard
+                .base_branch
+                .iter()
+                .map(|b| {
+                    ListItem::new(format!("  {}", b.name)).style(Style::default().fg(t.accent))
+                })
+                .collect(),
+        ),
+        // The input handlers keep the cached filter in step with the search text
+        _ => (
+            format!(
+                "Select Base Branch ({} options)",
+                wizard.filtered_base_branches.len()
+            ),
+            wizard
+                .filtered_base_branches
+                .iter()
+                .enumerate()
+                .map(|(i, branch)| {
+                    branch_ref_item(
+                        branch,
+                        i == wizard.base_list_index,
+                        " * (default)",
+                        true,
+                        t.selected_style(),
+                    )
+                })
+                .collect(),
+        ),
+    };
+
+    render_selector_overlay(frame, area, header, title, items);
 }
 
 /// Step 3: say what is about to happen, and where
@@ -335,12 +402,77 @@ fn render_confirm(frame: &mut Frame, area: Rect, state: &AppState, config: &Conf
                 t.muted_style(),
             )));
         }
+        WorktreeCreationType::FromRevision => {
+            let (id, summary) = wizard
+                .start_revision
+                .as_ref()
+                .map(|(id, summary)| (short_id(id), summary.as_str()))
+                .unwrap_or(("unknown", ""));
+            lines.push(Line::from(Span::styled(
+                "You are about to create a new branch:",
+                Style::default().fg(t.text),
+            )));
+            lines.push(Line::from(""));
+            lines.push(emphasis(&wizard.branch_name, t.accent));
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                "Starting at commit:",
+                Style::default().fg(t.text),
+            )));
+            lines.push(emphasis(&format!("{} {}", id, summary), t.accent));
+        }
+        WorktreeCreationType::FromStash => {
+            let (name, base) = wizard
+                .selected_stash()
+                .map(|stash| (stash.message.as_str(), short_id(&stash.base)))
+                .unwrap_or(("unknown", "unknown"));
+            lines.push(Line::from(Span::styled(
+                "You are about to create a new branch:",
+                Style::default().fg(t.text),
+            )));
+            lines.push(Line::from(""));
+            lines.push(emphasis(&wizard.branch_name, t.accent));
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                format!("At {}, where this stash was taken, and apply it:", base),
+                Style::default().fg(t.text),
+            )));
+            lines.push(emphasis(name, t.accent));
+        }
+        WorktreeCreationType::FromPatch => {
+            let base_name = wizard
+                .base_branch
+                .as_ref()
+                .map(|b| b.name.as_str())
+                .unwrap_or("unknown");
+            let patch = wizard
+                .patch_path
+                .as_ref()
+                .map(|p| p.display().to_string())
+                .unwrap_or_else(|| "unknown".to_string());
+            lines.push(Line::from(Span::styled(
+                "You are about to create a new branch:",
+                Style::default().fg(t.text),
+            )));
+            lines.push(Line::from(""));
+            lines.push(emphasis(&wizard.branch_name, t.accent));
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                format!("Branched from {}, with this patch applied:", base_name),
+                Style::default().fg(t.text),
+            )));
+            lines.push(emphasis(&patch, t.accent));
+        }
     }
 
     if !is_import {
         lines.push(Line::from(""));
         lines.push(Line::from(Span::styled(
-            "Worktree location:",
+            if wizard.multi_repo {
+                "One worktree per repository, inside:"
+            } else {
+                "Worktree location:"
+            },
             Style::default().fg(t.text),
         )));
         lines.push(Line::from(Span::styled(
@@ -394,6 +526,11 @@ fn render_confirm(frame: &mut Frame, area: Rect, state: &AppState, config: &Conf
     );
 }
 
+/// A commit ID shortened the way `git log --oneline` shows it
+fn short_id(id: &str) -> &str {
+    id.get(..7).unwrap_or(id)
+}
+
 fn emphasis(text: &str, color: Color) -> Line<'static> {
     Line::from(Span::styled(
         text.to_string(),
@@ -733,6 +870,41 @@ mod tests {
         assert!(contains_line(&lines, "[L] main * (default)"), "{lines:?}");
     }
 
+    #[test]
+    fn test_select_base_lists_stashes_on_the_stash_tab() {
+        let mut state = AppState {
+            input_mode: InputMode::WorktreeSelectBase,
+            ..Default::default()
+        };
+        state.worktree_wizard.branch_name = "from-stash".to_string();
+        state.worktree_wizard.creation_type = WorktreeCreationType::FromStash;
+        state.worktree_wizard.stashes = vec![crate::git::seed::StashEntry {
+            index: 0,
+            message: "WIP on main: parser".to_string(),
+            oid: "a".repeat(40),
+            base: "b".repeat(40),
+        }];
+
+        let lines = render_wizard(&state);
+
+        assert!(contains_line(&lines, "Start from:"), "{lines:?}");
+        assert!(
+            contains_line(&lines, "Stashes in the main checkout (1)"),
+            "{lines:?}"
+        );
+        assert!(
+            contains_line(&lines, "stash@{0} WIP on main: parser"),
+            "{lines:?}"
+        );
+
+        state.input_mode = InputMode::WorktreeConfirm;
+        let lines = render_wizard(&state);
+        assert!(
+            contains_line(&lines, "At bbbbbbb, where this stash was taken"),
+            "{lines:?}"
+        );
+    }
+
     #[test]
     fn test_confirm_step_shows_the_worktree_location() {
         let mut state = AppState {
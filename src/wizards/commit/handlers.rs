@@ -0,0 +1,320 @@
+//! Commit wizard input handlers
+//!
+//! Handles keyboard input for the two commit steps, and the small lookups the
+//! wizard needs from the session list: which agent's last reply can pre-fill
+//! the message, and which agent to ask for a draft.
+
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+
+use crate::app::{
+    cycle_next, cycle_prev, App, AppState, DraftRequest, InputMode, MAX_COMMIT_MESSAGE_LEN,
+};
+use crate::project::BranchId;
+use crate::session::{SessionId, SessionManager, SessionState, SessionType};
+
+/// What an agent is asked when the user wants it to draft the message
+pub const COMMIT_DRAFT_PROMPT: &str = "Write a one-line git commit message for the uncommitted \
+changes in this worktree. Reply with the message only - no quotes, no explanation.";
+
+/// How much of a file's patch the preview keeps; the rest is cut with a note
+const DIFF_PREVIEW_MAX_LINES: usize = 400;
+
+/// Handle key in CommitSelectFiles mode (Step 1)
+///
+/// User can:
+/// - Arrow keys to move through the files (the diff preview follows)
+/// - Space to tick or untick the highlighted file, `a` for all of them
+/// - Enter to go on to the message, once at least one file is ticked
+/// - Esc to cancel
+pub fn handle_commit_select_files_key(app: &mut App, key: KeyEvent) -> Result<()> {
+    if key.kind != KeyEventKind::Press {
+        return Ok(());
+    }
+    select_files_key(&mut app.state, key);
+    Ok(())
+}
+
+fn select_files_key(state: &mut AppState, key: KeyEvent) {
+    let wizard = &mut state.commit_wizard;
+    let count = wizard.changes.len();
+
+    match key.code {
+        KeyCode::Esc => cancel_commit_wizard(state),
+        KeyCode::Up => {
+            wizard.list_index = cycle_prev(wizard.list_index, count);
+            refresh_commit_diff_preview(state);
+        }
+        KeyCode::Down => {
+            wizard.list_index = cycle_next(wizard.list_index, count);
+            refresh_commit_diff_preview(state);
+        }
+        KeyCode::Char(' ') => {
+            if let Some(selected) = wizard.selected.get_mut(wizard.list_index) {
+                *selected = !*selected;
+            }
+        }
+        KeyCode::Char('a') => wizard.toggle_all(),
+        KeyCode::Enter => {
+            if wizard.selected.iter().any(|&s| s) {
+                state.input_mode = InputMode::CommitMessage;
+            } else {
+                state
+                    .header_notifications
+                    .push("Tick at least one file to commit");
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Handle key in CommitMessage mode (Step 2)
+///
+/// User can:
+/// - Type the message (pre-filled from the agent's last reply, if any)
+/// - Tab to toggle pushing after the commit, when there is somewhere to push
+/// - Ctrl+G to ask the branch's agent to draft the message
+/// - Enter to commit (and push) in the background
+/// - Esc to go back to the file list
+pub fn handle_commit_message_key(app: &mut App, key: KeyEvent) -> Result<()> {
+    if key.kind != KeyEventKind::Press {
+        return Ok(());
+    }
+
+    match key.code {
+        KeyCode::Char('g') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            ask_agent_for_draft(app)?;
+        }
+        KeyCode::Enter => {
+            let wizard = &app.state.commit_wizard;
+            if wizard.message.trim().is_empty() {
+                return Ok(());
+            }
+            let checkout = wizard.checkout.clone();
+            let branch_name = wizard.branch_name.clone();
+            let paths = wizard.selected_paths();
+            let message = wizard.message.clone();
+            let push = wizard.push && wizard.push_target.is_some();
+
+            // The wizard is done: the loading overlay takes over from here
+            cancel_commit_wizard(&mut app.state);
+            app.spawn_commit(checkout, branch_name, paths, message, push);
+        }
+        _ => message_key(&mut app.state, key),
+    }
+    Ok(())
+}
+
+/// The message step's keys that need nothing but the wizard state
+fn message_key(state: &mut AppState, key: KeyEvent) {
+    let wizard = &mut state.commit_wizard;
+
+    match key.code {
+        KeyCode::Esc => {
+            state.input_mode = InputMode::CommitSelectFiles;
+        }
+        KeyCode::Tab if wizard.push_target.is_some() => {
+            wizard.push = !wizard.push;
+        }
+        KeyCode::Backspace => {
+            wizard.message.pop();
+            wizard.message_edited = true;
+        }
+        KeyCode::Char(c)
+            if !key.modifiers.contains(KeyModifiers::CONTROL)
+                && wizard.message.len() < MAX_COMMIT_MESSAGE_LEN =>
+        {
+            wizard.message.push(c);
+            wizard.message_edited = true;
+        }
+        _ => {}
+    }
+}
+
+/// Ask the branch's agent to draft the commit message
+///
+/// The prompt is submitted to the session like typed input, as soon as it is
+/// between turns; the reply lands in the message field when that turn ends
+/// (see `App::tick_commit_draft`). A suspended agent is woken first.
+fn ask_agent_for_draft(app: &mut App) -> Result<()> {
+    let Some(branch_id) = app.state.commit_wizard.branch_id else {
+        return Ok(());
+    };
+    let Some(session_id) = drafting_session(&app.sessions, branch_id) else {
+        app.state
+            .header_notifications
+            .push("No running agent on this branch to ask");
+        return Ok(());
+    };
+    if app.sessions.is_suspended(session_id) && !app.wake_session(session_id)? {
+        return Ok(());
+    }
+    let Some(session) = app.sessions.get(session_id) else {
+        return Ok(());
+    };
+    if session.info.state != SessionState::Waiting {
+        app.state.header_notifications.push(format!(
+            "{} will be asked once its turn is over",
+            session.info.name
+        ));
+    }
+
+    // Asking is a request to replace what is there; typing after it is not
+    app.state.commit_wizard.drafting = Some(DraftRequest::new(session_id));
+    app.state.commit_wizard.message_edited = false;
+    Ok(())
+}
+
+/// The running agent session on `branch_id` that was active most recently
+///
+/// Shell sessions have no conversation to ask, and a session that has exited
+/// cannot answer.
+pub fn drafting_session(sessions: &SessionManager, branch_id: BranchId) -> Option<SessionId> {
+    sessions
+        .entries_for_branch(branch_id)
+        .into_iter()
+        .filter(|entry| entry.live && entry.info.session_type != SessionType::Shell)
+        .filter(|entry| entry.info.state.has_process() || sessions.is_suspended(entry.info.id))
+        .max_by_key(|entry| entry.info.last_activity)
+        .map(|entry| entry.info.id)
+}
+
+/// The most recent agent reply on `branch_id`, to pre-fill the message with
+pub fn latest_agent_message(sessions: &SessionManager, branch_id: BranchId) -> Option<String> {
+    sessions
+        .entries_for_branch(branch_id)
+        .into_iter()
+        .filter(|entry| entry.info.session_type != SessionType::Shell)
+        .filter(|entry| entry.info.last_message.is_some())
+        .max_by_key(|entry| entry.info.last_activity)
+        .and_then(|entry| entry.info.last_message.clone())
+}
+
+/// Re-read the patch of the highlighted file into the preview
+///
+/// A failure becomes the preview text: it is a preview, and saying why it is
+/// empty beats an empty box.
+pub fn refresh_commit_diff_preview(state: &mut AppState) {
+    let wizard = &mut state.commit_wizard;
+    let Some(change) = wizard.changes.get(wizard.list_index) else {
+        wizard.diff_preview.clear();
+        return;
+    };
+
+    let patch = crate::git::GitOps::discover(&wizard.checkout)
+        .and_then(|git| git.diff_for_path(&change.path));
+    wizard.diff_preview = match patch {
+        Ok(patch) => {
+            let total = patch.lines().count();
+            let mut kept = patch
+                .lines()
+                .take(DIFF_PREVIEW_MAX_LINES)
+                .collect::<Vec<_>>()
+                .join("\n");
+            if total > DIFF_PREVIEW_MAX_LINES {
+                kept.push_str(&format!(
+                    "\n… {} more lines",
+                    total - DIFF_PREVIEW_MAX_LINES
+                ));
+            }
+            kept
+        }
+        Err(e) => format!("No diff available: {:#}", e),
+    };
+}
+
+/// Cancel and clean up the commit wizard state
+fn cancel_commit_wizard(state: &mut AppState) {
+    state.input_mode = InputMode::Normal;
+    state.commit_wizard = Default::default();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::CommitWizardState;
+    use crate::git::commit::{ChangeKind, WorkingChange};
+
+    fn press(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn state_with_files(paths: &[&str]) -> AppState {
+        let changes = paths
+            .iter()
+            .map(|path| WorkingChange {
+                path: path.to_string(),
+                kind: ChangeKind::Modified,
+                staged: false,
+                unstaged: true,
+            })
+            .collect::<Vec<_>>();
+        AppState {
+            input_mode: InputMode::CommitSelectFiles,
+            commit_wizard: CommitWizardState {
+                selected: vec![true; changes.len()],
+                changes,
+                // Nowhere to discover a repo, so previews say so harmlessly
+                checkout: std::path::PathBuf::from("/nonexistent/panoptes-commit"),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_space_and_a_choose_what_is_committed() {
+        let mut state = state_with_files(&["a.rs", "b.rs", "c.rs"]);
+
+        select_files_key(&mut state, press(KeyCode::Down));
+        select_files_key(&mut state, press(KeyCode::Char(' ')));
+        assert_eq!(state.commit_wizard.selected_paths(), vec!["a.rs", "c.rs"]);
+
+        // Not all ticked, so `a` ticks the rest; then it unticks everything
+        select_files_key(&mut state, press(KeyCode::Char('a')));
+        assert_eq!(state.commit_wizard.selected_paths().len(), 3);
+        select_files_key(&mut state, press(KeyCode::Char('a')));
+        assert!(state.commit_wizard.selected_paths().is_empty());
+    }
+
+    #[test]
+    fn test_enter_needs_a_ticked_file() {
+        let mut state = state_with_files(&["a.rs"]);
+        select_files_key(&mut state, press(KeyCode::Char(' ')));
+        select_files_key(&mut state, press(KeyCode::Enter));
+        assert_eq!(state.input_mode, InputMode::CommitSelectFiles);
+
+        select_files_key(&mut state, press(KeyCode::Char(' ')));
+        select_files_key(&mut state, press(KeyCode::Enter));
+        assert_eq!(state.input_mode, InputMode::CommitMessage);
+    }
+
+    #[test]
+    fn test_message_typing_marks_it_edited_and_tab_only_toggles_a_real_push() {
+        let mut state = state_with_files(&["a.rs"]);
+        state.input_mode = InputMode::CommitMessage;
+        state.commit_wizard.message = "Drafted".into();
+
+        message_key(&mut state, press(KeyCode::Char('!')));
+        assert_eq!(state.commit_wizard.message, "Drafted!");
+        assert!(state.commit_wizard.message_edited);
+
+        message_key(&mut state, press(KeyCode::Tab));
+        assert!(!state.commit_wizard.push, "nowhere to push to");
+        state.commit_wizard.push_target = Some("origin/feature".into());
+        message_key(&mut state, press(KeyCode::Tab));
+        assert!(state.commit_wizard.push);
+
+        message_key(&mut state, press(KeyCode::Esc));
+        assert_eq!(state.input_mode, InputMode::CommitSelectFiles);
+        assert_eq!(state.commit_wizard.message, "Drafted!", "kept for later");
+    }
+
+    #[test]
+    fn test_esc_on_the_file_list_drops_the_wizard() {
+        let mut state = state_with_files(&["a.rs"]);
+        select_files_key(&mut state, press(KeyCode::Esc));
+        assert_eq!(state.input_mode, InputMode::Normal);
+        assert!(state.commit_wizard.changes.is_empty());
+    }
+}
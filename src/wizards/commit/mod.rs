@@ -0,0 +1,11 @@
+//! Commit wizard
+//!
+//! Two-step workflow for committing a branch's work from the dashboard: pick
+//! the files, then write (or have the agent draft) the message and push.
+
+mod handlers;
+
+pub use handlers::{
+    drafting_session, handle_commit_message_key, handle_commit_select_files_key,
+    latest_agent_message, refresh_commit_diff_preview, COMMIT_DRAFT_PROMPT,
+};
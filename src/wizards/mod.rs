@@ -2,4 +2,5 @@
 //!
 //! This module contains wizard-style workflows for complex multi-step operations.
 
+pub mod commit;
 pub mod worktree;
@@ -7,7 +7,9 @@ use crossterm::event::{KeyCode, KeyEvent, KeyEventKind};
 
 use crate::app::{
     cycle_next, cycle_prev, App, ClaudeSettingsCopyState, InputMode, MAX_BRANCH_NAME_LEN,
+    MAX_PROJECT_PATH_LEN,
 };
+use crate::git::seed::WorktreeSeed;
 use crate::project::ProjectId;
 use crate::wizards::worktree::{filter_branch_refs, BranchRefType, WorktreeCreationType};
 
@@ -158,14 +160,15 @@ pub fn handle_worktree_select_branch_key(
 /// Handle key in WorktreeSelectBase mode (Step 2)
 ///
 /// User can:
-/// - Type to filter base branches
-/// - Arrow keys to navigate the list
-/// - Enter to confirm and go to WorktreeConfirm
+/// - Tab to switch where the new branch starts: a base branch, a commit SHA
+///   or tag, a stash from the main checkout, or a patch file on a base branch
 /// - Esc to go back to WorktreeSelectBranch
+///
+/// Everything else depends on that choice; see the per-source handlers.
 pub fn handle_worktree_select_base_key(
     app: &mut App,
     key: KeyEvent,
-    _project_id: ProjectId,
+    project_id: ProjectId,
 ) -> Result<()> {
     if key.kind != KeyEventKind::Press {
         return Ok(());
@@ -175,9 +178,33 @@ pub fn handle_worktree_select_base_key(
         KeyCode::Esc => {
             // Go back to step 1
             app.state.input_mode = InputMode::WorktreeSelectBranch;
-            app.state.worktree_wizard.base_search_text.clear();
-            update_worktree_filtered_base_branches(app);
+            reset_start_source(app);
+        }
+        // A commit, stash or patch belongs to one repository, so a
+        // multi-repository branch always starts from a base branch
+        KeyCode::Tab if !app.state.worktree_wizard.multi_repo => {
+            let next = app.state.worktree_wizard.creation_type.next_source();
+            reset_start_source(app);
+            app.state.worktree_wizard.creation_type = next;
         }
+        _ => match app.state.worktree_wizard.creation_type {
+            WorktreeCreationType::FromStash => handle_select_stash_key(app, key),
+            WorktreeCreationType::FromRevision | WorktreeCreationType::FromPatch => {
+                handle_start_text_key(app, key, project_id)
+            }
+            _ => handle_select_base_branch_key(app, key),
+        },
+    }
+    Ok(())
+}
+
+/// Step 2, starting from a base branch
+///
+/// - Type to filter base branches
+/// - Arrow keys to navigate the list
+/// - Enter to confirm and go to WorktreeConfirm
+fn handle_select_base_branch_key(app: &mut App, key: KeyEvent) {
+    match key.code {
         KeyCode::Up => {
             let wizard = &mut app.state.worktree_wizard;
             wizard.base_list_index =
@@ -221,7 +248,126 @@ pub fn handle_worktree_select_base_key(
         }
         _ => {}
     }
-    Ok(())
+}
+
+/// Step 2, starting from a stash of the main checkout
+///
+/// - Arrow keys to navigate the stash list
+/// - Enter to confirm and go to WorktreeConfirm
+fn handle_select_stash_key(app: &mut App, key: KeyEvent) {
+    let wizard = &mut app.state.worktree_wizard;
+    match key.code {
+        KeyCode::Up => {
+            wizard.stash_index = cycle_prev(wizard.stash_index, wizard.stashes.len());
+        }
+        KeyCode::Down => {
+            wizard.stash_index = cycle_next(wizard.stash_index, wizard.stashes.len());
+        }
+        KeyCode::Enter => {
+            if wizard.selected_stash().is_some() {
+                app.state.input_mode = InputMode::WorktreeConfirm;
+            } else {
+                wizard.source_error = Some("The main checkout has no stashes".to_string());
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Step 2, starting from a typed commit/tag or patch file path
+///
+/// - Type the commit SHA, tag or path
+/// - Enter to check it and go to WorktreeConfirm; a bad one is reported in
+///   place, so nothing is created from a typo
+fn handle_start_text_key(app: &mut App, key: KeyEvent, project_id: ProjectId) {
+    let wizard = &mut app.state.worktree_wizard;
+    match key.code {
+        KeyCode::Backspace => {
+            wizard.base_search_text.pop();
+            wizard.source_error = None;
+        }
+        KeyCode::Char(c) => {
+            if wizard.base_search_text.len() < MAX_PROJECT_PATH_LEN {
+                wizard.base_search_text.push(c);
+                wizard.source_error = None;
+            }
+        }
+        KeyCode::Enter => {
+            let Some(repo_path) = app
+                .project_store
+                .get_project(project_id)
+                .map(|p| p.repo_path.clone())
+            else {
+                return;
+            };
+            let wizard = &mut app.state.worktree_wizard;
+            let checked = if wizard.creation_type == WorktreeCreationType::FromRevision {
+                resolve_revision(&repo_path, &wizard.base_search_text)
+                    .map(|resolved| wizard.start_revision = Some(resolved))
+            } else {
+                resolve_patch_path(&repo_path, &wizard.base_search_text)
+                    .map(|path| wizard.patch_path = Some(path))
+            };
+            match checked {
+                Ok(()) => app.state.input_mode = InputMode::WorktreeConfirm,
+                Err(e) => wizard.source_error = Some(format!("{:#}", e)),
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Resolve a typed commit SHA or tag in the project's repository
+///
+/// A local, fast read, so it runs on the event-loop thread.
+fn resolve_revision(repo_path: &std::path::Path, revision: &str) -> Result<(String, String)> {
+    let git = crate::git::GitOps::open(repo_path)?;
+    crate::git::seed::resolve_start_point(git.repository(), revision)
+}
+
+/// Expand a typed patch path and check there is a file there
+///
+/// `~` is the home directory; a relative path is taken from the main
+/// checkout, where patches made with `git diff > x.patch` usually land.
+fn resolve_patch_path(repo_path: &std::path::Path, typed: &str) -> Result<std::path::PathBuf> {
+    let typed = typed.trim();
+    if typed.is_empty() {
+        anyhow::bail!("Type the path of a .patch file");
+    }
+    let expanded = std::path::PathBuf::from(shellexpand::tilde(typed).as_ref());
+    let path = if expanded.is_absolute() {
+        expanded
+    } else {
+        repo_path.join(expanded)
+    };
+    if !path.is_file() {
+        anyhow::bail!("No file at {}", path.display());
+    }
+    Ok(path)
+}
+
+/// Forget what step 2 collected for the current start source
+///
+/// The base branch choice itself is kept: a patch is applied onto it, so
+/// switching sources back and forth should not lose it.
+fn reset_start_source(app: &mut App) {
+    let wizard = &mut app.state.worktree_wizard;
+    wizard.base_search_text.clear();
+    wizard.source_error = None;
+    wizard.start_revision = None;
+    wizard.patch_path = None;
+    update_worktree_filtered_base_branches(app);
+
+    let wizard = &mut app.state.worktree_wizard;
+    if let Some(base) = &wizard.base_branch {
+        if let Some(idx) = wizard
+            .filtered_base_branches
+            .iter()
+            .position(|b| b.name == base.name)
+        {
+            wizard.base_list_index = idx;
+        }
+    }
 }
 
 /// Handle key in WorktreeConfirm mode (Step 3)
@@ -241,19 +387,29 @@ pub fn handle_worktree_confirm_key(
     match key.code {
         KeyCode::Esc => {
             // Go back to appropriate step
-            match app.state.worktree_wizard.creation_type {
-                WorktreeCreationType::NewBranch => {
-                    app.state.input_mode = InputMode::WorktreeSelectBase;
-                }
-                _ => {
-                    app.state.input_mode = InputMode::WorktreeSelectBranch;
-                }
+            if app.state.worktree_wizard.creation_type.is_new_branch() {
+                app.state.input_mode = InputMode::WorktreeSelectBase;
+            } else {
+                app.state.input_mode = InputMode::WorktreeSelectBranch;
             }
         }
         KeyCode::Enter => {
             // Everything the wizard collected, before its state is cleared
             let creation_type = app.state.worktree_wizard.creation_type;
             let branch_name = app.state.worktree_wizard.branch_name.clone();
+            let wizard = &app.state.worktree_wizard;
+            let seed = match creation_type {
+                WorktreeCreationType::FromStash => {
+                    wizard.selected_stash().map(|stash| WorktreeSeed::Stash {
+                        oid: stash.oid.clone(),
+                    })
+                }
+                WorktreeCreationType::FromPatch => wizard
+                    .patch_path
+                    .clone()
+                    .map(|path| WorktreeSeed::Patch { path }),
+                _ => None,
+            };
             let base_ref = match creation_type {
                 // Create local tracking branch from the selected remote branch
                 WorktreeCreationType::RemoteTracking => app
@@ -262,13 +418,26 @@ pub fn handle_worktree_confirm_key(
                     .source_branch
                     .as_ref()
                     .map(|b| b.name.clone()),
-                // Create a new branch from the selected base
-                WorktreeCreationType::NewBranch => app
+                // Create a new branch from the selected base (a patch is
+                // applied on top of it)
+                WorktreeCreationType::NewBranch | WorktreeCreationType::FromPatch => app
                     .state
                     .worktree_wizard
                     .base_branch
                     .as_ref()
                     .map(|b| b.name.clone()),
+                WorktreeCreationType::FromRevision => app
+                    .state
+                    .worktree_wizard
+                    .start_revision
+                    .as_ref()
+                    .map(|(id, _)| id.clone()),
+                // Start where the stash was taken, so it applies cleanly
+                WorktreeCreationType::FromStash => app
+                    .state
+                    .worktree_wizard
+                    .selected_stash()
+                    .map(|stash| stash.base.clone()),
                 _ => None,
             };
 
@@ -294,6 +463,7 @@ pub fn handle_worktree_confirm_key(
                     &branch_name,
                     create_branch,
                     base_ref.as_deref(),
+                    seed,
                 ) {
                     tracing::error!("Failed to create worktree: {:#}", e);
                     app.state.error_message = Some(format!("Failed to create worktree: {}", e));
@@ -582,3 +752,31 @@ fn select_default_base_branch(app: &mut App) {
         app.state.base_branch_selector_index = 0;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_patch_paths_are_taken_from_the_main_checkout() {
+        let repo = TempDir::new().unwrap();
+        std::fs::write(repo.path().join("wip.patch"), "").unwrap();
+
+        assert_eq!(
+            resolve_patch_path(repo.path(), " wip.patch ").unwrap(),
+            repo.path().join("wip.patch")
+        );
+        let absolute = repo.path().join("wip.patch");
+        assert_eq!(
+            resolve_patch_path(repo.path(), absolute.to_str().unwrap()).unwrap(),
+            absolute
+        );
+
+        let err = resolve_patch_path(repo.path(), "missing.patch").unwrap_err();
+        assert!(err.to_string().starts_with("No file at"), "{err}");
+        assert!(resolve_patch_path(repo.path(), "").is_err());
+        // A directory is not a patch
+        assert!(resolve_patch_path(repo.path(), ".").is_err());
+    }
+}
@@ -85,6 +85,44 @@ pub enum WorktreeCreationType {
     NewBranch,
     /// Import an existing git worktree that is not tracked by Panoptes
     ImportExisting,
+    /// Create a new branch at a specific commit SHA or tag
+    FromRevision,
+    /// Create a new branch where a stash was taken, then apply the stash
+    FromStash,
+    /// Create a new branch from a base, then apply a patch file
+    FromPatch,
+}
+
+impl WorktreeCreationType {
+    /// The ways a new branch can start, in the order `Tab` cycles them (step 2)
+    pub const NEW_BRANCH_SOURCES: [WorktreeCreationType; 4] = [
+        WorktreeCreationType::NewBranch,
+        WorktreeCreationType::FromRevision,
+        WorktreeCreationType::FromStash,
+        WorktreeCreationType::FromPatch,
+    ];
+
+    /// Whether this creates a new branch (and so goes through step 2)
+    pub fn is_new_branch(self) -> bool {
+        Self::NEW_BRANCH_SOURCES.contains(&self)
+    }
+
+    /// The next new-branch source after this one, wrapping around
+    pub fn next_source(self) -> Self {
+        let sources = Self::NEW_BRANCH_SOURCES;
+        let position = sources.iter().position(|&s| s == self).unwrap_or(0);
+        sources[(position + 1) % sources.len()]
+    }
+
+    /// Label of a new-branch source, as step 2's source tabs show it
+    pub fn source_label(self) -> &'static str {
+        match self {
+            WorktreeCreationType::FromRevision => "Commit/tag",
+            WorktreeCreationType::FromStash => "Stash",
+            WorktreeCreationType::FromPatch => "Patch file",
+            _ => "Branch",
+        }
+    }
 }
 
 /// Filter branch refs by fuzzy substring match
@@ -150,6 +188,20 @@ mod tests {
         assert_eq!(default, WorktreeCreationType::ExistingLocal);
     }
 
+    #[test]
+    fn test_new_branch_sources_cycle_and_exclude_the_rest() {
+        let mut source = WorktreeCreationType::NewBranch;
+        for _ in 0..WorktreeCreationType::NEW_BRANCH_SOURCES.len() {
+            assert!(source.is_new_branch());
+            source = source.next_source();
+        }
+        assert_eq!(source, WorktreeCreationType::NewBranch);
+
+        assert!(!WorktreeCreationType::ExistingLocal.is_new_branch());
+        assert!(!WorktreeCreationType::RemoteTracking.is_new_branch());
+        assert!(!WorktreeCreationType::ImportExisting.is_new_branch());
+    }
+
     // filter_branch_refs tests
     #[test]
     fn test_filter_branch_refs_empty_query() {